@@ -0,0 +1,33 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Uint128};
+use std::fmt;
+use tsify::Tsify;
+
+/// An asset of a pair contract, either a cw20 token or a native token.
+#[cw_serde]
+#[derive(Hash, Eq, Tsify)]
+pub enum AssetInfo {
+    Token {
+        #[tsify(type = "string")]
+        contract_addr: Addr,
+    },
+    NativeToken {
+        denom: String,
+    },
+}
+
+impl fmt::Display for AssetInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssetInfo::Token { contract_addr } => write!(f, "{}", contract_addr),
+            AssetInfo::NativeToken { denom } => write!(f, "{}", denom),
+        }
+    }
+}
+
+#[cw_serde]
+#[derive(Tsify)]
+pub struct Asset {
+    pub info: AssetInfo,
+    pub amount: Uint128,
+}
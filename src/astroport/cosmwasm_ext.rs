@@ -23,6 +23,31 @@ impl AbsDiff for Uint64 {}
 impl AbsDiff for Decimal {}
 impl AbsDiff for Decimal256 {}
 
+pub trait MultiplyRatioCeil
+where
+    Self: Copy + Into<Uint256> + TryFrom<Uint256, Error = ConversionOverflowError>,
+{
+    /// Returns `self * numerator / denominator` rounded up.
+    fn multiply_ratio_ceil(
+        self,
+        numerator: impl Into<Uint256>,
+        denominator: impl Into<Uint256>,
+    ) -> StdResult<Self> {
+        let denominator = denominator.into();
+        let value: Uint256 = self.into();
+
+        let result = value
+            .checked_mul(numerator.into())?
+            .checked_add(denominator)?
+            .checked_sub(Uint256::one())?
+            .checked_div(denominator)?;
+
+        Ok(result.try_into()?)
+    }
+}
+
+impl MultiplyRatioCeil for Uint128 {}
+
 pub trait IntegerToDecimal
 where
     Self: Copy + Into<Uint128> + Into<Uint256>,
@@ -70,6 +95,8 @@ pub trait Decimal256Ext {
         value: impl Into<Uint256>,
         precision: impl Into<u32>,
     ) -> StdResult<Decimal256>;
+
+    fn to_decimal(&self) -> StdResult<Decimal>;
 }
 
 impl Decimal256Ext for Decimal256 {
@@ -121,4 +148,8 @@ impl Decimal256Ext for Decimal256 {
         Decimal256::from_atomics(value, precision.into())
             .map_err(|_| StdError::generic_err("Decimal256 range exceeded"))
     }
+
+    fn to_decimal(&self) -> StdResult<Decimal> {
+        Ok(Decimal::new(self.atomics().try_into()?))
+    }
 }
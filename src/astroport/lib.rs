@@ -39,3 +39,1161 @@ pub mod uints {
         pub struct U256(4);
     }
 }
+
+pub mod search {
+    use crate::astroport::cosmwasm_ext::{Decimal256Ext, MultiplyRatioCeil};
+    use cosmwasm_std::{Decimal256, StdError, StdResult, Uint128, Uint256};
+
+    /// Iterations limit for the deposits refinement
+    const REFINE_ITERATIONS: u8 = 16;
+    /// Limit of doublings while looking for the upper bound of a search
+    const BRACKET_ITERATIONS: u8 = 64;
+    /// The number of points the smallest argument of a maximum is looked for at
+    const TIE_BREAK_POINTS: u128 = 64;
+    /// Iterations limit for the Newton search, enough to bisect the whole range of [`Uint128`]
+    /// twice
+    const NEWTON_ITERATIONS: u16 = 256;
+
+    /// Finds the argument in `[low, high]` which maximizes the unimodal function `f` using
+    /// ternary search. Arguments for which `f` fails are considered the worst possible. Ties are
+    /// broken toward the smallest argument, as `f` is often flat around its maximum and rounding
+    /// makes it reach the maximum at scattered arguments.
+    pub fn maximize<F>(mut low: Uint128, mut high: Uint128, mut f: F) -> Uint128
+    where
+        F: FnMut(Uint128) -> StdResult<Uint128>,
+    {
+        let mut value = |x: Uint128| f(x).ok();
+        let start = low;
+
+        while high - low > Uint128::new(2) {
+            let third = (high - low) / Uint128::new(3);
+            let m1 = low + third;
+            let m2 = high - third;
+
+            let (v1, v2) = (value(m1), value(m2));
+            // On ties the maximum is kept at `m1` or between the arguments
+            if v1 < v2 {
+                low = m1 + Uint128::one();
+            } else {
+                high = m2 - Uint128::one();
+            }
+        }
+
+        let best = (low.u128()..=high.u128())
+            .rev()
+            .map(Uint128::new)
+            .max_by_key(|x| value(*x))
+            .unwrap_or(low);
+
+        // Rounding may reach the maximum again far left of it, thus the smallest argument reaching
+        // it is looked for on a grid first, then by bisection after the previous grid point
+        let best_value = value(best);
+        let step = ((best - start) / Uint128::new(TIE_BREAK_POINTS)).max(Uint128::one());
+        let mut high = best;
+        let mut low = start;
+        let mut point = start;
+        while point < best {
+            if value(point) >= best_value {
+                high = point;
+                break;
+            }
+            low = point + Uint128::one();
+            point += step;
+        }
+
+        while low < high {
+            let mid = low + (high - low) / Uint128::new(2);
+            if value(mid) >= best_value {
+                high = mid;
+            } else {
+                low = mid + Uint128::one();
+            }
+        }
+
+        high
+    }
+
+    /// Scales `deposits` up until `provide` mints at least `share_amount` lps. Returns the
+    /// deposits together with the amount of lps they mint.
+    pub fn refine_deposits<F, E>(
+        mut deposits: Vec<Uint128>,
+        share_amount: Uint128,
+        mut provide: F,
+    ) -> Result<(Vec<Uint128>, Uint128), E>
+    where
+        F: FnMut(&[Uint128]) -> Result<Uint128, E>,
+        E: From<StdError>,
+    {
+        for _ in 0..REFINE_ITERATIONS {
+            let minted = provide(&deposits)?;
+            if minted >= share_amount {
+                return Ok((deposits, minted));
+            }
+
+            deposits = deposits
+                .iter()
+                .map(|deposit| {
+                    let scaled =
+                        deposit.multiply_ratio_ceil(share_amount, minted.max(Uint128::one()))?;
+                    Ok(scaled.max(*deposit + Uint128::one()))
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+        }
+
+        Err(StdError::generic_err("Deposits refinement is not converging").into())
+    }
+
+    /// Finds the smallest amount for which the non-increasing function `f` drops to `target` or
+    /// below. The upper bound is found by doubling `high`, then the bracket is narrowed with
+    /// secant steps interleaved with bisections, which keeps the worst case logarithmic.
+    pub fn solve_decreasing<F, E>(
+        mut high: Uint128,
+        target: Decimal256,
+        mut f: F,
+    ) -> Result<Uint128, E>
+    where
+        F: FnMut(Uint128) -> Result<Decimal256, E>,
+        E: From<StdError>,
+    {
+        let mut low = Uint128::zero();
+        let mut f_low = f(low)?;
+        if f_low <= target {
+            return Ok(low);
+        }
+
+        high = high.max(Uint128::one());
+        let mut f_high = f(high)?;
+        let mut doublings = 0;
+        while f_high > target {
+            if doublings == BRACKET_ITERATIONS {
+                return Err(StdError::generic_err("Target is unreachable").into());
+            }
+            (low, f_low) = (high, f_high);
+            high = high.checked_mul(Uint128::new(2)).map_err(StdError::from)?;
+            f_high = f(high)?;
+            doublings += 1;
+        }
+
+        let mut bisect = false;
+        while high - low > Uint128::one() {
+            let mid = if bisect {
+                low + (high - low) / Uint128::new(2)
+            } else {
+                let fraction = (f_low - target) / (f_low - f_high);
+                let step: Uint128 = (Uint256::from(high - low) * fraction)
+                    .try_into()
+                    .map_err(StdError::from)?;
+                low + step.clamp(Uint128::one(), high - low - Uint128::one())
+            };
+            bisect = !bisect;
+
+            let f_mid = f(mid)?;
+            if f_mid <= target {
+                (high, f_high) = (mid, f_mid);
+            } else {
+                (low, f_low) = (mid, f_mid);
+            }
+        }
+
+        Ok(high)
+    }
+
+    /// Finds the smallest amount for which the non-increasing function `f` drops to `target` or
+    /// below with Newton steps. `f` returns its value along with the rate it decreases at per
+    /// `unit` of the amount.
+    ///
+    /// The tangent is taken at the end of the bracket closest to the target. Steps from below aim
+    /// at the smallest amount past the root of the tangent and steps from above at the largest
+    /// one before it, so that an accurate tangent closes the bracket. Until the root is
+    /// bracketed, steps which don't halve the distance to the target grow twice as large as the
+    /// previous one, as rounding leaves `f` flat on short ranges. Once it is bracketed, steps
+    /// leaving the bracket or following a step which didn't halve it are bisections, which keeps
+    /// the worst case logarithmic.
+    pub fn newton_decreasing<F, E>(
+        target: Decimal256,
+        unit: Uint128,
+        mut f: F,
+    ) -> Result<Uint128, E>
+    where
+        F: FnMut(Uint128) -> Result<(Decimal256, Decimal256), E>,
+        E: From<StdError>,
+    {
+        let mut low = Uint128::zero();
+        let (mut low_value, mut low_slope) = f(low)?;
+        if low_value <= target {
+            return Ok(low);
+        }
+
+        let unit = Decimal256::from_ratio(unit, 1u8);
+        // The distance to the root of the tangent in amounts
+        let tangent_step = |distance: Decimal256, slope: Decimal256| {
+            distance
+                .checked_div(slope)
+                .ok()
+                .and_then(|step| step.checked_mul(unit).ok())
+        };
+        let step_up = |value: Decimal256, slope: Decimal256| {
+            tangent_step(value - target, slope)
+                .and_then(|step| Uint128::try_from(step.ceil().to_uint256()).ok())
+        };
+        let step_down = |value: Decimal256, slope: Decimal256| {
+            tangent_step(target - value, slope)
+                .and_then(|step| Uint128::try_from(step.to_uint256()).ok())
+                .and_then(|step| step.checked_add(Uint128::one()).ok())
+        };
+
+        // The smallest amount known to reach the target along with the value and slope at it
+        let mut high: Option<(Uint128, Decimal256, Decimal256)> = None;
+        let mut last_step = Uint128::zero();
+        // Whether the last step made less than half of the expected progress
+        let mut slow = false;
+        for _ in 0..NEWTON_ITERATIONS {
+            let next = match high {
+                None => {
+                    let step = step_up(low_value, low_slope)
+                        .unwrap_or(low)
+                        .max(if slow {
+                            last_step.saturating_mul(Uint128::new(2))
+                        } else {
+                            Uint128::one()
+                        })
+                        .max(Uint128::one());
+                    last_step = step;
+                    match low.checked_add(step) {
+                        Ok(next) => next,
+                        Err(_) => break,
+                    }
+                }
+                Some((high, high_value, high_slope)) => {
+                    // Where `f` starts to reach the target exactly is left to bisections, as the
+                    // tangent can't tell it
+                    let candidate = if slow || high_value == target {
+                        None
+                    } else if low_value - target <= target - high_value {
+                        step_up(low_value, low_slope).and_then(|step| low.checked_add(step).ok())
+                    } else {
+                        step_down(high_value, high_slope)
+                            .and_then(|step| high.checked_sub(step).ok())
+                    };
+
+                    candidate
+                        .filter(|candidate| low < *candidate && *candidate < high)
+                        .unwrap_or(low + (high - low) / Uint128::new(2))
+                }
+            };
+
+            let distance = low_value - target;
+            let bracket = high.map(|(high, ..)| high - low);
+            let (value, slope) = f(next)?;
+            if value <= target {
+                high = Some((next, value, slope));
+            } else {
+                (low, low_value, low_slope) = (next, value, slope);
+            }
+
+            slow = match (bracket, high) {
+                (Some(bracket), Some((high, ..))) => high - low > bracket / Uint128::new(2),
+                (None, None) => low_value - target > distance / Decimal256::from_ratio(2u8, 1u8),
+                _ => false,
+            };
+            if let Some((high, ..)) = high.filter(|(high, ..)| *high - low <= Uint128::one()) {
+                return Ok(high);
+            }
+        }
+
+        Err(StdError::generic_err(if high.is_none() {
+            "Target is unreachable"
+        } else {
+            "Newton search is not converging"
+        })
+        .into())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn newton_search_finds_the_start_of_a_plateau() {
+            // Rounding leaves `f` flat over a thousand amounts, while the slope is exact
+            let f = |amount: Uint128| -> StdResult<(Decimal256, Decimal256)> {
+                let value = Decimal256::from_ratio(1_000_000u128, 1u8)
+                    .saturating_sub(Decimal256::from_ratio(amount / Uint128::new(1_000), 1u8));
+                Ok((value, Decimal256::one()))
+            };
+
+            for target in [999_000u128, 123_456, 1] {
+                let amount =
+                    newton_decreasing(Decimal256::from_ratio(target, 1u8), Uint128::new(1_000), f)
+                        .unwrap();
+                assert_eq!(amount, Uint128::new((1_000_000 - target) * 1_000));
+            }
+        }
+
+        #[test]
+        fn newton_search_rejects_unreachable_targets() {
+            let err = newton_decreasing(Decimal256::zero(), Uint128::one(), |_| {
+                StdResult::Ok((Decimal256::one(), Decimal256::zero()))
+            })
+            .unwrap_err();
+
+            assert_eq!(err.to_string(), "Generic error: Target is unreachable");
+        }
+    }
+}
+
+pub mod prices {
+    use cosmwasm_std::Decimal256;
+
+    /// Returns the price of the offer asset in ask assets for exchanging `offer_amount` into
+    /// `ask_amount`, zero if nothing is offered.
+    pub fn price(offer_amount: Decimal256, ask_amount: Decimal256) -> Decimal256 {
+        ask_amount.checked_div(offer_amount).unwrap_or_default()
+    }
+
+    /// Returns the price impact of a swap excluding fees, i.e. the relative shortfall of
+    /// `ask_amount` compared to `offer_amount` exchanged at `spot_price`.
+    ///
+    /// * **ask_amount** the amount of ask assets before fees are charged.
+    pub fn price_impact(
+        offer_amount: Decimal256,
+        ask_amount: Decimal256,
+        spot_price: Decimal256,
+    ) -> Decimal256 {
+        let ideal_amount = match offer_amount.checked_mul(spot_price) {
+            Ok(ideal_amount) => ideal_amount,
+            // Swapped amounts are token amounts, thus nothing next to an out of range amount
+            Err(_) => return Decimal256::one(),
+        };
+
+        ideal_amount
+            .saturating_sub(ask_amount)
+            .checked_div(ideal_amount)
+            .unwrap_or_default()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn price_of_nothing_offered_is_zero() {
+            assert_eq!(
+                price(Decimal256::percent(400), Decimal256::one()),
+                Decimal256::percent(25)
+            );
+            assert_eq!(
+                price(Decimal256::zero(), Decimal256::one()),
+                Decimal256::zero()
+            );
+        }
+
+        #[test]
+        fn price_impact_is_the_relative_shortfall_at_the_spot_price() {
+            let offer_amount = Decimal256::from_ratio(1_000u128, 1u8);
+            let spot_price = Decimal256::percent(200);
+
+            assert_eq!(
+                price_impact(
+                    offer_amount,
+                    Decimal256::from_ratio(1_990u128, 1u8),
+                    spot_price
+                ),
+                Decimal256::permille(5)
+            );
+            // Swaps at a better price than the spot one have no impact
+            assert_eq!(
+                price_impact(
+                    offer_amount,
+                    Decimal256::from_ratio(2_010u128, 1u8),
+                    spot_price
+                ),
+                Decimal256::zero()
+            );
+            assert_eq!(
+                price_impact(Decimal256::zero(), Decimal256::zero(), spot_price),
+                Decimal256::zero()
+            );
+            assert_eq!(
+                price_impact(offer_amount, Decimal256::zero(), Decimal256::zero()),
+                Decimal256::zero()
+            );
+        }
+
+        #[test]
+        fn price_impact_of_amounts_beyond_decimal_is_total() {
+            assert_eq!(
+                price_impact(Decimal256::MAX, Decimal256::one(), Decimal256::percent(200)),
+                Decimal256::one()
+            );
+        }
+    }
+}
+
+pub mod depth {
+    use super::search::solve_decreasing;
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{Decimal, Decimal256, StdError, Uint128};
+    use tsify::Tsify;
+
+    #[cw_serde]
+    #[derive(Tsify)]
+    pub struct DepthResponse {
+        /// The price impact threshold
+        pub price_impact: Decimal,
+        /// The maximum amounts of every asset which can be offered within the threshold
+        pub offer_amounts: Vec<Uint128>,
+    }
+
+    /// Computes the maximum offer amount of every asset whose swap stays within each of
+    /// `price_impacts`.
+    ///
+    /// * **price_impact** returns the price impact of offering an amount of the asset with the
+    ///   given index, it must be non-decreasing in the amount.
+    pub fn compute_depth<F, E>(
+        price_impacts: &[Decimal],
+        asset_amounts: &[Uint128],
+        mut price_impact: F,
+    ) -> Result<Vec<DepthResponse>, E>
+    where
+        F: FnMut(usize, Uint128) -> Result<Decimal256, E>,
+        E: From<StdError>,
+    {
+        price_impacts
+            .iter()
+            .map(|threshold| {
+                if *threshold >= Decimal::one() {
+                    return Err(StdError::generic_err(
+                        "Price impact threshold must be less than 1",
+                    )
+                    .into());
+                }
+
+                let offer_amounts = asset_amounts
+                    .iter()
+                    .enumerate()
+                    .map(|(offer_ind, offer_pool)| {
+                        // `1 + threshold - impact` is non-increasing and drops to 1 once the
+                        // threshold is reached
+                        let limit = Decimal256::one() + Decimal256::from(*threshold);
+                        let amount = solve_decreasing(
+                            *offer_pool,
+                            Decimal256::one(),
+                            |amount| -> Result<Decimal256, E> {
+                                Ok(limit.saturating_sub(price_impact(offer_ind, amount)?))
+                            },
+                        )?;
+
+                        if amount.is_zero()
+                            || price_impact(offer_ind, amount)? <= Decimal256::from(*threshold)
+                        {
+                            Ok(amount)
+                        } else {
+                            Ok(amount - Uint128::one())
+                        }
+                    })
+                    .collect::<Result<Vec<_>, E>>()?;
+
+                Ok(DepthResponse {
+                    price_impact: *threshold,
+                    offer_amounts,
+                })
+            })
+            .collect()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use cosmwasm_std::StdResult;
+
+        #[test]
+        fn depth_offers_the_largest_amount_within_every_threshold() {
+            // The impact reaches a threshold one unit before the amount proportional to it
+            let pools = [Uint128::new(1_000_000), Uint128::new(2_000_000)];
+            let price_impact = |offer_ind: usize, amount: Uint128| {
+                StdResult::Ok(Decimal256::from_ratio(
+                    amount + Uint128::one(),
+                    pools[offer_ind],
+                ))
+            };
+
+            let depth = compute_depth(
+                &[Decimal::zero(), Decimal::percent(1), Decimal::percent(10)],
+                &pools,
+                price_impact,
+            )
+            .unwrap();
+
+            let offer_amounts: Vec<_> = depth
+                .iter()
+                .map(|response| (response.price_impact, response.offer_amounts.clone()))
+                .collect();
+            assert_eq!(
+                offer_amounts,
+                [
+                    (Decimal::zero(), vec![Uint128::zero(), Uint128::zero()]),
+                    (
+                        Decimal::percent(1),
+                        vec![Uint128::new(9_999), Uint128::new(19_999)]
+                    ),
+                    (
+                        Decimal::percent(10),
+                        vec![Uint128::new(99_999), Uint128::new(199_999)]
+                    ),
+                ]
+            );
+        }
+
+        #[test]
+        fn depth_rejects_thresholds_of_one() {
+            let err = compute_depth(&[Decimal::one()], &[Uint128::new(1_000)], |_, _| {
+                StdResult::Ok(Decimal256::zero())
+            })
+            .unwrap_err();
+
+            assert_eq!(
+                err.to_string(),
+                "Generic error: Price impact threshold must be less than 1"
+            );
+        }
+
+        #[test]
+        fn depth_fails_with_the_simulation() {
+            let err = compute_depth(&[Decimal::percent(1)], &[Uint128::new(1_000)], |_, _| {
+                StdResult::<Decimal256>::Err(StdError::generic_err("Swap failed"))
+            })
+            .unwrap_err();
+
+            assert_eq!(err.to_string(), "Generic error: Swap failed");
+        }
+    }
+}
+
+pub mod ladder {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{Decimal, StdError, Uint128};
+    use tsify::Tsify;
+
+    #[cw_serde]
+    #[derive(Tsify)]
+    pub struct LadderLevel {
+        /// The price of the first asset in the second asset units
+        pub price: Decimal,
+        /// The cumulative amount of the first asset traded to reach the price
+        pub base_amount: Uint128,
+        /// The cumulative amount of the second asset traded to reach the price
+        pub quote_amount: Uint128,
+    }
+
+    #[cw_serde]
+    #[derive(Tsify)]
+    pub struct LadderResponse {
+        /// The current price of the first asset in the second asset units
+        pub mid_price: Decimal,
+        /// The levels below the mid price, where the pool buys the first asset
+        pub bids: Vec<LadderLevel>,
+        /// The levels above the mid price, where the pool sells the first asset
+        pub asks: Vec<LadderLevel>,
+    }
+
+    /// Builds up to `levels` price levels spaced by `price_step` on both sides of `mid_price`.
+    /// Bids stop before the price drops to zero.
+    ///
+    /// * **swap_to_price** returns the offer and return amounts of the swap which moves the price
+    ///   of the first asset to the given one.
+    pub fn compute_ladder<F, E>(
+        mid_price: Decimal,
+        price_step: Decimal,
+        levels: u32,
+        mut swap_to_price: F,
+    ) -> Result<LadderResponse, E>
+    where
+        F: FnMut(Decimal) -> Result<(Uint128, Uint128), E>,
+        E: From<StdError>,
+    {
+        if price_step.is_zero() {
+            return Err(StdError::generic_err("Price step must be positive").into());
+        }
+
+        let mut bids = vec![];
+        let mut asks = vec![];
+        for level in 1..=levels {
+            let offset = price_step
+                .checked_mul(Decimal::from_ratio(level, 1u8))
+                .map_err(StdError::from)?;
+
+            if offset < mid_price {
+                let price = mid_price - offset;
+                let (offer_amount, return_amount) = swap_to_price(price)?;
+                bids.push(LadderLevel {
+                    price,
+                    base_amount: offer_amount,
+                    quote_amount: return_amount,
+                });
+            }
+
+            let price = mid_price.checked_add(offset).map_err(StdError::from)?;
+            let (offer_amount, return_amount) = swap_to_price(price)?;
+            asks.push(LadderLevel {
+                price,
+                base_amount: return_amount,
+                quote_amount: offer_amount,
+            });
+        }
+
+        Ok(LadderResponse {
+            mid_price,
+            bids,
+            asks,
+        })
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use cosmwasm_std::StdResult;
+
+        /// Offers a thousand units per unit of distance to the mid price of 1, and receives a
+        /// hundred.
+        fn swap_to_price(price: Decimal) -> StdResult<(Uint128, Uint128)> {
+            let distance = price.abs_diff(Decimal::one());
+            Ok((Uint128::new(1_000) * distance, Uint128::new(100) * distance))
+        }
+
+        fn level(price: &str, base_amount: u128, quote_amount: u128) -> LadderLevel {
+            LadderLevel {
+                price: price.parse().unwrap(),
+                base_amount: Uint128::new(base_amount),
+                quote_amount: Uint128::new(quote_amount),
+            }
+        }
+
+        #[test]
+        fn ladder_stops_the_bids_before_a_zero_price() {
+            let ladder =
+                compute_ladder(Decimal::one(), Decimal::percent(30), 4, swap_to_price).unwrap();
+
+            assert_eq!(
+                ladder,
+                LadderResponse {
+                    mid_price: Decimal::one(),
+                    bids: vec![
+                        level("0.7", 300, 30),
+                        level("0.4", 600, 60),
+                        level("0.1", 900, 90),
+                    ],
+                    asks: vec![
+                        level("1.3", 30, 300),
+                        level("1.6", 60, 600),
+                        level("1.9", 90, 900),
+                        level("2.2", 120, 1_200),
+                    ],
+                }
+            );
+        }
+
+        #[test]
+        fn ladder_rejects_a_zero_step() {
+            let err =
+                compute_ladder(Decimal::one(), Decimal::zero(), 4, swap_to_price).unwrap_err();
+
+            assert_eq!(
+                err.to_string(),
+                "Generic error: Price step must be positive"
+            );
+        }
+
+        #[test]
+        fn ladder_fails_with_the_swap() {
+            let err = compute_ladder(Decimal::one(), Decimal::percent(1), 4, |_| {
+                StdResult::<(Uint128, Uint128)>::Err(StdError::generic_err("Swap failed"))
+            })
+            .unwrap_err();
+
+            assert_eq!(err.to_string(), "Generic error: Swap failed");
+        }
+    }
+}
+
+pub mod batch {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::StdError;
+    use std::fmt::Display;
+    use tsify::Tsify;
+
+    #[cw_serde]
+    #[derive(Tsify)]
+    pub enum BatchItem<T> {
+        /// The result of a successful simulation
+        Ok(T),
+        /// The error of a failed simulation
+        Error(String),
+    }
+
+    /// Ensures a request refers to a two-asset pool and a valid ask asset of it.
+    pub fn check_swap_request(asset_count: usize, ask_ind: usize) -> Result<(), StdError> {
+        if asset_count != 2 {
+            return Err(StdError::generic_err(format!(
+                "Wrong asset length: expected 2, actual {}",
+                asset_count
+            )));
+        }
+
+        if ask_ind >= asset_count {
+            return Err(StdError::generic_err(format!(
+                "Ask asset index {} is out of range",
+                ask_ind
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Runs `simulate` for every request against the pool with index `pool_ind(request)`.
+    /// A failed request is reported in its own item and doesn't fail the rest of the batch.
+    pub fn run_batch<P, R, T, E, F>(
+        pools: &[P],
+        requests: &[R],
+        pool_ind: impl Fn(&R) -> usize,
+        mut simulate: F,
+    ) -> Vec<BatchItem<T>>
+    where
+        F: FnMut(&P, &R) -> Result<T, E>,
+        E: Display,
+    {
+        requests
+            .iter()
+            .map(|request| {
+                let ind = pool_ind(request);
+                let result = match pools.get(ind) {
+                    Some(pool) => simulate(pool, request).map_err(|e| e.to_string()),
+                    None => Err(format!("Pool index {} is out of range", ind)),
+                };
+
+                match result {
+                    Ok(response) => BatchItem::Ok(response),
+                    Err(err) => BatchItem::Error(err),
+                }
+            })
+            .collect()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn swap_requests_need_two_assets_and_an_ask_asset_of_them() {
+            assert_eq!(check_swap_request(2, 1), Ok(()));
+            assert_eq!(
+                check_swap_request(3, 0).unwrap_err().to_string(),
+                "Generic error: Wrong asset length: expected 2, actual 3"
+            );
+            assert_eq!(
+                check_swap_request(2, 2).unwrap_err().to_string(),
+                "Generic error: Ask asset index 2 is out of range"
+            );
+        }
+
+        #[test]
+        fn batch_reports_the_failed_requests_in_their_items() {
+            let pools = [10u64, 0];
+            let requests = [(0, 5u64), (1, 5), (2, 5), (0, 2)];
+
+            let items = run_batch(
+                &pools,
+                &requests,
+                |(pool, _)| *pool,
+                |pool, (_, amount)| match pool {
+                    0 => Err(StdError::generic_err("Pool is empty")),
+                    pool => Ok(pool / amount),
+                },
+            );
+
+            assert_eq!(
+                items,
+                [
+                    BatchItem::Ok(2),
+                    BatchItem::Error("Generic error: Pool is empty".to_string()),
+                    BatchItem::Error("Pool index 2 is out of range".to_string()),
+                    BatchItem::Ok(5),
+                ]
+            );
+        }
+
+        #[test]
+        fn batch_items_are_tagged_by_their_outcome() {
+            let items = [BatchItem::Ok(1u8), BatchItem::Error("Failed".to_string())];
+
+            assert_eq!(
+                serde_json::to_string(&items).unwrap(),
+                r#"[{"ok":1},{"error":"Failed"}]"#
+            );
+        }
+    }
+}
+
+pub mod curve {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{Decimal256, StdError, StdResult, Uint128, Uint256};
+    use std::str::FromStr;
+    use tsify::Tsify;
+
+    use crate::astroport::cosmwasm_ext::Decimal256Ext;
+
+    /// The number of bisection steps used to find the ratio of log spaced amounts, enough to
+    /// reach the full precision of [`Decimal256`] for any ratio of [`Uint128`] amounts.
+    const ROOT_ITERATIONS: u8 = 192;
+
+    #[cw_serde]
+    #[derive(Tsify)]
+    pub enum Spacing {
+        /// Offer amounts differ by the same amount
+        Linear,
+        /// Offer amounts differ by the same factor
+        Log,
+    }
+
+    impl FromStr for Spacing {
+        type Err = StdError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "linear" => Ok(Spacing::Linear),
+                "log" => Ok(Spacing::Log),
+                _ => Err(StdError::generic_err(format!("Unknown spacing: {}", s))),
+            }
+        }
+    }
+
+    #[cw_serde]
+    #[derive(Tsify)]
+    pub struct CurvePoint {
+        /// The amount of offer assets
+        pub offer_amount: Uint128,
+        /// The amount of ask assets returned by the swap
+        pub return_amount: Uint128,
+        /// The amount of fees charged by the swap
+        pub commission_amount: Uint128,
+        /// The relative shortfall of the swap before fees compared to the spot price
+        pub price_impact: Decimal256,
+    }
+
+    /// Spreads `points` offer amounts from `min_amount` to `max_amount` inclusive.
+    pub fn offer_amounts(
+        min_amount: Uint128,
+        max_amount: Uint128,
+        points: u32,
+        spacing: &Spacing,
+    ) -> StdResult<Vec<Uint128>> {
+        if points < 2 {
+            return Err(StdError::generic_err("Number of points must be at least 2"));
+        }
+
+        if min_amount >= max_amount {
+            return Err(StdError::generic_err(
+                "Minimum offer amount must be less than the maximum one",
+            ));
+        }
+
+        let steps = points - 1;
+        match spacing {
+            Spacing::Linear => Ok((0..points)
+                .map(|i| min_amount + (max_amount - min_amount).multiply_ratio(i, steps))
+                .collect()),
+            Spacing::Log => {
+                if min_amount.is_zero() {
+                    return Err(StdError::generic_err(
+                        "Minimum offer amount must be positive for log spacing",
+                    ));
+                }
+
+                let min_amount = Decimal256::from_integer(min_amount);
+                let ratio = nth_root(Decimal256::from_integer(max_amount) / min_amount, steps)?;
+
+                let mut amounts = (0..steps)
+                    .map(|i| {
+                        let amount = min_amount.checked_mul(ratio.checked_pow(i)?)?;
+                        Ok((amount * Uint256::one()).try_into()?)
+                    })
+                    .collect::<StdResult<Vec<Uint128>>>()?;
+                amounts.push(max_amount);
+
+                Ok(amounts)
+            }
+        }
+    }
+
+    /// Finds the `n`-th root of `value` >= 1 by bisection.
+    fn nth_root(value: Decimal256, n: u32) -> StdResult<Decimal256> {
+        let exceeds = |root: Decimal256| root.checked_pow(n).map_or(true, |pow| pow > value);
+
+        let mut low = Decimal256::one();
+        let mut high = Decimal256::from_ratio(2u8, 1u8);
+        while !exceeds(high) {
+            low = high;
+            high = high.checked_mul(high)?;
+        }
+
+        for _ in 0..ROOT_ITERATIONS {
+            let mid = low + (high - low) / Decimal256::from_ratio(2u8, 1u8);
+            if mid == low || mid == high {
+                break;
+            }
+
+            if exceeds(mid) {
+                high = mid;
+            } else {
+                low = mid;
+            }
+        }
+
+        Ok(low)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn amounts(amounts: &[u128]) -> Vec<Uint128> {
+            amounts.iter().copied().map(Uint128::new).collect()
+        }
+
+        #[test]
+        fn offer_amounts_are_spaced_from_the_minimum_to_the_maximum() {
+            let linear =
+                offer_amounts(Uint128::zero(), Uint128::new(100), 5, &Spacing::Linear).unwrap();
+            let log = offer_amounts(Uint128::one(), Uint128::new(1_000), 4, &Spacing::Log).unwrap();
+            let uneven =
+                offer_amounts(Uint128::new(3), Uint128::new(10), 3, &Spacing::Linear).unwrap();
+
+            assert_eq!(linear, amounts(&[0, 25, 50, 75, 100]));
+            assert_eq!(log, amounts(&[1, 10, 100, 1_000]));
+            assert_eq!(uneven, amounts(&[3, 6, 10]));
+        }
+
+        #[test]
+        fn offer_amounts_reject_invalid_ranges() {
+            let err = |min: u128, max: u128, points: u32, spacing: Spacing| {
+                offer_amounts(Uint128::new(min), Uint128::new(max), points, &spacing)
+                    .unwrap_err()
+                    .to_string()
+            };
+
+            assert_eq!(
+                err(0, 100, 1, Spacing::Linear),
+                "Generic error: Number of points must be at least 2"
+            );
+            assert_eq!(
+                err(100, 100, 2, Spacing::Linear),
+                "Generic error: Minimum offer amount must be less than the maximum one"
+            );
+            assert_eq!(
+                err(0, 100, 2, Spacing::Log),
+                "Generic error: Minimum offer amount must be positive for log spacing"
+            );
+        }
+
+        #[test]
+        fn spacing_is_parsed_from_its_name() {
+            assert_eq!("linear".parse::<Spacing>().unwrap(), Spacing::Linear);
+            assert_eq!("log".parse::<Spacing>().unwrap(), Spacing::Log);
+            assert_eq!(
+                "Log".parse::<Spacing>().unwrap_err().to_string(),
+                "Generic error: Unknown spacing: Log"
+            );
+        }
+    }
+}
+
+pub mod guards {
+    use cosmwasm_std::Uint128;
+    use thiserror::Error;
+
+    use crate::astroport::asset::AssetInfo;
+
+    /// Violations of the minimum amounts accepted by provides and withdraws, worded the same as
+    /// by the pair contracts.
+    #[derive(Error, Debug, PartialEq)]
+    pub enum GuardError {
+        #[error("Slippage is more than expected: received {0}, expected {1} LP tokens")]
+        ProvideSlippageViolation(Uint128, Uint128),
+
+        #[error("Received {received} {asset_name} but expected {expected}")]
+        WithdrawSlippageViolation {
+            asset_name: String,
+            received: Uint128,
+            expected: Uint128,
+        },
+
+        #[error("Wrong asset length: expected {expected}, actual {actual}")]
+        WrongAssetLength { expected: usize, actual: usize },
+    }
+
+    /// Ensures the provide mints at least `min_lp_to_receive` lps.
+    pub fn ensure_min_lp_to_receive(
+        share_amount: Uint128,
+        min_lp_to_receive: Option<Uint128>,
+    ) -> Result<(), GuardError> {
+        match min_lp_to_receive {
+            Some(min_lp_to_receive) if share_amount < min_lp_to_receive => Err(
+                GuardError::ProvideSlippageViolation(share_amount, min_lp_to_receive),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Ensures every returned asset is at least its `min_assets_to_receive` counterpart.
+    ///
+    /// The assets are named by `asset_infos` as by the contracts. Without asset infos, e.g. in the
+    /// simulations of a single pool type, they are named `asset #<index>` by their index in the
+    /// pool.
+    pub fn ensure_min_assets_to_receive(
+        returned_amounts: &[Uint128],
+        min_assets_to_receive: Option<Vec<Uint128>>,
+        asset_infos: Option<&[AssetInfo]>,
+    ) -> Result<(), GuardError> {
+        if let Some(min_assets_to_receive) = min_assets_to_receive {
+            if min_assets_to_receive.len() != returned_amounts.len() {
+                return Err(GuardError::WrongAssetLength {
+                    expected: returned_amounts.len(),
+                    actual: min_assets_to_receive.len(),
+                });
+            }
+
+            for (i, (received, expected)) in returned_amounts
+                .iter()
+                .zip(min_assets_to_receive)
+                .enumerate()
+            {
+                ensure_min_return_amount(
+                    i,
+                    *received,
+                    Some(expected),
+                    asset_infos.and_then(|asset_infos| asset_infos.get(i)),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Ensures the asset with the index `ind` is returned for at least `min_return_amount`, see
+    /// [`ensure_min_assets_to_receive`] for its name.
+    pub fn ensure_min_return_amount(
+        ind: usize,
+        return_amount: Uint128,
+        min_return_amount: Option<Uint128>,
+        asset_info: Option<&AssetInfo>,
+    ) -> Result<(), GuardError> {
+        match min_return_amount {
+            Some(min_return_amount) if return_amount < min_return_amount => {
+                Err(GuardError::WithdrawSlippageViolation {
+                    asset_name: asset_info
+                        .map(AssetInfo::to_string)
+                        .unwrap_or_else(|| format!("asset #{ind}")),
+                    received: return_amount,
+                    expected: min_return_amount,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use cosmwasm_std::Addr;
+
+        #[test]
+        fn min_lp_to_receive_is_inclusive() {
+            assert_eq!(ensure_min_lp_to_receive(Uint128::new(100), None), Ok(()));
+            assert_eq!(
+                ensure_min_lp_to_receive(Uint128::new(100), Some(Uint128::new(100))),
+                Ok(())
+            );
+            assert_eq!(
+                ensure_min_lp_to_receive(Uint128::new(99), Some(Uint128::new(100)))
+                    .unwrap_err()
+                    .to_string(),
+                "Slippage is more than expected: received 99, expected 100 LP tokens"
+            );
+        }
+
+        #[test]
+        fn min_assets_to_receive_name_the_first_violation() {
+            let returned_amounts = [Uint128::new(10), Uint128::new(20)];
+            let asset_infos = [
+                AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+                AssetInfo::Token {
+                    contract_addr: Addr::unchecked("token"),
+                },
+            ];
+
+            assert_eq!(
+                ensure_min_assets_to_receive(&returned_amounts, None, None),
+                Ok(())
+            );
+            assert_eq!(
+                ensure_min_assets_to_receive(
+                    &returned_amounts,
+                    Some(returned_amounts.to_vec()),
+                    None
+                ),
+                Ok(())
+            );
+            assert_eq!(
+                ensure_min_assets_to_receive(
+                    &returned_amounts,
+                    Some(vec![Uint128::new(11), Uint128::new(21)]),
+                    None
+                )
+                .unwrap_err()
+                .to_string(),
+                "Received 10 asset #0 but expected 11"
+            );
+            assert_eq!(
+                ensure_min_assets_to_receive(
+                    &returned_amounts,
+                    Some(vec![Uint128::new(10), Uint128::new(21)]),
+                    Some(&asset_infos)
+                )
+                .unwrap_err()
+                .to_string(),
+                "Received 20 token but expected 21"
+            );
+        }
+
+        #[test]
+        fn min_assets_to_receive_need_one_amount_per_asset() {
+            assert_eq!(
+                ensure_min_assets_to_receive(
+                    &[Uint128::one(); 2],
+                    Some(vec![Uint128::one()]),
+                    None
+                ),
+                Err(GuardError::WrongAssetLength {
+                    expected: 2,
+                    actual: 1
+                })
+            );
+        }
+
+        #[test]
+        fn min_return_amount_names_the_asset_by_its_index_without_info() {
+            assert_eq!(
+                ensure_min_return_amount(1, Uint128::new(5), None, None),
+                Ok(())
+            );
+            assert_eq!(
+                ensure_min_return_amount(1, Uint128::new(5), Some(Uint128::new(6)), None)
+                    .unwrap_err()
+                    .to_string(),
+                "Received 5 asset #1 but expected 6"
+            );
+        }
+    }
+}
@@ -1,5 +1,8 @@
+pub mod asset;
 pub mod cosmwasm_ext;
 pub mod lib;
+pub mod pair;
 pub mod pair_concentrated;
 pub mod pair_stable;
 pub mod pair_xyk;
+pub mod pool;
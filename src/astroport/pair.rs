@@ -0,0 +1,471 @@
+//! Queries of the pair contracts, answered from a pool state instead of the chain.
+//!
+//! The messages and responses mirror `astroport::pair`, so a client can send the same query
+//! either to a pair contract or to [`Pair::query`].
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Binary, Decimal, StdError, StdResult, Uint128};
+use tsify::Tsify;
+
+use crate::astroport::asset::{Asset, AssetInfo};
+use crate::astroport::pool::{Pool, PoolState, ProvideResponse, ReverseSwapResponse, SwapResponse};
+
+/// The queries supported by [`Pair::query`], a subset of the pair contract queries.
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "PairQueryMsg")]
+pub enum QueryMsg {
+    /// Returns the balances of the pool and the total amount of issued lps
+    Pool {},
+    /// Returns the amount of assets an amount of lps is worth
+    Share { amount: Uint128 },
+    Simulation {
+        offer_asset: Asset,
+        ask_asset_info: Option<AssetInfo>,
+    },
+    ReverseSimulation {
+        offer_asset_info: Option<AssetInfo>,
+        ask_asset: Asset,
+    },
+    /// Returns the amount of lps received for providing assets
+    SimulateProvide {
+        assets: Vec<Asset>,
+        slippage_tolerance: Option<Decimal>,
+    },
+    /// Returns the amount of assets received for withdrawing lps
+    SimulateWithdraw { lp_amount: Uint128 },
+}
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "PairPoolResponse")]
+pub struct PoolResponse {
+    pub assets: Vec<Asset>,
+    pub total_share: Uint128,
+}
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "PairSimulationResponse")]
+pub struct SimulationResponse {
+    pub return_amount: Uint128,
+    pub spread_amount: Uint128,
+    pub commission_amount: Uint128,
+}
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "PairReverseSimulationResponse")]
+pub struct ReverseSimulationResponse {
+    pub offer_amount: Uint128,
+    pub spread_amount: Uint128,
+    pub commission_amount: Uint128,
+}
+
+/// The state of a pair contract: a pool state along with the assets of its balances.
+#[cw_serde]
+#[derive(Tsify)]
+pub struct PairState {
+    /// The assets of the pool in the order of its balances
+    pub asset_infos: Vec<AssetInfo>,
+    pub pool: PoolState,
+}
+
+/// A pair built once from its state, see [`PairState`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pair {
+    asset_infos: Vec<AssetInfo>,
+    pool: Pool,
+}
+
+impl Pair {
+    pub fn new(state: PairState) -> StdResult<Self> {
+        let pool = Pool::new(state.pool)?;
+
+        let asset_amounts = pool.asset_amounts()?;
+        if state.asset_infos.len() != asset_amounts.len() {
+            return Err(StdError::generic_err(format!(
+                "Wrong number of asset infos: expected {}, actual {}",
+                asset_amounts.len(),
+                state.asset_infos.len()
+            )));
+        }
+
+        Ok(Self {
+            asset_infos: state.asset_infos,
+            pool,
+        })
+    }
+
+    /// Returns the index of `info` in the pool balances.
+    fn asset_ind(&self, info: &AssetInfo) -> StdResult<usize> {
+        self.asset_infos
+            .iter()
+            .position(|asset_info| asset_info == info)
+            .ok_or_else(asset_mismatch)
+    }
+
+    /// Returns the index of the asset opposite to `ind`, checking it against `info` if given.
+    fn other_asset_ind(&self, ind: usize, info: Option<&AssetInfo>) -> StdResult<usize> {
+        let other_ind = 1 - ind;
+        match info {
+            Some(info) if self.asset_ind(info)? != other_ind => Err(asset_mismatch()),
+            _ => Ok(other_ind),
+        }
+    }
+
+    fn assets(&self, amounts: Vec<Uint128>) -> Vec<Asset> {
+        self.asset_infos
+            .iter()
+            .zip(amounts)
+            .map(|(info, amount)| Asset {
+                info: info.clone(),
+                amount,
+            })
+            .collect()
+    }
+
+    /// Simulates burning `amount` lps. The withdraw fails as in the pair contract when an asset is
+    /// returned for less than its `min_assets_to_receive` counterpart.
+    pub fn withdraw(
+        &self,
+        amount: Uint128,
+        min_assets_to_receive: Option<Vec<Uint128>>,
+    ) -> StdResult<Vec<Asset>> {
+        let response =
+            self.pool
+                .withdraw(amount, min_assets_to_receive, Some(&self.asset_infos))?;
+        Ok(self.assets(response.returned_amounts().to_vec()))
+    }
+
+    /// Answers `msg` with the response the pair contract would return.
+    pub fn query(&self, msg: QueryMsg) -> StdResult<Binary> {
+        match msg {
+            QueryMsg::Pool {} => to_json_binary(&PoolResponse {
+                assets: self.assets(self.pool.asset_amounts()?),
+                total_share: self.pool.total_share(),
+            }),
+            QueryMsg::Share { amount } | QueryMsg::SimulateWithdraw { lp_amount: amount } => {
+                to_json_binary(&self.withdraw(amount, None)?)
+            }
+            QueryMsg::Simulation {
+                offer_asset,
+                ask_asset_info,
+            } => {
+                let offer_ind = self.asset_ind(&offer_asset.info)?;
+                let ask_ind = self.other_asset_ind(offer_ind, ask_asset_info.as_ref())?;
+
+                to_json_binary(&match self.pool.swap(offer_asset.amount, ask_ind)? {
+                    SwapResponse::Xyk(response) => SimulationResponse {
+                        return_amount: response.return_amount,
+                        spread_amount: response.spread_amount,
+                        commission_amount: response.commission_amount,
+                    },
+                    SwapResponse::Stable(response) => SimulationResponse {
+                        return_amount: response.return_amount,
+                        spread_amount: response.spread_amount,
+                        commission_amount: response.commission_amount,
+                    },
+                    SwapResponse::Concentrated(response) => SimulationResponse {
+                        return_amount: response.return_amount,
+                        spread_amount: response.spread_amount,
+                        commission_amount: response.commission_amount,
+                    },
+                })
+            }
+            QueryMsg::ReverseSimulation {
+                offer_asset_info,
+                ask_asset,
+            } => {
+                let ask_ind = self.asset_ind(&ask_asset.info)?;
+                self.other_asset_ind(ask_ind, offer_asset_info.as_ref())?;
+
+                to_json_binary(&match self.pool.reverse_swap(ask_asset.amount, ask_ind)? {
+                    ReverseSwapResponse::Xyk(response) => ReverseSimulationResponse {
+                        offer_amount: response.offer_amount,
+                        spread_amount: response.spread_amount,
+                        commission_amount: response.commission_amount,
+                    },
+                    ReverseSwapResponse::Stable(response) => ReverseSimulationResponse {
+                        offer_amount: response.offer_amount,
+                        spread_amount: response.spread_amount,
+                        commission_amount: response.commission_amount,
+                    },
+                    ReverseSwapResponse::Concentrated(response) => ReverseSimulationResponse {
+                        offer_amount: response.offer_amount,
+                        spread_amount: response.spread_amount,
+                        commission_amount: response.commission_amount,
+                    },
+                })
+            }
+            QueryMsg::SimulateProvide {
+                assets,
+                slippage_tolerance,
+            } => {
+                let mut deposits = vec![Uint128::zero(); self.asset_infos.len()];
+                for asset in assets {
+                    let deposit = &mut deposits[self.asset_ind(&asset.info)?];
+                    *deposit = deposit.checked_add(asset.amount)?;
+                }
+
+                let share_amount = match self.pool.provide(&deposits, slippage_tolerance, None)? {
+                    ProvideResponse::Xyk(response) => response.share_amount,
+                    ProvideResponse::Stable(response) => response.share_amount,
+                    ProvideResponse::Concentrated(response) => response.share_amount,
+                };
+                to_json_binary(&share_amount)
+            }
+        }
+    }
+}
+
+fn asset_mismatch() -> StdError {
+    StdError::generic_err("Asset mismatch between the requested and the stored asset in contract")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astroport::pair_xyk;
+
+    fn xyk_pair() -> Pair {
+        Pair::new(PairState {
+            asset_infos: vec![
+                AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+                AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+            ],
+            pool: PoolState::Xyk(pair_xyk::pool::PoolState {
+                asset_amounts: vec![Uint128::new(1_000_000_000), Uint128::new(10_000_000)],
+                total_share: Uint128::new(100_000_000),
+                total_fee_rate: Decimal::permille(3),
+            }),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn withdraw_names_the_assets_by_their_infos() {
+        let err = xyk_pair()
+            .withdraw(
+                Uint128::new(1_000_000),
+                Some(vec![Uint128::new(10_000_000), Uint128::new(100_001)]),
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Received 100000 uusd but expected 100001"
+        );
+    }
+
+    fn info(denom: &str) -> AssetInfo {
+        AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        }
+    }
+
+    fn asset(denom: &str, amount: u128) -> Asset {
+        Asset {
+            info: info(denom),
+            amount: Uint128::new(amount),
+        }
+    }
+
+    fn query<T: serde::de::DeserializeOwned>(pair: &Pair, msg: QueryMsg) -> StdResult<T> {
+        cosmwasm_std::from_json(pair.query(msg)?)
+    }
+
+    #[test]
+    fn pool_and_share_queries_return_the_assets() {
+        let pair = xyk_pair();
+
+        let pool: PoolResponse = query(&pair, QueryMsg::Pool {}).unwrap();
+        assert_eq!(
+            pool,
+            PoolResponse {
+                assets: vec![asset("uluna", 1_000_000_000), asset("uusd", 10_000_000)],
+                total_share: Uint128::new(100_000_000),
+            }
+        );
+
+        let share: Vec<Asset> = query(
+            &pair,
+            QueryMsg::Share {
+                amount: Uint128::new(1_000_000),
+            },
+        )
+        .unwrap();
+        assert_eq!(share, [asset("uluna", 10_000_000), asset("uusd", 100_000)]);
+        let withdraw: Vec<Asset> = query(
+            &pair,
+            QueryMsg::SimulateWithdraw {
+                lp_amount: Uint128::new(1_000_000),
+            },
+        )
+        .unwrap();
+        assert_eq!(withdraw, share);
+    }
+
+    #[test]
+    fn simulations_swap_the_assets_named_by_their_infos() {
+        let pair = xyk_pair();
+        let swap = pair.pool.swap(Uint128::new(1_000_000), 1).unwrap();
+        let SwapResponse::Xyk(swap) = swap else {
+            unreachable!()
+        };
+
+        for ask_asset_info in [None, Some(info("uusd"))] {
+            let simulation: SimulationResponse = query(
+                &pair,
+                QueryMsg::Simulation {
+                    offer_asset: asset("uluna", 1_000_000),
+                    ask_asset_info,
+                },
+            )
+            .unwrap();
+            assert_eq!(
+                simulation,
+                SimulationResponse {
+                    return_amount: swap.return_amount,
+                    spread_amount: swap.spread_amount,
+                    commission_amount: swap.commission_amount,
+                }
+            );
+        }
+
+        let reverse: ReverseSimulationResponse = query(
+            &pair,
+            QueryMsg::ReverseSimulation {
+                offer_asset_info: Some(info("uluna")),
+                ask_asset: asset("uusd", 100_000),
+            },
+        )
+        .unwrap();
+        let ReverseSwapResponse::Xyk(reverse_swap) =
+            pair.pool.reverse_swap(Uint128::new(100_000), 1).unwrap()
+        else {
+            unreachable!()
+        };
+        assert_eq!(
+            reverse,
+            ReverseSimulationResponse {
+                offer_amount: reverse_swap.offer_amount,
+                spread_amount: reverse_swap.spread_amount,
+                commission_amount: reverse_swap.commission_amount,
+            }
+        );
+    }
+
+    #[test]
+    fn provides_add_up_the_assets_in_any_order() {
+        let pair = xyk_pair();
+        let provide = |assets| {
+            query::<Uint128>(
+                &pair,
+                QueryMsg::SimulateProvide {
+                    assets,
+                    slippage_tolerance: None,
+                },
+            )
+        };
+
+        let share_amount = provide(vec![asset("uluna", 10_000_000), asset("uusd", 100_000)]);
+        assert_eq!(share_amount, Ok(Uint128::new(1_000_000)));
+        assert_eq!(
+            provide(vec![
+                asset("uusd", 100_000),
+                asset("uluna", 4_000_000),
+                asset("uluna", 6_000_000),
+            ]),
+            share_amount
+        );
+        assert_eq!(
+            provide(vec![asset("uluna", u128::MAX), asset("uluna", 1)])
+                .unwrap_err()
+                .to_string(),
+            "Overflow: Cannot Add with 340282366920938463463374607431768211455 and 1"
+        );
+    }
+
+    #[test]
+    fn queries_of_other_assets_are_rejected() {
+        let pair = xyk_pair();
+        let mismatch =
+            "Generic error: Asset mismatch between the requested and the stored asset in contract";
+
+        assert_eq!(
+            pair.query(QueryMsg::Simulation {
+                offer_asset: asset("uatom", 1_000_000),
+                ask_asset_info: None,
+            })
+            .unwrap_err()
+            .to_string(),
+            mismatch
+        );
+        assert_eq!(
+            pair.query(QueryMsg::Simulation {
+                offer_asset: asset("uluna", 1_000_000),
+                ask_asset_info: Some(info("uluna")),
+            })
+            .unwrap_err()
+            .to_string(),
+            mismatch
+        );
+        assert_eq!(
+            pair.query(QueryMsg::ReverseSimulation {
+                offer_asset_info: Some(info("uatom")),
+                ask_asset: asset("uusd", 1_000),
+            })
+            .unwrap_err()
+            .to_string(),
+            mismatch
+        );
+        assert_eq!(
+            pair.query(QueryMsg::SimulateProvide {
+                assets: vec![asset("uatom", 1_000)],
+                slippage_tolerance: None,
+            })
+            .unwrap_err()
+            .to_string(),
+            mismatch
+        );
+    }
+
+    #[test]
+    fn pair_rejects_wrong_asset_infos() {
+        let err = Pair::new(PairState {
+            asset_infos: vec![info("uluna")],
+            pool: PoolState::Xyk(pair_xyk::pool::PoolState {
+                asset_amounts: vec![Uint128::new(1_000_000_000), Uint128::new(10_000_000)],
+                total_share: Uint128::new(100_000_000),
+                total_fee_rate: Decimal::permille(3),
+            }),
+        })
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Wrong number of asset infos: expected 2, actual 1"
+        );
+    }
+
+    #[test]
+    fn queries_are_read_from_the_json_of_the_pair_contracts() {
+        let msg: QueryMsg = cosmwasm_std::from_json(
+            br#"{"simulation":{"offer_asset":{"info":{"native_token":{"denom":"uluna"}},"amount":"1000"}}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            msg,
+            QueryMsg::Simulation {
+                offer_asset: asset("uluna", 1_000),
+                ask_asset_info: None,
+            }
+        );
+    }
+}
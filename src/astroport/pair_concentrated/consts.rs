@@ -1,4 +1,4 @@
-use cosmwasm_std::Decimal256;
+use cosmwasm_std::{Decimal256, Uint128};
 
 /// ## Internal constants
 /// Number of coins. (2.0)
@@ -11,3 +11,18 @@ pub const N_POW2: Decimal256 = Decimal256::raw(4000000000000000000);
 pub const TOL: Decimal256 = Decimal256::raw(10000000000000);
 /// Iterations limit for Newton's method
 pub const MAX_ITER: usize = 64;
+/// 2.0
+pub const TWO: Decimal256 = Decimal256::raw(2000000000000000000);
+/// Tolerance of the series expansion of the power of 0.5 (1e-10)
+pub const HALFPOW_TOL: Decimal256 = Decimal256::raw(100000000);
+/// Default slippage tolerance of the provide (0.05)
+pub const DEFAULT_SLIPPAGE: Decimal256 = Decimal256::raw(50000000000000000);
+/// Maximum allowed slippage tolerance of the provide (0.5)
+pub const MAX_ALLOWED_SLIPPAGE: Decimal256 = Decimal256::raw(500000000000000000);
+/// The minimum difference of a deposit from its balanced share, for the provide to be treated as
+/// a trade (1e-5)
+pub const MIN_TRADE_SIZE: Decimal256 = Decimal256::raw(10000000000000);
+/// Minimum initial LP share
+pub const MINIMUM_LIQUIDITY_AMOUNT: Uint128 = Uint128::new(1_000);
+/// LP token precision
+pub const LP_TOKEN_PRECISION: u32 = 6;
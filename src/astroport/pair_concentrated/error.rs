@@ -1,6 +1,10 @@
 use cosmwasm_std::{ConversionOverflowError, OverflowError, StdError};
 use thiserror::Error;
 
+use crate::astroport::lib::guards::GuardError;
+
+use super::consts::MINIMUM_LIQUIDITY_AMOUNT;
+
 /// This enum describes pair contract errors
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
@@ -12,4 +16,27 @@ pub enum ContractError {
 
     #[error("{0}")]
     OverflowError(#[from] OverflowError),
+
+    #[error("Event of zero transfer")]
+    InvalidZeroAmount {},
+
+    #[error("Initial liquidity must be more than {}", MINIMUM_LIQUIDITY_AMOUNT)]
+    MinimumLiquidityAmountError {},
+
+    #[error("Operation exceeds max spread limit")]
+    MaxSpreadAssertion {},
+
+    #[error("Provided spread amount exceeds allowed limit")]
+    AllowedSpreadAssertion {},
+
+    #[error(
+        "The slippage tolerance of concentrated pools requires the xcp_profit_real of the pool"
+    )]
+    MissingXcpProfitReal {},
+
+    #[error("{0}")]
+    Guard(#[from] GuardError),
+
+    #[error("Wrong asset length: expected {expected}, actual {actual}")]
+    WrongAssetLength { expected: usize, actual: usize },
 }
@@ -1,6 +1,7 @@
 use super::signed_decimal::SignedDecimal256;
-use crate::astroport::pair_concentrated::consts::{MAX_ITER, N, N_POW2, TOL};
-use cosmwasm_std::{Decimal256, StdError, StdResult};
+use crate::astroport::cosmwasm_ext::AbsDiff;
+use crate::astroport::pair_concentrated::consts::{HALFPOW_TOL, MAX_ITER, N, N_POW2, TOL};
+use cosmwasm_std::{Decimal256, Fraction, StdError, StdResult, Uint128};
 use itertools::Itertools;
 
 /// Internal constant to increase calculation accuracy.
@@ -116,3 +117,32 @@ pub(crate) fn newton_y(
 
     Err(StdError::generic_err("newton_y is not converging"))
 }
+
+/// Calculates 0.5^power, the integer part exactly and the fractional part with its binomial
+/// series.
+pub(crate) fn half_float_pow(power: Decimal256) -> StdResult<Decimal256> {
+    let intpow = power.floor();
+    let intpow_u128: Uint128 = (intpow.numerator() / intpow.denominator()).try_into()?;
+
+    let half = Decimal256::from_ratio(1u8, 2u8);
+    let frac_pow = power - intpow;
+
+    // 0.5 ^ int_power
+    let result = half.pow(u32::try_from(intpow_u128.u128()).unwrap_or(u32::MAX));
+
+    let mut term = Decimal256::one();
+    let mut sum = Decimal256::one();
+
+    for i in 1..(MAX_ITER as u128) {
+        let k = Decimal256::from_ratio(i, 1u8);
+        let c = frac_pow.diff(k - Decimal256::one());
+        term = term * c * half / k;
+        sum -= term;
+
+        if term < HALFPOW_TOL {
+            return Ok(result * sum);
+        }
+    }
+
+    Err(StdError::generic_err("halfpow is not converging"))
+}
@@ -1,10 +1,15 @@
-use super::math::math_decimal::{newton_d, newton_y};
+use super::consts::N;
+use super::math::math_decimal::{df_dx, geometric_mean, newton_d, newton_y};
+use super::math::signed_decimal::SignedDecimal256;
 use super::state::AmpGamma;
 use cosmwasm_std::{Decimal256, StdResult};
+use itertools::Itertools;
 
 mod math_decimal;
 mod signed_decimal;
 
+pub(crate) use math_decimal::half_float_pow;
+
 /// Calculate D invariant based on known pool volumes.
 ///
 /// * **xs** - internal representation of pool volumes.
@@ -27,3 +32,32 @@ pub fn calc_y(
 ) -> StdResult<Decimal256> {
     newton_y(xs, amp_gamma.amp.into(), amp_gamma.gamma.into(), d, ask_ind)
 }
+
+/// Calculate cross price invariant (xcp) which is the value of D in units of the first asset.
+///
+/// * **d** - current D invariant.
+/// * **price_scale** - the price scale of the second asset.
+pub fn get_xcp(d: Decimal256, price_scale: Decimal256) -> Decimal256 {
+    let xs = [d / N, d / (N * price_scale)];
+    geometric_mean(&xs)
+}
+
+/// Calculate the marginal price of the offer pool in ask pool units, i.e. the ratio of the
+/// invariant's partial derivatives.
+///
+/// * **xs** - internal representation of pool volumes.
+/// * **d** - current D invariant.
+/// * **amp_gamma** - an object which represents current Amp and Gamma parameters.
+/// * **ask_ind** - the index of the ask pool.
+pub fn calc_spot_price(
+    xs: &[Decimal256],
+    d: Decimal256,
+    amp_gamma: &AmpGamma,
+    ask_ind: usize,
+) -> StdResult<Decimal256> {
+    let x = xs.iter().map(SignedDecimal256::from).collect_vec();
+    let a: Decimal256 = amp_gamma.amp.into();
+    let gamma: Decimal256 = amp_gamma.gamma.into();
+
+    (df_dx(d, &x, a, gamma, 1 ^ ask_ind) / df_dx(d, &x, a, gamma, ask_ind)).try_into()
+}
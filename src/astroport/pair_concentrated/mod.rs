@@ -3,6 +3,9 @@ pub mod error;
 pub mod math;
 pub mod state;
 
+pub mod pool;
 pub mod provide;
+pub mod snapshot;
 pub mod swap;
 pub mod withdraw;
+pub mod zap;
@@ -0,0 +1,291 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Decimal256, StdResult, Uint128};
+use tsify::Tsify;
+
+use super::{error::ContractError, provide, snapshot::PoolSnapshot, swap, withdraw};
+use crate::astroport::cosmwasm_ext::Decimal256Ext;
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "ConcentratedPoolState")]
+pub struct PoolState {
+    /// The pool balances of both assets
+    pub asset_amounts: Vec<Decimal256>,
+    pub asset_precisions: Vec<u32>,
+    /// The total amount of issued lps
+    pub total_share: Uint128,
+    pub maker_fee_share: Decimal256,
+    pub oracle_price: Decimal256,
+    pub price_scale: Decimal256,
+    pub fee_gamma: Decimal256,
+    pub mid_fee: Decimal256,
+    pub out_fee: Decimal256,
+    pub block_time: u64,
+    pub initial_time: u64,
+    pub initial_amp: Decimal,
+    pub initial_gamma: Decimal,
+    pub future_time: u64,
+    pub future_amp: Decimal,
+    pub future_gamma: Decimal,
+    /// The virtual price of the lps at the last update of the pair, needed by the slippage check
+    /// of provides
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[tsify(optional)]
+    pub xcp_profit_real: Option<Decimal256>,
+}
+
+/// A pool which is built once from its state and serves simulations until the balances are
+/// updated. Swaps reuse the Amp, Gamma and the invariant of the current balances.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pool {
+    state: PoolState,
+    snapshot: PoolSnapshot,
+}
+
+impl Pool {
+    pub fn new(state: PoolState) -> Result<Self, ContractError> {
+        let snapshot = PoolSnapshot::new(
+            &state.asset_amounts,
+            &state.asset_precisions,
+            state.maker_fee_share,
+            state.oracle_price,
+            state.price_scale,
+            state.fee_gamma,
+            state.mid_fee,
+            state.out_fee,
+            state.block_time,
+            state.initial_time,
+            state.initial_amp,
+            state.initial_gamma,
+            state.future_time,
+            state.future_amp,
+            state.future_gamma,
+        )?;
+
+        Ok(Self { state, snapshot })
+    }
+
+    pub fn state(&self) -> &PoolState {
+        &self.state
+    }
+
+    /// Replaces the pool balances and, if specified, the total amount of lps.
+    pub fn update(
+        &mut self,
+        asset_amounts: Vec<Decimal256>,
+        total_share: Option<Uint128>,
+    ) -> Result<(), ContractError> {
+        let mut state = self.state.clone();
+        state.asset_amounts = asset_amounts;
+        if let Some(total_share) = total_share {
+            state.total_share = total_share;
+        }
+
+        *self = Self::new(state)?;
+
+        Ok(())
+    }
+
+    /// Returns the invariant (D) of the pool balances at the current price scale.
+    #[cfg(any(test, feature = "testing"))]
+    pub fn d(&self) -> Decimal256 {
+        self.snapshot.d()
+    }
+
+    /// Returns the price of the offer asset in ask assets, excluding fees.
+    pub fn spot_price(&self, ask_ind: usize) -> StdResult<Decimal> {
+        self.snapshot.spot_price(ask_ind)?.to_decimal()
+    }
+
+    pub fn swap(
+        &self,
+        offer_amount: Uint128,
+        ask_ind: usize,
+    ) -> Result<swap::SwapSimulationResponse, ContractError> {
+        self.snapshot.swap(offer_amount, ask_ind)
+    }
+
+    pub fn reverse_swap(
+        &self,
+        ask_amount: Uint128,
+        ask_ind: usize,
+    ) -> Result<swap::ReverseSwapSimulationResponse, ContractError> {
+        self.snapshot.reverse_swap(ask_amount, ask_ind)
+    }
+
+    pub fn provide(
+        &self,
+        deposits: &[Decimal256],
+        slippage_tolerance: Option<Decimal>,
+        min_lp_to_receive: Option<Uint128>,
+    ) -> Result<provide::SimulationResponse, ContractError> {
+        provide::simulate(
+            deposits,
+            &self.state.asset_amounts,
+            &self.state.asset_precisions,
+            self.state.total_share,
+            self.state.price_scale,
+            self.state.fee_gamma,
+            self.state.mid_fee,
+            self.state.out_fee,
+            self.state.block_time,
+            self.state.initial_time,
+            self.state.initial_amp,
+            self.state.initial_gamma,
+            self.state.future_time,
+            self.state.future_amp,
+            self.state.future_gamma,
+            slippage_tolerance,
+            self.state.xcp_profit_real,
+            min_lp_to_receive,
+        )
+    }
+
+    pub fn withdraw(
+        &self,
+        amount: Uint128,
+        min_assets_to_receive: Option<Vec<Uint128>>,
+    ) -> Result<withdraw::SimulationResponse, ContractError> {
+        withdraw::simulate(
+            amount,
+            &self.state.asset_amounts,
+            &self.state.asset_precisions,
+            self.state.total_share,
+            min_assets_to_receive,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn dec(value: &str) -> Decimal256 {
+        Decimal256::from_str(value).unwrap()
+    }
+
+    fn state() -> PoolState {
+        PoolState {
+            asset_amounts: vec![
+                Decimal256::from_integer(1_000_000_000_000u128),
+                Decimal256::from_integer(666_666_000_000u128),
+            ],
+            asset_precisions: vec![6, 6],
+            total_share: Uint128::new(816_496_000_000),
+            maker_fee_share: dec("0.5"),
+            oracle_price: dec("1.5"),
+            price_scale: dec("1.5"),
+            fee_gamma: dec("0.00023"),
+            mid_fee: dec("0.0026"),
+            out_fee: dec("0.0045"),
+            block_time: 100,
+            initial_time: 0,
+            initial_amp: Decimal::from_ratio(40u8, 1u8),
+            initial_gamma: Decimal::from_str("0.000145").unwrap(),
+            future_time: 0,
+            future_amp: Decimal::from_ratio(40u8, 1u8),
+            future_gamma: Decimal::from_str("0.000145").unwrap(),
+            xcp_profit_real: None,
+        }
+    }
+
+    fn snapshot_of(state: &PoolState) -> PoolSnapshot {
+        PoolSnapshot::new(
+            &state.asset_amounts,
+            &state.asset_precisions,
+            state.maker_fee_share,
+            state.oracle_price,
+            state.price_scale,
+            state.fee_gamma,
+            state.mid_fee,
+            state.out_fee,
+            state.block_time,
+            state.initial_time,
+            state.initial_amp,
+            state.initial_gamma,
+            state.future_time,
+            state.future_amp,
+            state.future_gamma,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn pool_simulates_its_state() {
+        let pool = Pool::new(state()).unwrap();
+        let snapshot = snapshot_of(&state());
+
+        assert_eq!(pool.d(), snapshot.d());
+        assert_eq!(
+            Decimal256::from(pool.spot_price(1).unwrap()),
+            snapshot.spot_price(1).unwrap()
+        );
+        assert_eq!(
+            pool.swap(Uint128::new(1_000_000), 1).unwrap(),
+            snapshot.swap(Uint128::new(1_000_000), 1).unwrap()
+        );
+        assert_eq!(
+            pool.reverse_swap(Uint128::new(1_000_000), 0).unwrap(),
+            snapshot.reverse_swap(Uint128::new(1_000_000), 0).unwrap()
+        );
+        assert_eq!(
+            pool.withdraw(Uint128::new(81_649_600_000), None).unwrap(),
+            withdraw::simulate(
+                Uint128::new(81_649_600_000),
+                &state().asset_amounts,
+                &state().asset_precisions,
+                state().total_share,
+                None
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn update_recomputes_the_invariant() {
+        let mut pool = Pool::new(state()).unwrap();
+        let asset_amounts = vec![
+            Decimal256::from_integer(1_500_000_000_000u128),
+            Decimal256::from_integer(1_000_000_000_000u128),
+        ];
+
+        pool.update(asset_amounts.clone(), Some(Uint128::new(1_224_744_000_000)))
+            .unwrap();
+
+        let state = PoolState {
+            asset_amounts,
+            total_share: Uint128::new(1_224_744_000_000),
+            ..state()
+        };
+        assert_eq!(pool.state(), &state);
+        assert_eq!(pool.d(), snapshot_of(&state).d());
+    }
+
+    #[test]
+    fn pool_rejects_wrong_requests() {
+        assert_eq!(
+            Pool::new(PoolState {
+                asset_amounts: vec![Decimal256::zero(), Decimal256::one()],
+                ..state()
+            })
+            .unwrap_err()
+            .to_string(),
+            "Generic error: One of the assets is empty"
+        );
+
+        let mut pool = Pool::new(state()).unwrap();
+        assert_eq!(
+            pool.update(vec![Decimal256::one()], None)
+                .unwrap_err()
+                .to_string(),
+            "Wrong asset length: expected 2, actual 1"
+        );
+        assert_eq!(pool, Pool::new(state()).unwrap());
+
+        assert_eq!(
+            pool.swap(Uint128::one(), 2).unwrap_err().to_string(),
+            "Generic error: Ask asset index 2 is out of range"
+        );
+    }
+}
@@ -1,5 +1,485 @@
-use super::error::ContractError;
+use super::{
+    consts::{
+        DEFAULT_SLIPPAGE, LP_TOKEN_PRECISION, MAX_ALLOWED_SLIPPAGE, MINIMUM_LIQUIDITY_AMOUNT,
+        MIN_TRADE_SIZE, N,
+    },
+    error::ContractError,
+    math::{calc_d, get_xcp},
+    state::{fee, get_amp_gamma},
+};
+use crate::astroport::cosmwasm_ext::{
+    AbsDiff, Decimal256Ext, DecimalToInteger, IntegerToDecimal, MultiplyRatioCeil,
+};
+use crate::astroport::lib::guards::ensure_min_lp_to_receive;
+use crate::astroport::lib::search::refine_deposits;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Decimal256, StdError, Uint128};
+use tsify::Tsify;
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "ConcentratedProvideSimulationResponse")]
+pub struct SimulationResponse {
+    /// The amount of lps returned by the provide
+    pub share_amount: Uint128,
+}
+
+/// Simulates the provide.
+///
+/// * **slippage_tolerance** the provide fails when the lps minted are less than the value of the
+///   deposits by more than the tolerance, [`DEFAULT_SLIPPAGE`] if not specified. The check needs
+///   the virtual price of the lps in **xcp_profit_real**, without it the tolerance must be
+///   omitted and the provide isn't checked.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate(
+    deposits: &[Decimal256],
+    asset_amounts: &[Decimal256],
+    asset_precisions: &[u32],
+    total_share: Uint128,
+    price_scale: Decimal256,
+    fee_gamma: Decimal256,
+    mid_fee: Decimal256,
+    out_fee: Decimal256,
+    block_time: u64,
+    initial_time: u64,
+    inital_amp: Decimal,
+    initial_gamma: Decimal,
+    future_time: u64,
+    future_amp: Decimal,
+    future_gamma: Decimal,
+    slippage_tolerance: Option<Decimal>,
+    xcp_profit_real: Option<Decimal256>,
+    min_lp_to_receive: Option<Uint128>,
+) -> Result<SimulationResponse, ContractError> {
+    if deposits.len() != 2 {
+        return Err(ContractError::WrongAssetLength {
+            expected: 2,
+            actual: deposits.len(),
+        });
+    }
+    if asset_precisions.len() != asset_amounts.len() {
+        return Err(ContractError::WrongAssetLength {
+            expected: asset_amounts.len(),
+            actual: asset_precisions.len(),
+        });
+    }
+
+    let deposits = deposits
+        .iter()
+        .enumerate()
+        .map(|(i, amount)| Decimal256::with_precision(amount.to_uint256(), asset_precisions[i]))
+        .collect::<Result<Vec<Decimal256>, StdError>>()?;
+
+    let asset_amounts = asset_amounts
+        .iter()
+        .enumerate()
+        .map(|(i, amount)| Decimal256::with_precision(amount.to_uint256(), asset_precisions[i]))
+        .collect::<Result<Vec<Decimal256>, StdError>>()?;
+
+    let total_share = total_share.to_decimal256(LP_TOKEN_PRECISION)?;
+    let share = compute_provide(
+        &deposits,
+        &asset_amounts,
+        total_share,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
+        block_time,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+    )?;
+
+    match xcp_profit_real {
+        Some(xcp_profit_real) => assert_slippage_tolerance(
+            &deposits,
+            &asset_amounts,
+            share,
+            total_share,
+            price_scale,
+            xcp_profit_real,
+            slippage_tolerance,
+        )?,
+        None if slippage_tolerance.is_some() => return Err(ContractError::MissingXcpProfitReal {}),
+        None => {}
+    }
+
+    let share_amount = share.to_uint(LP_TOKEN_PRECISION)?;
+
+    ensure_min_lp_to_receive(share_amount, min_lp_to_receive)?;
+
+    Ok(SimulationResponse { share_amount })
+}
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "ConcentratedReverseProvideSimulationResponse")]
+pub struct ReverseSimulationResponse {
+    /// The amounts of assets to deposit
+    pub deposits: Vec<Uint128>,
+    /// The amount of lps returned by the provide of `deposits`
+    pub share_amount: Uint128,
+}
+
+/// Computes the deposits required to receive `share_amount` lps.
+///
+/// Deposits proportional to the pools grow the invariant (D) proportionally, they are scaled up
+/// until the provide mints at least `share_amount` lps after the provide fee.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_reverse(
+    share_amount: Uint128,
+    asset_amounts: &[Decimal256],
+    asset_precisions: &[u32],
+    total_share: Uint128,
+    price_scale: Decimal256,
+    fee_gamma: Decimal256,
+    mid_fee: Decimal256,
+    out_fee: Decimal256,
+    block_time: u64,
+    initial_time: u64,
+    inital_amp: Decimal,
+    initial_gamma: Decimal,
+    future_time: u64,
+    future_amp: Decimal,
+    future_gamma: Decimal,
+) -> Result<ReverseSimulationResponse, ContractError> {
+    if asset_amounts.iter().any(|amount| amount.is_zero()) || total_share.is_zero() {
+        return Err(StdError::generic_err("One of the assets is empty").into());
+    }
+
+    let deposits = asset_amounts
+        .iter()
+        .map(|amount| {
+            Uint128::try_from(amount.to_uint256())?.multiply_ratio_ceil(share_amount, total_share)
+        })
+        .collect::<Result<Vec<_>, StdError>>()?;
+
+    let (deposits, share_amount) = refine_deposits(deposits, share_amount, |deposits| {
+        let deposits = deposits
+            .iter()
+            .map(|amount| Decimal256::from_integer(*amount))
+            .collect::<Vec<_>>();
+
+        Ok::<_, ContractError>(
+            simulate(
+                &deposits,
+                asset_amounts,
+                asset_precisions,
+                total_share,
+                price_scale,
+                fee_gamma,
+                mid_fee,
+                out_fee,
+                block_time,
+                initial_time,
+                inital_amp,
+                initial_gamma,
+                future_time,
+                future_amp,
+                future_gamma,
+                None,
+                None,
+                None,
+            )?
+            .share_amount,
+        )
+    })?;
+
+    Ok(ReverseSimulationResponse {
+        deposits,
+        share_amount,
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn compute_provide(
+    deposits: &[Decimal256],
+    xs: &[Decimal256],
+    total_share: Decimal256,
+    price_scale: Decimal256,
+    fee_gamma: Decimal256,
+    mid_fee: Decimal256,
+    out_fee: Decimal256,
+    block_time: u64,
+    initial_time: u64,
+    inital_amp: Decimal,
+    initial_gamma: Decimal,
+    future_time: u64,
+    future_amp: Decimal,
+    future_gamma: Decimal,
+) -> Result<Decimal256, ContractError> {
+    if deposits.iter().all(|amount| amount.is_zero()) {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let amp_gamma = get_amp_gamma(
+        block_time,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+    );
+
+    let mut new_xp = xs
+        .iter()
+        .zip(deposits)
+        .map(|(amount, deposit)| *amount + deposit)
+        .collect::<Vec<_>>();
+    new_xp[1] *= price_scale;
+    let new_d = calc_d(&new_xp, &amp_gamma)?;
+
+    let share = if total_share.is_zero() {
+        if deposits.iter().any(|amount| amount.is_zero()) {
+            return Err(ContractError::InvalidZeroAmount {});
+        }
+
+        get_xcp(new_d, price_scale)
+            .checked_sub(MINIMUM_LIQUIDITY_AMOUNT.to_decimal256(LP_TOKEN_PRECISION)?)
+            .map_err(|_| ContractError::MinimumLiquidityAmountError {})?
+    } else {
+        let mut old_xp = xs.to_vec();
+        old_xp[1] *= price_scale;
+        let old_d = calc_d(&old_xp, &amp_gamma)?;
+        let share = (total_share * new_d / old_d).saturating_sub(total_share);
+
+        let mut ideposits = deposits.to_vec();
+        ideposits[1] *= price_scale;
+
+        share
+            * (Decimal256::one()
+                - calc_provide_fee(&ideposits, &new_xp, fee_gamma, mid_fee, out_fee))
+    };
+
+    Ok(share)
+}
+
+/// Ensures the lps minted are worth the deposits within the slippage tolerance, as in the pair
+/// contract: only provides which trade against the pool are checked, i.e. both deposits differ
+/// from their balanced share of the pool by at least [`MIN_TRADE_SIZE`].
+fn assert_slippage_tolerance(
+    deposits: &[Decimal256],
+    xs: &[Decimal256],
+    share: Decimal256,
+    total_share: Decimal256,
+    price_scale: Decimal256,
+    xcp_profit_real: Decimal256,
+    slippage_tolerance: Option<Decimal>,
+) -> Result<(), ContractError> {
+    let share_ratio = share
+        .checked_div(total_share + share)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+    let balanced_share = [
+        (xs[0] + deposits[0]) * share_ratio,
+        (xs[1] + deposits[1]) * price_scale * share_ratio / price_scale,
+    ];
+    if deposits[0].diff(balanced_share[0]) < MIN_TRADE_SIZE
+        || deposits[1].diff(balanced_share[1]) < MIN_TRADE_SIZE
+    {
+        return Ok(());
+    }
+
+    let slippage_tolerance = slippage_tolerance
+        .map(Decimal256::from)
+        .unwrap_or(DEFAULT_SLIPPAGE);
+    if slippage_tolerance > MAX_ALLOWED_SLIPPAGE {
+        return Err(ContractError::AllowedSpreadAssertion {});
+    }
+
+    let deposit_value = deposits[0] + deposits[1] * price_scale;
+    let lp_expected = (deposit_value / N * deposit_value / (N * price_scale))
+        .sqrt()
+        .checked_div(xcp_profit_real)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+    let slippage = lp_expected
+        .saturating_sub(share)
+        .checked_div(lp_expected)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    if slippage > slippage_tolerance {
+        return Err(ContractError::MaxSpreadAssertion {});
+    }
 
-pub fn simulate() -> Result<(), ContractError> {
     Ok(())
 }
+
+/// Calculates the fee charged on the imbalanced part of the provide.
+///
+/// * **deposits** - internal representation of deposits.
+/// * **xp** - internal representation of pool volumes after the provide.
+fn calc_provide_fee(
+    deposits: &[Decimal256],
+    xp: &[Decimal256],
+    fee_gamma: Decimal256,
+    mid_fee: Decimal256,
+    out_fee: Decimal256,
+) -> Decimal256 {
+    let sum = deposits[0] + deposits[1];
+    if sum.is_zero() {
+        return Decimal256::zero();
+    }
+    let avg = sum / N;
+
+    deposits[0].diff(avg) * fee(xp, fee_gamma, mid_fee, out_fee) / sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn dec(value: &str) -> Decimal256 {
+        Decimal256::from_str(value).unwrap()
+    }
+
+    fn pool_amounts() -> [Decimal256; 2] {
+        [
+            Decimal256::from_integer(1_000_000_000_000u128),
+            Decimal256::from_integer(666_666_000_000u128),
+        ]
+    }
+
+    fn provide_of(deposits: &[Uint128]) -> Result<SimulationResponse, ContractError> {
+        provide_with_min_lp_of(deposits, None)
+    }
+
+    fn provide_with_min_lp_of(
+        deposits: &[Uint128],
+        min_lp_to_receive: Option<Uint128>,
+    ) -> Result<SimulationResponse, ContractError> {
+        let deposits = deposits
+            .iter()
+            .map(|amount| Decimal256::from_integer(*amount))
+            .collect::<Vec<_>>();
+
+        simulate(
+            &deposits,
+            &pool_amounts(),
+            &[6, 6],
+            Uint128::new(816_496_000_000),
+            dec("1.5"),
+            dec("0.00023"),
+            dec("0.0026"),
+            dec("0.0045"),
+            100,
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+            None,
+            None,
+            min_lp_to_receive,
+        )
+    }
+
+    fn reverse_provide_of(
+        share_amount: u128,
+        asset_amounts: &[Decimal256],
+        asset_precisions: &[u32],
+        total_share: Uint128,
+    ) -> Result<ReverseSimulationResponse, ContractError> {
+        simulate_reverse(
+            Uint128::new(share_amount),
+            asset_amounts,
+            asset_precisions,
+            total_share,
+            dec("1.5"),
+            dec("0.00023"),
+            dec("0.0026"),
+            dec("0.0045"),
+            100,
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+        )
+    }
+
+    #[test]
+    fn reverse_provide_deposits_enough_for_the_share_amount() {
+        let response = reverse_provide_of(
+            1_000_000_000,
+            &pool_amounts(),
+            &[6, 6],
+            Uint128::new(816_496_000_000),
+        )
+        .unwrap();
+
+        assert!(response.share_amount >= Uint128::new(1_000_000_000));
+        assert_eq!(
+            provide_of(&response.deposits).unwrap().share_amount,
+            response.share_amount
+        );
+
+        // The refinement overshoots the deposits by a tiny fraction at most
+        let deposits = response
+            .deposits
+            .iter()
+            .map(|deposit| *deposit - *deposit / Uint128::new(1_000_000))
+            .collect::<Vec<_>>();
+        assert!(provide_of(&deposits).unwrap().share_amount < Uint128::new(1_000_000_000));
+    }
+
+    #[test]
+    fn provide_checks_the_min_lp_to_receive() {
+        let deposits = [Uint128::new(1_224_745), Uint128::new(816_496)];
+        let share_amount = provide_of(&deposits).unwrap().share_amount;
+
+        assert!(provide_with_min_lp_of(&deposits, Some(share_amount)).is_ok());
+        assert_eq!(
+            provide_with_min_lp_of(&deposits, Some(share_amount + Uint128::one()))
+                .unwrap_err()
+                .to_string(),
+            format!(
+                "Slippage is more than expected: received {share_amount}, expected {} LP tokens",
+                share_amount + Uint128::one()
+            )
+        );
+    }
+
+    #[test]
+    fn reverse_provide_rejects_wrong_requests() {
+        let total_share = Uint128::new(816_496_000_000);
+        let [amount0, _] = pool_amounts();
+
+        assert_eq!(
+            reverse_provide_of(0, &pool_amounts(), &[6, 6], total_share)
+                .unwrap_err()
+                .to_string(),
+            "Event of zero transfer"
+        );
+        assert_eq!(
+            reverse_provide_of(1_000, &[amount0, Decimal256::zero()], &[6, 6], total_share)
+                .unwrap_err()
+                .to_string(),
+            "Generic error: One of the assets is empty"
+        );
+        assert_eq!(
+            reverse_provide_of(1_000, &pool_amounts(), &[6, 6], Uint128::zero())
+                .unwrap_err()
+                .to_string(),
+            "Generic error: One of the assets is empty"
+        );
+        assert_eq!(
+            reverse_provide_of(1_000, &pool_amounts(), &[6], total_share)
+                .unwrap_err()
+                .to_string(),
+            "Wrong asset length: expected 2, actual 1"
+        );
+        assert_eq!(
+            reverse_provide_of(1_000, &[amount0], &[6], total_share)
+                .unwrap_err()
+                .to_string(),
+            "Wrong asset length: expected 2, actual 1"
+        );
+    }
+}
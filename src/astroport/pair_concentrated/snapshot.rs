@@ -0,0 +1,577 @@
+use cosmwasm_std::{Decimal, Decimal256, StdError, StdResult, Uint128};
+
+use super::{
+    consts::{LP_TOKEN_PRECISION, MIN_TRADE_SIZE},
+    error::ContractError,
+    math::{calc_d, calc_y},
+    state::{get_amp_gamma, AmpGamma, RepegParams},
+    swap::{
+        compute_spot_price, compute_swap, empty_swap, ReverseSwapSimulationResponse, SwapResult,
+        SwapSimulationResponse,
+    },
+};
+use crate::astroport::cosmwasm_ext::{Decimal256Ext, DecimalToInteger, IntegerToDecimal};
+use crate::astroport::lib::batch::check_swap_request;
+use crate::astroport::lib::curve::CurvePoint;
+use crate::astroport::lib::prices::{price, price_impact};
+
+/// The share of the offer pool the slope of the price is taken over
+const SLOPE_STEP: Decimal256 = Decimal256::raw(1_000_000_000_000_000);
+
+/// The state of a pool with the current Amp and Gamma and the invariant (D) computed once, so
+/// that repeated swaps against the same balances don't recompute them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PoolSnapshot {
+    /// The pool volumes with the precision of [`Decimal256`]
+    pools: Vec<Decimal256>,
+    /// The internal representation of pool volumes
+    ixs: Vec<Decimal256>,
+    asset_precisions: Vec<u32>,
+    maker_fee_share: Decimal256,
+    oracle_price: Decimal256,
+    price_scale: Decimal256,
+    fee_gamma: Decimal256,
+    mid_fee: Decimal256,
+    out_fee: Decimal256,
+    amp_gamma: AmpGamma,
+    d: Decimal256,
+    block_time: u64,
+    future_time: u64,
+}
+
+impl PoolSnapshot {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        asset_amounts: &[Decimal256],
+        asset_precisions: &[u32],
+        maker_fee_share: Decimal256,
+        oracle_price: Decimal256,
+        price_scale: Decimal256,
+        fee_gamma: Decimal256,
+        mid_fee: Decimal256,
+        out_fee: Decimal256,
+        block_time: u64,
+        initial_time: u64,
+        inital_amp: Decimal,
+        initial_gamma: Decimal,
+        future_time: u64,
+        future_amp: Decimal,
+        future_gamma: Decimal,
+    ) -> Result<Self, ContractError> {
+        for actual in [asset_amounts.len(), asset_precisions.len()] {
+            if actual != 2 {
+                return Err(ContractError::WrongAssetLength {
+                    expected: 2,
+                    actual,
+                });
+            }
+        }
+
+        // The invariant of the pair is undefined for an empty pool
+        if asset_amounts.iter().any(|amount| amount.is_zero()) {
+            return Err(StdError::generic_err("One of the assets is empty").into());
+        }
+
+        let pools = asset_amounts
+            .iter()
+            .zip(asset_precisions)
+            .map(|(amount, precision)| Decimal256::with_precision(amount.to_uint256(), *precision))
+            .collect::<Result<Vec<Decimal256>, StdError>>()?;
+
+        let amp_gamma = get_amp_gamma(
+            block_time,
+            initial_time,
+            inital_amp,
+            initial_gamma,
+            future_time,
+            future_amp,
+            future_gamma,
+        );
+
+        let mut ixs = pools.clone();
+        ixs[1] *= price_scale;
+        let d = calc_d(&ixs, &amp_gamma)?;
+
+        Ok(Self {
+            pools,
+            ixs,
+            asset_precisions: asset_precisions.to_vec(),
+            maker_fee_share,
+            oracle_price,
+            price_scale,
+            fee_gamma,
+            mid_fee,
+            out_fee,
+            amp_gamma,
+            d,
+            block_time,
+            future_time,
+        })
+    }
+
+    /// Returns the invariant (D) of the internal representation of pool volumes.
+    #[cfg(any(test, feature = "testing"))]
+    pub fn d(&self) -> Decimal256 {
+        self.d
+    }
+
+    /// Returns the price of the offer asset in ask assets, excluding fees.
+    pub fn spot_price(&self, ask_ind: usize) -> StdResult<Decimal256> {
+        check_swap_request(self.pools.len(), ask_ind)?;
+
+        compute_spot_price(
+            &self.ixs,
+            self.d,
+            ask_ind,
+            self.price_scale,
+            &self.amp_gamma,
+        )
+    }
+
+    /// Simulates a swap of `offer_amount` for the asset with index `ask_ind`.
+    pub fn swap(
+        &self,
+        offer_amount: Uint128,
+        ask_ind: usize,
+    ) -> Result<SwapSimulationResponse, ContractError> {
+        check_swap_request(self.pools.len(), ask_ind)?;
+
+        if offer_amount.is_zero() {
+            return Ok(empty_swap());
+        }
+
+        let ask_asset_prec = self.asset_precisions[ask_ind];
+        let offer_amount =
+            Decimal256::with_precision(offer_amount, self.asset_precisions[1 ^ ask_ind])?;
+
+        let swap_result = self.compute_swap(offer_amount, ask_ind)?;
+        let return_amount = swap_result.dy.to_uint(ask_asset_prec)?;
+        let commission_amount = swap_result.total_fee.to_uint(ask_asset_prec)?;
+
+        let new_ixs = self.ixs_after(offer_amount, ask_ind, &swap_result)?;
+        let new_d = calc_d(&new_ixs, &self.amp_gamma)?;
+
+        let spot_price_before = self.spot_price(ask_ind)?;
+        let spot_price_after =
+            compute_spot_price(&new_ixs, new_d, ask_ind, self.price_scale, &self.amp_gamma)?;
+
+        let net_ask_amount = Decimal256::with_precision(return_amount, ask_asset_prec)?;
+        let ask_amount =
+            Decimal256::with_precision(return_amount + commission_amount, ask_asset_prec)?;
+
+        Ok(SwapSimulationResponse {
+            return_amount,
+            spread_amount: swap_result.spread_fee.to_uint(ask_asset_prec)?,
+            commission_amount,
+            spot_price_before,
+            execution_price: price(offer_amount, net_ask_amount),
+            spot_price_after,
+            price_impact: price_impact(offer_amount, ask_amount, spot_price_before),
+        })
+    }
+
+    /// Executes a swap of `offer_amount` for the asset with index `ask_ind` as the pair does.
+    /// Returns the simulation, the pool balances after the swap, which exclude the maker fee,
+    /// and the price scale, repegged with `repeg` if specified.
+    ///
+    /// * **total_share** - the total amount of lps, needed by the repeg.
+    pub fn execute_swap(
+        &self,
+        offer_amount: Uint128,
+        ask_ind: usize,
+        total_share: Uint128,
+        repeg: Option<&RepegParams>,
+    ) -> Result<(SwapSimulationResponse, Vec<Decimal256>, Decimal256), ContractError> {
+        let swap = self.swap(offer_amount, ask_ind)?;
+        let offer_ind = 1 ^ ask_ind;
+
+        let mut pools = self
+            .pools
+            .iter()
+            .zip(&self.asset_precisions)
+            .map(|(pool, precision)| pool.to_uint(*precision))
+            .collect::<Result<Vec<Uint128>, _>>()?;
+        if offer_amount.is_zero() {
+            let pools = pools.into_iter().map(Decimal256::from_integer).collect();
+            return Ok((swap, pools, self.price_scale));
+        }
+
+        let ask_asset_prec = self.asset_precisions[ask_ind];
+        let offer_amount_dec =
+            Decimal256::with_precision(offer_amount, self.asset_precisions[offer_ind])?;
+        let swap_result = self.compute_swap(offer_amount_dec, ask_ind)?;
+        let maker_fee = swap_result.maker_fee.to_uint(ask_asset_prec)?;
+
+        pools[offer_ind] = pools[offer_ind].checked_add(offer_amount)?;
+        pools[ask_ind] = pools[ask_ind]
+            .checked_sub(swap.return_amount)?
+            .checked_sub(maker_fee)?;
+
+        let mut price_scale = self.price_scale;
+        let ask_amount = swap_result.dy + swap_result.maker_fee;
+        // The pair skips the update on trades small enough to mess up the price by rounding
+        if let Some(repeg) =
+            repeg.filter(|_| ask_amount >= MIN_TRADE_SIZE && offer_amount_dec >= MIN_TRADE_SIZE)
+        {
+            price_scale = repeg.price_scale_after(
+                &self.ixs_after(offer_amount_dec, ask_ind, &swap_result)?,
+                total_share.to_decimal256(LP_TOKEN_PRECISION)?,
+                self.oracle_price,
+                self.price_scale,
+                &self.amp_gamma,
+                self.block_time,
+                self.future_time,
+            )?;
+        }
+
+        let pools = pools.into_iter().map(Decimal256::from_integer).collect();
+
+        Ok((swap, pools, price_scale))
+    }
+
+    /// Returns the price after a swap of `offer_amount` for the asset with index `ask_ind` along
+    /// with the rate the price decreases at per token offered on top of it. The rate is taken
+    /// over a small offer along the invariant of the balances after the swap, which accounts for
+    /// the price scale.
+    pub fn price_after_with_slope(
+        &self,
+        offer_amount: Uint128,
+        ask_ind: usize,
+    ) -> Result<(Decimal256, Decimal256), ContractError> {
+        check_swap_request(self.pools.len(), ask_ind)?;
+
+        if self.pools.iter().any(|pool| pool.is_zero()) {
+            return Err(StdError::generic_err("One of the assets is empty").into());
+        }
+
+        let offer_ind = 1 ^ ask_ind;
+        let (ixs, d) = if offer_amount.is_zero() {
+            (self.ixs.clone(), self.d)
+        } else {
+            let offer_amount =
+                Decimal256::with_precision(offer_amount, self.asset_precisions[offer_ind])?;
+            let swap_result = self.compute_swap(offer_amount, ask_ind)?;
+            let ixs = self.ixs_after(offer_amount, ask_ind, &swap_result)?;
+            let d = calc_d(&ixs, &self.amp_gamma)?;
+            (ixs, d)
+        };
+
+        let price_after = compute_spot_price(&ixs, d, ask_ind, self.price_scale, &self.amp_gamma)?;
+
+        // The central difference of the price along the invariant
+        let step = (ixs[offer_ind] * SLOPE_STEP).max(Decimal256::raw(1));
+        let price_at = |offer_pool: Decimal256| {
+            let mut ixs = ixs.clone();
+            ixs[offer_ind] = offer_pool;
+            ixs[ask_ind] = calc_y(&ixs, d, &self.amp_gamma, ask_ind)?;
+            compute_spot_price(&ixs, d, ask_ind, self.price_scale, &self.amp_gamma)
+        };
+        let price_drop =
+            price_at(ixs[offer_ind] - step)?.saturating_sub(price_at(ixs[offer_ind] + step)?);
+
+        // The second pool is internally expressed in the first asset units
+        let offer_step = if offer_ind == 1 {
+            step / self.price_scale
+        } else {
+            step
+        };
+
+        Ok((price_after, price_drop / (offer_step + offer_step)))
+    }
+
+    /// Simulates a swap which returns `ask_amount` of the asset with index `ask_ind` after fees.
+    ///
+    /// The fee rate depends on the balances after the swap, thus the maximum one (`out_fee`) is
+    /// assumed and the offer amount may be slightly overestimated.
+    pub fn reverse_swap(
+        &self,
+        ask_amount: Uint128,
+        ask_ind: usize,
+    ) -> Result<ReverseSwapSimulationResponse, ContractError> {
+        check_swap_request(self.pools.len(), ask_ind)?;
+
+        let offer_ind = 1 ^ ask_ind;
+        let ask_asset_prec = self.asset_precisions[ask_ind];
+        let ask_amount = Decimal256::with_precision(ask_amount, ask_asset_prec)?;
+
+        let one_minus_fee = Decimal256::one() - self.out_fee;
+        if one_minus_fee.is_zero() {
+            return Err(StdError::generic_err("Fee rate must be less than 1").into());
+        }
+
+        let before_fee = ask_amount / one_minus_fee;
+        if before_fee >= self.pools[ask_ind] {
+            return Err(StdError::generic_err("Ask amount exceeds the pool").into());
+        }
+
+        // The amounts are computed in the internal units, the ones of the first asset
+        let internal = |amount: Decimal256| {
+            if ask_ind == 1 {
+                amount * self.price_scale
+            } else {
+                amount
+            }
+        };
+        let mut ixs = self.ixs.clone();
+        ixs[ask_ind] -= internal(before_fee);
+
+        let new_x = calc_y(&ixs, self.d, &self.amp_gamma, offer_ind)?;
+        let mut dx = new_x.saturating_sub(ixs[offer_ind]);
+        let mut spread_fee = dx.saturating_sub(internal(before_fee));
+        let mut fee = internal(before_fee) - internal(ask_amount);
+
+        // Like the pair, reports all the amounts in the offer asset units
+        if offer_ind == 1 {
+            dx /= self.price_scale;
+            spread_fee /= self.price_scale;
+            fee /= self.price_scale;
+        }
+
+        let offer_asset_prec = self.asset_precisions[offer_ind];
+        Ok(ReverseSwapSimulationResponse {
+            offer_amount: dx.to_uint(offer_asset_prec)?,
+            spread_amount: spread_fee.to_uint(offer_asset_prec)?,
+            commission_amount: fee.to_uint(offer_asset_prec)?,
+        })
+    }
+
+    /// Same as [`PoolSnapshot::swap`], but skips the price after the swap which requires the
+    /// invariant of the new balances.
+    pub fn quote(
+        &self,
+        offer_amount: Uint128,
+        ask_ind: usize,
+    ) -> Result<CurvePoint, ContractError> {
+        check_swap_request(self.pools.len(), ask_ind)?;
+
+        if offer_amount.is_zero() {
+            return Ok(CurvePoint {
+                offer_amount,
+                return_amount: Uint128::zero(),
+                commission_amount: Uint128::zero(),
+                price_impact: Decimal256::zero(),
+            });
+        }
+
+        let ask_asset_prec = self.asset_precisions[ask_ind];
+        let amount = offer_amount;
+        let offer_amount = Decimal256::with_precision(amount, self.asset_precisions[1 ^ ask_ind])?;
+
+        let swap_result = self.compute_swap(offer_amount, ask_ind)?;
+        let return_amount = swap_result.dy.to_uint(ask_asset_prec)?;
+        let commission_amount = swap_result.total_fee.to_uint(ask_asset_prec)?;
+        let ask_amount =
+            Decimal256::with_precision(return_amount + commission_amount, ask_asset_prec)?;
+        let spot_price = self.spot_price(ask_ind)?;
+
+        Ok(CurvePoint {
+            offer_amount: amount,
+            return_amount,
+            commission_amount,
+            price_impact: price_impact(offer_amount, ask_amount, spot_price),
+        })
+    }
+
+    /// Returns the internal representation of the pool volumes after `swap_result`, which
+    /// excludes the maker fee.
+    fn ixs_after(
+        &self,
+        offer_amount: Decimal256,
+        ask_ind: usize,
+        swap_result: &SwapResult,
+    ) -> StdResult<Vec<Decimal256>> {
+        let mut ixs = self.pools.clone();
+        ixs[1 ^ ask_ind] += offer_amount;
+        ixs[ask_ind] = ixs[ask_ind].checked_sub(swap_result.dy + swap_result.maker_fee)?;
+        ixs[1] *= self.price_scale;
+
+        Ok(ixs)
+    }
+
+    fn compute_swap(&self, offer_amount: Decimal256, ask_ind: usize) -> StdResult<SwapResult> {
+        compute_swap(
+            self.ixs.clone(),
+            self.d,
+            &self.amp_gamma,
+            offer_amount,
+            ask_ind,
+            self.maker_fee_share,
+            self.oracle_price,
+            self.price_scale,
+            self.fee_gamma,
+            self.mid_fee,
+            self.out_fee,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn dec(value: &str) -> Decimal256 {
+        Decimal256::from_str(value).unwrap()
+    }
+
+    fn snapshot_of(
+        asset_amounts: &[Decimal256],
+        asset_precisions: &[u32],
+    ) -> Result<PoolSnapshot, ContractError> {
+        PoolSnapshot::new(
+            asset_amounts,
+            asset_precisions,
+            dec("0.5"),
+            dec("1.5"),
+            dec("1.5"),
+            dec("0.00023"),
+            dec("0.0026"),
+            dec("0.0045"),
+            100,
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+        )
+    }
+
+    fn pool_amounts() -> [Decimal256; 2] {
+        [
+            Decimal256::from_integer(1_000_000_000_000u128),
+            Decimal256::from_integer(666_666_000_000u128),
+        ]
+    }
+
+    #[test]
+    fn snapshot_computes_the_invariant_of_the_internal_volumes() {
+        let snapshot = snapshot_of(&pool_amounts(), &[6, 6]).unwrap();
+
+        let ixs = [dec("1000000"), dec("666666") * dec("1.5")];
+        let amp_gamma = get_amp_gamma(
+            100,
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+        );
+        assert_eq!(snapshot.d(), calc_d(&ixs, &amp_gamma).unwrap());
+    }
+
+    #[test]
+    fn quotes_match_the_swaps() {
+        let snapshot = snapshot_of(&pool_amounts(), &[6, 6]).unwrap();
+
+        for (offer_amount, ask_ind) in [
+            (1_000_000u128, 0),
+            (1_000_000, 1),
+            (100_000_000_000, 0),
+            (0, 1),
+        ] {
+            let swap = snapshot.swap(Uint128::new(offer_amount), ask_ind).unwrap();
+            let quote = snapshot.quote(Uint128::new(offer_amount), ask_ind).unwrap();
+
+            assert_eq!(quote.offer_amount, Uint128::new(offer_amount));
+            assert_eq!(quote.return_amount, swap.return_amount);
+            assert_eq!(quote.commission_amount, swap.commission_amount);
+            assert_eq!(quote.price_impact, swap.price_impact);
+        }
+    }
+
+    #[test]
+    fn swaps_execute_between_the_spot_prices_before_fees() {
+        let snapshot = snapshot_of(&pool_amounts(), &[6, 6]).unwrap();
+
+        for (offer_amount, ask_ind) in [
+            (1_000_000_000u128, 0),
+            (100_000_000_000, 0),
+            (100_000_000_000, 1),
+        ] {
+            let swap = snapshot.swap(Uint128::new(offer_amount), ask_ind).unwrap();
+
+            // The execution price is net of fees, only the gross one lies between the spot prices
+            let gross_price = price(
+                Decimal256::from_integer(offer_amount),
+                Decimal256::from_integer(swap.return_amount + swap.commission_amount),
+            );
+            assert!(swap.execution_price < gross_price);
+            assert!(gross_price < swap.spot_price_before);
+            assert!(gross_price > swap.spot_price_after);
+            assert!(!swap.price_impact.is_zero());
+            assert!(swap.price_impact < Decimal256::one());
+        }
+    }
+
+    #[test]
+    fn reverse_swaps_offer_enough_for_the_ask_amount() {
+        let snapshot = snapshot_of(&pool_amounts(), &[6, 6]).unwrap();
+
+        for ask_ind in [0, 1] {
+            let ask_amount = Uint128::new(10_000_000_000);
+            let reverse = snapshot.reverse_swap(ask_amount, ask_ind).unwrap();
+
+            let swap = snapshot.swap(reverse.offer_amount, ask_ind).unwrap();
+            assert!(swap.return_amount >= ask_amount);
+        }
+    }
+
+    #[test]
+    fn executed_swaps_move_the_balances() {
+        let snapshot = snapshot_of(&pool_amounts(), &[6, 6]).unwrap();
+
+        let (swap, pools, price_scale) = snapshot
+            .execute_swap(Uint128::new(1_000_000_000), 1, Uint128::zero(), None)
+            .unwrap();
+        assert_eq!(price_scale, dec("1.5"));
+        assert_eq!(
+            pools[0],
+            Decimal256::from_integer(1_000_000_000_000u128 + 1_000_000_000)
+        );
+        assert!(pools[1] < Decimal256::from_integer(666_666_000_000 - swap.return_amount.u128()));
+
+        let (swap, pools, _) = snapshot
+            .execute_swap(Uint128::zero(), 1, Uint128::zero(), None)
+            .unwrap();
+        assert_eq!(swap, empty_swap());
+        assert_eq!(pools, pool_amounts());
+    }
+
+    #[test]
+    fn snapshot_rejects_wrong_requests() {
+        assert_eq!(
+            snapshot_of(&pool_amounts(), &[6]).unwrap_err().to_string(),
+            "Wrong asset length: expected 2, actual 1"
+        );
+        assert_eq!(
+            snapshot_of(&[Decimal256::zero(), pool_amounts()[1]], &[6, 6])
+                .unwrap_err()
+                .to_string(),
+            "Generic error: One of the assets is empty"
+        );
+
+        let snapshot = snapshot_of(&pool_amounts(), &[6, 6]).unwrap();
+        let out_of_range = "Generic error: Ask asset index 2 is out of range";
+        assert_eq!(
+            snapshot.swap(Uint128::one(), 2).unwrap_err().to_string(),
+            out_of_range
+        );
+        assert_eq!(
+            snapshot.quote(Uint128::one(), 2).unwrap_err().to_string(),
+            out_of_range
+        );
+        assert_eq!(
+            snapshot.spot_price(2).unwrap_err().to_string(),
+            out_of_range
+        );
+        assert_eq!(
+            snapshot
+                .reverse_swap(Uint128::new(666_666_000_000), 1)
+                .unwrap_err()
+                .to_string(),
+            "Generic error: Ask amount exceeds the pool"
+        );
+    }
+}
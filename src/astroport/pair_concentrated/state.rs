@@ -1,9 +1,11 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Decimal, Decimal256};
+use cosmwasm_std::{Decimal, Decimal256, StdError, StdResult};
+use tsify::Tsify;
 
-use crate::astroport::cosmwasm_ext::IntegerToDecimal;
+use crate::astroport::cosmwasm_ext::{AbsDiff, IntegerToDecimal};
 
-use super::consts::{FEE_TOL, N_POW2};
+use super::consts::{FEE_TOL, N_POW2, TWO};
+use super::math::{calc_d, get_xcp, half_float_pow};
 
 #[cw_serde]
 #[derive(Default, Copy)]
@@ -56,3 +58,96 @@ pub fn fee(
 
     k * mid_fee + (Decimal256::one() - k) * out_fee
 }
+
+/// The price state of the pair and the pool parameters which drive the repegging of the price
+/// scale after swaps.
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "ConcentratedRepegParams")]
+pub struct RepegParams {
+    /// The price of the last trade
+    pub last_price: Decimal256,
+    /// The time of the last update of the oracle price
+    pub last_price_update: u64,
+    /// The profit of the pool, including the part used up by the repegs
+    pub xcp_profit: Decimal256,
+    /// The virtual price of the lps
+    pub xcp_profit_real: Decimal256,
+    /// The half time of the moving average of the oracle price, in seconds
+    pub ma_half_time: u64,
+    /// The profit the pool must keep above half of `xcp_profit` to repeg
+    pub repeg_profit_threshold: Decimal,
+    /// The minimum move of the price scale on a repeg
+    pub min_price_scale_delta: Decimal,
+}
+
+impl RepegParams {
+    /// Returns the price scale after a trade which left the pool at `xs`, as `update_price` of
+    /// the pair does: the oracle price is moved toward the last price and the price scale toward
+    /// the oracle price if the pool keeps enough profit.
+    ///
+    /// * **xs** - internal representation of pool volumes after the trade.
+    /// * **total_lp** - the total amount of lps with the precision of [`Decimal256`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn price_scale_after(
+        &self,
+        xs: &[Decimal256],
+        total_lp: Decimal256,
+        oracle_price: Decimal256,
+        price_scale: Decimal256,
+        amp_gamma: &AmpGamma,
+        block_time: u64,
+        future_time: u64,
+    ) -> StdResult<Decimal256> {
+        let mut oracle_price = oracle_price;
+        if self.last_price_update < block_time {
+            let arg = Decimal256::checked_from_ratio(
+                block_time - self.last_price_update,
+                self.ma_half_time,
+            )
+            .map_err(|err| StdError::generic_err(err.to_string()))?;
+            let alpha = half_float_pow(arg)?;
+            oracle_price = self.last_price * (Decimal256::one() - alpha) + oracle_price * alpha;
+        }
+
+        let xcp = get_xcp(calc_d(xs, amp_gamma)?, price_scale);
+        let mut xcp_profit = self.xcp_profit;
+        let mut xcp_profit_real = self.xcp_profit_real;
+        if !xcp_profit_real.is_zero() {
+            let new_xcp_profit_real = xcp / total_lp;
+
+            // If xcp dropped and no ramping happens then this swap makes loss
+            if new_xcp_profit_real < xcp_profit_real && block_time >= future_time {
+                return Err(StdError::generic_err(
+                    "XCP profit real value dropped. This action makes loss",
+                ));
+            }
+
+            xcp_profit = xcp_profit * new_xcp_profit_real / xcp_profit_real;
+            xcp_profit_real = new_xcp_profit_real;
+        }
+
+        let norm = (oracle_price / price_scale).diff(Decimal256::one());
+        let scale_delta = Decimal256::from(self.min_price_scale_delta)
+            .max(norm * Decimal256::from_ratio(1u8, 10u8));
+
+        if !norm.is_zero()
+            && norm >= scale_delta
+            && xcp_profit_real.saturating_sub(Decimal256::one())
+                > xcp_profit.saturating_sub(Decimal256::one()) / TWO
+                    + Decimal256::from(self.repeg_profit_threshold)
+        {
+            let price_scale_new =
+                (price_scale * (norm - scale_delta) + scale_delta * oracle_price) / norm;
+
+            let xs = [xs[0], xs[1] * price_scale_new / price_scale];
+            let new_xcp_profit_real = get_xcp(calc_d(&xs, amp_gamma)?, price_scale_new) / total_lp;
+
+            if TWO * new_xcp_profit_real > xcp_profit + Decimal256::one() {
+                return Ok(price_scale_new);
+            }
+        }
+
+        Ok(price_scale)
+    }
+}
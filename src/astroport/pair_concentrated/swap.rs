@@ -1,13 +1,22 @@
 use super::{
     error::ContractError,
-    math::{calc_d, calc_y},
-    state::{fee, get_amp_gamma},
+    math::{calc_spot_price, calc_y},
+    snapshot::PoolSnapshot,
+    state::{fee, AmpGamma},
 };
-use crate::astroport::cosmwasm_ext::{Decimal256Ext, DecimalToInteger};
+use crate::astroport::cosmwasm_ext::Decimal256Ext;
+use crate::astroport::lib::batch::{check_swap_request, run_batch, BatchItem};
+use crate::astroport::lib::curve::{offer_amounts, CurvePoint, Spacing};
+use crate::astroport::lib::depth::{compute_depth, DepthResponse};
+use crate::astroport::lib::ladder::{compute_ladder, LadderResponse};
+use crate::astroport::lib::search::newton_decreasing;
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Decimal, Decimal256, StdError, StdResult, Uint128};
+use tsify::Tsify;
 
 #[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "ConcentratedSwapSimulationResponse")]
 pub struct SwapSimulationResponse {
     /// The amount of ask assets returned by the swap
     pub return_amount: Uint128,
@@ -15,6 +24,26 @@ pub struct SwapSimulationResponse {
     pub spread_amount: Uint128,
     /// The amount of fees charged by the transaction
     pub commission_amount: Uint128,
+    /// The price of the offer asset in ask assets before the swap, excluding fees
+    pub spot_price_before: Decimal256,
+    /// The price the swap is executed at, i.e. `return_amount / offer_amount`
+    pub execution_price: Decimal256,
+    /// The price of the offer asset in ask assets after the swap, excluding fees
+    pub spot_price_after: Decimal256,
+    /// The relative shortfall of the swap before fees compared to the spot price
+    pub price_impact: Decimal256,
+}
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "ConcentratedReverseSwapSimulationResponse")]
+pub struct ReverseSwapSimulationResponse {
+    /// The amount of offer assets required by the swap
+    pub offer_amount: Uint128,
+    /// The spread used in the swap operation
+    pub spread_amount: Uint128,
+    /// The amount of fees charged by the transaction
+    pub commission_amount: Uint128,
 }
 
 pub fn simulate(
@@ -37,24 +66,105 @@ pub fn simulate(
     future_amp: Decimal,
     future_gamma: Decimal,
 ) -> Result<SwapSimulationResponse, ContractError> {
-    let offer_amount = Decimal256::with_precision(offer_amount.to_uint256(), offer_asset_prec)?;
-
-    let asset_amounts = asset_amounts
-        .iter()
-        .enumerate()
-        .map(|(i, amount)| {
+    let asset_precisions = (0..asset_amounts.len())
+        .map(|i| {
             if i == ask_ind {
-                Decimal256::with_precision(amount.to_uint256(), ask_asset_prec)
+                ask_asset_prec
             } else {
-                Decimal256::with_precision(amount.to_uint256(), offer_asset_prec)
+                offer_asset_prec
             }
         })
-        .collect::<Result<Vec<Decimal256>, StdError>>()?;
+        .collect::<Vec<u32>>();
+    let asset_precisions = &asset_precisions;
 
-    let swap_result = compute_swap(
-        &asset_amounts,
-        offer_amount,
-        ask_ind,
+    PoolSnapshot::new(
+        asset_amounts,
+        asset_precisions,
+        maker_fee_share,
+        oracle_price,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
+        block_time,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+    )?
+    .swap(offer_amount.to_uint256().try_into()?, ask_ind)
+}
+
+/// Computes the price of the offer asset in ask assets, excluding fees.
+///
+/// * **ixs** - internal representation of pool volumes.
+///
+/// * **d** - the invariant of `ixs`.
+pub(crate) fn compute_spot_price(
+    ixs: &[Decimal256],
+    d: Decimal256,
+    ask_ind: usize,
+    price_scale: Decimal256,
+    amp_gamma: &AmpGamma,
+) -> StdResult<Decimal256> {
+    let spot_price = calc_spot_price(ixs, d, amp_gamma, ask_ind)?;
+
+    // Internally the second pool is expressed in the first asset units
+    if ask_ind == 1 {
+        Ok(spot_price / price_scale)
+    } else {
+        Ok(spot_price * price_scale)
+    }
+}
+
+/// Zero amounts can't be swapped in the pool, thus the swap is empty.
+pub(crate) fn empty_swap() -> SwapSimulationResponse {
+    SwapSimulationResponse {
+        return_amount: Uint128::zero(),
+        spread_amount: Uint128::zero(),
+        commission_amount: Uint128::zero(),
+        spot_price_before: Decimal256::zero(),
+        execution_price: Decimal256::zero(),
+        spot_price_after: Decimal256::zero(),
+        price_impact: Decimal256::zero(),
+    }
+}
+
+/// Samples `points` swaps with offer amounts spread from `min_offer_amount` to
+/// `max_offer_amount` according to `spacing`. The invariant and the spot price are computed once
+/// for the whole curve.
+#[allow(clippy::too_many_arguments)]
+pub fn sample_curve(
+    min_offer_amount: Uint128,
+    max_offer_amount: Uint128,
+    points: u32,
+    spacing: Spacing,
+    ask_ind: usize,
+    asset_amounts: &[Decimal256],
+    asset_precisions: &[u32],
+    maker_fee_share: Decimal256,
+    oracle_price: Decimal256,
+    price_scale: Decimal256,
+    fee_gamma: Decimal256,
+    mid_fee: Decimal256,
+    out_fee: Decimal256,
+    block_time: u64,
+    initial_time: u64,
+    inital_amp: Decimal,
+    initial_gamma: Decimal,
+    future_time: u64,
+    future_amp: Decimal,
+    future_gamma: Decimal,
+) -> Result<Vec<CurvePoint>, ContractError> {
+    if asset_amounts.iter().any(|amount| amount.is_zero()) {
+        return Err(StdError::generic_err("One of the assets is empty").into());
+    }
+
+    let snapshot = PoolSnapshot::new(
+        asset_amounts,
+        asset_precisions,
         maker_fee_share,
         oracle_price,
         price_scale,
@@ -70,27 +180,34 @@ pub fn simulate(
         future_gamma,
     )?;
 
-    Ok(SwapSimulationResponse {
-        return_amount: swap_result.dy.to_uint(ask_asset_prec)?,
-        spread_amount: swap_result.spread_fee.to_uint(ask_asset_prec)?,
-        commission_amount: swap_result.total_fee.to_uint(ask_asset_prec)?,
-    })
+    offer_amounts(min_offer_amount, max_offer_amount, points, &spacing)?
+        .into_iter()
+        .map(|amount| snapshot.quote(amount, ask_ind))
+        .collect()
 }
 
 #[cw_serde]
-pub struct SwapResult {
-    pub new_y: Decimal256,
-    pub offer_amount: Decimal256,
-    pub dy: Decimal256,
-    pub spread_fee: Decimal256,
-    pub maker_fee: Decimal256,
-    pub total_fee: Decimal256,
+#[derive(Tsify)]
+#[serde(rename = "ConcentratedSwapToPriceResponse")]
+pub struct SwapToPriceResponse {
+    /// The index of the asset to offer
+    pub offer_ind: usize,
+    /// The amount of assets to offer
+    pub offer_amount: Uint128,
+    /// The simulation of the swap
+    #[tsify(type = "ConcentratedSwapSimulationResponse")]
+    pub swap: SwapSimulationResponse,
 }
 
-fn compute_swap(
-    xs: &[Decimal256],
-    offer_amount: Decimal256,
-    ask_ind: usize,
+/// Computes the smallest swap which moves the price of the first asset in the second asset
+/// units to `target_price`, fees included. The price after a swap has no closed form for the
+/// concentrated invariant, thus the offer amount is solved with Newton's method, the slope of the
+/// price being taken along the invariant with the price scale applied.
+#[allow(clippy::too_many_arguments)]
+pub fn swap_to_price(
+    target_price: Decimal,
+    asset_amounts: &[Decimal256],
+    asset_precisions: &[u32],
     maker_fee_share: Decimal256,
     oracle_price: Decimal256,
     price_scale: Decimal256,
@@ -104,13 +221,142 @@ fn compute_swap(
     future_time: u64,
     future_amp: Decimal,
     future_gamma: Decimal,
-) -> StdResult<SwapResult> {
-    let offer_ind = 1 ^ ask_ind;
+) -> Result<SwapToPriceResponse, ContractError> {
+    if target_price.is_zero() {
+        return Err(StdError::generic_err("Target price must be positive").into());
+    }
+
+    if asset_amounts.iter().any(|amount| amount.is_zero()) {
+        return Err(StdError::generic_err("One of the assets is empty").into());
+    }
+
+    let snapshot = PoolSnapshot::new(
+        asset_amounts,
+        asset_precisions,
+        maker_fee_share,
+        oracle_price,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
+        block_time,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+    )?;
+
+    let target_price = Decimal256::from(target_price);
+    let (offer_ind, target_price) = if snapshot.spot_price(1)? > target_price {
+        (0, target_price)
+    } else {
+        (1, Decimal256::one() / target_price)
+    };
+    let ask_ind = 1 ^ offer_ind;
+
+    let offer_amount = newton_decreasing(
+        target_price,
+        Uint128::new(10).checked_pow(asset_precisions[offer_ind])?,
+        |amount| snapshot.price_after_with_slope(amount, ask_ind),
+    )?;
+
+    Ok(SwapToPriceResponse {
+        offer_ind,
+        offer_amount,
+        swap: snapshot.swap(offer_amount, ask_ind)?,
+    })
+}
+
+/// Computes the maximum offer amounts of both assets which can be swapped within each of
+/// `price_impacts`.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_depth(
+    price_impacts: &[Decimal],
+    asset_amounts: &[Decimal256],
+    asset_precisions: &[u32],
+    maker_fee_share: Decimal256,
+    oracle_price: Decimal256,
+    price_scale: Decimal256,
+    fee_gamma: Decimal256,
+    mid_fee: Decimal256,
+    out_fee: Decimal256,
+    block_time: u64,
+    initial_time: u64,
+    inital_amp: Decimal,
+    initial_gamma: Decimal,
+    future_time: u64,
+    future_amp: Decimal,
+    future_gamma: Decimal,
+) -> Result<Vec<DepthResponse>, ContractError> {
+    if asset_amounts.iter().any(|amount| amount.is_zero()) {
+        return Err(StdError::generic_err("One of the assets is empty").into());
+    }
+
+    let offer_pools = asset_amounts
+        .iter()
+        .map(|amount| amount.to_uint256().try_into())
+        .collect::<Result<Vec<Uint128>, _>>()?;
+
+    let snapshot = PoolSnapshot::new(
+        asset_amounts,
+        asset_precisions,
+        maker_fee_share,
+        oracle_price,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
+        block_time,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+    )?;
 
-    let mut ixs = xs.to_vec();
-    ixs[1] *= price_scale;
+    compute_depth(price_impacts, &offer_pools, |offer_ind, amount| {
+        Ok(snapshot.swap(amount, 1 ^ offer_ind)?.price_impact)
+    })
+}
 
-    let amp_gamma = get_amp_gamma(
+/// Builds the synthetic order book of the pool with `levels` levels spaced by `price_step` on
+/// each side.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_ladder(
+    price_step: Decimal,
+    levels: u32,
+    asset_amounts: &[Decimal256],
+    asset_precisions: &[u32],
+    maker_fee_share: Decimal256,
+    oracle_price: Decimal256,
+    price_scale: Decimal256,
+    fee_gamma: Decimal256,
+    mid_fee: Decimal256,
+    out_fee: Decimal256,
+    block_time: u64,
+    initial_time: u64,
+    inital_amp: Decimal,
+    initial_gamma: Decimal,
+    future_time: u64,
+    future_amp: Decimal,
+    future_gamma: Decimal,
+) -> Result<LadderResponse, ContractError> {
+    if asset_amounts.iter().any(|amount| amount.is_zero()) {
+        return Err(StdError::generic_err("One of the assets is empty").into());
+    }
+
+    let mid_price = PoolSnapshot::new(
+        asset_amounts,
+        asset_precisions,
+        maker_fee_share,
+        oracle_price,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
         block_time,
         initial_time,
         inital_amp,
@@ -118,8 +364,137 @@ fn compute_swap(
         future_time,
         future_amp,
         future_gamma,
-    );
-    let d = calc_d(&ixs, &amp_gamma)?;
+    )?
+    .spot_price(1)?;
+
+    compute_ladder(mid_price.to_decimal()?, price_step, levels, |price| {
+        let response = swap_to_price(
+            price,
+            asset_amounts,
+            asset_precisions,
+            maker_fee_share,
+            oracle_price,
+            price_scale,
+            fee_gamma,
+            mid_fee,
+            out_fee,
+            block_time,
+            initial_time,
+            inital_amp,
+            initial_gamma,
+            future_time,
+            future_amp,
+            future_gamma,
+        )?;
+        Ok((response.offer_amount, response.swap.return_amount))
+    })
+}
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "ConcentratedBatchPool")]
+pub struct BatchPool {
+    /// The pool balances of both assets
+    pub asset_amounts: Vec<Decimal256>,
+    /// The precisions of both assets
+    pub asset_precisions: Vec<u32>,
+    pub maker_fee_share: Decimal256,
+    pub oracle_price: Decimal256,
+    pub price_scale: Decimal256,
+    pub fee_gamma: Decimal256,
+    pub mid_fee: Decimal256,
+    pub out_fee: Decimal256,
+    pub block_time: u64,
+    pub initial_time: u64,
+    pub initial_amp: Decimal,
+    pub initial_gamma: Decimal,
+    pub future_time: u64,
+    pub future_amp: Decimal,
+    pub future_gamma: Decimal,
+}
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "ConcentratedBatchSwapRequest")]
+pub struct BatchSwapRequest {
+    /// The index of the pool in the batch
+    pub pool: usize,
+    /// The amount of offer assets
+    pub offer_amount: Decimal256,
+    /// The index of the ask asset
+    pub ask_ind: usize,
+}
+
+/// Simulates every swap request against its pool, reporting errors per request.
+pub fn simulate_batch(
+    pools: &[BatchPool],
+    requests: &[BatchSwapRequest],
+) -> Vec<BatchItem<SwapSimulationResponse>> {
+    run_batch(
+        pools,
+        requests,
+        |request| request.pool,
+        |pool, request| {
+            check_swap_request(pool.asset_amounts.len(), request.ask_ind)?;
+            if pool.asset_precisions.len() != pool.asset_amounts.len() {
+                return Err(ContractError::WrongAssetLength {
+                    expected: pool.asset_amounts.len(),
+                    actual: pool.asset_precisions.len(),
+                });
+            }
+
+            let offer_ind = 1 - request.ask_ind;
+            simulate(
+                request.offer_amount,
+                pool.asset_precisions[offer_ind],
+                request.ask_ind,
+                pool.asset_precisions[request.ask_ind],
+                &pool.asset_amounts,
+                pool.maker_fee_share,
+                pool.oracle_price,
+                pool.price_scale,
+                pool.fee_gamma,
+                pool.mid_fee,
+                pool.out_fee,
+                pool.block_time,
+                pool.initial_time,
+                pool.initial_amp,
+                pool.initial_gamma,
+                pool.future_time,
+                pool.future_amp,
+                pool.future_gamma,
+            )
+        },
+    )
+}
+
+#[cw_serde]
+pub struct SwapResult {
+    pub new_y: Decimal256,
+    pub offer_amount: Decimal256,
+    pub dy: Decimal256,
+    pub spread_fee: Decimal256,
+    pub maker_fee: Decimal256,
+    pub total_fee: Decimal256,
+}
+
+/// Computes the swap of `offer_amount` against the pools with the invariant `d`.
+///
+/// * **ixs** - internal representation of pool volumes.
+pub(crate) fn compute_swap(
+    mut ixs: Vec<Decimal256>,
+    d: Decimal256,
+    amp_gamma: &AmpGamma,
+    offer_amount: Decimal256,
+    ask_ind: usize,
+    maker_fee_share: Decimal256,
+    oracle_price: Decimal256,
+    price_scale: Decimal256,
+    fee_gamma: Decimal256,
+    mid_fee: Decimal256,
+    out_fee: Decimal256,
+) -> StdResult<SwapResult> {
+    let offer_ind = 1 ^ ask_ind;
 
     if offer_ind == 1 {
         ixs[offer_ind] += offer_amount * price_scale;
@@ -127,7 +502,7 @@ fn compute_swap(
         ixs[offer_ind] += offer_amount;
     }
 
-    let new_y = calc_y(&ixs, d, &amp_gamma, ask_ind)?;
+    let new_y = calc_y(&ixs, d, amp_gamma, ask_ind)?;
     let mut dy = ixs[ask_ind] - new_y;
     ixs[ask_ind] = new_y;
 
@@ -151,3 +526,403 @@ fn compute_swap(
         total_fee,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn dec(value: &str) -> Decimal256 {
+        Decimal256::from_str(value).unwrap()
+    }
+
+    fn snapshot_of(asset_amounts: &[Decimal256]) -> Result<PoolSnapshot, ContractError> {
+        PoolSnapshot::new(
+            asset_amounts,
+            &[6, 6],
+            dec("0.5"),
+            dec("1.5"),
+            dec("1.5"),
+            dec("0.00023"),
+            dec("0.0026"),
+            dec("0.0045"),
+            100,
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+        )
+    }
+
+    fn swap_to_price_of(
+        target_price: Decimal,
+        asset_amounts: &[Decimal256],
+    ) -> Result<SwapToPriceResponse, ContractError> {
+        swap_to_price(
+            target_price,
+            asset_amounts,
+            &[6, 6],
+            dec("0.5"),
+            dec("1.5"),
+            dec("1.5"),
+            dec("0.00023"),
+            dec("0.0026"),
+            dec("0.0045"),
+            100,
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+        )
+    }
+
+    #[test]
+    fn swap_to_price_offers_the_smallest_amount_reaching_the_price() {
+        let asset_amounts = [
+            Decimal256::from_integer(1_000_000_000_000u128),
+            Decimal256::from_integer(666_666_000_000u128),
+        ];
+        let snapshot = snapshot_of(&asset_amounts).unwrap();
+
+        for (target_price, offer_ind) in [
+            (Decimal::percent(60), 0),
+            (Decimal::percent(30), 0),
+            (Decimal::percent(70), 1),
+            (Decimal::percent(200), 1),
+        ] {
+            let response = swap_to_price_of(target_price, &asset_amounts).unwrap();
+            assert_eq!(response.offer_ind, offer_ind);
+
+            let ask_ind = 1 ^ offer_ind;
+            let target_price = if offer_ind == 0 {
+                Decimal256::from(target_price)
+            } else {
+                Decimal256::one() / Decimal256::from(target_price)
+            };
+            let previous = snapshot
+                .swap(response.offer_amount - Uint128::one(), ask_ind)
+                .unwrap();
+            assert!(response.swap.spot_price_after <= target_price);
+            assert!(previous.spot_price_after > target_price);
+        }
+    }
+
+    #[test]
+    fn swap_to_price_rejects_empty_pools() {
+        let err = swap_to_price_of(
+            Decimal::percent(60),
+            &[Decimal256::zero(), Decimal256::from_integer(1_000_000u128)],
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Generic error: One of the assets is empty");
+    }
+
+    fn depth_of(
+        price_impacts: &[Decimal],
+        asset_amounts: &[Decimal256],
+    ) -> Result<Vec<DepthResponse>, ContractError> {
+        simulate_depth(
+            price_impacts,
+            asset_amounts,
+            &[6, 6],
+            dec("0.5"),
+            dec("1.5"),
+            dec("1.5"),
+            dec("0.00023"),
+            dec("0.0026"),
+            dec("0.0045"),
+            100,
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+        )
+    }
+
+    #[test]
+    fn depth_offers_the_largest_amounts_within_the_thresholds() {
+        let asset_amounts = [
+            Decimal256::from_integer(1_000_000_000_000u128),
+            Decimal256::from_integer(666_666_000_000u128),
+        ];
+        let snapshot = snapshot_of(&asset_amounts).unwrap();
+
+        let depth = depth_of(&[Decimal::permille(1), Decimal::percent(1)], &asset_amounts).unwrap();
+
+        for response in depth {
+            let threshold = Decimal256::from(response.price_impact);
+            for (offer_ind, offer_amount) in response.offer_amounts.into_iter().enumerate() {
+                let impact_of = |amount| snapshot.swap(amount, 1 ^ offer_ind).unwrap().price_impact;
+                assert!(impact_of(offer_amount) <= threshold);
+                assert!(impact_of(offer_amount + Uint128::one()) > threshold);
+            }
+        }
+    }
+
+    #[test]
+    fn depth_rejects_wrong_asset_length() {
+        let err = depth_of(
+            &[Decimal::percent(1)],
+            &[Decimal256::from_integer(1_000_000u128)],
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Wrong asset length: expected 2, actual 1");
+    }
+
+    #[test]
+    fn depth_rejects_empty_pools() {
+        let err = depth_of(
+            &[Decimal::percent(1)],
+            &[Decimal256::zero(), Decimal256::from_integer(1_000_000u128)],
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Generic error: One of the assets is empty");
+    }
+
+    fn ladder_of(
+        price_step: Decimal,
+        levels: u32,
+        asset_amounts: &[Decimal256],
+    ) -> Result<LadderResponse, ContractError> {
+        simulate_ladder(
+            price_step,
+            levels,
+            asset_amounts,
+            &[6, 6],
+            dec("0.5"),
+            dec("1.5"),
+            dec("1.5"),
+            dec("0.00023"),
+            dec("0.0026"),
+            dec("0.0045"),
+            100,
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+        )
+    }
+
+    #[test]
+    fn ladder_levels_move_the_price_to_their_prices() {
+        let asset_amounts = [
+            Decimal256::from_integer(1_000_000_000_000u128),
+            Decimal256::from_integer(666_666_000_000u128),
+        ];
+        let snapshot = snapshot_of(&asset_amounts).unwrap();
+
+        let ladder = ladder_of(Decimal::percent(5), 3, &asset_amounts).unwrap();
+
+        assert_eq!((ladder.bids.len(), ladder.asks.len()), (3, 3));
+        for (levels, offer_ind) in [(&ladder.bids, 0), (&ladder.asks, 1)] {
+            let mut previous_offer_amount = Uint128::zero();
+            for level in levels {
+                let (offer_amount, return_amount, target_price) = if offer_ind == 0 {
+                    (
+                        level.base_amount,
+                        level.quote_amount,
+                        Decimal256::from(level.price),
+                    )
+                } else {
+                    (
+                        level.quote_amount,
+                        level.base_amount,
+                        Decimal256::one() / Decimal256::from(level.price),
+                    )
+                };
+                let swap = snapshot.swap(offer_amount, 1 ^ offer_ind).unwrap();
+
+                assert!(offer_amount > previous_offer_amount);
+                assert_eq!(swap.return_amount, return_amount);
+                assert!(swap.spot_price_after <= target_price);
+                previous_offer_amount = offer_amount;
+            }
+        }
+    }
+
+    #[test]
+    fn ladder_rejects_wrong_asset_length() {
+        let err = ladder_of(
+            Decimal::percent(5),
+            3,
+            &[Decimal256::from_integer(1_000_000u128)],
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Wrong asset length: expected 2, actual 1");
+    }
+
+    #[test]
+    fn ladder_rejects_empty_pools() {
+        let err = ladder_of(
+            Decimal::percent(5),
+            3,
+            &[Decimal256::zero(), Decimal256::from_integer(1_000_000u128)],
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Generic error: One of the assets is empty");
+    }
+
+    fn curve_of(
+        asset_amounts: &[Decimal256],
+        asset_precisions: &[u32],
+        points: u32,
+        ask_ind: usize,
+    ) -> Result<Vec<CurvePoint>, ContractError> {
+        sample_curve(
+            Uint128::new(1_000_000),
+            Uint128::new(100_000_000_000),
+            points,
+            Spacing::Log,
+            ask_ind,
+            asset_amounts,
+            asset_precisions,
+            dec("0.5"),
+            dec("1.5"),
+            dec("1.5"),
+            dec("0.00023"),
+            dec("0.0026"),
+            dec("0.0045"),
+            100,
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+        )
+    }
+
+    #[test]
+    fn curve_points_match_the_swaps() {
+        let asset_amounts = [
+            Decimal256::from_integer(1_000_000_000_000u128),
+            Decimal256::from_integer(666_666_000_000u128),
+        ];
+        let snapshot = snapshot_of(&asset_amounts).unwrap();
+
+        for ask_ind in [0, 1] {
+            let points = curve_of(&asset_amounts, &[6, 6], 3, ask_ind).unwrap();
+
+            assert_eq!(points.len(), 3);
+            for (point, offer_amount) in
+                points
+                    .iter()
+                    .zip([1_000_000u128, 316_227_766, 100_000_000_000])
+            {
+                let swap = snapshot.swap(Uint128::new(offer_amount), ask_ind).unwrap();
+                assert_eq!(point.offer_amount, Uint128::new(offer_amount));
+                assert_eq!(point.return_amount, swap.return_amount);
+                assert_eq!(point.commission_amount, swap.commission_amount);
+                assert_eq!(point.price_impact, swap.price_impact);
+            }
+        }
+    }
+
+    #[test]
+    fn curve_rejects_empty_pools() {
+        let err = curve_of(
+            &[Decimal256::zero(), Decimal256::from_integer(1_000_000u128)],
+            &[6, 6],
+            2,
+            1,
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Generic error: One of the assets is empty");
+    }
+
+    #[test]
+    fn curve_rejects_wrong_requests() {
+        let asset_amounts = [
+            Decimal256::from_integer(1_000_000_000_000u128),
+            Decimal256::from_integer(666_666_000_000u128),
+        ];
+        let err = |asset_precisions: &[u32], points, ask_ind| {
+            curve_of(&asset_amounts, asset_precisions, points, ask_ind)
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(err(&[6], 2, 0), "Wrong asset length: expected 2, actual 1");
+        assert_eq!(
+            err(&[6, 6], 2, 2),
+            "Generic error: Ask asset index 2 is out of range"
+        );
+        assert_eq!(
+            err(&[6, 6], 1, 0),
+            "Generic error: Number of points must be at least 2"
+        );
+    }
+
+    #[test]
+    fn batch_matches_the_single_swaps() {
+        let pool = BatchPool {
+            asset_amounts: vec![
+                Decimal256::from_integer(1_000_000_000_000u128),
+                Decimal256::from_integer(666_666_000_000u128),
+            ],
+            asset_precisions: vec![6, 6],
+            maker_fee_share: dec("0.5"),
+            oracle_price: dec("1.5"),
+            price_scale: dec("1.5"),
+            fee_gamma: dec("0.00023"),
+            mid_fee: dec("0.0026"),
+            out_fee: dec("0.0045"),
+            block_time: 100,
+            initial_time: 0,
+            initial_amp: Decimal::from_ratio(40u8, 1u8),
+            initial_gamma: Decimal::from_str("0.000145").unwrap(),
+            future_time: 0,
+            future_amp: Decimal::from_ratio(40u8, 1u8),
+            future_gamma: Decimal::from_str("0.000145").unwrap(),
+        };
+        let pools = [
+            pool.clone(),
+            BatchPool {
+                asset_precisions: vec![6],
+                ..pool.clone()
+            },
+        ];
+        let request = |pool, ask_ind| BatchSwapRequest {
+            pool,
+            offer_amount: Decimal256::from_integer(1_000_000u128),
+            ask_ind,
+        };
+
+        let items = simulate_batch(
+            &pools,
+            &[request(0, 1), request(0, 0), request(0, 2), request(1, 0)],
+        );
+
+        let swap = |ask_ind| {
+            BatchItem::Ok(
+                snapshot_of(&pool.asset_amounts)
+                    .unwrap()
+                    .swap(Uint128::new(1_000_000), ask_ind)
+                    .unwrap(),
+            )
+        };
+        assert_eq!(
+            items,
+            [
+                swap(1),
+                swap(0),
+                BatchItem::Error("Generic error: Ask asset index 2 is out of range".to_string()),
+                BatchItem::Error("Wrong asset length: expected 2, actual 1".to_string()),
+            ]
+        );
+    }
+}
@@ -1,5 +1,130 @@
 use super::error::ContractError;
+use crate::astroport::cosmwasm_ext::{Decimal256Ext, DecimalToInteger};
+use crate::astroport::lib::guards::ensure_min_assets_to_receive;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal256, StdError, Uint128};
+use tsify::Tsify;
 
-pub fn simulate() -> Result<(), ContractError> {
-    Ok(())
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "ConcentratedWithdrawSimulationResponse")]
+pub struct SimulationResponse {
+    /// The amount of assets returned by the withdraw
+    pub returned_amounts: Vec<Uint128>,
+}
+
+pub fn simulate(
+    amount: Uint128,
+    asset_amounts: &[Decimal256],
+    asset_precisions: &[u32],
+    total_share: Uint128,
+    min_assets_to_receive: Option<Vec<Uint128>>,
+) -> Result<SimulationResponse, ContractError> {
+    if asset_precisions.len() != asset_amounts.len() {
+        return Err(ContractError::WrongAssetLength {
+            expected: asset_amounts.len(),
+            actual: asset_precisions.len(),
+        });
+    }
+    if amount > total_share {
+        return Err(StdError::generic_err("Insufficient amount of liquidity").into());
+    }
+
+    let asset_amounts = asset_amounts
+        .iter()
+        .enumerate()
+        .map(|(i, amount)| Decimal256::with_precision(amount.to_uint256(), asset_precisions[i]))
+        .collect::<Result<Vec<Decimal256>, StdError>>()?;
+
+    // As in the contract, one lp is kept from the share so that the withdraw rounds down
+    let returned_amounts = get_share_in_assets(
+        &asset_amounts,
+        amount.saturating_sub(Uint128::one()),
+        total_share,
+    )
+    .iter()
+    .enumerate()
+    .map(|(i, amount)| amount.to_uint(asset_precisions[i]))
+    .collect::<Result<Vec<Uint128>, _>>()?;
+
+    ensure_min_assets_to_receive(&returned_amounts, min_assets_to_receive, None)?;
+
+    Ok(SimulationResponse { returned_amounts })
+}
+
+/// Returns the share of assets.
+///
+/// * **xs** - pool volumes.
+/// * **amount** - amount of LP tokens to calculate share of assets for.
+/// * **total_share** - total amount of LP tokens.
+pub fn get_share_in_assets(
+    xs: &[Decimal256],
+    amount: Uint128,
+    total_share: Uint128,
+) -> Vec<Decimal256> {
+    let mut share_ratio = Decimal256::zero();
+    if !total_share.is_zero() {
+        share_ratio = Decimal256::from_ratio(amount, total_share);
+    }
+
+    xs.iter().map(|amount| *amount * share_ratio).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn withdraw_of(
+        amount: Uint128,
+        min_assets_to_receive: Option<Vec<Uint128>>,
+    ) -> Result<SimulationResponse, ContractError> {
+        simulate(
+            amount,
+            &[
+                Decimal256::from_integer(1_000_000_000_000u128),
+                Decimal256::from_integer(666_666_000_000u128),
+            ],
+            &[6, 6],
+            Uint128::new(816_496_000_000),
+            min_assets_to_receive,
+        )
+    }
+
+    #[test]
+    fn withdraw_checks_the_min_assets_to_receive() {
+        let returned_amounts = [Uint128::new(1_224_744), Uint128::new(816_495)];
+
+        assert_eq!(
+            withdraw_of(Uint128::new(1_000_000), None)
+                .unwrap()
+                .returned_amounts,
+            returned_amounts
+        );
+        assert!(withdraw_of(Uint128::new(1_000_000), Some(returned_amounts.to_vec())).is_ok());
+        assert_eq!(
+            withdraw_of(
+                Uint128::new(1_000_000),
+                Some(vec![Uint128::new(1_224_745), Uint128::zero()])
+            )
+            .unwrap_err()
+            .to_string(),
+            "Received 1224744 asset #0 but expected 1224745"
+        );
+        assert_eq!(
+            withdraw_of(Uint128::new(1_000_000), Some(vec![Uint128::zero(); 3]))
+                .unwrap_err()
+                .to_string(),
+            "Wrong asset length: expected 2, actual 3"
+        );
+    }
+
+    #[test]
+    fn withdraw_rejects_more_than_the_total_share() {
+        for amount in [Uint128::new(816_496_000_001), Uint128::MAX] {
+            assert_eq!(
+                withdraw_of(amount, None).unwrap_err().to_string(),
+                "Generic error: Insufficient amount of liquidity"
+            );
+        }
+    }
 }
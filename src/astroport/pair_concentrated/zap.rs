@@ -0,0 +1,463 @@
+use super::{
+    error::ContractError,
+    provide::{self, SimulationResponse},
+    snapshot::PoolSnapshot,
+    state::RepegParams,
+    swap::{self, empty_swap, SwapSimulationResponse},
+    withdraw,
+};
+use crate::astroport::cosmwasm_ext::Decimal256Ext;
+use crate::astroport::lib::batch::check_swap_request;
+use crate::astroport::lib::search::maximize;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Decimal256, StdError, Uint128};
+use tsify::Tsify;
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "ConcentratedZapInSimulationResponse")]
+pub struct ZapInSimulationResponse {
+    /// The amount of offer asset swapped before the provide
+    pub swap_amount: Uint128,
+    /// The result of the swap leg
+    #[tsify(type = "ConcentratedSwapSimulationResponse")]
+    pub swap: SwapSimulationResponse,
+    /// The amounts deposited by the provide leg
+    pub deposits: Vec<Uint128>,
+    /// The result of the provide leg
+    #[tsify(type = "ConcentratedProvideSimulationResponse")]
+    pub provide: SimulationResponse,
+    /// The amounts of deposits which are not backed by the minted lps. Concentrated pools accept
+    /// imbalanced deposits, thus these are always zero.
+    pub dust_amounts: Vec<Uint128>,
+}
+
+/// Simulates providing a single asset: the part of `offer_amount` which maximizes the minted lps
+/// is swapped first and the resulting pair of amounts is provided against the post-swap pools.
+///
+/// The maker fee leaves the pool with the swap. The pair may repeg the price scale on the swap,
+/// the provide leg uses the repegged one if **repeg** is specified, the current one otherwise.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_zap_in(
+    offer_amount: Decimal256,
+    offer_ind: usize,
+    asset_amounts: &[Decimal256],
+    asset_precisions: &[u32],
+    total_share: Uint128,
+    maker_fee_share: Decimal256,
+    oracle_price: Decimal256,
+    price_scale: Decimal256,
+    fee_gamma: Decimal256,
+    mid_fee: Decimal256,
+    out_fee: Decimal256,
+    block_time: u64,
+    initial_time: u64,
+    inital_amp: Decimal,
+    initial_gamma: Decimal,
+    future_time: u64,
+    future_amp: Decimal,
+    future_gamma: Decimal,
+    repeg: Option<&RepegParams>,
+) -> Result<ZapInSimulationResponse, ContractError> {
+    check_swap_request(asset_amounts.len(), offer_ind)?;
+    let offer_amount: Uint128 = offer_amount.to_uint256().try_into()?;
+    if offer_amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    if asset_amounts.iter().any(|amount| amount.is_zero()) || total_share.is_zero() {
+        return Err(StdError::generic_err("One of the assets is empty").into());
+    }
+
+    let snapshot = PoolSnapshot::new(
+        asset_amounts,
+        asset_precisions,
+        maker_fee_share,
+        oracle_price,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
+        block_time,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+    )?;
+
+    let legs = |swap_amount: Uint128| -> Result<_, ContractError> {
+        let ask_ind = 1 ^ offer_ind;
+        let (swap, pools, price_scale) =
+            snapshot.execute_swap(swap_amount, ask_ind, total_share, repeg)?;
+
+        let mut deposits = vec![Uint128::zero(); 2];
+        deposits[offer_ind] = offer_amount - swap_amount;
+        deposits[ask_ind] = swap.return_amount;
+
+        let provide = provide::simulate(
+            &deposits
+                .iter()
+                .map(|amount| Decimal256::from_integer(*amount))
+                .collect::<Vec<_>>(),
+            &pools,
+            asset_precisions,
+            total_share,
+            price_scale,
+            fee_gamma,
+            mid_fee,
+            out_fee,
+            block_time,
+            initial_time,
+            inital_amp,
+            initial_gamma,
+            future_time,
+            future_amp,
+            future_gamma,
+            None,
+            None,
+            None,
+        )?;
+
+        Ok((swap, deposits, provide))
+    };
+
+    let swap_amount = maximize(Uint128::zero(), offer_amount, |swap_amount| {
+        legs(swap_amount)
+            .map(|(_, _, provide)| provide.share_amount)
+            .map_err(|err| StdError::generic_err(err.to_string()))
+    });
+    let (swap, deposits, provide) = legs(swap_amount)?;
+
+    Ok(ZapInSimulationResponse {
+        swap_amount,
+        swap,
+        deposits,
+        provide,
+        dust_amounts: vec![Uint128::zero(); 2],
+    })
+}
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "ConcentratedZapOutSimulationResponse")]
+pub struct ZapOutSimulationResponse {
+    /// The result of the withdraw leg
+    #[tsify(type = "ConcentratedWithdrawSimulationResponse")]
+    pub withdraw: withdraw::SimulationResponse,
+    /// The result of swapping the other withdrawn asset into the ask asset
+    #[tsify(type = "ConcentratedSwapSimulationResponse")]
+    pub swap: SwapSimulationResponse,
+    /// The total amount of ask asset returned
+    pub return_amount: Uint128,
+}
+
+/// Simulates burning `amount` lps for a single asset: the assets are withdrawn proportionally
+/// and the other withdrawn asset is swapped into the ask asset against the post-withdraw pools.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_zap_out(
+    amount: Uint128,
+    ask_ind: usize,
+    asset_amounts: &[Decimal256],
+    asset_precisions: &[u32],
+    total_share: Uint128,
+    maker_fee_share: Decimal256,
+    oracle_price: Decimal256,
+    price_scale: Decimal256,
+    fee_gamma: Decimal256,
+    mid_fee: Decimal256,
+    out_fee: Decimal256,
+    block_time: u64,
+    initial_time: u64,
+    inital_amp: Decimal,
+    initial_gamma: Decimal,
+    future_time: u64,
+    future_amp: Decimal,
+    future_gamma: Decimal,
+) -> Result<ZapOutSimulationResponse, ContractError> {
+    check_swap_request(asset_amounts.len(), ask_ind)?;
+    let offer_ind = 1 ^ ask_ind;
+
+    let withdraw = withdraw::simulate(amount, asset_amounts, asset_precisions, total_share, None)?;
+
+    let pools = asset_amounts
+        .iter()
+        .zip(&withdraw.returned_amounts)
+        .map(|(pool, returned_amount)| pool.checked_sub(Decimal256::from_integer(*returned_amount)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let swap = if withdraw.returned_amounts[offer_ind].is_zero() {
+        empty_swap()
+    } else {
+        swap::simulate(
+            Decimal256::from_integer(withdraw.returned_amounts[offer_ind]),
+            asset_precisions[offer_ind],
+            ask_ind,
+            asset_precisions[ask_ind],
+            &pools,
+            maker_fee_share,
+            oracle_price,
+            price_scale,
+            fee_gamma,
+            mid_fee,
+            out_fee,
+            block_time,
+            initial_time,
+            inital_amp,
+            initial_gamma,
+            future_time,
+            future_amp,
+            future_gamma,
+        )?
+    };
+    let return_amount = withdraw.returned_amounts[ask_ind].checked_add(swap.return_amount)?;
+
+    Ok(ZapOutSimulationResponse {
+        withdraw,
+        swap,
+        return_amount,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn dec(value: &str) -> Decimal256 {
+        Decimal256::from_str(value).unwrap()
+    }
+
+    fn pool_amounts() -> [Decimal256; 2] {
+        [
+            Decimal256::from_integer(1_000_000_000_000u128),
+            Decimal256::from_integer(666_666_000_000u128),
+        ]
+    }
+
+    fn zap_in_of(
+        offer_amount: u128,
+        offer_ind: usize,
+        asset_amounts: &[Decimal256],
+        total_share: Uint128,
+    ) -> Result<ZapInSimulationResponse, ContractError> {
+        simulate_zap_in(
+            Decimal256::from_integer(offer_amount),
+            offer_ind,
+            asset_amounts,
+            &[6, 6],
+            total_share,
+            dec("0.5"),
+            dec("1.5"),
+            dec("1.5"),
+            dec("0.00023"),
+            dec("0.0026"),
+            dec("0.0045"),
+            100,
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+            None,
+        )
+    }
+
+    #[test]
+    fn zap_in_swaps_the_optimal_amount() {
+        let total_share = Uint128::new(816_496_000_000);
+        let snapshot = PoolSnapshot::new(
+            &pool_amounts(),
+            &[6, 6],
+            dec("0.5"),
+            dec("1.5"),
+            dec("1.5"),
+            dec("0.00023"),
+            dec("0.0026"),
+            dec("0.0045"),
+            100,
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+        )
+        .unwrap();
+        let offer_amount = 10_000_000_000u128;
+        let share_amount = |swap_amount: u128| {
+            let (swap, pools, price_scale) = snapshot
+                .execute_swap(Uint128::new(swap_amount), 0, total_share, None)
+                .unwrap();
+            provide::simulate(
+                &[
+                    Decimal256::from_integer(swap.return_amount),
+                    Decimal256::from_integer(offer_amount - swap_amount),
+                ],
+                &pools,
+                &[6, 6],
+                total_share,
+                price_scale,
+                dec("0.00023"),
+                dec("0.0026"),
+                dec("0.0045"),
+                100,
+                0,
+                Decimal::from_ratio(40u8, 1u8),
+                Decimal::from_str("0.000145").unwrap(),
+                0,
+                Decimal::from_ratio(40u8, 1u8),
+                Decimal::from_str("0.000145").unwrap(),
+                None,
+                None,
+                None,
+            )
+            .unwrap()
+            .share_amount
+        };
+
+        let response = zap_in_of(offer_amount, 1, &pool_amounts(), total_share).unwrap();
+
+        let swap_amount = response.swap_amount.u128();
+        assert_eq!(response.provide.share_amount, share_amount(swap_amount));
+        assert_eq!(
+            response.deposits,
+            [
+                response.swap.return_amount,
+                Uint128::new(offer_amount - swap_amount)
+            ]
+        );
+        assert_eq!(response.dust_amounts, vec![Uint128::zero(); 2]);
+        for other in (0..=offer_amount)
+            .step_by(offer_amount as usize / 50)
+            .chain([swap_amount - 1, swap_amount + 1])
+        {
+            assert!(
+                share_amount(other) <= response.provide.share_amount,
+                "{other}"
+            );
+        }
+    }
+
+    #[test]
+    fn zap_in_rejects_wrong_requests() {
+        let total_share = Uint128::new(816_496_000_000);
+        let err = |offer_amount, offer_ind, asset_amounts: &[Decimal256], total_share| {
+            zap_in_of(offer_amount, offer_ind, asset_amounts, total_share)
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            err(1_000, 2, &pool_amounts(), total_share),
+            "Generic error: Ask asset index 2 is out of range"
+        );
+        assert_eq!(
+            err(0, 0, &pool_amounts(), total_share),
+            "Event of zero transfer"
+        );
+        assert_eq!(
+            err(
+                1_000,
+                0,
+                &[Decimal256::zero(), pool_amounts()[1]],
+                total_share
+            ),
+            "Generic error: One of the assets is empty"
+        );
+        assert_eq!(
+            err(1_000, 0, &pool_amounts(), Uint128::zero()),
+            "Generic error: One of the assets is empty"
+        );
+    }
+
+    fn zap_out_of(amount: u128, ask_ind: usize) -> Result<ZapOutSimulationResponse, ContractError> {
+        simulate_zap_out(
+            Uint128::new(amount),
+            ask_ind,
+            &pool_amounts(),
+            &[6, 6],
+            Uint128::new(816_496_000_000),
+            dec("0.5"),
+            dec("1.5"),
+            dec("1.5"),
+            dec("0.00023"),
+            dec("0.0026"),
+            dec("0.0045"),
+            100,
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+        )
+    }
+
+    #[test]
+    fn zap_out_swaps_the_other_asset_against_the_remaining_pools() {
+        let response = zap_out_of(8_164_960_000, 1).unwrap();
+
+        let withdraw = withdraw::simulate(
+            Uint128::new(8_164_960_000),
+            &pool_amounts(),
+            &[6, 6],
+            Uint128::new(816_496_000_000),
+            None,
+        )
+        .unwrap();
+        let returned_amounts = &withdraw.returned_amounts;
+        let swap = swap::simulate(
+            Decimal256::from_integer(returned_amounts[0]),
+            6,
+            1,
+            6,
+            &[
+                pool_amounts()[0] - Decimal256::from_integer(returned_amounts[0]),
+                pool_amounts()[1] - Decimal256::from_integer(returned_amounts[1]),
+            ],
+            dec("0.5"),
+            dec("1.5"),
+            dec("1.5"),
+            dec("0.00023"),
+            dec("0.0026"),
+            dec("0.0045"),
+            100,
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+            0,
+            Decimal::from_ratio(40u8, 1u8),
+            Decimal::from_str("0.000145").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(response.swap, swap);
+        assert_eq!(
+            response.return_amount,
+            returned_amounts[1] + swap.return_amount
+        );
+        assert_eq!(response.withdraw, withdraw);
+    }
+
+    #[test]
+    fn zap_out_of_nothing_swaps_nothing() {
+        let response = zap_out_of(0, 1).unwrap();
+
+        assert_eq!(response.return_amount, Uint128::zero());
+        assert_eq!(response.swap, empty_swap());
+    }
+
+    #[test]
+    fn zap_out_rejects_wrong_requests() {
+        assert_eq!(
+            zap_out_of(1_000, 2).unwrap_err().to_string(),
+            "Generic error: Ask asset index 2 is out of range"
+        );
+        assert_eq!(
+            zap_out_of(816_496_000_001, 1).unwrap_err().to_string(),
+            "Generic error: Insufficient amount of liquidity"
+        );
+    }
+}
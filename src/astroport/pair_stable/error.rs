@@ -1,6 +1,8 @@
 use cosmwasm_std::{CheckedMultiplyRatioError, ConversionOverflowError, OverflowError, StdError};
 use thiserror::Error;
 
+use crate::astroport::lib::guards::GuardError;
+
 // use astroport_circular_buffer::error::BufferError;
 
 use super::consts::MINIMUM_LIQUIDITY_AMOUNT;
@@ -22,6 +24,19 @@ pub enum ContractError {
 
     #[error("Initial liquidity must be more than {}", MINIMUM_LIQUIDITY_AMOUNT)]
     MinimumLiquidityAmountError {},
+
+    #[error("Provided spread amount exceeds allowed limit")]
+    AllowedSpreadAssertion {},
+
+    // The typo is copied verbatim from the pair contract, so that the errors match
+    #[error("Operation exceeds max splippage tolerance")]
+    MaxSlippageAssertion {},
+
+    #[error("{0}")]
+    Guard(#[from] GuardError),
+
+    #[error("Wrong asset length: expected {expected}, actual {actual}")]
+    WrongAssetLength { expected: usize, actual: usize },
 }
 
 impl From<OverflowError> for ContractError {
@@ -1,6 +1,4 @@
-use cosmwasm_std::{Decimal256, StdError, StdResult, Uint128, Uint64};
-
-use crate::astroport::cosmwasm_ext::Decimal256Ext;
+use cosmwasm_std::{Decimal256, StdError, StdResult, Uint64};
 
 const ITERATIONS: u8 = 64;
 
@@ -71,20 +69,19 @@ fn calculate_step(
         .map_err(|e| StdError::generic_err(e.to_string()))
 }
 
-/// Compute the swap amount `y` in proportion to `x`.
+/// Compute the pool volume `y` for the given invariant `D`, where `new_amount` is the volume
+/// of the other pool.
 ///
 /// * **Solve for y**
 ///
 /// y**2 + y * (sum' - (A*n**n - 1) * D / (A * n**n)) = D ** (n + 1) / (n ** (2 * n) * prod' * A)
 ///
 /// y**2 + b*y = c
-pub(crate) fn calc_y(
+pub(crate) fn calc_y_with_d(
     amp: Uint64,
     new_amount: Decimal256,
-    xp: &[Decimal256],
-    target_precision: u8,
-) -> StdResult<Uint128> {
-    let d = compute_d(amp, xp)?;
+    d: Decimal256,
+) -> StdResult<Decimal256> {
     let leverage = Decimal256::from_ratio(amp, 1u8) * N_COINS;
     let amp_prec = Decimal256::from_ratio(AMP_PRECISION, 1u8);
 
@@ -106,10 +103,51 @@ pub(crate) fn calc_y(
             .checked_div(y.checked_mul(N_COINS)?.checked_add(b)?.checked_sub(d)?)
             .map_err(|e| StdError::generic_err(e.to_string()))?;
         if y.abs_diff(y_prev) <= TOL {
-            return y.to_uint128_with_precision(target_precision);
+            return Ok(y);
         }
     }
 
     // Should definitely converge in 64 iterations.
     Err(StdError::generic_err("y is not converging"))
 }
+
+/// Computes the marginal price of the offer asset in ask assets, i.e. `-dy/dx` along the
+/// invariant.
+///
+/// * **Equation**
+///
+/// (leverage + D**3 / (n**n * x**2 * y)) / (leverage + D**3 / (n**n * x * y**2))
+pub(crate) fn compute_spot_price(
+    amp: Uint64,
+    offer_pool: Decimal256,
+    ask_pool: Decimal256,
+) -> StdResult<Decimal256> {
+    if offer_pool.is_zero() || ask_pool.is_zero() {
+        return Err(StdError::generic_err("One of the assets is empty"));
+    }
+
+    let d = compute_d(amp, &[offer_pool, ask_pool])?;
+
+    compute_spot_price_with_d(amp, d, offer_pool, ask_pool)
+}
+
+/// Same as [`compute_spot_price`], but reuses the invariant `d` of the pools.
+pub(crate) fn compute_spot_price_with_d(
+    amp: Uint64,
+    d: Decimal256,
+    offer_pool: Decimal256,
+    ask_pool: Decimal256,
+) -> StdResult<Decimal256> {
+    if offer_pool.is_zero() || ask_pool.is_zero() {
+        return Err(StdError::generic_err("One of the assets is empty"));
+    }
+
+    let leverage = Decimal256::from_ratio(amp, AMP_PRECISION) * N_COINS;
+
+    // D**3 / (n**n * x * y)
+    let d_product =
+        (d / offer_pool).checked_mul(d / ask_pool)?.checked_mul(d)? / (N_COINS * N_COINS);
+
+    Ok(leverage.checked_add(d_product / offer_pool)?
+        / leverage.checked_add(d_product / ask_pool)?)
+}
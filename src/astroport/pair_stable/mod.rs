@@ -3,6 +3,9 @@ mod error;
 mod math;
 mod state;
 
+pub mod pool;
 pub mod provide;
+pub mod snapshot;
 pub mod swap;
 pub mod withdraw;
+pub mod zap;
@@ -0,0 +1,241 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Decimal256, StdResult, Uint128};
+use tsify::Tsify;
+
+use super::{error::ContractError, provide, snapshot::PoolSnapshot, swap, withdraw};
+use crate::astroport::cosmwasm_ext::Decimal256Ext;
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "StablePoolState")]
+pub struct PoolState {
+    /// The pool balances of both assets
+    pub asset_amounts: Vec<Decimal256>,
+    pub asset_precisions: Vec<u8>,
+    /// The total amount of issued lps
+    pub total_share: Uint128,
+    pub total_fee_rate: Decimal,
+    pub block_time: u64,
+    pub init_amp_time: u64,
+    pub init_amp: u64,
+    pub next_amp_time: u64,
+    pub next_amp: u64,
+}
+
+/// A pool which is built once from its state and serves simulations until the balances are
+/// updated. Swaps reuse the invariant of the current balances.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pool {
+    state: PoolState,
+    snapshot: PoolSnapshot,
+}
+
+impl Pool {
+    pub fn new(state: PoolState) -> Result<Self, ContractError> {
+        let snapshot = PoolSnapshot::new(
+            &state.asset_amounts,
+            &state.asset_precisions,
+            state.total_fee_rate,
+            state.block_time,
+            state.init_amp_time,
+            state.init_amp,
+            state.next_amp_time,
+            state.next_amp,
+        )?;
+
+        Ok(Self { state, snapshot })
+    }
+
+    pub fn state(&self) -> &PoolState {
+        &self.state
+    }
+
+    /// Replaces the pool balances and, if specified, the total amount of lps.
+    pub fn update(
+        &mut self,
+        asset_amounts: Vec<Decimal256>,
+        total_share: Option<Uint128>,
+    ) -> Result<(), ContractError> {
+        let mut state = self.state.clone();
+        state.asset_amounts = asset_amounts;
+        if let Some(total_share) = total_share {
+            state.total_share = total_share;
+        }
+
+        *self = Self::new(state)?;
+
+        Ok(())
+    }
+
+    /// Returns the invariant (D) of the pool balances.
+    #[cfg(any(test, feature = "testing"))]
+    pub fn d(&self) -> Decimal256 {
+        self.snapshot.d()
+    }
+
+    /// Returns the price of the offer asset in ask assets, excluding fees.
+    pub fn spot_price(&self, ask_ind: usize) -> StdResult<Decimal> {
+        self.snapshot.spot_price(ask_ind)?.to_decimal()
+    }
+
+    pub fn swap(
+        &self,
+        offer_amount: Uint128,
+        ask_ind: usize,
+    ) -> StdResult<swap::SimulationResponse> {
+        self.snapshot.swap(offer_amount, ask_ind)
+    }
+
+    pub fn reverse_swap(
+        &self,
+        ask_amount: Uint128,
+        ask_ind: usize,
+    ) -> StdResult<swap::ReverseSimulationResponse> {
+        self.snapshot.reverse_swap(ask_amount, ask_ind)
+    }
+
+    pub fn provide(
+        &self,
+        deposits: &[Decimal256],
+        slippage_tolerance: Option<Decimal>,
+        min_lp_to_receive: Option<Uint128>,
+    ) -> StdResult<provide::SimulationResponse> {
+        provide::simulate(
+            deposits,
+            &self.state.asset_amounts,
+            &self.state.asset_precisions,
+            self.state.total_share,
+            self.state.block_time,
+            self.state.init_amp_time,
+            self.state.init_amp,
+            self.state.next_amp_time,
+            self.state.next_amp,
+            slippage_tolerance,
+            min_lp_to_receive,
+        )
+    }
+
+    pub fn withdraw(
+        &self,
+        amount: Uint128,
+        min_assets_to_receive: Option<Vec<Uint128>>,
+    ) -> StdResult<withdraw::SimulationResponse> {
+        let asset_amounts = self
+            .state
+            .asset_amounts
+            .iter()
+            .map(|amount| amount.to_uint256().try_into())
+            .collect::<Result<Vec<Uint128>, _>>()?;
+
+        withdraw::simulate(
+            amount,
+            &asset_amounts,
+            self.state.total_share,
+            min_assets_to_receive,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> PoolState {
+        PoolState {
+            asset_amounts: vec![
+                Decimal256::from_integer(530256812u128),
+                Decimal256::from_integer(100446728u128),
+            ],
+            asset_precisions: vec![6, 6],
+            total_share: Uint128::new(620000000),
+            total_fee_rate: Decimal::bps(5),
+            block_time: 0,
+            init_amp_time: 0,
+            init_amp: 10000,
+            next_amp_time: 0,
+            next_amp: 10000,
+        }
+    }
+
+    #[test]
+    fn pool_simulates_its_state() {
+        let state = state();
+        let pool = Pool::new(state.clone()).unwrap();
+        let snapshot = PoolSnapshot::new(
+            &state.asset_amounts,
+            &state.asset_precisions,
+            state.total_fee_rate,
+            0,
+            0,
+            10000,
+            0,
+            10000,
+        )
+        .unwrap();
+
+        assert_eq!(pool.d(), snapshot.d());
+        assert_eq!(
+            Decimal256::from(pool.spot_price(0).unwrap()),
+            snapshot.spot_price(0).unwrap()
+        );
+        assert_eq!(
+            pool.swap(Uint128::new(1_000_000), 1).unwrap(),
+            snapshot.swap(Uint128::new(1_000_000), 1).unwrap()
+        );
+        assert_eq!(
+            pool.reverse_swap(Uint128::new(1_000_000), 0).unwrap(),
+            snapshot.reverse_swap(Uint128::new(1_000_000), 0).unwrap()
+        );
+        let withdraw = pool.withdraw(Uint128::new(62000000), None).unwrap();
+        assert_eq!(
+            withdraw.returned_amounts,
+            [Uint128::new(53025681), Uint128::new(10044672)]
+        );
+    }
+
+    #[test]
+    fn update_recomputes_the_invariant() {
+        let mut pool = Pool::new(state()).unwrap();
+        let asset_amounts = vec![
+            Decimal256::from_integer(100_000_000u128),
+            Decimal256::from_integer(100_000_000u128),
+        ];
+
+        pool.update(asset_amounts.clone(), None).unwrap();
+
+        let expected = Pool::new(PoolState {
+            asset_amounts,
+            ..state()
+        })
+        .unwrap();
+        assert_eq!(pool, expected);
+        assert_eq!(pool.spot_price(1).unwrap(), Decimal::one());
+    }
+
+    #[test]
+    fn pool_rejects_wrong_requests() {
+        assert_eq!(
+            Pool::new(PoolState {
+                asset_precisions: vec![6],
+                ..state()
+            })
+            .unwrap_err()
+            .to_string(),
+            "Wrong asset length: expected 2, actual 1"
+        );
+
+        let mut pool = Pool::new(state()).unwrap();
+        assert_eq!(
+            pool.update(vec![Decimal256::one()], Some(Uint128::one()))
+                .unwrap_err()
+                .to_string(),
+            "Wrong asset length: expected 2, actual 1"
+        );
+        assert_eq!(pool, Pool::new(state()).unwrap());
+
+        assert_eq!(
+            pool.swap(Uint128::one(), 2).unwrap_err().to_string(),
+            "Generic error: Ask asset index 2 is out of range"
+        );
+    }
+}
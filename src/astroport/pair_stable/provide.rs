@@ -1,23 +1,36 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Decimal, Decimal256, StdError, StdResult, Uint128};
+use tsify::Tsify;
 
 use super::{
-    consts::MINIMUM_LIQUIDITY_AMOUNT,
+    consts::{MAX_ALLOWED_SLIPPAGE, MINIMUM_LIQUIDITY_AMOUNT},
     error::ContractError,
     math::compute_d,
     state::{compute_current_amp, greatest_precision},
 };
 
-use crate::astroport::cosmwasm_ext::Decimal256Ext;
+use crate::astroport::cosmwasm_ext::{Decimal256Ext, MultiplyRatioCeil};
+use crate::astroport::lib::guards::ensure_min_lp_to_receive;
+use crate::astroport::lib::search::refine_deposits;
+use crate::astroport::lib::DecimalCheckedOps;
+use crate::astroport::pair_xyk::provide::compute_returned_amounts;
 
 #[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "StableProvideSimulationResponse")]
 pub struct SimulationResponse {
     /// The amount of lps returned by the provide
     pub share_amount: Uint128,
     pub slippage: Decimal,
     pub positive_slippage: bool,
+    /// The amounts of deposits which can't be withdrawn back by burning the minted lps
+    pub lost_amounts: Vec<Uint128>,
 }
 
+/// Simulates the provide.
+///
+/// * **slippage_tolerance** if specified, the provide fails when the value lost exceeds it. The
+///   stable pair contract doesn't check the tolerance of provides, thus there's no default.
 pub fn simulate(
     deposits: &[Decimal256],
     asset_amounts: &[Decimal256],
@@ -28,7 +41,35 @@ pub fn simulate(
     init_amp: u64,
     next_amp_time: u64,
     next_amp: u64,
+    slippage_tolerance: Option<Decimal>,
+    min_lp_to_receive: Option<Uint128>,
 ) -> StdResult<SimulationResponse> {
+    if deposits.len() != 2 {
+        let err = ContractError::WrongAssetLength {
+            expected: 2,
+            actual: deposits.len(),
+        };
+        return Err(StdError::generic_err(format!("{err}")));
+    }
+    for len in [asset_amounts.len(), asset_precisions.len()] {
+        if len != deposits.len() {
+            let err = ContractError::WrongAssetLength {
+                expected: deposits.len(),
+                actual: len,
+            };
+            return Err(StdError::generic_err(format!("{err}")));
+        }
+    }
+
+    let uint_deposits = deposits
+        .iter()
+        .map(|amount| amount.to_uint256().try_into())
+        .collect::<Result<Vec<Uint128>, _>>()?;
+    let uint_asset_amounts = asset_amounts
+        .iter()
+        .map(|amount| amount.to_uint256().try_into())
+        .collect::<Result<Vec<Uint128>, _>>()?;
+
     let deposits = deposits
         .iter()
         .enumerate()
@@ -52,12 +93,138 @@ pub fn simulate(
         next_amp_time,
         next_amp,
     )
+    .and_then(|(share_amount, slippage, positive_slippage)| {
+        ensure_min_lp_to_receive(share_amount, min_lp_to_receive)?;
+        Ok((share_amount, slippage, positive_slippage))
+    })
+    .map_err(|err| StdError::generic_err(format!("{err}")))?;
+
+    let returned_amounts = compute_returned_amounts(
+        &uint_deposits,
+        &uint_asset_amounts,
+        total_share,
+        share_amount,
+    )?;
+
+    assert_slippage_tolerance(
+        slippage_tolerance,
+        &deposits,
+        &returned_amounts,
+        asset_precisions,
+    )
     .map_err(|err| StdError::generic_err(format!("{err}")))?;
 
+    let lost_amounts = uint_deposits
+        .iter()
+        .zip(returned_amounts)
+        .map(|(deposit, returned_amount)| deposit.saturating_sub(returned_amount))
+        .collect();
+
     Ok(SimulationResponse {
         share_amount,
         slippage,
         positive_slippage,
+        lost_amounts,
+    })
+}
+
+/// Ensures the value lost by the provide does not exceed the slippage tolerance. As in swaps, the
+/// assets are valued 1:1, so the loss is the relative difference between the sum of deposits and
+/// the sum of assets returned by burning the minted lps.
+fn assert_slippage_tolerance(
+    slippage_tolerance: Option<Decimal>,
+    deposits: &[Decimal256],
+    returned_amounts: &[Uint128],
+    asset_precisions: &[u8],
+) -> Result<(), ContractError> {
+    if let Some(slippage_tolerance) = slippage_tolerance {
+        if slippage_tolerance > MAX_ALLOWED_SLIPPAGE {
+            return Err(ContractError::AllowedSpreadAssertion {});
+        }
+
+        let sum_deposit = deposits
+            .iter()
+            .fold(Decimal256::zero(), |sum, amount| sum + amount);
+        let sum_returned = returned_amounts
+            .iter()
+            .enumerate()
+            .map(|(i, amount)| Decimal256::with_precision(*amount, asset_precisions[i]))
+            .sum::<StdResult<Decimal256>>()?;
+
+        if sum_deposit.saturating_sub(sum_returned)
+            > sum_deposit * Decimal256::from(slippage_tolerance)
+        {
+            return Err(ContractError::MaxSlippageAssertion {});
+        }
+    }
+
+    Ok(())
+}
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "StableReverseProvideSimulationResponse")]
+pub struct ReverseSimulationResponse {
+    /// The amounts of assets to deposit
+    pub deposits: Vec<Uint128>,
+    /// The amount of lps returned by the provide of `deposits`
+    pub share_amount: Uint128,
+}
+
+/// Computes the deposits required to receive `share_amount` lps.
+///
+/// Deposits proportional to the pools grow the invariant (D) proportionally, they are scaled up
+/// until the provide mints at least `share_amount` lps.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_reverse(
+    share_amount: Uint128,
+    asset_amounts: &[Decimal256],
+    asset_precisions: &[u8],
+    total_share: Uint128,
+    block_time: u64,
+    init_amp_time: u64,
+    init_amp: u64,
+    next_amp_time: u64,
+    next_amp: u64,
+) -> StdResult<ReverseSimulationResponse> {
+    if asset_amounts.iter().any(|amount| amount.is_zero()) || total_share.is_zero() {
+        return Err(StdError::generic_err("One of the assets is empty"));
+    }
+
+    let deposits = asset_amounts
+        .iter()
+        .map(|amount| {
+            Uint128::try_from(amount.to_uint256())?.multiply_ratio_ceil(share_amount, total_share)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let (deposits, share_amount) = refine_deposits(deposits, share_amount, |deposits| {
+        let deposits = deposits
+            .iter()
+            .map(|amount| Decimal256::from_integer(*amount))
+            .collect::<Vec<_>>();
+
+        Ok::<_, StdError>(
+            simulate(
+                &deposits,
+                asset_amounts,
+                asset_precisions,
+                total_share,
+                block_time,
+                init_amp_time,
+                init_amp,
+                next_amp_time,
+                next_amp,
+                None,
+                None,
+            )?
+            .share_amount,
+        )
+    })?;
+
+    Ok(ReverseSimulationResponse {
+        deposits,
+        share_amount,
     })
 }
 
@@ -130,22 +297,251 @@ fn compute_provide(
         .map(|(i, amount)| amount.to_uint128_with_precision(asset_precisions[i]))
         .sum::<StdResult<Uint128>>()?;
 
-    let virtual_price = deposit_d
-        .to_uint128_with_precision(greatest_precision(asset_precisions))?
-        / (total_share + share);
+    let share_value = share_value(share, deposit_d, total_share + share, asset_precisions)?;
+    let (slippage, positive_slippage) = compute_slippage(sum_deposit, share_value)?;
 
-    let positive_slippage: bool;
-    let slippage_diff = if sum_deposit > (share * virtual_price) {
-        positive_slippage = false;
+    Ok((share, slippage, positive_slippage))
+}
 
-        sum_deposit - (share * virtual_price)
-    } else {
-        positive_slippage = true;
+/// Values `share` lps at the virtual price of the pool, i.e. its invariant (D) per lp, where `d`
+/// and `total_share` are the ones of the pool after the provide or the withdraw.
+pub(crate) fn share_value(
+    share: Uint128,
+    d: Decimal256,
+    total_share: Uint128,
+    asset_precisions: &[u8],
+) -> Result<Uint128, ContractError> {
+    let virtual_price = Decimal::checked_from_ratio(
+        d.to_uint128_with_precision(greatest_precision(asset_precisions))?,
+        total_share,
+    )
+    .map_err(|err| StdError::generic_err(err.to_string()))?;
 
-        (share * virtual_price) - sum_deposit
-    };
+    Ok(virtual_price.checked_mul_uint128(share)?)
+}
 
-    let slippage = Decimal::from_ratio(slippage_diff, sum_deposit);
+/// Computes the slippage of exchanging assets worth `given` for assets worth `received`, relative
+/// to `given`, along with whether it's positive, i.e. more is received than given.
+pub(crate) fn compute_slippage(
+    given: Uint128,
+    received: Uint128,
+) -> Result<(Decimal, bool), ContractError> {
+    let slippage = Decimal::checked_from_ratio(given.abs_diff(received), given)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
 
-    Ok((share, slippage, positive_slippage))
+    Ok((slippage, received >= given))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool_amounts() -> [Decimal256; 2] {
+        [
+            Decimal256::from_integer(530256812u128),
+            Decimal256::from_integer(100446728u128),
+        ]
+    }
+
+    fn provide_of(deposits: &[Uint128]) -> StdResult<SimulationResponse> {
+        provide_with_tolerance_of(deposits, None)
+    }
+
+    fn provide_with_tolerance_of(
+        deposits: &[Uint128],
+        slippage_tolerance: Option<Decimal>,
+    ) -> StdResult<SimulationResponse> {
+        provide_with_guards_of(deposits, slippage_tolerance, None)
+    }
+
+    fn provide_with_guards_of(
+        deposits: &[Uint128],
+        slippage_tolerance: Option<Decimal>,
+        min_lp_to_receive: Option<Uint128>,
+    ) -> StdResult<SimulationResponse> {
+        let deposits = deposits
+            .iter()
+            .map(|amount| Decimal256::from_integer(*amount))
+            .collect::<Vec<_>>();
+
+        simulate(
+            &deposits,
+            &pool_amounts(),
+            &[6, 6],
+            Uint128::new(630703540),
+            0,
+            0,
+            10000,
+            0,
+            10000,
+            slippage_tolerance,
+            min_lp_to_receive,
+        )
+    }
+
+    fn reverse_provide_of(
+        share_amount: u128,
+        asset_amounts: &[Decimal256],
+        asset_precisions: &[u8],
+        total_share: Uint128,
+    ) -> StdResult<ReverseSimulationResponse> {
+        simulate_reverse(
+            Uint128::new(share_amount),
+            asset_amounts,
+            asset_precisions,
+            total_share,
+            0,
+            0,
+            10000,
+            0,
+            10000,
+        )
+    }
+
+    #[test]
+    fn provide_reports_the_deposits_donated_to_the_pool() {
+        let response =
+            provide_with_tolerance_of(&[Uint128::zero(), Uint128::new(100_000_000)], None).unwrap();
+
+        assert_eq!(response.share_amount, Uint128::new(102_184_341));
+        assert!(response.positive_slippage);
+        // Burning the lps returns mostly the other asset, which is worth more than given
+        assert_eq!(
+            response.lost_amounts,
+            [Uint128::zero(), Uint128::new(72_052_319)]
+        );
+    }
+
+    #[test]
+    fn provide_checks_the_slippage_tolerance_only_if_specified() {
+        let deposits = [Uint128::new(100_000_000), Uint128::zero()];
+
+        assert!(provide_with_tolerance_of(&deposits, None).is_ok());
+        assert_eq!(
+            provide_with_tolerance_of(&deposits, Some(Decimal::permille(1)))
+                .unwrap_err()
+                .to_string(),
+            "Generic error: Operation exceeds max splippage tolerance"
+        );
+        assert!(provide_with_tolerance_of(
+            &[Uint128::zero(), Uint128::new(100_000_000)],
+            Some(Decimal::permille(1))
+        )
+        .is_ok());
+        assert_eq!(
+            provide_with_tolerance_of(&deposits, Some(Decimal::percent(51)))
+                .unwrap_err()
+                .to_string(),
+            "Generic error: Provided spread amount exceeds allowed limit"
+        );
+    }
+
+    #[test]
+    fn provide_checks_the_min_lp_to_receive() {
+        let deposits = [Uint128::zero(), Uint128::new(100_000_000)];
+
+        assert!(provide_with_guards_of(&deposits, None, Some(Uint128::new(102_184_341))).is_ok());
+        assert_eq!(
+            provide_with_guards_of(&deposits, None, Some(Uint128::new(102_184_342)))
+                .unwrap_err()
+                .to_string(),
+            "Generic error: Slippage is more than expected: received 102184341, expected 102184342 LP tokens"
+        );
+    }
+
+    #[test]
+    fn reverse_provide_deposits_enough_for_the_share_amount() {
+        let response =
+            reverse_provide_of(1_000_000, &pool_amounts(), &[6, 6], Uint128::new(630703540))
+                .unwrap();
+
+        assert!(response.share_amount >= Uint128::new(1_000_000));
+        assert_eq!(
+            provide_of(&response.deposits).unwrap().share_amount,
+            response.share_amount
+        );
+
+        let deposits = response
+            .deposits
+            .iter()
+            .map(|deposit| *deposit - Uint128::one())
+            .collect::<Vec<_>>();
+        assert!(provide_of(&deposits).unwrap().share_amount < Uint128::new(1_000_000));
+    }
+
+    #[test]
+    fn reverse_provide_rejects_wrong_requests() {
+        let total_share = Uint128::new(630703540);
+        let [amount0, amount1] = pool_amounts();
+
+        assert_eq!(
+            reverse_provide_of(0, &pool_amounts(), &[6, 6], total_share)
+                .unwrap_err()
+                .to_string(),
+            "Generic error: Event of zero transfer"
+        );
+        assert_eq!(
+            reverse_provide_of(
+                1_000_000,
+                &[amount0, Decimal256::zero()],
+                &[6, 6],
+                total_share
+            )
+            .unwrap_err()
+            .to_string(),
+            "Generic error: One of the assets is empty"
+        );
+        assert_eq!(
+            reverse_provide_of(1_000_000, &pool_amounts(), &[6, 6], Uint128::zero())
+                .unwrap_err()
+                .to_string(),
+            "Generic error: One of the assets is empty"
+        );
+        assert_eq!(
+            reverse_provide_of(
+                1_000_000,
+                &[amount0, amount1, amount1],
+                &[6, 6, 6],
+                total_share
+            )
+            .unwrap_err()
+            .to_string(),
+            "Generic error: Wrong asset length: expected 2, actual 3"
+        );
+        assert_eq!(
+            reverse_provide_of(1_000_000, &pool_amounts(), &[6], total_share)
+                .unwrap_err()
+                .to_string(),
+            "Generic error: Wrong asset length: expected 2, actual 1"
+        );
+    }
+
+    #[test]
+    fn provide_checks_the_lengths_of_the_pools() {
+        let deposits = pool_amounts();
+
+        for (asset_amounts, asset_precisions) in
+            [(&deposits[..1], &[6, 6][..]), (&deposits[..], &[6][..])]
+        {
+            let err = simulate(
+                &deposits,
+                asset_amounts,
+                asset_precisions,
+                Uint128::new(630703540),
+                0,
+                0,
+                10000,
+                0,
+                10000,
+                None,
+                None,
+            )
+            .unwrap_err();
+
+            assert_eq!(
+                err.to_string(),
+                "Generic error: Wrong asset length: expected 2, actual 1"
+            );
+        }
+    }
 }
@@ -0,0 +1,433 @@
+use cosmwasm_std::{Decimal, Decimal256, StdError, StdResult, Uint128, Uint64};
+
+use crate::astroport::cosmwasm_ext::Decimal256Ext;
+use crate::astroport::lib::batch::check_swap_request;
+use crate::astroport::lib::curve::CurvePoint;
+use crate::astroport::lib::prices::{price, price_impact};
+use crate::astroport::lib::DecimalCheckedOps;
+
+use super::{
+    error::ContractError,
+    math::{calc_y_with_d, compute_d, compute_spot_price, compute_spot_price_with_d},
+    state::compute_current_amp,
+    swap::{
+        check_swap_parameters, compute_swap, empty_swap, ReverseSimulationResponse,
+        SimulationResponse, SwapResult,
+    },
+};
+
+/// The share of the offer pool the slope of the price is taken over
+const SLOPE_STEP: Decimal256 = Decimal256::raw(1_000_000_000_000_000);
+
+/// The state of a pool with the current amplification and the invariant (D) computed once, so
+/// that repeated swaps against the same balances don't recompute them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PoolSnapshot {
+    /// The pool volumes with the precision of [`Decimal256`]
+    pools: Vec<Decimal256>,
+    asset_precisions: Vec<u8>,
+    total_fee_rate: Decimal,
+    amp: Uint64,
+    /// The invariant, zero if one of the pools is empty
+    d: Decimal256,
+}
+
+impl PoolSnapshot {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        asset_amounts: &[Decimal256],
+        asset_precisions: &[u8],
+        total_fee_rate: Decimal,
+        block_time: u64,
+        init_amp_time: u64,
+        init_amp: u64,
+        next_amp_time: u64,
+        next_amp: u64,
+    ) -> Result<Self, ContractError> {
+        for actual in [asset_amounts.len(), asset_precisions.len()] {
+            if actual != 2 {
+                return Err(ContractError::WrongAssetLength {
+                    expected: 2,
+                    actual,
+                });
+            }
+        }
+
+        let pools = asset_amounts
+            .iter()
+            .zip(asset_precisions)
+            .map(|(amount, precision)| Decimal256::with_precision(amount.to_uint256(), *precision))
+            .collect::<Result<Vec<Decimal256>, StdError>>()?;
+
+        let amp =
+            compute_current_amp(block_time, init_amp_time, init_amp, next_amp_time, next_amp)?;
+        let d = if pools.iter().any(|pool| pool.is_zero()) {
+            Decimal256::zero()
+        } else {
+            compute_d(amp, &pools)?
+        };
+
+        Ok(Self {
+            pools,
+            asset_precisions: asset_precisions.to_vec(),
+            total_fee_rate,
+            amp,
+            d,
+        })
+    }
+
+    /// Returns the invariant (D) of the pool volumes.
+    #[cfg(any(test, feature = "testing"))]
+    pub fn d(&self) -> Decimal256 {
+        self.d
+    }
+
+    /// Returns the price of the offer asset in ask assets, excluding fees.
+    pub fn spot_price(&self, ask_ind: usize) -> StdResult<Decimal256> {
+        check_swap_request(self.pools.len(), ask_ind)?;
+
+        compute_spot_price_with_d(
+            self.amp,
+            self.d,
+            self.pools[1 ^ ask_ind],
+            self.pools[ask_ind],
+        )
+    }
+
+    /// Simulates a swap of `offer_amount` for the asset with index `ask_ind`.
+    pub fn swap(&self, offer_amount: Uint128, ask_ind: usize) -> StdResult<SimulationResponse> {
+        check_swap_request(self.pools.len(), ask_ind)?;
+
+        let offer_ind = 1 ^ ask_ind;
+        let ask_asset_prec = self.asset_precisions[ask_ind];
+        let offer_amount =
+            Decimal256::with_precision(offer_amount, self.asset_precisions[offer_ind])?;
+
+        if check_swap_parameters(self.pools.clone(), offer_amount).is_err() {
+            return Ok(empty_swap());
+        }
+
+        let SwapResult {
+            return_amount,
+            spread_amount,
+        } = self.compute_swap(offer_amount, ask_ind)?;
+
+        let commission_amount = self.total_fee_rate.checked_mul_uint128(return_amount)?;
+        let ask_amount = Decimal256::with_precision(return_amount, ask_asset_prec)?;
+        let return_amount = return_amount.saturating_sub(commission_amount);
+        let net_ask_amount = Decimal256::with_precision(return_amount, ask_asset_prec)?;
+
+        let spot_price_before = self.spot_price(ask_ind)?;
+        let spot_price_after = compute_spot_price(
+            self.amp,
+            self.pools[offer_ind] + offer_amount,
+            self.pools[ask_ind].checked_sub(net_ask_amount)?,
+        )?;
+
+        Ok(SimulationResponse {
+            return_amount,
+            spread_amount,
+            commission_amount,
+            spot_price_before,
+            execution_price: price(offer_amount, net_ask_amount),
+            spot_price_after,
+            price_impact: price_impact(offer_amount, ask_amount, spot_price_before),
+        })
+    }
+
+    /// Returns the price after a swap of `offer_amount` for the asset with index `ask_ind` along
+    /// with the rate the price decreases at per token offered on top of it. The rate is taken
+    /// over a small offer along the invariant of the balances after the swap.
+    pub fn price_after_with_slope(
+        &self,
+        offer_amount: Uint128,
+        ask_ind: usize,
+    ) -> StdResult<(Decimal256, Decimal256)> {
+        check_swap_request(self.pools.len(), ask_ind)?;
+
+        if self.d.is_zero() {
+            return Err(StdError::generic_err("One of the assets is empty"));
+        }
+
+        let offer_ind = 1 ^ ask_ind;
+        let (offer_pool, ask_pool, d) = if offer_amount.is_zero() {
+            (self.pools[offer_ind], self.pools[ask_ind], self.d)
+        } else {
+            let return_amount = self.swap(offer_amount, ask_ind)?.return_amount;
+            let offer_pool = self.pools[offer_ind]
+                + Decimal256::with_precision(offer_amount, self.asset_precisions[offer_ind])?;
+            let ask_pool = self.pools[ask_ind].checked_sub(Decimal256::with_precision(
+                return_amount,
+                self.asset_precisions[ask_ind],
+            )?)?;
+            (
+                offer_pool,
+                ask_pool,
+                compute_d(self.amp, &[offer_pool, ask_pool])?,
+            )
+        };
+
+        let price_after = compute_spot_price_with_d(self.amp, d, offer_pool, ask_pool)?;
+
+        // The central difference of the price along the invariant
+        let step = (offer_pool * SLOPE_STEP).max(Decimal256::raw(1));
+        let price_at = |offer_pool: Decimal256| {
+            compute_spot_price_with_d(
+                self.amp,
+                d,
+                offer_pool,
+                calc_y_with_d(self.amp, offer_pool, d)?,
+            )
+        };
+        let price_drop = price_at(offer_pool - step)?.saturating_sub(price_at(offer_pool + step)?);
+
+        Ok((price_after, price_drop / (step + step)))
+    }
+
+    /// Simulates a swap which returns `ask_amount` of the asset with index `ask_ind` after fees.
+    pub fn reverse_swap(
+        &self,
+        ask_amount: Uint128,
+        ask_ind: usize,
+    ) -> StdResult<ReverseSimulationResponse> {
+        check_swap_request(self.pools.len(), ask_ind)?;
+
+        if self.d.is_zero() {
+            return Err(StdError::generic_err("One of the assets is empty"));
+        }
+
+        let offer_ind = 1 ^ ask_ind;
+        let ask_asset_prec = self.asset_precisions[ask_ind];
+        let commission_rate = Decimal256::from(self.total_fee_rate);
+        let one_minus_commission = Decimal256::one() - commission_rate;
+        if one_minus_commission.is_zero() {
+            return Err(StdError::generic_err("Commission rate must be less than 1"));
+        }
+
+        // The pool has to return the ask amount along with the commission taken from it
+        let before_commission_deduction = (Decimal256::one() / one_minus_commission)
+            * Decimal256::with_precision(ask_amount, ask_asset_prec)?;
+        if before_commission_deduction >= self.pools[ask_ind] {
+            return Err(StdError::generic_err("Ask amount exceeds the pool"));
+        }
+
+        let new_offer_pool = calc_y_with_d(
+            self.amp,
+            self.pools[ask_ind] - before_commission_deduction,
+            self.d,
+        )?;
+        let offer_asset_prec = self.asset_precisions[offer_ind];
+        let offer_amount = new_offer_pool
+            .saturating_sub(self.pools[offer_ind])
+            .to_uint128_with_precision(offer_asset_prec)?;
+
+        // We consider swap rate 1:1 in stable swap thus any difference is considered as spread.
+        // The amounts are truncated before they are subtracted, as the pair does
+        let spread_amount = offer_amount.saturating_sub(
+            before_commission_deduction.to_uint128_with_precision(offer_asset_prec)?,
+        );
+
+        Ok(ReverseSimulationResponse {
+            offer_amount,
+            spread_amount,
+            commission_amount: self.total_fee_rate.checked_mul_uint128(
+                before_commission_deduction.to_uint128_with_precision(ask_asset_prec)?,
+            )?,
+        })
+    }
+
+    /// Same as [`PoolSnapshot::swap`], but skips the price after the swap which requires the
+    /// invariant of the new balances.
+    pub fn quote(&self, offer_amount: Uint128, ask_ind: usize) -> StdResult<CurvePoint> {
+        check_swap_request(self.pools.len(), ask_ind)?;
+
+        let ask_asset_prec = self.asset_precisions[ask_ind];
+        let amount = offer_amount;
+        let offer_amount = Decimal256::with_precision(amount, self.asset_precisions[1 ^ ask_ind])?;
+
+        if check_swap_parameters(self.pools.clone(), offer_amount).is_err() {
+            return Ok(CurvePoint {
+                offer_amount: amount,
+                return_amount: Uint128::zero(),
+                commission_amount: Uint128::zero(),
+                price_impact: Decimal256::zero(),
+            });
+        }
+
+        let SwapResult { return_amount, .. } = self.compute_swap(offer_amount, ask_ind)?;
+
+        let commission_amount = self.total_fee_rate.checked_mul_uint128(return_amount)?;
+        let ask_amount = Decimal256::with_precision(return_amount, ask_asset_prec)?;
+        let spot_price = self.spot_price(ask_ind)?;
+
+        Ok(CurvePoint {
+            offer_amount: amount,
+            return_amount: return_amount.saturating_sub(commission_amount),
+            commission_amount,
+            price_impact: price_impact(offer_amount, ask_amount, spot_price),
+        })
+    }
+
+    fn compute_swap(&self, offer_amount: Decimal256, ask_ind: usize) -> StdResult<SwapResult> {
+        compute_swap(
+            self.pools[1 ^ ask_ind],
+            self.pools[ask_ind],
+            offer_amount,
+            self.asset_precisions[ask_ind],
+            self.amp,
+            self.d,
+        )
+        .map_err(|err| StdError::generic_err(format!("{err}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot_of(asset_amounts: [u128; 2]) -> PoolSnapshot {
+        PoolSnapshot::new(
+            &asset_amounts.map(Decimal256::from_integer),
+            &[6, 6],
+            Decimal::bps(5),
+            0,
+            0,
+            10000,
+            0,
+            10000,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn snapshot_computes_the_invariant_of_the_pools() {
+        let snapshot = snapshot_of([530256812, 100446728]);
+
+        let pools = [
+            Decimal256::with_precision(Uint128::new(530256812), 6u8).unwrap(),
+            Decimal256::with_precision(Uint128::new(100446728), 6u8).unwrap(),
+        ];
+        assert_eq!(snapshot.d(), compute_d(Uint64::new(10000), &pools).unwrap());
+        assert_eq!(snapshot_of([530256812, 0]).d(), Decimal256::zero());
+    }
+
+    #[test]
+    fn quotes_match_the_swaps() {
+        let snapshot = snapshot_of([530256812, 100446728]);
+
+        for (offer_amount, ask_ind) in [(1_000_000u128, 0), (1_000_000, 1), (90_000_000, 0)] {
+            let swap = snapshot.swap(Uint128::new(offer_amount), ask_ind).unwrap();
+            let quote = snapshot.quote(Uint128::new(offer_amount), ask_ind).unwrap();
+
+            assert_eq!(quote.offer_amount, Uint128::new(offer_amount));
+            assert_eq!(quote.return_amount, swap.return_amount);
+            assert_eq!(quote.commission_amount, swap.commission_amount);
+            assert_eq!(quote.price_impact, swap.price_impact);
+            assert_eq!(
+                snapshot.spot_price(ask_ind).unwrap(),
+                swap.spot_price_before
+            );
+        }
+    }
+
+    #[test]
+    fn swaps_execute_between_the_spot_prices_before_fees() {
+        let snapshot = snapshot_of([530256812, 100446728]);
+
+        for (offer_amount, ask_ind) in [(1_000_000u128, 0), (90_000_000, 0), (90_000_000, 1)] {
+            let swap = snapshot.swap(Uint128::new(offer_amount), ask_ind).unwrap();
+
+            // The execution price is net of fees, only the gross one lies between the spot prices
+            let gross_price = price(
+                Decimal256::from_integer(offer_amount),
+                Decimal256::from_integer(swap.return_amount + swap.commission_amount),
+            );
+            assert!(swap.execution_price < gross_price);
+            assert!(gross_price < swap.spot_price_before);
+            assert!(gross_price > swap.spot_price_after);
+            assert!(!swap.price_impact.is_zero());
+            assert!(swap.price_impact < Decimal256::one());
+        }
+    }
+
+    #[test]
+    fn reverse_swaps_offer_enough_for_the_ask_amount() {
+        let snapshot = snapshot_of([530256812, 100446728]);
+
+        for ask_ind in [0, 1] {
+            let ask_amount = Uint128::new(10_000_000);
+            let reverse = snapshot.reverse_swap(ask_amount, ask_ind).unwrap();
+
+            let swap = snapshot.swap(reverse.offer_amount, ask_ind).unwrap();
+            assert!(swap.return_amount >= ask_amount);
+            let swap = snapshot
+                .swap(reverse.offer_amount - Uint128::new(2), ask_ind)
+                .unwrap();
+            assert!(swap.return_amount < ask_amount);
+        }
+    }
+
+    #[test]
+    fn swaps_of_empty_pools_return_nothing() {
+        let snapshot = snapshot_of([530256812, 0]);
+
+        assert_eq!(
+            snapshot.swap(Uint128::new(1_000_000), 1).unwrap(),
+            empty_swap()
+        );
+        assert!(snapshot
+            .quote(Uint128::new(1_000_000), 1)
+            .unwrap()
+            .return_amount
+            .is_zero());
+        assert_eq!(
+            snapshot.spot_price(1).unwrap_err().to_string(),
+            "Generic error: One of the assets is empty"
+        );
+        assert_eq!(
+            snapshot
+                .reverse_swap(Uint128::new(1_000_000), 1)
+                .unwrap_err()
+                .to_string(),
+            "Generic error: One of the assets is empty"
+        );
+    }
+
+    #[test]
+    fn snapshot_rejects_wrong_requests() {
+        let err = PoolSnapshot::new(
+            &[Decimal256::from_integer(530256812u128)],
+            &[6, 6],
+            Decimal::bps(5),
+            0,
+            0,
+            10000,
+            0,
+            10000,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Wrong asset length: expected 2, actual 1");
+
+        let snapshot = snapshot_of([530256812, 100446728]);
+        let out_of_range = "Generic error: Ask asset index 2 is out of range";
+        assert_eq!(
+            snapshot.swap(Uint128::one(), 2).unwrap_err().to_string(),
+            out_of_range
+        );
+        assert_eq!(
+            snapshot.quote(Uint128::one(), 2).unwrap_err().to_string(),
+            out_of_range
+        );
+        assert_eq!(
+            snapshot.spot_price(2).unwrap_err().to_string(),
+            out_of_range
+        );
+        assert_eq!(
+            snapshot
+                .reverse_swap(Uint128::new(100446728), 1)
+                .unwrap_err()
+                .to_string(),
+            "Generic error: Ask amount exceeds the pool"
+        );
+    }
+}
@@ -1,12 +1,19 @@
 use crate::astroport::cosmwasm_ext::Decimal256Ext;
-use crate::astroport::lib::DecimalCheckedOps;
+use crate::astroport::lib::batch::{check_swap_request, run_batch, BatchItem};
+use crate::astroport::lib::curve::{offer_amounts, CurvePoint, Spacing};
+use crate::astroport::lib::depth::{compute_depth, DepthResponse};
+use crate::astroport::lib::ladder::{compute_ladder, LadderResponse};
+use crate::astroport::lib::search::newton_decreasing;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Decimal, Decimal256, StdError, StdResult, Uint128};
+use cosmwasm_std::{Decimal, Decimal256, StdError, StdResult, Uint128, Uint64};
+use tsify::Tsify;
 
-use super::{error::ContractError, math::calc_y, state::compute_current_amp};
+use super::{error::ContractError, math::calc_y_with_d, snapshot::PoolSnapshot};
 
 #[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "StableSwapSimulationResponse")]
 pub struct SimulationResponse {
     /// The amount of ask assets returned by the swap
     pub return_amount: Uint128,
@@ -14,6 +21,26 @@ pub struct SimulationResponse {
     pub spread_amount: Uint128,
     /// The amount of fees charged by the transaction
     pub commission_amount: Uint128,
+    /// The price of the offer asset in ask assets before the swap, excluding fees
+    pub spot_price_before: Decimal256,
+    /// The price the swap is executed at, i.e. `return_amount / offer_amount`
+    pub execution_price: Decimal256,
+    /// The price of the offer asset in ask assets after the swap, excluding fees
+    pub spot_price_after: Decimal256,
+    /// The relative shortfall of the swap before fees compared to the spot price
+    pub price_impact: Decimal256,
+}
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "StableReverseSwapSimulationResponse")]
+pub struct ReverseSimulationResponse {
+    /// The amount of offer assets required by the swap
+    pub offer_amount: Uint128,
+    /// The spread used in the swap operation
+    pub spread_amount: Uint128,
+    /// The amount of fees charged by the transaction
+    pub commission_amount: Uint128,
 }
 
 pub fn simulate(
@@ -29,47 +56,177 @@ pub fn simulate(
     next_amp_time: u64,
     next_amp: u64,
 ) -> StdResult<SimulationResponse> {
-    let offer_amount = Decimal256::with_precision(offer_amount.to_uint256(), offer_asset_prec)?;
-
-    let asset_amounts = asset_amounts
-        .iter()
-        .enumerate()
-        .map(|(i, amount)| {
+    let asset_precisions = (0..asset_amounts.len())
+        .map(|i| {
             if i == ask_ind {
-                Decimal256::with_precision(amount.to_uint256(), ask_asset_prec)
+                ask_asset_prec
             } else {
-                Decimal256::with_precision(amount.to_uint256(), offer_asset_prec)
+                offer_asset_prec
             }
         })
-        .collect::<Result<Vec<Decimal256>, StdError>>()?;
+        .collect::<Vec<u8>>();
 
-    let total_offer_amount: Decimal256;
-    let total_ask_amount: Decimal256;
-    if ask_ind == 0 {
-        total_offer_amount = asset_amounts[1];
-        total_ask_amount = asset_amounts[0];
-    } else {
-        total_offer_amount = asset_amounts[0];
-        total_ask_amount = asset_amounts[1];
+    PoolSnapshot::new(
+        asset_amounts,
+        &asset_precisions,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|err| StdError::generic_err(format!("{err}")))?
+    .swap(offer_amount.to_uint256().try_into()?, ask_ind)
+}
+
+/// Zero amounts can't be swapped in the pool, thus the swap is empty.
+pub(crate) fn empty_swap() -> SimulationResponse {
+    SimulationResponse {
+        return_amount: Uint128::zero(),
+        spread_amount: Uint128::zero(),
+        commission_amount: Uint128::zero(),
+        spot_price_before: Decimal256::zero(),
+        execution_price: Decimal256::zero(),
+        spot_price_after: Decimal256::zero(),
+        price_impact: Decimal256::zero(),
     }
+}
+
+/// Samples `points` swaps with offer amounts spread from `min_offer_amount` to
+/// `max_offer_amount` according to `spacing`. The invariant and the spot price are computed once
+/// for the whole curve.
+#[allow(clippy::too_many_arguments)]
+pub fn sample_curve(
+    min_offer_amount: Uint128,
+    max_offer_amount: Uint128,
+    points: u32,
+    spacing: Spacing,
+    ask_ind: usize,
+    asset_amounts: &[Decimal256],
+    asset_precisions: &[u8],
+    total_fee_rate: Decimal,
+    block_time: u64,
+    init_amp_time: u64,
+    init_amp: u64,
+    next_amp_time: u64,
+    next_amp: u64,
+) -> StdResult<Vec<CurvePoint>> {
+    let snapshot = PoolSnapshot::new(
+        asset_amounts,
+        asset_precisions,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|err| StdError::generic_err(format!("{err}")))?;
+
+    offer_amounts(min_offer_amount, max_offer_amount, points, &spacing)?
+        .into_iter()
+        .map(|amount| snapshot.quote(amount, ask_ind))
+        .collect()
+}
 
-    if check_swap_parameters(asset_amounts.to_vec(), offer_amount).is_err() {
-        return Ok(SimulationResponse {
-            return_amount: Uint128::zero(),
-            spread_amount: Uint128::zero(),
-            commission_amount: Uint128::zero(),
-        });
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "StableSwapToPriceResponse")]
+pub struct SwapToPriceResponse {
+    /// The index of the asset to offer
+    pub offer_ind: usize,
+    /// The amount of assets to offer
+    pub offer_amount: Uint128,
+    /// The simulation of the swap
+    #[tsify(type = "StableSwapSimulationResponse")]
+    pub swap: SimulationResponse,
+}
+
+/// Computes the smallest swap which moves the price of the first asset in the second asset
+/// units to `target_price`, fees included. The price after a swap has no closed form for the
+/// stableswap invariant, thus the offer amount is solved with Newton's method, the slope of the
+/// price being taken along the invariant with [`calc_y_with_d`].
+#[allow(clippy::too_many_arguments)]
+pub fn swap_to_price(
+    target_price: Decimal,
+    asset_amounts: &[Decimal256],
+    asset_precisions: &[u8],
+    total_fee_rate: Decimal,
+    block_time: u64,
+    init_amp_time: u64,
+    init_amp: u64,
+    next_amp_time: u64,
+    next_amp: u64,
+) -> StdResult<SwapToPriceResponse> {
+    if target_price.is_zero() {
+        return Err(StdError::generic_err("Target price must be positive"));
     }
 
-    let SwapResult {
-        return_amount,
-        spread_amount,
-    } = compute_swap(
-        total_offer_amount,
-        total_ask_amount,
+    if asset_amounts.iter().any(|amount| amount.is_zero()) {
+        return Err(StdError::generic_err("One of the assets is empty"));
+    }
+
+    let snapshot = PoolSnapshot::new(
+        asset_amounts,
+        asset_precisions,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|err| StdError::generic_err(format!("{err}")))?;
+
+    let target_price = Decimal256::from(target_price);
+    let (offer_ind, target_price) = if snapshot.spot_price(1)? > target_price {
+        (0, target_price)
+    } else {
+        (1, Decimal256::one() / target_price)
+    };
+    let ask_ind = 1 ^ offer_ind;
+
+    let offer_amount = newton_decreasing(
+        target_price,
+        Uint128::new(10).checked_pow(asset_precisions[offer_ind].into())?,
+        |amount| snapshot.price_after_with_slope(amount, ask_ind),
+    )?;
+
+    Ok(SwapToPriceResponse {
+        offer_ind,
         offer_amount,
-        ask_asset_prec,
-        &asset_amounts,
+        swap: snapshot.swap(offer_amount, ask_ind)?,
+    })
+}
+
+/// Computes the maximum offer amounts of both assets which can be swapped within each of
+/// `price_impacts`.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_depth(
+    price_impacts: &[Decimal],
+    asset_amounts: &[Decimal256],
+    asset_precisions: &[u8],
+    total_fee_rate: Decimal,
+    block_time: u64,
+    init_amp_time: u64,
+    init_amp: u64,
+    next_amp_time: u64,
+    next_amp: u64,
+) -> StdResult<Vec<DepthResponse>> {
+    if asset_amounts.iter().any(|amount| amount.is_zero()) {
+        return Err(StdError::generic_err("One of the assets is empty"));
+    }
+
+    let offer_pools = asset_amounts
+        .iter()
+        .map(|amount| amount.to_uint256().try_into())
+        .collect::<Result<Vec<Uint128>, _>>()?;
+
+    let snapshot = PoolSnapshot::new(
+        asset_amounts,
+        asset_precisions,
+        total_fee_rate,
         block_time,
         init_amp_time,
         init_amp,
@@ -78,16 +235,120 @@ pub fn simulate(
     )
     .map_err(|err| StdError::generic_err(format!("{err}")))?;
 
-    let commission_amount = total_fee_rate.checked_mul_uint128(return_amount)?;
-    let return_amount = return_amount.saturating_sub(commission_amount);
+    compute_depth(price_impacts, &offer_pools, |offer_ind, amount| {
+        Ok(snapshot.swap(amount, 1 ^ offer_ind)?.price_impact)
+    })
+}
 
-    Ok(SimulationResponse {
-        return_amount,
-        spread_amount,
-        commission_amount,
+/// Builds the synthetic order book of the pool with `levels` levels spaced by `price_step` on
+/// each side.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_ladder(
+    price_step: Decimal,
+    levels: u32,
+    asset_amounts: &[Decimal256],
+    asset_precisions: &[u8],
+    total_fee_rate: Decimal,
+    block_time: u64,
+    init_amp_time: u64,
+    init_amp: u64,
+    next_amp_time: u64,
+    next_amp: u64,
+) -> StdResult<LadderResponse> {
+    let mid_price = PoolSnapshot::new(
+        asset_amounts,
+        asset_precisions,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|err| StdError::generic_err(format!("{err}")))?
+    .spot_price(1)?;
+
+    compute_ladder(mid_price.to_decimal()?, price_step, levels, |price| {
+        let response = swap_to_price(
+            price,
+            asset_amounts,
+            asset_precisions,
+            total_fee_rate,
+            block_time,
+            init_amp_time,
+            init_amp,
+            next_amp_time,
+            next_amp,
+        )?;
+        Ok((response.offer_amount, response.swap.return_amount))
     })
 }
 
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "StableBatchPool")]
+pub struct BatchPool {
+    /// The pool balances of both assets
+    pub asset_amounts: Vec<Decimal256>,
+    /// The precisions of both assets
+    pub asset_precisions: Vec<u8>,
+    /// The total fee rate of the pool
+    pub total_fee_rate: Decimal,
+    pub block_time: u64,
+    pub init_amp_time: u64,
+    pub init_amp: u64,
+    pub next_amp_time: u64,
+    pub next_amp: u64,
+}
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "StableBatchSwapRequest")]
+pub struct BatchSwapRequest {
+    /// The index of the pool in the batch
+    pub pool: usize,
+    /// The amount of offer assets
+    pub offer_amount: Decimal256,
+    /// The index of the ask asset
+    pub ask_ind: usize,
+}
+
+/// Simulates every swap request against its pool, reporting errors per request.
+pub fn simulate_batch(
+    pools: &[BatchPool],
+    requests: &[BatchSwapRequest],
+) -> Vec<BatchItem<SimulationResponse>> {
+    run_batch(
+        pools,
+        requests,
+        |request| request.pool,
+        |pool, request| -> Result<SimulationResponse, ContractError> {
+            check_swap_request(pool.asset_amounts.len(), request.ask_ind)?;
+            if pool.asset_precisions.len() != pool.asset_amounts.len() {
+                return Err(ContractError::WrongAssetLength {
+                    expected: pool.asset_amounts.len(),
+                    actual: pool.asset_precisions.len(),
+                });
+            }
+
+            let offer_ind = 1 - request.ask_ind;
+            Ok(simulate(
+                request.offer_amount,
+                pool.asset_precisions[offer_ind],
+                request.ask_ind,
+                pool.asset_precisions[request.ask_ind],
+                &pool.asset_amounts,
+                pool.total_fee_rate,
+                pool.block_time,
+                pool.init_amp_time,
+                pool.init_amp,
+                pool.next_amp_time,
+                pool.next_amp,
+            )?)
+        },
+    )
+}
+
 /// Checks swap parameters.
 ///
 /// * **pools** amount of tokens in pools.
@@ -109,29 +370,22 @@ pub fn check_swap_parameters(
 }
 
 /// Structure for internal use which represents swap result.
-struct SwapResult {
+pub(crate) struct SwapResult {
     pub return_amount: Uint128,
     pub spread_amount: Uint128,
 }
 
-fn compute_swap(
+/// Computes the swap of `offer_amount` against the pools with the invariant `d`.
+pub(crate) fn compute_swap(
     total_offer_amount: Decimal256,
     total_ask_amount: Decimal256,
     offer_amount: Decimal256,
     ask_asset_prec: u8,
-    asset_amounts: &[Decimal256],
-    block_time: u64,
-    init_amp_time: u64,
-    init_amp: u64,
-    next_amp_time: u64,
-    next_amp: u64,
+    amp: Uint64,
+    d: Decimal256,
 ) -> Result<SwapResult, ContractError> {
-    let new_total_ask_amount = calc_y(
-        compute_current_amp(block_time, init_amp_time, init_amp, next_amp_time, next_amp)?,
-        total_offer_amount + offer_amount,
-        asset_amounts,
-        ask_asset_prec,
-    )?;
+    let new_total_ask_amount = calc_y_with_d(amp, total_offer_amount + offer_amount, d)?
+        .to_uint128_with_precision(ask_asset_prec)?;
 
     let return_amount =
         total_ask_amount.to_uint128_with_precision(ask_asset_prec)? - new_total_ask_amount;
@@ -145,3 +399,408 @@ fn compute_swap(
         spread_amount,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn swap_to_price_of(
+        target_price: Decimal,
+        asset_amounts: &[Decimal256],
+    ) -> StdResult<SwapToPriceResponse> {
+        swap_to_price(
+            target_price,
+            asset_amounts,
+            &[6, 6],
+            Decimal::bps(5),
+            1692147376,
+            1692039296,
+            10000,
+            1692039296,
+            10000,
+        )
+    }
+
+    #[test]
+    fn swap_to_price_offers_the_smallest_amount_reaching_the_price() {
+        let asset_amounts = [
+            Decimal256::from_integer(530256812u128),
+            Decimal256::from_integer(100446728u128),
+        ];
+        let snapshot = PoolSnapshot::new(
+            &asset_amounts,
+            &[6, 6],
+            Decimal::bps(5),
+            0,
+            0,
+            10000,
+            0,
+            10000,
+        )
+        .unwrap();
+
+        for (target_price, offer_ind) in [
+            (Decimal::percent(90), 0),
+            (Decimal::percent(50), 0),
+            (Decimal::percent(99), 1),
+            (Decimal::percent(200), 1),
+        ] {
+            let response = swap_to_price_of(target_price, &asset_amounts).unwrap();
+            assert_eq!(response.offer_ind, offer_ind);
+
+            let ask_ind = 1 ^ offer_ind;
+            let target_price = if offer_ind == 0 {
+                Decimal256::from(target_price)
+            } else {
+                Decimal256::one() / Decimal256::from(target_price)
+            };
+            let previous = snapshot
+                .swap(response.offer_amount - Uint128::one(), ask_ind)
+                .unwrap();
+            assert!(response.swap.spot_price_after <= target_price);
+            assert!(previous.spot_price_after > target_price);
+        }
+    }
+
+    #[test]
+    fn swap_to_price_rejects_empty_pools() {
+        let err = swap_to_price_of(
+            Decimal::percent(90),
+            &[Decimal256::from_integer(1_000_000u128), Decimal256::zero()],
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Generic error: One of the assets is empty");
+    }
+
+    #[test]
+    fn swap_to_price_rejects_wrong_asset_length() {
+        let err = swap_to_price_of(
+            Decimal::percent(90),
+            &[Decimal256::from_integer(1_000_000u128)],
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Wrong asset length: expected 2, actual 1"
+        );
+    }
+
+    fn depth_of(
+        price_impacts: &[Decimal],
+        asset_amounts: &[Decimal256],
+    ) -> StdResult<Vec<DepthResponse>> {
+        simulate_depth(
+            price_impacts,
+            asset_amounts,
+            &[6, 6],
+            Decimal::bps(5),
+            0,
+            0,
+            10000,
+            0,
+            10000,
+        )
+    }
+
+    #[test]
+    fn depth_offers_the_largest_amounts_within_the_thresholds() {
+        let asset_amounts = [
+            Decimal256::from_integer(530256812u128),
+            Decimal256::from_integer(100446728u128),
+        ];
+        let snapshot = PoolSnapshot::new(
+            &asset_amounts,
+            &[6, 6],
+            Decimal::bps(5),
+            0,
+            0,
+            10000,
+            0,
+            10000,
+        )
+        .unwrap();
+
+        let depth = depth_of(&[Decimal::permille(1), Decimal::percent(1)], &asset_amounts).unwrap();
+
+        for response in depth {
+            let threshold = Decimal256::from(response.price_impact);
+            for (offer_ind, offer_amount) in response.offer_amounts.into_iter().enumerate() {
+                let impact_of = |amount| snapshot.swap(amount, 1 ^ offer_ind).unwrap().price_impact;
+                assert!(impact_of(offer_amount) <= threshold);
+                assert!(impact_of(offer_amount + Uint128::one()) > threshold);
+            }
+        }
+    }
+
+    #[test]
+    fn depth_rejects_wrong_asset_length() {
+        let err = depth_of(
+            &[Decimal::percent(1)],
+            &[Decimal256::from_integer(1_000_000u128)],
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Wrong asset length: expected 2, actual 1"
+        );
+    }
+
+    #[test]
+    fn depth_rejects_empty_pools() {
+        let err = depth_of(
+            &[Decimal::percent(1)],
+            &[Decimal256::from_integer(1_000_000u128), Decimal256::zero()],
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Generic error: One of the assets is empty");
+    }
+
+    fn ladder_of(
+        price_step: Decimal,
+        levels: u32,
+        asset_amounts: &[Decimal256],
+    ) -> StdResult<LadderResponse> {
+        simulate_ladder(
+            price_step,
+            levels,
+            asset_amounts,
+            &[6, 6],
+            Decimal::bps(5),
+            0,
+            0,
+            10000,
+            0,
+            10000,
+        )
+    }
+
+    #[test]
+    fn ladder_levels_move_the_price_to_their_prices() {
+        let asset_amounts = [
+            Decimal256::from_integer(530256812u128),
+            Decimal256::from_integer(100446728u128),
+        ];
+        let snapshot = PoolSnapshot::new(
+            &asset_amounts,
+            &[6, 6],
+            Decimal::bps(5),
+            0,
+            0,
+            10000,
+            0,
+            10000,
+        )
+        .unwrap();
+
+        let ladder = ladder_of(Decimal::percent(5), 3, &asset_amounts).unwrap();
+
+        assert_eq!((ladder.bids.len(), ladder.asks.len()), (3, 3));
+        for (levels, offer_ind) in [(&ladder.bids, 0), (&ladder.asks, 1)] {
+            let mut previous_offer_amount = Uint128::zero();
+            for level in levels {
+                let (offer_amount, return_amount, target_price) = if offer_ind == 0 {
+                    (
+                        level.base_amount,
+                        level.quote_amount,
+                        Decimal256::from(level.price),
+                    )
+                } else {
+                    (
+                        level.quote_amount,
+                        level.base_amount,
+                        Decimal256::one() / Decimal256::from(level.price),
+                    )
+                };
+                let swap = snapshot.swap(offer_amount, 1 ^ offer_ind).unwrap();
+
+                assert!(offer_amount > previous_offer_amount);
+                assert_eq!(swap.return_amount, return_amount);
+                assert!(swap.spot_price_after <= target_price);
+                previous_offer_amount = offer_amount;
+            }
+        }
+    }
+
+    #[test]
+    fn ladder_rejects_wrong_asset_length() {
+        let err = ladder_of(
+            Decimal::percent(5),
+            3,
+            &[Decimal256::from_integer(1_000_000u128)],
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Wrong asset length: expected 2, actual 1"
+        );
+    }
+
+    #[test]
+    fn ladder_rejects_empty_pools() {
+        let err = ladder_of(
+            Decimal::percent(5),
+            3,
+            &[Decimal256::zero(), Decimal256::from_integer(1_000_000u128)],
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Generic error: One of the assets is empty");
+    }
+
+    fn curve_of(
+        asset_amounts: &[Decimal256],
+        asset_precisions: &[u8],
+        points: u32,
+        ask_ind: usize,
+    ) -> StdResult<Vec<CurvePoint>> {
+        sample_curve(
+            Uint128::new(1_000_000),
+            Uint128::new(100_000_000),
+            points,
+            Spacing::Linear,
+            ask_ind,
+            asset_amounts,
+            asset_precisions,
+            Decimal::bps(5),
+            0,
+            0,
+            10000,
+            0,
+            10000,
+        )
+    }
+
+    #[test]
+    fn curve_points_match_the_swaps() {
+        let asset_amounts = [
+            Decimal256::from_integer(530256812u128),
+            Decimal256::from_integer(100446728u128),
+        ];
+
+        let points = curve_of(&asset_amounts, &[6, 6], 3, 1).unwrap();
+
+        assert_eq!(points.len(), 3);
+        for (point, offer_amount) in points.iter().zip([1_000_000u128, 50_500_000, 100_000_000]) {
+            let swap = simulate(
+                Decimal256::from_integer(offer_amount),
+                6,
+                1,
+                6,
+                &asset_amounts,
+                Decimal::bps(5),
+                0,
+                0,
+                10000,
+                0,
+                10000,
+            )
+            .unwrap();
+            assert_eq!(point.offer_amount, Uint128::new(offer_amount));
+            assert_eq!(point.return_amount, swap.return_amount);
+            assert_eq!(point.commission_amount, swap.commission_amount);
+            assert_eq!(point.price_impact, swap.price_impact);
+        }
+    }
+
+    #[test]
+    fn curve_of_empty_pools_returns_nothing() {
+        let asset_amounts = [Decimal256::from_integer(530256812u128), Decimal256::zero()];
+
+        let points = curve_of(&asset_amounts, &[6, 6], 2, 1).unwrap();
+
+        assert!(points
+            .iter()
+            .all(|point| point.return_amount.is_zero() && point.commission_amount.is_zero()));
+    }
+
+    #[test]
+    fn curve_rejects_wrong_requests() {
+        let asset_amounts = [
+            Decimal256::from_integer(530256812u128),
+            Decimal256::from_integer(100446728u128),
+        ];
+        let err = |asset_precisions: &[u8], points, ask_ind| {
+            curve_of(&asset_amounts, asset_precisions, points, ask_ind)
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            err(&[6], 2, 0),
+            "Generic error: Wrong asset length: expected 2, actual 1"
+        );
+        assert_eq!(
+            err(&[6, 6], 2, 2),
+            "Generic error: Ask asset index 2 is out of range"
+        );
+        assert_eq!(
+            err(&[6, 6], 1, 0),
+            "Generic error: Number of points must be at least 2"
+        );
+    }
+
+    #[test]
+    fn batch_matches_the_single_swaps() {
+        let pool = BatchPool {
+            asset_amounts: vec![
+                Decimal256::from_integer(530256812u128),
+                Decimal256::from_integer(100446728u128),
+            ],
+            asset_precisions: vec![6, 6],
+            total_fee_rate: Decimal::bps(5),
+            block_time: 0,
+            init_amp_time: 0,
+            init_amp: 10000,
+            next_amp_time: 0,
+            next_amp: 10000,
+        };
+        let pools = [
+            pool.clone(),
+            BatchPool {
+                asset_precisions: vec![6],
+                ..pool.clone()
+            },
+        ];
+        let request = |pool, ask_ind| BatchSwapRequest {
+            pool,
+            offer_amount: Decimal256::from_integer(1_000_000u128),
+            ask_ind,
+        };
+
+        let items = simulate_batch(
+            &pools,
+            &[request(0, 1), request(0, 0), request(0, 2), request(1, 0)],
+        );
+
+        let swap = |ask_ind| {
+            simulate(
+                Decimal256::from_integer(1_000_000u128),
+                6,
+                ask_ind,
+                6,
+                &pool.asset_amounts,
+                pool.total_fee_rate,
+                pool.block_time,
+                pool.init_amp_time,
+                pool.init_amp,
+                pool.next_amp_time,
+                pool.next_amp,
+            )
+            .unwrap()
+        };
+        assert_eq!(
+            items,
+            [
+                BatchItem::Ok(swap(1)),
+                BatchItem::Ok(swap(0)),
+                BatchItem::Error("Generic error: Ask asset index 2 is out of range".to_string()),
+                BatchItem::Error("Wrong asset length: expected 2, actual 1".to_string()),
+            ]
+        );
+    }
+}
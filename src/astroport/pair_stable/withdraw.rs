@@ -1,9 +1,22 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{StdResult, Uint128};
+use cosmwasm_std::{Decimal, Decimal256, StdError, StdResult, Uint128, Uint256};
+use tsify::Tsify;
 
+use super::{
+    error::ContractError,
+    math::{calc_y_with_d, compute_d, N_COINS},
+    provide::{compute_slippage, share_value},
+    state::compute_current_amp,
+};
+
+use crate::astroport::cosmwasm_ext::{AbsDiff, Decimal256Ext};
+use crate::astroport::lib::batch::check_swap_request;
+use crate::astroport::lib::guards::{ensure_min_assets_to_receive, ensure_min_return_amount};
 use crate::astroport::pair_xyk::withdraw::compute_withdraw;
 
 #[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "StableWithdrawSimulationResponse")]
 pub struct SimulationResponse {
     /// The amount of assets returned by the withdraw
     pub returned_amounts: Vec<Uint128>,
@@ -13,8 +26,582 @@ pub fn simulate(
     amount: Uint128,
     asset_amounts: &[Uint128],
     total_share: Uint128,
+    min_assets_to_receive: Option<Vec<Uint128>>,
 ) -> StdResult<SimulationResponse> {
+    if amount > total_share {
+        let err = ContractError::LiquidityAmountTooSmall {};
+        return Err(StdError::generic_err(format!("{err}")));
+    }
+
     let returned_amounts = compute_withdraw(amount, asset_amounts, total_share);
 
+    ensure_min_assets_to_receive(&returned_amounts, min_assets_to_receive, None)
+        .map_err(|err| StdError::generic_err(format!("{err}")))?;
+
     Ok(SimulationResponse { returned_amounts })
 }
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "StableWithdrawOneSimulationResponse")]
+pub struct WithdrawOneSimulationResponse {
+    /// The amount of ask assets returned by the withdraw
+    pub return_amount: Uint128,
+    /// The amount of fees charged on the imbalanced part of the withdraw
+    pub commission_amount: Uint128,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_one(
+    amount: Uint128,
+    ask_ind: usize,
+    asset_amounts: &[Decimal256],
+    asset_precisions: &[u8],
+    total_share: Uint128,
+    total_fee_rate: Decimal,
+    block_time: u64,
+    init_amp_time: u64,
+    init_amp: u64,
+    next_amp_time: u64,
+    next_amp: u64,
+    min_return_amount: Option<Uint128>,
+) -> StdResult<WithdrawOneSimulationResponse> {
+    check_swap_request(asset_amounts.len(), ask_ind)?;
+    if asset_precisions.len() != asset_amounts.len() {
+        let err = ContractError::WrongAssetLength {
+            expected: asset_amounts.len(),
+            actual: asset_precisions.len(),
+        };
+        return Err(StdError::generic_err(format!("{err}")));
+    }
+    if asset_amounts.iter().any(|amount| amount.is_zero()) {
+        return Err(StdError::generic_err("One of the assets is empty"));
+    }
+
+    let asset_amounts = asset_amounts
+        .iter()
+        .enumerate()
+        .map(|(i, amount)| Decimal256::with_precision(amount.to_uint256(), asset_precisions[i]))
+        .collect::<Result<Vec<Decimal256>, StdError>>()?;
+
+    let (return_amount, commission_amount) = compute_withdraw_one(
+        amount,
+        ask_ind,
+        &asset_amounts,
+        asset_precisions[ask_ind],
+        total_share,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|err| StdError::generic_err(format!("{err}")))?;
+
+    ensure_min_return_amount(ask_ind, return_amount, min_return_amount, None)
+        .map_err(|err| StdError::generic_err(format!("{err}")))?;
+
+    Ok(WithdrawOneSimulationResponse {
+        return_amount,
+        commission_amount,
+    })
+}
+
+/// Computes the amount of a single asset returned for burning `amount` LP tokens.
+///
+/// The invariant is reduced proportionally to the burnt share and the ask pool is solved against
+/// it. The imbalance fee (`total_fee_rate * N / (4 * (N - 1))`) is charged on the difference
+/// between the ideal proportional withdraw and the actual one, as in Curve's
+/// `calc_withdraw_one_coin`.
+#[allow(clippy::too_many_arguments)]
+fn compute_withdraw_one(
+    amount: Uint128,
+    ask_ind: usize,
+    asset_amounts: &[Decimal256],
+    ask_asset_prec: u8,
+    total_share: Uint128,
+    total_fee_rate: Decimal,
+    block_time: u64,
+    init_amp_time: u64,
+    init_amp: u64,
+    next_amp_time: u64,
+    next_amp: u64,
+) -> Result<(Uint128, Uint128), ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    if amount > total_share {
+        return Err(ContractError::LiquidityAmountTooSmall {});
+    }
+
+    let offer_ind = 1 ^ ask_ind;
+    let amp = compute_current_amp(block_time, init_amp_time, init_amp, next_amp_time, next_amp)?;
+
+    let init_d = compute_d(amp, asset_amounts)?;
+    let withdraw_d = Decimal256::new(
+        init_d
+            .atomics()
+            .checked_multiply_ratio(total_share - amount, total_share)?,
+    );
+
+    let new_y = calc_y_with_d(amp, asset_amounts[offer_ind], withdraw_d)?;
+
+    let fee = imbalance_fee(total_fee_rate);
+
+    let mut reduced_amounts = asset_amounts.to_vec();
+    for (i, reduced_amount) in reduced_amounts.iter_mut().enumerate() {
+        let ideal_amount = asset_amounts[i].checked_multiply_ratio(withdraw_d, init_d)?;
+        let difference = if i == ask_ind {
+            ideal_amount.saturating_sub(new_y)
+        } else {
+            asset_amounts[i].saturating_sub(ideal_amount)
+        };
+        *reduced_amount = reduced_amount.checked_sub(fee.checked_mul(difference)?)?;
+    }
+
+    let reduced_y = calc_y_with_d(amp, reduced_amounts[offer_ind], withdraw_d)?;
+
+    let return_amount = reduced_amounts[ask_ind]
+        .checked_sub(reduced_y)?
+        .to_uint128_with_precision(ask_asset_prec)?;
+    let no_fee_amount = asset_amounts[ask_ind]
+        .checked_sub(new_y)?
+        .to_uint128_with_precision(ask_asset_prec)?;
+
+    Ok((return_amount, no_fee_amount.saturating_sub(return_amount)))
+}
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "StableImbalancedWithdrawSimulationResponse")]
+pub struct ImbalancedWithdrawSimulationResponse {
+    /// The amount of lps burnt by the withdraw
+    pub burn_amount: Uint128,
+    pub slippage: Decimal,
+    pub positive_slippage: bool,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_imbalanced(
+    withdrawals: &[Decimal256],
+    asset_amounts: &[Decimal256],
+    asset_precisions: &[u8],
+    total_share: Uint128,
+    total_fee_rate: Decimal,
+    block_time: u64,
+    init_amp_time: u64,
+    init_amp: u64,
+    next_amp_time: u64,
+    next_amp: u64,
+) -> StdResult<ImbalancedWithdrawSimulationResponse> {
+    for len in [
+        withdrawals.len(),
+        asset_amounts.len(),
+        asset_precisions.len(),
+    ] {
+        if len != 2 {
+            let err = ContractError::WrongAssetLength {
+                expected: 2,
+                actual: len,
+            };
+            return Err(StdError::generic_err(format!("{err}")));
+        }
+    }
+    if asset_amounts.iter().any(|amount| amount.is_zero()) {
+        return Err(StdError::generic_err("One of the assets is empty"));
+    }
+    if withdrawals
+        .iter()
+        .zip(asset_amounts)
+        .any(|(withdrawal, amount)| withdrawal >= amount)
+    {
+        return Err(StdError::generic_err(
+            "Withdrawals must be less than the pool balances",
+        ));
+    }
+
+    let withdrawals = withdrawals
+        .iter()
+        .enumerate()
+        .map(|(i, amount)| Decimal256::with_precision(amount.to_uint256(), asset_precisions[i]))
+        .collect::<Result<Vec<Decimal256>, StdError>>()?;
+
+    let asset_amounts = asset_amounts
+        .iter()
+        .enumerate()
+        .map(|(i, amount)| Decimal256::with_precision(amount.to_uint256(), asset_precisions[i]))
+        .collect::<Result<Vec<Decimal256>, StdError>>()?;
+
+    let (burn_amount, slippage, positive_slippage) = compute_imbalanced_withdraw(
+        &withdrawals,
+        &asset_amounts,
+        asset_precisions,
+        total_share,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|err| StdError::generic_err(format!("{err}")))?;
+
+    Ok(ImbalancedWithdrawSimulationResponse {
+        burn_amount,
+        slippage,
+        positive_slippage,
+    })
+}
+
+/// Computes the amount of LP tokens to burn in order to withdraw exactly `withdrawals`.
+///
+/// Mirrors Curve's `remove_liquidity_imbalance`: the imbalance fee is charged in the asset units
+/// on the difference between every new pool volume and its ideal (proportional) volume, and the
+/// LP amount is derived from the relative decrease of the invariant after fees.
+#[allow(clippy::too_many_arguments)]
+fn compute_imbalanced_withdraw(
+    withdrawals: &[Decimal256],
+    asset_amounts: &[Decimal256],
+    asset_precisions: &[u8],
+    total_share: Uint128,
+    total_fee_rate: Decimal,
+    block_time: u64,
+    init_amp_time: u64,
+    init_amp: u64,
+    next_amp_time: u64,
+    next_amp: u64,
+) -> Result<(Uint128, Decimal, bool), ContractError> {
+    if withdrawals.iter().all(|amount| amount.is_zero()) {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let amp = compute_current_amp(block_time, init_amp_time, init_amp, next_amp_time, next_amp)?;
+
+    // Initial invariant (D)
+    let init_d = compute_d(amp, asset_amounts)?;
+
+    // Invariant (D) after assets withdrawn
+    let mut new_balances = asset_amounts
+        .iter()
+        .zip(withdrawals)
+        .map(|(amount, withdrawal)| amount.checked_sub(*withdrawal))
+        .collect::<Result<Vec<_>, _>>()?;
+    let withdraw_d = compute_d(amp, &new_balances)?;
+
+    // The volumes and the fees are truncated to the asset precisions as Curve does
+    let truncate = |amount: Decimal256, precision: u8| -> StdResult<Decimal256> {
+        Decimal256::with_precision(amount.to_uint128_with_precision(precision)?, precision)
+    };
+    let fee = imbalance_fee(total_fee_rate);
+    for (i, new_balance) in new_balances.iter_mut().enumerate() {
+        let ideal_balance = truncate(
+            withdraw_d.checked_multiply_ratio(asset_amounts[i], init_d)?,
+            asset_precisions[i],
+        )?;
+        let difference = ideal_balance.diff(*new_balance);
+        let fee_amount = truncate(fee.checked_mul(difference)?, asset_precisions[i])?;
+        *new_balance = new_balance.checked_sub(fee_amount)?;
+    }
+
+    let after_fee_d = compute_d(amp, &new_balances)?;
+
+    // In case of rounding errors - make it unfavorable for the withdrawer
+    let burn_amount: Uint128 = Uint256::from(total_share)
+        .checked_multiply_ratio(
+            init_d.atomics().checked_sub(after_fee_d.atomics())?,
+            init_d.atomics(),
+        )?
+        .checked_add(Uint256::from(1u8))?
+        .try_into()?;
+
+    if burn_amount > total_share {
+        return Err(ContractError::LiquidityAmountTooSmall {});
+    }
+
+    let sum_withdrawal = withdrawals
+        .iter()
+        .enumerate()
+        .map(|(i, amount)| amount.to_uint128_with_precision(asset_precisions[i]))
+        .sum::<StdResult<Uint128>>()?;
+
+    // The fees stay in the pool, thus the lps are valued by the invariant without them
+    let burn_value = share_value(
+        burn_amount,
+        withdraw_d,
+        total_share - burn_amount,
+        asset_precisions,
+    )?;
+    let (slippage, positive_slippage) = compute_slippage(burn_value, sum_withdrawal)?;
+
+    Ok((burn_amount, slippage, positive_slippage))
+}
+
+/// The fee charged on the imbalanced part of a withdraw: `total_fee_rate * N / (4 * (N - 1))`.
+fn imbalance_fee(total_fee_rate: Decimal) -> Decimal256 {
+    Decimal256::from(total_fee_rate) * N_COINS
+        / (Decimal256::from_ratio(4u8, 1u8) * (N_COINS - Decimal256::one()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn withdraw_one(
+        asset_amounts: [u128; 2],
+        asset_precisions: [u8; 2],
+        total_share: u128,
+        amount: u128,
+        ask_ind: usize,
+        amp: u64,
+        total_fee_rate: Decimal,
+    ) -> StdResult<WithdrawOneSimulationResponse> {
+        simulate_one(
+            Uint128::new(amount),
+            ask_ind,
+            &asset_amounts.map(|amount| Decimal256::from_ratio(amount, 1u8)),
+            &asset_precisions,
+            Uint128::new(total_share),
+            total_fee_rate,
+            0,
+            0,
+            amp,
+            0,
+            amp,
+            None,
+        )
+    }
+
+    /// The expected amounts are computed by `_calc_withdraw_one_coin` of the Curve stableswap
+    /// pools in integer arithmetic, with `dy` and `dy_0 - dy` as the return and the commission.
+    #[test]
+    fn withdraw_rejects_more_than_the_total_share() {
+        let asset_amounts = [Uint128::new(530256812), Uint128::new(100446728)];
+        let total_share = Uint128::new(630703540);
+
+        assert_eq!(
+            simulate(total_share, &asset_amounts, total_share, None)
+                .unwrap()
+                .returned_amounts,
+            asset_amounts
+        );
+        assert_eq!(
+            simulate(Uint128::MAX, &asset_amounts, total_share, None)
+                .unwrap_err()
+                .to_string(),
+            "Generic error: Insufficient amount of liquidity"
+        );
+    }
+
+    #[test]
+    fn withdraw_one_matches_curve() {
+        let cases: [(
+            ([u128; 2], [u8; 2], u128, u128, usize, u64, u64),
+            (u128, u128),
+        ); 4] = [
+            (
+                (
+                    [530256812, 100446728],
+                    [6, 6],
+                    300000000,
+                    30000000,
+                    1,
+                    10000,
+                    5,
+                ),
+                (58198149, 22950),
+            ),
+            (
+                (
+                    [530256812, 100446728],
+                    [6, 6],
+                    300000000,
+                    30000000,
+                    0,
+                    10000,
+                    5,
+                ),
+                (63482793, 5220),
+            ),
+            (
+                (
+                    [1000000000000, 1000000000000],
+                    [6, 6],
+                    2000000000000,
+                    1000000000,
+                    0,
+                    5000,
+                    5,
+                ),
+                (999745099, 249996),
+            ),
+            (
+                (
+                    [1000000000000000000000, 2000000000],
+                    [18, 6],
+                    2000000000000,
+                    50000000000,
+                    1,
+                    20000,
+                    4,
+                ),
+                (75090936, 10040),
+            ),
+        ];
+
+        for ((amounts, precisions, total_share, amount, ask_ind, amp, fee_bps), expected) in cases {
+            let response = withdraw_one(
+                amounts,
+                precisions,
+                total_share,
+                amount,
+                ask_ind,
+                amp,
+                Decimal::from_ratio(fee_bps, 10_000u16),
+            )
+            .unwrap();
+
+            assert_eq!(
+                (
+                    response.return_amount.u128(),
+                    response.commission_amount.u128()
+                ),
+                expected,
+                "{amounts:?} {amount} {ask_ind}"
+            );
+        }
+    }
+
+    #[test]
+    fn withdraw_one_rejects_empty_pools() {
+        for asset_amounts in [[0, 1_000_000], [1_000_000, 0], [0, 0]] {
+            let err = withdraw_one(
+                asset_amounts,
+                [6, 6],
+                1_000_000,
+                1_000,
+                1,
+                10000,
+                Decimal::permille(5),
+            )
+            .unwrap_err();
+
+            assert_eq!(err.to_string(), "Generic error: One of the assets is empty");
+        }
+    }
+
+    #[test]
+    fn withdraw_one_rejects_burning_more_than_the_total_share() {
+        let err = withdraw_one(
+            [1_000_000, 1_000_000],
+            [6, 6],
+            1_000_000,
+            1_000_001,
+            0,
+            10000,
+            Decimal::permille(5),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Insufficient amount of liquidity"
+        );
+    }
+
+    fn withdraw_imbalanced(
+        withdrawals: &[u128],
+        asset_amounts: &[u128],
+        asset_precisions: &[u8],
+    ) -> StdResult<ImbalancedWithdrawSimulationResponse> {
+        let decimals = |amounts: &[u128]| -> Vec<Decimal256> {
+            amounts
+                .iter()
+                .map(|&amount| Decimal256::from_ratio(amount, 1u8))
+                .collect()
+        };
+
+        simulate_imbalanced(
+            &decimals(withdrawals),
+            &decimals(asset_amounts),
+            asset_precisions,
+            Uint128::new(300_000_000),
+            Decimal::from_ratio(5u8, 10_000u16),
+            0,
+            0,
+            10000,
+            0,
+            10000,
+        )
+    }
+
+    #[test]
+    fn imbalanced_withdraw_checks_the_asset_lengths() {
+        let pools = [530_256_812, 100_446_728];
+        let cases: [(&[u128], &[u128], &[u8], usize); 4] = [
+            (&[1_000_000], &pools, &[6, 6], 1),
+            (&[1_000_000, 0, 0], &pools, &[6, 6], 3),
+            (&[1_000_000, 0], &[530_256_812], &[6, 6], 1),
+            (&[1_000_000, 0], &pools, &[6], 1),
+        ];
+
+        for (withdrawals, asset_amounts, asset_precisions, actual) in cases {
+            let err =
+                withdraw_imbalanced(withdrawals, asset_amounts, asset_precisions).unwrap_err();
+
+            assert_eq!(
+                err.to_string(),
+                format!("Generic error: Wrong asset length: expected 2, actual {actual}")
+            );
+        }
+    }
+
+    #[test]
+    fn imbalanced_withdraw_rejects_emptying_the_pools() {
+        let err = withdraw_imbalanced(&[1_000_000, 0], &[0, 100_446_728], &[6, 6]).unwrap_err();
+        assert_eq!(err.to_string(), "Generic error: One of the assets is empty");
+
+        for withdrawals in [[530_256_812, 0], [0, 100_446_729]] {
+            let err = withdraw_imbalanced(&withdrawals, &[530_256_812, 100_446_728], &[6, 6])
+                .unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "Generic error: Withdrawals must be less than the pool balances"
+            );
+        }
+    }
+
+    /// The assets are valued 1:1 and the lps at the invariant per lp by both the provide and the
+    /// withdraw, thus exchanging proportional amounts has opposite slippages of the same size.
+    #[test]
+    fn imbalanced_withdraw_slippage_mirrors_the_provide() {
+        let amounts = [53_025_681, 10_044_672];
+        let pools = [530_256_812, 100_446_728];
+
+        let withdraw = withdraw_imbalanced(&amounts, &pools, &[6, 6]).unwrap();
+        let provide = super::super::provide::simulate(
+            &amounts.map(|amount| Decimal256::from_ratio(amount, 1u8)),
+            &pools.map(|amount| Decimal256::from_ratio(amount, 1u8)),
+            &[6, 6],
+            Uint128::new(300_000_000),
+            0,
+            0,
+            10000,
+            0,
+            10000,
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(withdraw.burn_amount, Uint128::new(30_000_000));
+        assert_eq!(provide.share_amount, Uint128::new(29_999_999));
+        assert!(withdraw.positive_slippage);
+        assert!(!provide.positive_slippage);
+        assert!(
+            withdraw.slippage.abs_diff(provide.slippage) < Decimal::from_ratio(1u8, 10_000u16),
+            "{withdraw:?} {provide:?}"
+        );
+    }
+}
@@ -0,0 +1,413 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Decimal256, StdError, StdResult, Uint128};
+use tsify::Tsify;
+
+use super::{error::ContractError, provide, swap, withdraw};
+
+use crate::astroport::cosmwasm_ext::Decimal256Ext;
+use crate::astroport::lib::batch::check_swap_request;
+use crate::astroport::lib::search::maximize;
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "StableZapInSimulationResponse")]
+pub struct ZapInSimulationResponse {
+    /// The amount of offer asset swapped before the provide
+    pub swap_amount: Uint128,
+    /// The result of the swap leg
+    #[tsify(type = "StableSwapSimulationResponse")]
+    pub swap: swap::SimulationResponse,
+    /// The amounts deposited by the provide leg
+    pub deposits: Vec<Uint128>,
+    /// The result of the provide leg
+    #[tsify(type = "StableProvideSimulationResponse")]
+    pub provide: provide::SimulationResponse,
+    /// The amounts of deposits which are not backed by the minted lps. Stableswap pools accept
+    /// imbalanced deposits, thus these are always zero.
+    pub dust_amounts: Vec<Uint128>,
+}
+
+/// Simulates providing a single asset: the part of `offer_amount` which maximizes the minted lps
+/// is swapped first and the resulting pair of amounts is provided against the post-swap pools.
+///
+/// * **maker_fee_share** the share of the commission sent to the maker, which leaves the pool
+///   before the provide. The pair charges it only if its fee address is set.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_zap_in(
+    offer_amount: Decimal256,
+    offer_ind: usize,
+    asset_amounts: &[Decimal256],
+    asset_precisions: &[u8],
+    total_share: Uint128,
+    total_fee_rate: Decimal,
+    block_time: u64,
+    init_amp_time: u64,
+    init_amp: u64,
+    next_amp_time: u64,
+    next_amp: u64,
+    maker_fee_share: Option<Decimal>,
+) -> StdResult<ZapInSimulationResponse> {
+    check_swap_request(asset_amounts.len(), offer_ind)?;
+    let offer_amount: Uint128 = offer_amount.to_uint256().try_into()?;
+    if offer_amount.is_zero() {
+        return Err(StdError::generic_err("Offer amount must not be zero"));
+    }
+
+    if asset_amounts.iter().any(|amount| amount.is_zero()) || total_share.is_zero() {
+        return Err(StdError::generic_err("One of the assets is empty"));
+    }
+
+    let legs = |swap_amount: Uint128| -> StdResult<_> {
+        let (swap, deposits, pools) = swap_leg(
+            offer_amount,
+            offer_ind,
+            swap_amount,
+            asset_amounts,
+            asset_precisions,
+            total_fee_rate,
+            block_time,
+            init_amp_time,
+            init_amp,
+            next_amp_time,
+            next_amp,
+            maker_fee_share,
+        )?;
+        let provide = provide::simulate(
+            &deposits
+                .iter()
+                .map(|amount| Decimal256::from_integer(*amount))
+                .collect::<Vec<_>>(),
+            &pools,
+            asset_precisions,
+            total_share,
+            block_time,
+            init_amp_time,
+            init_amp,
+            next_amp_time,
+            next_amp,
+            None,
+            None,
+        )?;
+
+        Ok((swap, deposits, provide))
+    };
+
+    let swap_amount = maximize(Uint128::zero(), offer_amount, |swap_amount| {
+        Ok(legs(swap_amount)?.2.share_amount)
+    });
+    let (swap, deposits, provide) = legs(swap_amount)?;
+
+    Ok(ZapInSimulationResponse {
+        swap_amount,
+        swap,
+        deposits,
+        provide,
+        dust_amounts: vec![Uint128::zero(); 2],
+    })
+}
+
+/// Swaps `swap_amount` out of `offer_amount` and returns the swap result, the amounts left for
+/// the provide and the post-swap pools. The maker fee leaves the pool.
+#[allow(clippy::too_many_arguments)]
+fn swap_leg(
+    offer_amount: Uint128,
+    offer_ind: usize,
+    swap_amount: Uint128,
+    asset_amounts: &[Decimal256],
+    asset_precisions: &[u8],
+    total_fee_rate: Decimal,
+    block_time: u64,
+    init_amp_time: u64,
+    init_amp: u64,
+    next_amp_time: u64,
+    next_amp: u64,
+    maker_fee_share: Option<Decimal>,
+) -> StdResult<(swap::SimulationResponse, Vec<Uint128>, Vec<Decimal256>)> {
+    let ask_ind = 1 ^ offer_ind;
+
+    let swap = swap::simulate(
+        Decimal256::from_integer(swap_amount),
+        asset_precisions[offer_ind],
+        ask_ind,
+        asset_precisions[ask_ind],
+        asset_amounts,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )?;
+    let maker_fee = swap.commission_amount * maker_fee_share.unwrap_or_default();
+
+    let mut deposits = vec![Uint128::zero(); 2];
+    deposits[offer_ind] = offer_amount - swap_amount;
+    deposits[ask_ind] = swap.return_amount;
+
+    let mut pools = asset_amounts.to_vec();
+    pools[offer_ind] += Decimal256::from_integer(swap_amount);
+    pools[ask_ind] = pools[ask_ind]
+        .checked_sub(Decimal256::from_integer(swap.return_amount))?
+        .checked_sub(Decimal256::from_integer(maker_fee))?;
+
+    Ok((swap, deposits, pools))
+}
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "StableZapOutSimulationResponse")]
+pub struct ZapOutSimulationResponse {
+    /// The result of the withdraw leg
+    #[tsify(type = "StableWithdrawSimulationResponse")]
+    pub withdraw: withdraw::SimulationResponse,
+    /// The result of swapping the other withdrawn asset into the ask asset
+    #[tsify(type = "StableSwapSimulationResponse")]
+    pub swap: swap::SimulationResponse,
+    /// The total amount of ask asset returned
+    pub return_amount: Uint128,
+}
+
+/// Simulates burning `amount` lps for a single asset: the assets are withdrawn proportionally
+/// and the other withdrawn asset is swapped into the ask asset against the post-withdraw pools.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_zap_out(
+    amount: Uint128,
+    ask_ind: usize,
+    asset_amounts: &[Decimal256],
+    asset_precisions: &[u8],
+    total_share: Uint128,
+    total_fee_rate: Decimal,
+    block_time: u64,
+    init_amp_time: u64,
+    init_amp: u64,
+    next_amp_time: u64,
+    next_amp: u64,
+) -> StdResult<ZapOutSimulationResponse> {
+    check_swap_request(asset_amounts.len(), ask_ind)?;
+    if asset_precisions.len() != asset_amounts.len() {
+        let err = ContractError::WrongAssetLength {
+            expected: asset_amounts.len(),
+            actual: asset_precisions.len(),
+        };
+        return Err(StdError::generic_err(format!("{err}")));
+    }
+    let offer_ind = 1 ^ ask_ind;
+
+    let withdraw = withdraw::simulate(
+        amount,
+        &asset_amounts
+            .iter()
+            .map(|amount| amount.to_uint256().try_into())
+            .collect::<Result<Vec<Uint128>, _>>()?,
+        total_share,
+        None,
+    )?;
+
+    let pools = asset_amounts
+        .iter()
+        .zip(&withdraw.returned_amounts)
+        .map(|(pool, returned_amount)| pool.checked_sub(Decimal256::from_integer(*returned_amount)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let swap = swap::simulate(
+        Decimal256::from_integer(withdraw.returned_amounts[offer_ind]),
+        asset_precisions[offer_ind],
+        ask_ind,
+        asset_precisions[ask_ind],
+        &pools,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )?;
+    let return_amount = withdraw.returned_amounts[ask_ind].checked_add(swap.return_amount)?;
+
+    Ok(ZapOutSimulationResponse {
+        withdraw,
+        swap,
+        return_amount,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zap_in_swaps_the_smallest_optimal_amount() {
+        let asset_amounts = [
+            Decimal256::from_integer(1_000_000u128),
+            Decimal256::from_integer(1_300_000u128),
+        ];
+        let asset_precisions = [6, 6];
+        let total_share = Uint128::new(2_290_000);
+        let total_fee_rate = Decimal::bps(5);
+        let offer_amount = Uint128::new(4_000);
+
+        let share_amount = |swap_amount: Uint128| -> StdResult<Uint128> {
+            let (_, deposits, pools) = swap_leg(
+                offer_amount,
+                1,
+                swap_amount,
+                &asset_amounts,
+                &asset_precisions,
+                total_fee_rate,
+                0,
+                0,
+                100,
+                0,
+                100,
+                None,
+            )?;
+            let provide = provide::simulate(
+                &deposits
+                    .iter()
+                    .map(|amount| Decimal256::from_integer(*amount))
+                    .collect::<Vec<_>>(),
+                &pools,
+                &asset_precisions,
+                total_share,
+                0,
+                0,
+                100,
+                0,
+                100,
+                None,
+                None,
+            )?;
+            Ok(provide.share_amount)
+        };
+        let shares = (0..=offer_amount.u128())
+            .map(|swap_amount| share_amount(Uint128::new(swap_amount)).unwrap_or_default())
+            .collect::<Vec<_>>();
+        let best = *shares.iter().max().unwrap();
+        let smallest = shares.iter().position(|share| *share == best).unwrap();
+
+        let response = simulate_zap_in(
+            Decimal256::from_integer(offer_amount),
+            1,
+            &asset_amounts,
+            &asset_precisions,
+            total_share,
+            total_fee_rate,
+            0,
+            0,
+            100,
+            0,
+            100,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(response.provide.share_amount, best);
+        assert_eq!(response.swap_amount, Uint128::new(smallest as u128));
+        assert_eq!(response.dust_amounts, vec![Uint128::zero(); 2]);
+    }
+
+    #[test]
+    fn zap_in_checks_the_offer_index() {
+        let err = simulate_zap_in(
+            Decimal256::from_integer(1_000u128),
+            2,
+            &[Decimal256::from_integer(1_000_000u128); 2],
+            &[6, 6],
+            Uint128::new(2_000_000),
+            Decimal::bps(5),
+            0,
+            0,
+            100,
+            0,
+            100,
+            None,
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains("out of range"), "{err}");
+    }
+
+    fn zap_out_of(
+        amount: u128,
+        ask_ind: usize,
+        asset_precisions: &[u8],
+    ) -> StdResult<ZapOutSimulationResponse> {
+        simulate_zap_out(
+            Uint128::new(amount),
+            ask_ind,
+            &[
+                Decimal256::from_integer(530256812u128),
+                Decimal256::from_integer(100446728u128),
+            ],
+            asset_precisions,
+            Uint128::new(630703540),
+            Decimal::bps(5),
+            0,
+            0,
+            10000,
+            0,
+            10000,
+        )
+    }
+
+    #[test]
+    fn zap_out_swaps_the_other_asset_against_the_remaining_pools() {
+        let response = zap_out_of(6_307_035, 0, &[6, 6]).unwrap();
+
+        let returned_amounts = &response.withdraw.returned_amounts;
+        let swap = swap::simulate(
+            Decimal256::from_integer(returned_amounts[1]),
+            6,
+            0,
+            6,
+            &[
+                Decimal256::from_integer(530256812 - returned_amounts[0].u128()),
+                Decimal256::from_integer(100446728 - returned_amounts[1].u128()),
+            ],
+            Decimal::bps(5),
+            0,
+            0,
+            10000,
+            0,
+            10000,
+        )
+        .unwrap();
+        let withdraw = withdraw::simulate(
+            Uint128::new(6_307_035),
+            &[Uint128::new(530256812), Uint128::new(100446728)],
+            Uint128::new(630703540),
+            None,
+        )
+        .unwrap();
+        assert_eq!(response.withdraw, withdraw);
+        assert_eq!(response.swap, swap);
+        assert_eq!(
+            response.return_amount,
+            returned_amounts[0] + swap.return_amount
+        );
+    }
+
+    #[test]
+    fn zap_out_of_the_whole_pool_returns_the_ask_pool() {
+        let response = zap_out_of(630703540, 1, &[6, 6]).unwrap();
+
+        assert_eq!(response.return_amount, Uint128::new(100446728));
+        assert_eq!(response.swap, swap::empty_swap());
+    }
+
+    #[test]
+    fn zap_out_rejects_wrong_requests() {
+        assert_eq!(
+            zap_out_of(1_000, 2, &[6, 6]).unwrap_err().to_string(),
+            "Generic error: Ask asset index 2 is out of range"
+        );
+        assert_eq!(
+            zap_out_of(1_000, 0, &[6]).unwrap_err().to_string(),
+            "Generic error: Wrong asset length: expected 2, actual 1"
+        );
+        assert_eq!(
+            zap_out_of(630703541, 1, &[6, 6]).unwrap_err().to_string(),
+            "Generic error: Insufficient amount of liquidity"
+        );
+    }
+}
@@ -1,4 +1,8 @@
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Decimal, Uint128};
 
 /// Minimum initial LP share
 pub const MINIMUM_LIQUIDITY_AMOUNT: Uint128 = Uint128::new(1_000);
+/// Default slippage tolerance for the provide (0.005)
+pub const DEFAULT_SLIPPAGE: Decimal = Decimal::raw(5000000000000000);
+/// Maximum allowed slippage tolerance for the provide (0.5)
+pub const MAX_ALLOWED_SLIPPAGE: Decimal = Decimal::raw(500000000000000000);
@@ -2,6 +2,8 @@ use crate::astroport::pair_xyk::consts::MINIMUM_LIQUIDITY_AMOUNT;
 use cosmwasm_std::{ConversionOverflowError, OverflowError, StdError};
 use thiserror::Error;
 
+use crate::astroport::lib::guards::GuardError;
+
 /// This enum describes stableswap pair contract errors
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
@@ -13,6 +15,19 @@ pub enum ContractError {
 
     #[error("Initial liquidity must be more than {}", MINIMUM_LIQUIDITY_AMOUNT)]
     MinimumLiquidityAmountError {},
+
+    #[error("Provided spread amount exceeds allowed limit")]
+    AllowedSpreadAssertion {},
+
+    // The typo is copied verbatim from the pair contract, so that the errors match
+    #[error("Operation exceeds max splippage tolerance")]
+    MaxSlippageAssertion {},
+
+    #[error("{0}")]
+    Guard(#[from] GuardError),
+
+    #[error("Wrong asset length: expected {expected}, actual {actual}")]
+    WrongAssetLength { expected: usize, actual: usize },
 }
 
 impl From<OverflowError> for ContractError {
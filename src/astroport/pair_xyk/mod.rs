@@ -1,6 +1,8 @@
 pub mod consts;
 pub mod error;
 
+pub mod pool;
 pub mod provide;
 pub mod swap;
 pub mod withdraw;
+pub mod zap;
@@ -0,0 +1,254 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Decimal256, StdResult, Uint128};
+use tsify::Tsify;
+
+use super::{error::ContractError, provide, swap, withdraw};
+use crate::astroport::cosmwasm_ext::Decimal256Ext;
+use crate::astroport::lib::batch::check_swap_request;
+use crate::astroport::lib::prices::price;
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "XykPoolState")]
+pub struct PoolState {
+    /// The pool balances of both assets
+    pub asset_amounts: Vec<Uint128>,
+    /// The total amount of issued lps
+    pub total_share: Uint128,
+    pub total_fee_rate: Decimal,
+}
+
+/// A pool which is built once from its state and serves simulations until the balances are
+/// updated.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pool {
+    state: PoolState,
+}
+
+impl Pool {
+    pub fn new(state: PoolState) -> Result<Self, ContractError> {
+        check_asset_length(&state.asset_amounts)?;
+
+        Ok(Self { state })
+    }
+
+    pub fn state(&self) -> &PoolState {
+        &self.state
+    }
+
+    /// Replaces the pool balances and, if specified, the total amount of lps.
+    pub fn update(
+        &mut self,
+        asset_amounts: Vec<Uint128>,
+        total_share: Option<Uint128>,
+    ) -> Result<(), ContractError> {
+        check_asset_length(&asset_amounts)?;
+
+        self.state.asset_amounts = asset_amounts;
+        if let Some(total_share) = total_share {
+            self.state.total_share = total_share;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the price of the offer asset in ask assets, excluding fees.
+    pub fn spot_price(&self, ask_ind: usize) -> StdResult<Decimal> {
+        check_swap_request(self.state.asset_amounts.len(), ask_ind)?;
+
+        price(
+            Decimal256::from_integer(self.state.asset_amounts[1 ^ ask_ind]),
+            Decimal256::from_integer(self.state.asset_amounts[ask_ind]),
+        )
+        .to_decimal()
+    }
+
+    pub fn swap(
+        &self,
+        offer_amount: Uint128,
+        ask_ind: usize,
+    ) -> StdResult<swap::SimulationResponse> {
+        check_swap_request(self.state.asset_amounts.len(), ask_ind)?;
+
+        swap::simulate(
+            offer_amount,
+            ask_ind,
+            &self.state.asset_amounts,
+            self.state.total_fee_rate,
+        )
+    }
+
+    pub fn reverse_swap(
+        &self,
+        ask_amount: Uint128,
+        ask_ind: usize,
+    ) -> StdResult<swap::ReverseSimulationResponse> {
+        check_swap_request(self.state.asset_amounts.len(), ask_ind)?;
+
+        swap::simulate_reverse(
+            ask_amount,
+            ask_ind,
+            &self.state.asset_amounts,
+            self.state.total_fee_rate,
+        )
+    }
+
+    pub fn provide(
+        &self,
+        deposits: &[Uint128],
+        slippage_tolerance: Option<Decimal>,
+        min_lp_to_receive: Option<Uint128>,
+    ) -> StdResult<provide::SimulationResponse> {
+        provide::simulate(
+            deposits,
+            &self.state.asset_amounts,
+            self.state.total_share,
+            slippage_tolerance,
+            min_lp_to_receive,
+        )
+    }
+
+    pub fn withdraw(
+        &self,
+        amount: Uint128,
+        min_assets_to_receive: Option<Vec<Uint128>>,
+    ) -> StdResult<withdraw::SimulationResponse> {
+        withdraw::simulate(
+            amount,
+            &self.state.asset_amounts,
+            self.state.total_share,
+            min_assets_to_receive,
+        )
+    }
+}
+
+fn check_asset_length(asset_amounts: &[Uint128]) -> Result<(), ContractError> {
+    if asset_amounts.len() != 2 {
+        return Err(ContractError::WrongAssetLength {
+            expected: 2,
+            actual: asset_amounts.len(),
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> PoolState {
+        PoolState {
+            asset_amounts: vec![Uint128::new(1_000_000_000), Uint128::new(2_000_000_000)],
+            total_share: Uint128::new(1_414_213_562),
+            total_fee_rate: Decimal::permille(3),
+        }
+    }
+
+    #[test]
+    fn pool_simulates_its_state() {
+        let state = state();
+        let pool = Pool::new(state.clone()).unwrap();
+
+        assert_eq!(pool.spot_price(1).unwrap(), Decimal::percent(200));
+        assert_eq!(pool.spot_price(0).unwrap(), Decimal::percent(50));
+        assert_eq!(
+            pool.swap(Uint128::new(1_000_000), 1).unwrap(),
+            swap::simulate(
+                Uint128::new(1_000_000),
+                1,
+                &state.asset_amounts,
+                state.total_fee_rate
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            pool.reverse_swap(Uint128::new(1_000_000), 0).unwrap(),
+            swap::simulate_reverse(
+                Uint128::new(1_000_000),
+                0,
+                &state.asset_amounts,
+                state.total_fee_rate
+            )
+            .unwrap()
+        );
+        let deposits = [Uint128::new(1_000_000), Uint128::new(2_000_000)];
+        assert_eq!(
+            pool.provide(&deposits, None, None).unwrap(),
+            provide::simulate(
+                &deposits,
+                &state.asset_amounts,
+                state.total_share,
+                None,
+                None
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            pool.withdraw(Uint128::new(1_000_000), None).unwrap(),
+            withdraw::simulate(
+                Uint128::new(1_000_000),
+                &state.asset_amounts,
+                state.total_share,
+                None
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn update_replaces_the_balances() {
+        let mut pool = Pool::new(state()).unwrap();
+
+        pool.update(vec![Uint128::new(3_000), Uint128::new(1_000)], None)
+            .unwrap();
+        assert_eq!(
+            pool.state().asset_amounts,
+            [Uint128::new(3_000), Uint128::new(1_000)]
+        );
+        assert_eq!(pool.state().total_share, state().total_share);
+        assert_eq!(pool.spot_price(1).unwrap(), Decimal::from_ratio(1u8, 3u8));
+
+        pool.update(
+            vec![Uint128::new(3_000), Uint128::new(1_000)],
+            Some(Uint128::new(1_732)),
+        )
+        .unwrap();
+        assert_eq!(pool.state().total_share, Uint128::new(1_732));
+    }
+
+    #[test]
+    fn pool_rejects_wrong_requests() {
+        assert_eq!(
+            Pool::new(PoolState {
+                asset_amounts: vec![Uint128::new(1_000)],
+                ..state()
+            })
+            .unwrap_err()
+            .to_string(),
+            "Wrong asset length: expected 2, actual 1"
+        );
+
+        let mut pool = Pool::new(state()).unwrap();
+        assert_eq!(
+            pool.update(vec![Uint128::new(1_000); 3], None)
+                .unwrap_err()
+                .to_string(),
+            "Wrong asset length: expected 2, actual 3"
+        );
+        assert_eq!(pool, Pool::new(state()).unwrap());
+
+        let out_of_range = "Generic error: Ask asset index 2 is out of range";
+        assert_eq!(pool.spot_price(2).unwrap_err().to_string(), out_of_range);
+        assert_eq!(
+            pool.swap(Uint128::one(), 2).unwrap_err().to_string(),
+            out_of_range
+        );
+        assert_eq!(
+            pool.reverse_swap(Uint128::one(), 2)
+                .unwrap_err()
+                .to_string(),
+            out_of_range
+        );
+    }
+}
@@ -1,32 +1,138 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{StdError, StdResult, Uint128};
+use cosmwasm_std::{Decimal, Decimal256, StdError, StdResult, Uint128, Uint256};
+use tsify::Tsify;
 
-use super::{consts::MINIMUM_LIQUIDITY_AMOUNT, error::ContractError};
+use super::{
+    consts::{DEFAULT_SLIPPAGE, MAX_ALLOWED_SLIPPAGE, MINIMUM_LIQUIDITY_AMOUNT},
+    error::ContractError,
+    withdraw::compute_withdraw,
+};
 
+use crate::astroport::cosmwasm_ext::MultiplyRatioCeil;
+use crate::astroport::lib::guards::ensure_min_lp_to_receive;
 use crate::astroport::lib::uints::U256;
 
 #[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "XykProvideSimulationResponse")]
 pub struct SimulationResponse {
     /// The amount of lps returned by the provide
     pub share_amount: Uint128,
+    /// The amounts of deposits which can't be withdrawn back by burning the minted lps
+    pub lost_amounts: Vec<Uint128>,
 }
 
 pub fn simulate(
     deposits: &[Uint128],
     asset_amounts: &[Uint128],
     total_share: Uint128,
+    slippage_tolerance: Option<Decimal>,
+    min_lp_to_receive: Option<Uint128>,
 ) -> StdResult<SimulationResponse> {
-    let share_amount = compute_provide(deposits, asset_amounts, total_share)
+    let share_amount = compute_provide(deposits, asset_amounts, total_share, slippage_tolerance)
+        .and_then(|share_amount| {
+            ensure_min_lp_to_receive(share_amount, min_lp_to_receive)?;
+            Ok(share_amount)
+        })
         .map_err(|err| StdError::generic_err(format!("{err}")))?;
 
-    Ok(SimulationResponse { share_amount })
+    let lost_amounts = compute_lost_amounts(deposits, asset_amounts, total_share, share_amount)?;
+
+    Ok(SimulationResponse {
+        share_amount,
+        lost_amounts,
+    })
+}
+
+/// Computes the amounts of deposits which are donated to the pool, i.e. the difference between
+/// the deposits and the assets returned by burning the minted lps right after the provide.
+fn compute_lost_amounts(
+    deposits: &[Uint128],
+    asset_amounts: &[Uint128],
+    total_share: Uint128,
+    share_amount: Uint128,
+) -> StdResult<Vec<Uint128>> {
+    let returned_amounts =
+        compute_returned_amounts(deposits, asset_amounts, total_share, share_amount)?;
+
+    Ok(deposits
+        .iter()
+        .zip(returned_amounts)
+        .map(|(deposit, returned_amount)| deposit.saturating_sub(returned_amount))
+        .collect())
+}
+
+/// Computes the assets returned by burning `share_amount` lps right after they were minted for
+/// `deposits`.
+pub fn compute_returned_amounts(
+    deposits: &[Uint128],
+    asset_amounts: &[Uint128],
+    total_share: Uint128,
+    share_amount: Uint128,
+) -> StdResult<Vec<Uint128>> {
+    let new_asset_amounts = asset_amounts
+        .iter()
+        .zip(deposits)
+        .map(|(amount, deposit)| amount.checked_add(*deposit))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(compute_withdraw(
+        share_amount,
+        &new_asset_amounts,
+        total_share.checked_add(share_amount)?,
+    ))
+}
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "XykReverseProvideSimulationResponse")]
+pub struct ReverseSimulationResponse {
+    /// The amounts of assets to deposit
+    pub deposits: Vec<Uint128>,
+    /// The amount of lps returned by the provide of `deposits`
+    pub share_amount: Uint128,
+}
+
+/// Computes the deposits required to receive `share_amount` lps.
+///
+/// Deposits are proportional to the pools and rounded up to beat the truncation of
+/// `multiply_ratio` in the provide.
+pub fn simulate_reverse(
+    share_amount: Uint128,
+    asset_amounts: &[Uint128],
+    total_share: Uint128,
+) -> StdResult<ReverseSimulationResponse> {
+    if asset_amounts.iter().any(|amount| amount.is_zero()) || total_share.is_zero() {
+        return Err(StdError::generic_err("One of the assets is empty"));
+    }
+
+    let deposits = asset_amounts
+        .iter()
+        .map(|amount| share_amount.multiply_ratio_ceil(*amount, total_share))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let share_amount = compute_provide(&deposits, asset_amounts, total_share, None)
+        .map_err(|err| StdError::generic_err(format!("{err}")))?;
+
+    Ok(ReverseSimulationResponse {
+        deposits,
+        share_amount,
+    })
 }
 
 fn compute_provide(
     deposits: &[Uint128],
     asset_amounts: &[Uint128],
     total_share: Uint128,
+    slippage_tolerance: Option<Decimal>,
 ) -> Result<Uint128, ContractError> {
+    if deposits.len() != 2 {
+        return Err(ContractError::WrongAssetLength {
+            expected: 2,
+            actual: deposits.len(),
+        });
+    }
+
     if deposits[0].is_zero() || deposits[1].is_zero() {
         return Err(ContractError::InvalidZeroAmount {});
     }
@@ -48,6 +154,8 @@ fn compute_provide(
 
         share
     } else {
+        assert_slippage_tolerance(slippage_tolerance, deposits, asset_amounts)?;
+
         // min(1, 2)
         // 1. sqrt(deposit_0 * exchange_rate_0_to_1 * deposit_0) * (total_share / sqrt(pool_0 * pool_0))
         // == deposit_0 * total_share / pool_0
@@ -61,3 +169,185 @@ fn compute_provide(
 
     Ok(share)
 }
+
+/// Ensures each price does not change more than what the slippage tolerance allows.
+///
+/// * **slippage_tolerance** the slippage tolerance, [`DEFAULT_SLIPPAGE`] if not specified.
+fn assert_slippage_tolerance(
+    slippage_tolerance: Option<Decimal>,
+    deposits: &[Uint128],
+    asset_amounts: &[Uint128],
+) -> Result<(), ContractError> {
+    let slippage_tolerance = slippage_tolerance.unwrap_or(DEFAULT_SLIPPAGE);
+    if slippage_tolerance > MAX_ALLOWED_SLIPPAGE {
+        return Err(ContractError::AllowedSpreadAssertion {});
+    }
+
+    let one_minus_slippage_tolerance = Decimal256::one() - Decimal256::from(slippage_tolerance);
+    let deposits: [Uint256; 2] = [deposits[0].into(), deposits[1].into()];
+    let pools: [Uint256; 2] = [asset_amounts[0].into(), asset_amounts[1].into()];
+
+    if Decimal256::from_ratio(deposits[0], deposits[1]) * one_minus_slippage_tolerance
+        > Decimal256::from_ratio(pools[0], pools[1])
+        || Decimal256::from_ratio(deposits[1], deposits[0]) * one_minus_slippage_tolerance
+            > Decimal256::from_ratio(pools[1], pools[0])
+    {
+        return Err(ContractError::MaxSlippageAssertion {});
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POOLS: [Uint128; 2] = [Uint128::new(1_000_000_000), Uint128::new(2_000_000_000)];
+    const TOTAL_SHARE: Uint128 = Uint128::new(1_414_213_562);
+
+    fn provide_of(
+        deposits: [u128; 2],
+        slippage_tolerance: Option<Decimal>,
+    ) -> StdResult<SimulationResponse> {
+        simulate(
+            &deposits.map(Uint128::new),
+            &POOLS,
+            TOTAL_SHARE,
+            slippage_tolerance,
+            None,
+        )
+    }
+
+    #[test]
+    fn provide_reports_the_deposits_donated_to_the_pool() {
+        let response = provide_of([1_000_000, 2_020_000], Some(Decimal::percent(2))).unwrap();
+
+        assert_eq!(response.share_amount, Uint128::new(1_414_213));
+        assert_eq!(
+            response.lost_amounts,
+            [Uint128::new(1), Uint128::new(19_981)]
+        );
+    }
+
+    #[test]
+    fn provide_checks_the_slippage_tolerance() {
+        assert!(provide_of([1_000_000, 2_000_000], None).is_ok());
+        assert!(provide_of([1_000_000, 2_000_000], Some(Decimal::percent(50))).is_ok());
+        assert_eq!(
+            provide_of([1_000_000, 2_020_000], None)
+                .unwrap_err()
+                .to_string(),
+            "Generic error: Operation exceeds max splippage tolerance"
+        );
+        assert_eq!(
+            provide_of([1_020_000, 2_000_000], Some(Decimal::percent(1)))
+                .unwrap_err()
+                .to_string(),
+            "Generic error: Operation exceeds max splippage tolerance"
+        );
+        assert_eq!(
+            provide_of([1_000_000, 2_000_000], Some(Decimal::percent(51)))
+                .unwrap_err()
+                .to_string(),
+            "Generic error: Provided spread amount exceeds allowed limit"
+        );
+    }
+
+    #[test]
+    fn provide_checks_the_min_lp_to_receive() {
+        let provide = |min_lp_to_receive| {
+            simulate(
+                &[Uint128::new(1_000_000), Uint128::new(2_000_000)],
+                &POOLS,
+                TOTAL_SHARE,
+                None,
+                Some(Uint128::new(min_lp_to_receive)),
+            )
+        };
+
+        assert_eq!(
+            provide(1_414_213).unwrap().share_amount,
+            Uint128::new(1_414_213)
+        );
+        assert_eq!(
+            provide(1_414_214).unwrap_err().to_string(),
+            "Generic error: Slippage is more than expected: received 1414213, expected 1414214 LP tokens"
+        );
+    }
+
+    #[test]
+    fn initial_provide_ignores_the_slippage_tolerance() {
+        let response = simulate(
+            &[Uint128::new(1_000_000), Uint128::new(9_000_000)],
+            &[Uint128::zero(); 2],
+            Uint128::zero(),
+            Some(Decimal::permille(1)),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(response.share_amount, Uint128::new(2_999_000));
+        assert_eq!(response.lost_amounts, [Uint128::zero(); 2]);
+    }
+
+    #[test]
+    fn reverse_provide_deposits_the_least_for_the_share_amount() {
+        let response = simulate_reverse(Uint128::new(1_000), &POOLS, TOTAL_SHARE).unwrap();
+
+        assert_eq!(response.deposits, [Uint128::new(708), Uint128::new(1_415)]);
+        assert_eq!(response.share_amount, Uint128::new(1_000));
+        assert_eq!(
+            simulate(&response.deposits, &POOLS, TOTAL_SHARE, None, None)
+                .unwrap()
+                .share_amount,
+            response.share_amount
+        );
+
+        for i in 0..2 {
+            let mut deposits = response.deposits.clone();
+            deposits[i] -= Uint128::one();
+            let share_amount = simulate(
+                &deposits,
+                &POOLS,
+                TOTAL_SHARE,
+                Some(Decimal::percent(1)),
+                None,
+            )
+            .unwrap()
+            .share_amount;
+            assert!(share_amount < response.share_amount);
+        }
+    }
+
+    #[test]
+    fn reverse_provide_rejects_wrong_requests() {
+        assert_eq!(
+            simulate_reverse(Uint128::zero(), &POOLS, TOTAL_SHARE)
+                .unwrap_err()
+                .to_string(),
+            "Generic error: Event of zero transfer"
+        );
+        assert_eq!(
+            simulate_reverse(
+                Uint128::new(1_000),
+                &[POOLS[0], Uint128::zero()],
+                TOTAL_SHARE
+            )
+            .unwrap_err()
+            .to_string(),
+            "Generic error: One of the assets is empty"
+        );
+        assert_eq!(
+            simulate_reverse(Uint128::new(1_000), &POOLS, Uint128::zero())
+                .unwrap_err()
+                .to_string(),
+            "Generic error: One of the assets is empty"
+        );
+        assert_eq!(
+            simulate_reverse(Uint128::new(1_000), &POOLS[..1], TOTAL_SHARE)
+                .unwrap_err()
+                .to_string(),
+            "Generic error: Wrong asset length: expected 2, actual 1"
+        );
+    }
+}
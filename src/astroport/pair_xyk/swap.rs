@@ -1,7 +1,18 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Decimal, Decimal256, StdResult, Uint128, Uint256};
+use cosmwasm_std::{Decimal, Decimal256, StdError, StdResult, Uint128, Uint256};
+use tsify::Tsify;
+
+use crate::astroport::cosmwasm_ext::Decimal256Ext;
+use crate::astroport::lib::batch::{check_swap_request, run_batch, BatchItem};
+use crate::astroport::lib::curve::{offer_amounts, CurvePoint, Spacing};
+use crate::astroport::lib::depth::{compute_depth, DepthResponse};
+use crate::astroport::lib::ladder::{compute_ladder, LadderResponse};
+use crate::astroport::lib::prices::{price, price_impact};
+use crate::astroport::lib::search::solve_decreasing;
 
 #[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "XykSwapSimulationResponse")]
 pub struct SimulationResponse {
     /// The amount of ask assets returned by the swap
     pub return_amount: Uint128,
@@ -9,6 +20,14 @@ pub struct SimulationResponse {
     pub spread_amount: Uint128,
     /// The amount of fees charged by the transaction
     pub commission_amount: Uint128,
+    /// The price of the offer asset in ask assets before the swap, excluding fees
+    pub spot_price_before: Decimal256,
+    /// The price the swap is executed at, i.e. `return_amount / offer_amount`
+    pub execution_price: Decimal256,
+    /// The price of the offer asset in ask assets after the swap, excluding fees
+    pub spot_price_after: Decimal256,
+    /// The relative shortfall of the swap before fees compared to the spot price
+    pub price_impact: Decimal256,
 }
 
 pub fn simulate(
@@ -17,6 +36,12 @@ pub fn simulate(
     asset_amounts: &[Uint128],
     total_fee_rate: Decimal,
 ) -> StdResult<SimulationResponse> {
+    check_swap_request(asset_amounts.len(), ask_ind)?;
+
+    if asset_amounts.iter().any(|amount| amount.is_zero()) {
+        return Err(StdError::generic_err("One of the assets is empty"));
+    }
+
     let total_offer_amount: Uint128;
     let total_ask_amount: Uint128;
     if ask_ind == 0 {
@@ -34,13 +59,271 @@ pub fn simulate(
         total_fee_rate,
     )?;
 
+    let offer_amount = Decimal256::from_integer(offer_amount);
+    let spot_price_before = price(
+        Decimal256::from_integer(total_offer_amount),
+        Decimal256::from_integer(total_ask_amount),
+    );
+    let spot_price_after = price(
+        Decimal256::from_integer(total_offer_amount) + offer_amount,
+        Decimal256::from_integer(total_ask_amount - return_amount),
+    );
+
     Ok(SimulationResponse {
         return_amount,
         spread_amount,
         commission_amount,
+        spot_price_before,
+        execution_price: price(offer_amount, Decimal256::from_integer(return_amount)),
+        spot_price_after,
+        price_impact: price_impact(
+            offer_amount,
+            Decimal256::from_integer(return_amount + commission_amount),
+            spot_price_before,
+        ),
+    })
+}
+
+/// Nothing can be swapped against an empty pool, thus the swap is empty.
+pub(crate) fn empty_swap() -> SimulationResponse {
+    SimulationResponse {
+        return_amount: Uint128::zero(),
+        spread_amount: Uint128::zero(),
+        commission_amount: Uint128::zero(),
+        spot_price_before: Decimal256::zero(),
+        execution_price: Decimal256::zero(),
+        spot_price_after: Decimal256::zero(),
+        price_impact: Decimal256::zero(),
+    }
+}
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "XykReverseSwapSimulationResponse")]
+pub struct ReverseSimulationResponse {
+    /// The amount of offer assets required by the swap
+    pub offer_amount: Uint128,
+    /// The spread used in the swap operation
+    pub spread_amount: Uint128,
+    /// The amount of fees charged by the transaction
+    pub commission_amount: Uint128,
+}
+
+/// Simulates a swap which returns `ask_amount` of the asset with index `ask_ind` after fees.
+pub fn simulate_reverse(
+    ask_amount: Uint128,
+    ask_ind: usize,
+    asset_amounts: &[Uint128],
+    total_fee_rate: Decimal,
+) -> StdResult<ReverseSimulationResponse> {
+    check_swap_request(asset_amounts.len(), ask_ind)?;
+
+    let (offer_amount, spread_amount, commission_amount) = compute_offer_amount(
+        asset_amounts[1 ^ ask_ind],
+        asset_amounts[ask_ind],
+        ask_amount,
+        total_fee_rate,
+    )?;
+
+    Ok(ReverseSimulationResponse {
+        offer_amount,
+        spread_amount,
+        commission_amount,
+    })
+}
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "XykSwapToPriceResponse")]
+pub struct SwapToPriceResponse {
+    /// The index of the asset to offer
+    pub offer_ind: usize,
+    /// The amount of assets to offer
+    pub offer_amount: Uint128,
+    /// The simulation of the swap
+    #[tsify(type = "XykSwapSimulationResponse")]
+    pub swap: SimulationResponse,
+}
+
+/// Computes the swap which moves the price of the first asset in the second asset units to
+/// `target_price`.
+///
+/// The commission stays in the pool, so the new offer pool `A` solves
+/// `q * A**2 - fee * b * A - (1 - fee) * a * b = 0`, where `a` and `b` are the offer and ask
+/// pools and `q` is the target price of the offer asset. The return amount is truncated, which
+/// leaves the price slightly above the root, thus the root only brackets the search for the
+/// smallest amount reaching the price.
+pub fn swap_to_price(
+    target_price: Decimal,
+    asset_amounts: &[Uint128],
+    total_fee_rate: Decimal,
+) -> StdResult<SwapToPriceResponse> {
+    check_swap_request(asset_amounts.len(), 0)?;
+
+    if target_price.is_zero() {
+        return Err(StdError::generic_err("Target price must be positive"));
+    }
+
+    if asset_amounts.iter().any(|amount| amount.is_zero()) {
+        return Err(StdError::generic_err("One of the assets is empty"));
+    }
+
+    let target_price = Decimal256::from(target_price);
+    let spot_price = price(
+        Decimal256::from_integer(asset_amounts[0]),
+        Decimal256::from_integer(asset_amounts[1]),
+    );
+    let (offer_ind, target_price) = if spot_price > target_price {
+        (0, target_price)
+    } else {
+        (1, Decimal256::one() / target_price)
+    };
+    let ask_ind = 1 ^ offer_ind;
+
+    let offer_pool = Decimal256::from_integer(asset_amounts[offer_ind]);
+    let ask_pool = Decimal256::from_integer(asset_amounts[ask_ind]);
+    let fee = Decimal256::from(total_fee_rate);
+
+    let b = fee * ask_pool;
+    let discriminant = b.checked_pow(2)?.checked_add(
+        Decimal256::from_integer(4u8)
+            .checked_mul(target_price)?
+            .checked_mul(Decimal256::one() - fee)?
+            .checked_mul(offer_pool)?
+            .checked_mul(ask_pool)?,
+    )?;
+    let new_offer_pool = (b + discriminant.sqrt()) / (target_price * Decimal256::from_integer(2u8));
+
+    let root: Uint128 = new_offer_pool
+        .saturating_sub(offer_pool)
+        .ceil()
+        .to_uint256()
+        .try_into()?;
+    let offer_amount = solve_decreasing(root, target_price, |amount| {
+        StdResult::Ok(simulate(amount, ask_ind, asset_amounts, total_fee_rate)?.spot_price_after)
+    })?;
+    let swap = simulate(offer_amount, ask_ind, asset_amounts, total_fee_rate)?;
+
+    Ok(SwapToPriceResponse {
+        offer_ind,
+        offer_amount,
+        swap,
+    })
+}
+
+/// Computes the maximum offer amounts of both assets which can be swapped within each of
+/// `price_impacts`.
+pub fn simulate_depth(
+    price_impacts: &[Decimal],
+    asset_amounts: &[Uint128],
+    total_fee_rate: Decimal,
+) -> StdResult<Vec<DepthResponse>> {
+    check_swap_request(asset_amounts.len(), 0)?;
+
+    if asset_amounts.iter().any(|amount| amount.is_zero()) {
+        return Err(StdError::generic_err("One of the assets is empty"));
+    }
+
+    compute_depth(price_impacts, asset_amounts, |offer_ind, amount| {
+        Ok(simulate(amount, 1 ^ offer_ind, asset_amounts, total_fee_rate)?.price_impact)
+    })
+}
+
+/// Builds the synthetic order book of the pool with `levels` levels spaced by `price_step` on
+/// each side.
+pub fn simulate_ladder(
+    price_step: Decimal,
+    levels: u32,
+    asset_amounts: &[Uint128],
+    total_fee_rate: Decimal,
+) -> StdResult<LadderResponse> {
+    check_swap_request(asset_amounts.len(), 0)?;
+
+    if asset_amounts.iter().any(|amount| amount.is_zero()) {
+        return Err(StdError::generic_err("One of the assets is empty"));
+    }
+
+    let mid_price = price(
+        Decimal256::from_integer(asset_amounts[0]),
+        Decimal256::from_integer(asset_amounts[1]),
+    );
+
+    compute_ladder(mid_price.to_decimal()?, price_step, levels, |price| {
+        let response = swap_to_price(price, asset_amounts, total_fee_rate)?;
+        Ok((response.offer_amount, response.swap.return_amount))
     })
 }
 
+/// Samples `points` swaps with offer amounts spread from `min_offer_amount` to
+/// `max_offer_amount` according to `spacing`.
+pub fn sample_curve(
+    min_offer_amount: Uint128,
+    max_offer_amount: Uint128,
+    points: u32,
+    spacing: Spacing,
+    ask_ind: usize,
+    asset_amounts: &[Uint128],
+    total_fee_rate: Decimal,
+) -> StdResult<Vec<CurvePoint>> {
+    check_swap_request(asset_amounts.len(), ask_ind)?;
+
+    offer_amounts(min_offer_amount, max_offer_amount, points, &spacing)?
+        .into_iter()
+        .map(|offer_amount| {
+            let response = simulate(offer_amount, ask_ind, asset_amounts, total_fee_rate)?;
+            Ok(CurvePoint {
+                offer_amount,
+                return_amount: response.return_amount,
+                commission_amount: response.commission_amount,
+                price_impact: response.price_impact,
+            })
+        })
+        .collect()
+}
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "XykBatchPool")]
+pub struct BatchPool {
+    /// The pool balances of both assets
+    pub asset_amounts: Vec<Uint128>,
+    /// The total fee rate of the pool
+    pub total_fee_rate: Decimal,
+}
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "XykBatchSwapRequest")]
+pub struct BatchSwapRequest {
+    /// The index of the pool in the batch
+    pub pool: usize,
+    /// The amount of offer assets
+    pub offer_amount: Uint128,
+    /// The index of the ask asset
+    pub ask_ind: usize,
+}
+
+/// Simulates every swap request against its pool, reporting errors per request.
+pub fn simulate_batch(
+    pools: &[BatchPool],
+    requests: &[BatchSwapRequest],
+) -> Vec<BatchItem<SimulationResponse>> {
+    run_batch(
+        pools,
+        requests,
+        |request| request.pool,
+        |pool, request| {
+            check_swap_request(pool.asset_amounts.len(), request.ask_ind)?;
+            simulate(
+                request.offer_amount,
+                request.ask_ind,
+                &pool.asset_amounts,
+                pool.total_fee_rate,
+            )
+        },
+    )
+}
+
 pub fn compute_swap(
     offer_pool: Uint128,
     ask_pool: Uint128,
@@ -71,3 +354,445 @@ pub fn compute_swap(
         commission_amount.try_into()?,
     ))
 }
+
+pub fn compute_offer_amount(
+    offer_pool: Uint128,
+    ask_pool: Uint128,
+    ask_amount: Uint128,
+    commission_rate: Decimal,
+) -> StdResult<(Uint128, Uint128, Uint128)> {
+    if offer_pool.is_zero() || ask_pool.is_zero() {
+        return Err(StdError::generic_err("One of the assets is empty"));
+    }
+
+    let offer_pool: Uint256 = offer_pool.into();
+    let ask_pool: Uint256 = ask_pool.into();
+    let ask_amount: Uint256 = ask_amount.into();
+    let commission_rate = Decimal256::from(commission_rate);
+
+    let one_minus_commission = Decimal256::one() - commission_rate;
+    if one_minus_commission.is_zero() {
+        return Err(StdError::generic_err("Commission rate must be less than 1"));
+    }
+
+    // The pool has to return the ask amount along with the commission taken from it
+    let before_commission_deduction: Uint256 =
+        ask_amount * (Decimal256::one() / one_minus_commission);
+    if before_commission_deduction >= ask_pool {
+        return Err(StdError::generic_err("Ask amount exceeds the pool"));
+    }
+
+    // offer_amount = cp / (ask_pool - ask_amount / (1 - commission_rate)) - offer_pool
+    let cp: Uint256 = offer_pool * ask_pool;
+    let offer_amount: Uint256 =
+        cp.multiply_ratio(1u8, ask_pool - before_commission_deduction) - offer_pool;
+
+    // Calculate spread & commission
+    let spread_amount: Uint256 = (offer_amount * Decimal256::from_ratio(ask_pool, offer_pool))
+        .saturating_sub(before_commission_deduction);
+    let commission_amount: Uint256 = before_commission_deduction * commission_rate;
+
+    Ok((
+        offer_amount.try_into()?,
+        spread_amount.try_into()?,
+        commission_amount.try_into()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swap_reports_the_prices_of_the_trade() {
+        let asset_amounts = [Uint128::new(1_000_000_000), Uint128::new(2_000_000_000)];
+
+        let response = simulate(
+            Uint128::new(1_000_000),
+            1,
+            &asset_amounts,
+            Decimal::permille(3),
+        )
+        .unwrap();
+
+        assert_eq!(response.return_amount, Uint128::new(1_992_007));
+        assert_eq!(response.spread_amount, Uint128::new(1_999));
+        assert_eq!(response.commission_amount, Uint128::new(5_994));
+        assert_eq!(response.spot_price_before, Decimal256::percent(200));
+        assert_eq!(
+            response.execution_price,
+            Decimal256::from_ratio(1_992_007u128, 1_000_000u128)
+        );
+        assert_eq!(
+            response.spot_price_after,
+            Decimal256::from_ratio(1_998_007_993u128, 1_001_000_000u128)
+        );
+        // The impact excludes the fees
+        assert_eq!(
+            response.price_impact,
+            Decimal256::from_ratio(1_999u128, 2_000_000u128)
+        );
+    }
+
+    #[test]
+    fn swap_of_nothing_keeps_the_prices() {
+        let response = simulate(
+            Uint128::zero(),
+            0,
+            &[Uint128::new(1_000), Uint128::new(4_000)],
+            Decimal::permille(3),
+        )
+        .unwrap();
+
+        assert_eq!(response.return_amount, Uint128::zero());
+        assert_eq!(response.spot_price_before, Decimal256::percent(25));
+        assert_eq!(response.spot_price_after, Decimal256::percent(25));
+        assert_eq!(response.execution_price, Decimal256::zero());
+        assert_eq!(response.price_impact, Decimal256::zero());
+    }
+
+    #[test]
+    fn swap_rejects_wrong_requests() {
+        let swap = |ask_ind, asset_amounts: &[Uint128]| {
+            simulate(
+                Uint128::new(10),
+                ask_ind,
+                asset_amounts,
+                Decimal::permille(3),
+            )
+            .unwrap_err()
+            .to_string()
+        };
+
+        assert_eq!(
+            swap(2, &[Uint128::one(); 2]),
+            "Generic error: Ask asset index 2 is out of range"
+        );
+        assert_eq!(
+            swap(1, &[Uint128::one()]),
+            "Generic error: Wrong asset length: expected 2, actual 1"
+        );
+        assert_eq!(
+            swap(1, &[Uint128::zero(), Uint128::one()]),
+            "Generic error: One of the assets is empty"
+        );
+    }
+
+    #[test]
+    fn swap_reports_prices_beyond_the_range_of_decimal() {
+        let asset_amounts = [Uint128::new(1_000), Uint128::new(10u128.pow(30))];
+
+        let response = simulate(Uint128::new(10), 1, &asset_amounts, Decimal::permille(3)).unwrap();
+
+        assert_eq!(
+            response.spot_price_before,
+            Decimal256::from_ratio(10u128.pow(27), 1u8)
+        );
+        assert!(response.spot_price_after > Decimal256::from(Decimal::MAX));
+        assert!(response.execution_price > Decimal256::from(Decimal::MAX));
+    }
+
+    #[test]
+    fn swap_to_price_rejects_empty_pools() {
+        let err = swap_to_price(
+            Decimal::percent(50),
+            &[Uint128::new(1_000), Uint128::zero()],
+            Decimal::permille(3),
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Generic error: One of the assets is empty");
+    }
+
+    #[test]
+    fn swap_to_price_rejects_wrong_asset_length() {
+        let err = swap_to_price(
+            Decimal::percent(50),
+            &[Uint128::new(1_000)],
+            Decimal::permille(3),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Wrong asset length: expected 2, actual 1"
+        );
+    }
+
+    #[test]
+    fn depth_offers_the_largest_amounts_within_the_thresholds() {
+        let asset_amounts = [
+            Uint128::new(1_000_000_000_000),
+            Uint128::new(2_000_000_000_000),
+        ];
+        let total_fee_rate = Decimal::permille(3);
+
+        let depth = simulate_depth(
+            &[Decimal::percent(1), Decimal::percent(5)],
+            &asset_amounts,
+            total_fee_rate,
+        )
+        .unwrap();
+
+        for response in depth {
+            let threshold = Decimal256::from(response.price_impact);
+            for (offer_ind, offer_amount) in response.offer_amounts.into_iter().enumerate() {
+                let impact_of = |amount| {
+                    simulate(amount, 1 ^ offer_ind, &asset_amounts, total_fee_rate)
+                        .unwrap()
+                        .price_impact
+                };
+                assert!(impact_of(offer_amount) <= threshold);
+                assert!(impact_of(offer_amount + Uint128::one()) > threshold);
+            }
+        }
+    }
+
+    #[test]
+    fn depth_rejects_wrong_asset_length() {
+        let err = simulate_depth(
+            &[Decimal::percent(1)],
+            &[Uint128::new(1_000)],
+            Decimal::permille(3),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Wrong asset length: expected 2, actual 1"
+        );
+    }
+
+    #[test]
+    fn depth_rejects_empty_pools() {
+        let err = simulate_depth(
+            &[Decimal::percent(1)],
+            &[Uint128::new(1_000), Uint128::zero()],
+            Decimal::permille(3),
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Generic error: One of the assets is empty");
+    }
+
+    #[test]
+    fn swap_to_price_offers_the_smallest_amount_reaching_the_price() {
+        let asset_amounts = [
+            Uint128::new(1_000_000_000_000),
+            Uint128::new(2_000_000_000_000),
+        ];
+        let total_fee_rate = Decimal::permille(3);
+
+        for (target_price, offer_ind) in [
+            (Decimal::percent(190), 0),
+            (Decimal::percent(170), 0),
+            (Decimal::percent(210), 1),
+            (Decimal::percent(400), 1),
+        ] {
+            let response = swap_to_price(target_price, &asset_amounts, total_fee_rate).unwrap();
+            assert_eq!(response.offer_ind, offer_ind);
+
+            let ask_ind = 1 ^ offer_ind;
+            let target_price = if offer_ind == 0 {
+                Decimal256::from(target_price)
+            } else {
+                Decimal256::one() / Decimal256::from(target_price)
+            };
+            let previous = simulate(
+                response.offer_amount - Uint128::one(),
+                ask_ind,
+                &asset_amounts,
+                total_fee_rate,
+            )
+            .unwrap();
+            assert!(response.swap.spot_price_after <= target_price);
+            assert!(previous.spot_price_after > target_price);
+        }
+    }
+    #[test]
+    fn ladder_levels_move_the_price_to_their_prices() {
+        let asset_amounts = [
+            Uint128::new(1_000_000_000_000),
+            Uint128::new(2_000_000_000_000),
+        ];
+        let total_fee_rate = Decimal::permille(3);
+
+        let ladder =
+            simulate_ladder(Decimal::percent(10), 3, &asset_amounts, total_fee_rate).unwrap();
+
+        assert_eq!(ladder.mid_price, Decimal::percent(200));
+        assert_eq!((ladder.bids.len(), ladder.asks.len()), (3, 3));
+        for (levels, offer_ind) in [(&ladder.bids, 0), (&ladder.asks, 1)] {
+            let mut previous_offer_amount = Uint128::zero();
+            for level in levels {
+                let (offer_amount, return_amount, target_price) = if offer_ind == 0 {
+                    (
+                        level.base_amount,
+                        level.quote_amount,
+                        Decimal256::from(level.price),
+                    )
+                } else {
+                    (
+                        level.quote_amount,
+                        level.base_amount,
+                        Decimal256::one() / Decimal256::from(level.price),
+                    )
+                };
+                let swap =
+                    simulate(offer_amount, 1 ^ offer_ind, &asset_amounts, total_fee_rate).unwrap();
+
+                assert!(offer_amount > previous_offer_amount);
+                assert_eq!(swap.return_amount, return_amount);
+                assert!(swap.spot_price_after <= target_price);
+                previous_offer_amount = offer_amount;
+            }
+        }
+    }
+
+    #[test]
+    fn ladder_rejects_wrong_asset_length() {
+        let err = simulate_ladder(
+            Decimal::percent(10),
+            3,
+            &[Uint128::new(1_000)],
+            Decimal::permille(3),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Wrong asset length: expected 2, actual 1"
+        );
+    }
+
+    #[test]
+    fn ladder_rejects_empty_pools() {
+        let err = simulate_ladder(
+            Decimal::percent(10),
+            3,
+            &[Uint128::zero(), Uint128::new(1_000)],
+            Decimal::permille(3),
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Generic error: One of the assets is empty");
+    }
+
+    #[test]
+    fn curve_points_match_the_swaps() {
+        let asset_amounts = [Uint128::new(1_000_000_000), Uint128::new(2_000_000_000)];
+
+        let points = sample_curve(
+            Uint128::new(1_000),
+            Uint128::new(1_000_000_000),
+            4,
+            Spacing::Log,
+            0,
+            &asset_amounts,
+            Decimal::permille(3),
+        )
+        .unwrap();
+
+        assert_eq!(points.len(), 4);
+        for (point, offer_amount) in
+            points
+                .iter()
+                .zip([1_000u128, 100_000, 10_000_000, 1_000_000_000])
+        {
+            let swap = simulate(
+                Uint128::new(offer_amount),
+                0,
+                &asset_amounts,
+                Decimal::permille(3),
+            )
+            .unwrap();
+            assert_eq!(point.offer_amount, Uint128::new(offer_amount));
+            assert_eq!(point.return_amount, swap.return_amount);
+            assert_eq!(point.commission_amount, swap.commission_amount);
+            assert_eq!(point.price_impact, swap.price_impact);
+        }
+    }
+
+    #[test]
+    fn curve_rejects_wrong_requests() {
+        let curve = |asset_amounts: &[Uint128], points, ask_ind| {
+            sample_curve(
+                Uint128::new(1_000),
+                Uint128::new(1_000_000),
+                points,
+                Spacing::Linear,
+                ask_ind,
+                asset_amounts,
+                Decimal::permille(3),
+            )
+            .unwrap_err()
+            .to_string()
+        };
+        let asset_amounts = [Uint128::new(1_000_000_000), Uint128::new(2_000_000_000)];
+
+        assert_eq!(
+            curve(&asset_amounts[..1], 2, 0),
+            "Generic error: Wrong asset length: expected 2, actual 1"
+        );
+        assert_eq!(
+            curve(&asset_amounts, 2, 2),
+            "Generic error: Ask asset index 2 is out of range"
+        );
+        assert_eq!(
+            curve(&asset_amounts, 1, 0),
+            "Generic error: Number of points must be at least 2"
+        );
+    }
+
+    #[test]
+    fn batch_matches_the_single_swaps() {
+        let pools = [
+            BatchPool {
+                asset_amounts: vec![Uint128::new(1_000_000_000), Uint128::new(2_000_000_000)],
+                total_fee_rate: Decimal::permille(3),
+            },
+            BatchPool {
+                asset_amounts: vec![Uint128::new(1_000_000_000)],
+                total_fee_rate: Decimal::permille(3),
+            },
+        ];
+        let request = |pool, ask_ind| BatchSwapRequest {
+            pool,
+            offer_amount: Uint128::new(1_000_000),
+            ask_ind,
+        };
+
+        let items = simulate_batch(
+            &pools,
+            &[
+                request(0, 1),
+                request(0, 0),
+                request(0, 2),
+                request(1, 0),
+                request(2, 0),
+            ],
+        );
+
+        let swap = |ask_ind| {
+            simulate(
+                Uint128::new(1_000_000),
+                ask_ind,
+                &pools[0].asset_amounts,
+                pools[0].total_fee_rate,
+            )
+            .unwrap()
+        };
+        assert_eq!(
+            items,
+            [
+                BatchItem::Ok(swap(1)),
+                BatchItem::Ok(swap(0)),
+                BatchItem::Error("Generic error: Ask asset index 2 is out of range".to_string()),
+                BatchItem::Error(
+                    "Generic error: Wrong asset length: expected 2, actual 1".to_string()
+                ),
+                BatchItem::Error("Pool index 2 is out of range".to_string()),
+            ]
+        );
+    }
+}
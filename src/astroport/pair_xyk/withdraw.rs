@@ -1,7 +1,12 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Decimal, StdResult, Uint128};
+use cosmwasm_std::{Decimal, StdError, StdResult, Uint128};
+use tsify::Tsify;
+
+use crate::astroport::lib::guards::ensure_min_assets_to_receive;
 
 #[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "XykWithdrawSimulationResponse")]
 pub struct SimulationResponse {
     /// The amount of assets returned by the withdraw
     pub returned_amounts: Vec<Uint128>,
@@ -11,9 +16,17 @@ pub fn simulate(
     amount: Uint128,
     asset_amounts: &[Uint128],
     total_share: Uint128,
+    min_assets_to_receive: Option<Vec<Uint128>>,
 ) -> StdResult<SimulationResponse> {
+    if amount > total_share {
+        return Err(StdError::generic_err("Insufficient amount of liquidity"));
+    }
+
     let returned_amounts = compute_withdraw(amount, asset_amounts, total_share);
 
+    ensure_min_assets_to_receive(&returned_amounts, min_assets_to_receive, None)
+        .map_err(|err| StdError::generic_err(format!("{err}")))?;
+
     Ok(SimulationResponse { returned_amounts })
 }
 
@@ -35,3 +48,73 @@ pub fn compute_withdraw(
 
     refund_assets
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POOLS: [Uint128; 2] = [Uint128::new(1_000_000_000), Uint128::new(2_000_000_000)];
+    const TOTAL_SHARE: Uint128 = Uint128::new(1_414_213_562);
+
+    #[test]
+    fn withdraw_checks_the_min_assets_to_receive() {
+        let response = simulate(Uint128::new(1_000), &POOLS, TOTAL_SHARE, None).unwrap();
+        assert_eq!(
+            response.returned_amounts,
+            [Uint128::new(707), Uint128::new(1_414)]
+        );
+
+        assert_eq!(
+            simulate(
+                Uint128::new(1_000),
+                &POOLS,
+                TOTAL_SHARE,
+                Some(response.returned_amounts)
+            )
+            .unwrap()
+            .returned_amounts,
+            [Uint128::new(707), Uint128::new(1_414)]
+        );
+        assert_eq!(
+            simulate(
+                Uint128::new(1_000),
+                &POOLS,
+                TOTAL_SHARE,
+                Some(vec![Uint128::new(707), Uint128::new(1_415)])
+            )
+            .unwrap_err()
+            .to_string(),
+            "Generic error: Received 1414 asset #1 but expected 1415"
+        );
+        assert_eq!(
+            simulate(
+                Uint128::new(1_000),
+                &POOLS,
+                TOTAL_SHARE,
+                Some(vec![Uint128::new(707)])
+            )
+            .unwrap_err()
+            .to_string(),
+            "Generic error: Wrong asset length: expected 2, actual 1"
+        );
+    }
+
+    #[test]
+    fn withdraw_rejects_more_than_the_total_share() {
+        assert_eq!(
+            simulate(TOTAL_SHARE, &POOLS, TOTAL_SHARE, None)
+                .unwrap()
+                .returned_amounts,
+            POOLS
+        );
+
+        for amount in [TOTAL_SHARE + Uint128::one(), Uint128::MAX] {
+            assert_eq!(
+                simulate(amount, &POOLS, TOTAL_SHARE, None)
+                    .unwrap_err()
+                    .to_string(),
+                "Generic error: Insufficient amount of liquidity"
+            );
+        }
+    }
+}
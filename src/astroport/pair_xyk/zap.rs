@@ -0,0 +1,306 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, StdError, StdResult, Uint128};
+use tsify::Tsify;
+
+use super::{consts::MAX_ALLOWED_SLIPPAGE, provide, swap, withdraw};
+
+use crate::astroport::lib::batch::check_swap_request;
+use crate::astroport::lib::search::maximize;
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "XykZapInSimulationResponse")]
+pub struct ZapInSimulationResponse {
+    /// The amount of offer asset swapped before the provide
+    pub swap_amount: Uint128,
+    /// The result of the swap leg
+    #[tsify(type = "XykSwapSimulationResponse")]
+    pub swap: swap::SimulationResponse,
+    /// The amounts deposited by the provide leg
+    pub deposits: Vec<Uint128>,
+    /// The result of the provide leg
+    #[tsify(type = "XykProvideSimulationResponse")]
+    pub provide: provide::SimulationResponse,
+    /// The amounts of deposits which are not backed by the minted lps
+    pub dust_amounts: Vec<Uint128>,
+}
+
+/// Simulates providing a single asset: the part of `offer_amount` which maximizes the minted lps
+/// is swapped first and the resulting pair of amounts is provided against the post-swap pools.
+///
+/// The provide leg is checked with [`MAX_ALLOWED_SLIPPAGE`], so that splits far from the optimum
+/// don't fail the search. The optimal deposits follow the post-swap pools, thus they pass the
+/// default tolerance of the contract too.
+///
+/// * **maker_fee_share** the share of the commission sent to the maker, which leaves the pool
+///   before the provide. The pair charges it only if its fee address is set.
+pub fn simulate_zap_in(
+    offer_amount: Uint128,
+    offer_ind: usize,
+    asset_amounts: &[Uint128],
+    total_share: Uint128,
+    total_fee_rate: Decimal,
+    maker_fee_share: Option<Decimal>,
+) -> StdResult<ZapInSimulationResponse> {
+    check_swap_request(asset_amounts.len(), offer_ind)?;
+    if offer_amount.is_zero() {
+        return Err(StdError::generic_err("Offer amount must not be zero"));
+    }
+
+    if asset_amounts.iter().any(|amount| amount.is_zero()) || total_share.is_zero() {
+        return Err(StdError::generic_err("One of the assets is empty"));
+    }
+
+    let swap_amount = maximize(Uint128::zero(), offer_amount, |swap_amount| {
+        let (_, deposits, pools) = swap_leg(
+            offer_amount,
+            offer_ind,
+            swap_amount,
+            asset_amounts,
+            total_fee_rate,
+            maker_fee_share,
+        )?;
+        let provide = provide::simulate(
+            &deposits,
+            &pools,
+            total_share,
+            Some(MAX_ALLOWED_SLIPPAGE),
+            None,
+        )?;
+        Ok(provide.share_amount)
+    });
+
+    let (swap, deposits, pools) = swap_leg(
+        offer_amount,
+        offer_ind,
+        swap_amount,
+        asset_amounts,
+        total_fee_rate,
+        maker_fee_share,
+    )?;
+    let provide = provide::simulate(
+        &deposits,
+        &pools,
+        total_share,
+        Some(MAX_ALLOWED_SLIPPAGE),
+        None,
+    )?;
+    let dust_amounts = provide.lost_amounts.clone();
+
+    Ok(ZapInSimulationResponse {
+        swap_amount,
+        swap,
+        deposits,
+        provide,
+        dust_amounts,
+    })
+}
+
+/// Swaps `swap_amount` out of `offer_amount` and returns the swap result, the amounts left for
+/// the provide and the post-swap pools. The maker fee leaves the pool.
+fn swap_leg(
+    offer_amount: Uint128,
+    offer_ind: usize,
+    swap_amount: Uint128,
+    asset_amounts: &[Uint128],
+    total_fee_rate: Decimal,
+    maker_fee_share: Option<Decimal>,
+) -> StdResult<(swap::SimulationResponse, Vec<Uint128>, Vec<Uint128>)> {
+    let ask_ind = 1 ^ offer_ind;
+
+    let swap = swap::simulate(swap_amount, ask_ind, asset_amounts, total_fee_rate)?;
+    let maker_fee = swap.commission_amount * maker_fee_share.unwrap_or_default();
+
+    let mut deposits = vec![Uint128::zero(); 2];
+    deposits[offer_ind] = offer_amount - swap_amount;
+    deposits[ask_ind] = swap.return_amount;
+
+    let mut pools = asset_amounts.to_vec();
+    pools[offer_ind] += swap_amount;
+    pools[ask_ind] = pools[ask_ind]
+        .checked_sub(swap.return_amount)?
+        .checked_sub(maker_fee)?;
+
+    Ok((swap, deposits, pools))
+}
+
+#[cw_serde]
+#[derive(Tsify)]
+#[serde(rename = "XykZapOutSimulationResponse")]
+pub struct ZapOutSimulationResponse {
+    /// The result of the withdraw leg
+    #[tsify(type = "XykWithdrawSimulationResponse")]
+    pub withdraw: withdraw::SimulationResponse,
+    /// The result of swapping the other withdrawn asset into the ask asset
+    #[tsify(type = "XykSwapSimulationResponse")]
+    pub swap: swap::SimulationResponse,
+    /// The total amount of ask asset returned
+    pub return_amount: Uint128,
+}
+
+/// Simulates burning `amount` lps for a single asset: the assets are withdrawn proportionally
+/// and the other withdrawn asset is swapped into the ask asset against the post-withdraw pools.
+pub fn simulate_zap_out(
+    amount: Uint128,
+    ask_ind: usize,
+    asset_amounts: &[Uint128],
+    total_share: Uint128,
+    total_fee_rate: Decimal,
+) -> StdResult<ZapOutSimulationResponse> {
+    check_swap_request(asset_amounts.len(), ask_ind)?;
+    let offer_ind = 1 ^ ask_ind;
+
+    let withdraw = withdraw::simulate(amount, asset_amounts, total_share, None)?;
+
+    let pools = asset_amounts
+        .iter()
+        .zip(&withdraw.returned_amounts)
+        .map(|(pool, returned_amount)| pool.checked_sub(*returned_amount))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Withdrawing the whole pool leaves nothing to swap against
+    let swap = if pools.iter().any(|pool| pool.is_zero()) {
+        swap::empty_swap()
+    } else {
+        swap::simulate(
+            withdraw.returned_amounts[offer_ind],
+            ask_ind,
+            &pools,
+            total_fee_rate,
+        )?
+    };
+    let return_amount = withdraw.returned_amounts[ask_ind].checked_add(swap.return_amount)?;
+
+    Ok(ZapOutSimulationResponse {
+        withdraw,
+        swap,
+        return_amount,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zap_in_takes_the_maker_fee_out_of_the_pools() {
+        let (swap, deposits, pools) = swap_leg(
+            Uint128::new(10_000),
+            0,
+            Uint128::new(5_000),
+            &[Uint128::new(1_000_000); 2],
+            Decimal::percent(3),
+            Some(Decimal::percent(50)),
+        )
+        .unwrap();
+
+        assert_eq!(swap.return_amount, Uint128::new(4_826));
+        assert_eq!(swap.commission_amount, Uint128::new(149));
+        assert_eq!(deposits, vec![Uint128::new(5_000), Uint128::new(4_826)]);
+        assert_eq!(pools, vec![Uint128::new(1_005_000), Uint128::new(995_100)]);
+    }
+
+    #[test]
+    fn zap_in_checks_the_offer_index() {
+        let err = simulate_zap_in(
+            Uint128::new(1_000),
+            2,
+            &[Uint128::new(1_000_000); 2],
+            Uint128::new(1_000_000),
+            Decimal::percent(3),
+            None,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Ask asset index 2 is out of range"
+        );
+    }
+
+    #[test]
+    fn zap_out_swaps_the_other_asset_against_the_remaining_pools() {
+        let asset_amounts = [Uint128::new(1_000_000_000), Uint128::new(2_000_000_000)];
+        let total_share = Uint128::new(1_414_213_562);
+
+        let response = simulate_zap_out(
+            Uint128::new(14_142_135),
+            1,
+            &asset_amounts,
+            total_share,
+            Decimal::permille(3),
+        )
+        .unwrap();
+
+        let withdraw =
+            withdraw::simulate(Uint128::new(14_142_135), &asset_amounts, total_share, None)
+                .unwrap();
+        let swap = swap::simulate(
+            withdraw.returned_amounts[0],
+            1,
+            &[
+                asset_amounts[0] - withdraw.returned_amounts[0],
+                asset_amounts[1] - withdraw.returned_amounts[1],
+            ],
+            Decimal::permille(3),
+        )
+        .unwrap();
+        assert_eq!(
+            response.return_amount,
+            withdraw.returned_amounts[1] + swap.return_amount
+        );
+        assert_eq!(response.withdraw, withdraw);
+        assert_eq!(response.swap, swap);
+    }
+
+    #[test]
+    fn zap_out_of_the_whole_pool_returns_the_ask_pool() {
+        let asset_amounts = [Uint128::new(1_000_000_000), Uint128::new(2_000_000_000)];
+
+        let response = simulate_zap_out(
+            Uint128::new(1_414_213_562),
+            1,
+            &asset_amounts,
+            Uint128::new(1_414_213_562),
+            Decimal::permille(3),
+        )
+        .unwrap();
+
+        assert_eq!(response.return_amount, asset_amounts[1]);
+        assert_eq!(response.swap, swap::empty_swap());
+    }
+
+    #[test]
+    fn zap_out_rejects_more_than_the_total_share() {
+        let err = simulate_zap_out(
+            Uint128::new(1_000_001),
+            1,
+            &[Uint128::new(1_000_000); 2],
+            Uint128::new(1_000_000),
+            Decimal::permille(3),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Insufficient amount of liquidity"
+        );
+    }
+
+    #[test]
+    fn zap_out_checks_the_ask_index() {
+        let err = simulate_zap_out(
+            Uint128::new(1_000),
+            2,
+            &[Uint128::new(1_000_000); 2],
+            Uint128::new(1_000_000),
+            Decimal::permille(3),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Ask asset index 2 is out of range"
+        );
+    }
+}
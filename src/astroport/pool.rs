@@ -0,0 +1,310 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Decimal256, StdError, StdResult, Uint128};
+use tsify::Tsify;
+
+use crate::astroport::asset::AssetInfo;
+use crate::astroport::cosmwasm_ext::Decimal256Ext;
+use crate::astroport::lib::guards::ensure_min_assets_to_receive;
+use crate::astroport::{pair_concentrated, pair_stable, pair_xyk};
+
+/// The state of a pool of any type, tagged with the type, e.g. `{"xyk": {...}}`.
+#[cw_serde]
+#[derive(Tsify)]
+pub enum PoolState {
+    Xyk(#[tsify(type = "XykPoolState")] pair_xyk::pool::PoolState),
+    Stable(#[tsify(type = "StablePoolState")] pair_stable::pool::PoolState),
+    Concentrated(#[tsify(type = "ConcentratedPoolState")] Box<pair_concentrated::pool::PoolState>),
+}
+
+/// The result of a swap by a pool of any type, serialized as the result of the pool type.
+#[cw_serde]
+#[serde(untagged)]
+pub enum SwapResponse {
+    Xyk(pair_xyk::swap::SimulationResponse),
+    Stable(pair_stable::swap::SimulationResponse),
+    Concentrated(pair_concentrated::swap::SwapSimulationResponse),
+}
+
+impl SwapResponse {
+    /// The amount of ask assets returned by the swap
+    pub fn return_amount(&self) -> Uint128 {
+        match self {
+            SwapResponse::Xyk(response) => response.return_amount,
+            SwapResponse::Stable(response) => response.return_amount,
+            SwapResponse::Concentrated(response) => response.return_amount,
+        }
+    }
+}
+
+#[cw_serde]
+#[serde(untagged)]
+pub enum ReverseSwapResponse {
+    Xyk(pair_xyk::swap::ReverseSimulationResponse),
+    Stable(pair_stable::swap::ReverseSimulationResponse),
+    Concentrated(pair_concentrated::swap::ReverseSwapSimulationResponse),
+}
+
+#[cw_serde]
+#[serde(untagged)]
+pub enum ProvideResponse {
+    Xyk(pair_xyk::provide::SimulationResponse),
+    Stable(pair_stable::provide::SimulationResponse),
+    Concentrated(pair_concentrated::provide::SimulationResponse),
+}
+
+#[cw_serde]
+#[serde(untagged)]
+pub enum WithdrawResponse {
+    Xyk(pair_xyk::withdraw::SimulationResponse),
+    Stable(pair_stable::withdraw::SimulationResponse),
+    Concentrated(pair_concentrated::withdraw::SimulationResponse),
+}
+
+impl WithdrawResponse {
+    /// The amounts of assets returned by the withdraw
+    pub fn returned_amounts(&self) -> &[Uint128] {
+        match self {
+            WithdrawResponse::Xyk(response) => &response.returned_amounts,
+            WithdrawResponse::Stable(response) => &response.returned_amounts,
+            WithdrawResponse::Concentrated(response) => &response.returned_amounts,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct RouteResponse {
+    /// The amount of ask assets returned by the last swap
+    pub return_amount: Uint128,
+    /// The swaps of every hop in the order of the route
+    pub swaps: Vec<SwapResponse>,
+}
+
+/// A pool of any type, see [`PoolState`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Pool {
+    Xyk(pair_xyk::pool::Pool),
+    Stable(pair_stable::pool::Pool),
+    Concentrated(Box<pair_concentrated::pool::Pool>),
+}
+
+impl Pool {
+    pub fn new(state: PoolState) -> StdResult<Self> {
+        match state {
+            PoolState::Xyk(state) => pair_xyk::pool::Pool::new(state)
+                .map(Pool::Xyk)
+                .map_err(|err| StdError::generic_err(format!("{err}"))),
+            PoolState::Stable(state) => pair_stable::pool::Pool::new(state)
+                .map(Pool::Stable)
+                .map_err(|err| StdError::generic_err(format!("{err}"))),
+            PoolState::Concentrated(state) => pair_concentrated::pool::Pool::new(*state)
+                .map(|pool| Pool::Concentrated(Box::new(pool)))
+                .map_err(|err| StdError::generic_err(format!("{err}"))),
+        }
+    }
+
+    /// Returns the pool balances, truncated to integers.
+    pub fn asset_amounts(&self) -> StdResult<Vec<Uint128>> {
+        let asset_amounts = match self {
+            Pool::Xyk(pool) => return Ok(pool.state().asset_amounts.clone()),
+            Pool::Stable(pool) => &pool.state().asset_amounts,
+            Pool::Concentrated(pool) => &pool.state().asset_amounts,
+        };
+
+        asset_amounts
+            .iter()
+            .map(|amount| Ok(Uint128::try_from(amount.to_uint256())?))
+            .collect()
+    }
+
+    /// Returns the total amount of issued lps.
+    pub fn total_share(&self) -> Uint128 {
+        match self {
+            Pool::Xyk(pool) => pool.state().total_share,
+            Pool::Stable(pool) => pool.state().total_share,
+            Pool::Concentrated(pool) => pool.state().total_share,
+        }
+    }
+
+    /// Returns the price of the offer asset in ask assets, excluding fees.
+    pub fn spot_price(&self, ask_ind: usize) -> StdResult<Decimal> {
+        match self {
+            Pool::Xyk(pool) => pool.spot_price(ask_ind),
+            Pool::Stable(pool) => pool.spot_price(ask_ind),
+            Pool::Concentrated(pool) => pool.spot_price(ask_ind),
+        }
+    }
+
+    pub fn swap(&self, offer_amount: Uint128, ask_ind: usize) -> StdResult<SwapResponse> {
+        match self {
+            Pool::Xyk(pool) => pool.swap(offer_amount, ask_ind).map(SwapResponse::Xyk),
+            Pool::Stable(pool) => pool.swap(offer_amount, ask_ind).map(SwapResponse::Stable),
+            Pool::Concentrated(pool) => pool
+                .swap(offer_amount, ask_ind)
+                .map(SwapResponse::Concentrated)
+                .map_err(|err| StdError::generic_err(format!("{err}"))),
+        }
+    }
+
+    pub fn reverse_swap(
+        &self,
+        ask_amount: Uint128,
+        ask_ind: usize,
+    ) -> StdResult<ReverseSwapResponse> {
+        match self {
+            Pool::Xyk(pool) => pool
+                .reverse_swap(ask_amount, ask_ind)
+                .map(ReverseSwapResponse::Xyk),
+            Pool::Stable(pool) => pool
+                .reverse_swap(ask_amount, ask_ind)
+                .map(ReverseSwapResponse::Stable),
+            Pool::Concentrated(pool) => pool
+                .reverse_swap(ask_amount, ask_ind)
+                .map(ReverseSwapResponse::Concentrated)
+                .map_err(|err| StdError::generic_err(format!("{err}"))),
+        }
+    }
+
+    /// Concentrated pools don't check the slippage of a provide, thus `slippage_tolerance` must
+    /// be `None` for them.
+    pub fn provide(
+        &self,
+        deposits: &[Uint128],
+        slippage_tolerance: Option<Decimal>,
+        min_lp_to_receive: Option<Uint128>,
+    ) -> StdResult<ProvideResponse> {
+        let decimal_deposits = deposits
+            .iter()
+            .map(|deposit| Decimal256::from_integer(*deposit))
+            .collect::<Vec<_>>();
+
+        match self {
+            Pool::Xyk(pool) => pool
+                .provide(deposits, slippage_tolerance, min_lp_to_receive)
+                .map(ProvideResponse::Xyk),
+            Pool::Stable(pool) => pool
+                .provide(&decimal_deposits, slippage_tolerance, min_lp_to_receive)
+                .map(ProvideResponse::Stable),
+            Pool::Concentrated(pool) => pool
+                .provide(&decimal_deposits, slippage_tolerance, min_lp_to_receive)
+                .map(ProvideResponse::Concentrated)
+                .map_err(|err| StdError::generic_err(format!("{err}"))),
+        }
+    }
+
+    /// Simulates burning `amount` lps. The withdraw fails when an asset is returned for less than
+    /// its `min_assets_to_receive` counterpart, naming the asset by its info in `asset_infos`.
+    pub fn withdraw(
+        &self,
+        amount: Uint128,
+        min_assets_to_receive: Option<Vec<Uint128>>,
+        asset_infos: Option<&[AssetInfo]>,
+    ) -> StdResult<WithdrawResponse> {
+        let response = match self {
+            Pool::Xyk(pool) => pool.withdraw(amount, None).map(WithdrawResponse::Xyk),
+            Pool::Stable(pool) => pool.withdraw(amount, None).map(WithdrawResponse::Stable),
+            Pool::Concentrated(pool) => pool
+                .withdraw(amount, None)
+                .map(WithdrawResponse::Concentrated)
+                .map_err(|err| StdError::generic_err(format!("{err}"))),
+        }?;
+
+        ensure_min_assets_to_receive(
+            response.returned_amounts(),
+            min_assets_to_receive,
+            asset_infos,
+        )
+        .map_err(|err| StdError::generic_err(format!("{err}")))?;
+
+        Ok(response)
+    }
+}
+
+/// Simulates swapping `offer_amount` through `pools` in order, where every hop offers the
+/// return of the previous one and asks for the asset with the corresponding index of `ask_inds`.
+pub fn route(
+    pools: &[Pool],
+    offer_amount: Uint128,
+    ask_inds: &[usize],
+) -> StdResult<RouteResponse> {
+    if pools.is_empty() {
+        return Err(StdError::generic_err(
+            "Route must contain at least one pool",
+        ));
+    }
+
+    if pools.len() != ask_inds.len() {
+        return Err(StdError::generic_err(format!(
+            "Wrong number of ask indices: expected {}, actual {}",
+            pools.len(),
+            ask_inds.len()
+        )));
+    }
+
+    let mut return_amount = offer_amount;
+    let swaps = pools
+        .iter()
+        .zip(ask_inds)
+        .map(|(pool, ask_ind)| {
+            let swap = pool.swap(return_amount, *ask_ind)?;
+            return_amount = swap.return_amount();
+            Ok(swap)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(RouteResponse {
+        return_amount,
+        swaps,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn xyk_pool(asset_amounts: [u128; 2]) -> Pool {
+        Pool::new(PoolState::Xyk(pair_xyk::pool::PoolState {
+            asset_amounts: asset_amounts.map(Uint128::new).to_vec(),
+            total_share: Uint128::new(1_000_000),
+            total_fee_rate: Decimal::permille(3),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn route_chains_the_swaps_of_the_pools() {
+        let pools = [
+            xyk_pool([1_000_000_000, 2_000_000_000]),
+            xyk_pool([3_000_000_000, 1_000_000_000]),
+        ];
+
+        let response = route(&pools, Uint128::new(1_000_000), &[1, 1]).unwrap();
+
+        let first = pools[0].swap(Uint128::new(1_000_000), 1).unwrap();
+        let second = pools[1].swap(first.return_amount(), 1).unwrap();
+        assert_eq!(response.return_amount, second.return_amount());
+        assert_eq!(response.swaps, [first, second]);
+    }
+
+    #[test]
+    fn route_rejects_wrong_hops() {
+        let pools = [xyk_pool([1_000_000_000, 2_000_000_000])];
+        let err = |pools: &[Pool], ask_inds: &[usize]| {
+            route(pools, Uint128::new(1_000_000), ask_inds)
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            err(&[], &[]),
+            "Generic error: Route must contain at least one pool"
+        );
+        assert_eq!(
+            err(&pools, &[1, 0]),
+            "Generic error: Wrong number of ask indices: expected 1, actual 2"
+        );
+        assert_eq!(
+            err(&pools, &[2]),
+            "Generic error: Ask asset index 2 is out of range"
+        );
+    }
+}
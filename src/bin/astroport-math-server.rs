@@ -0,0 +1,11 @@
+use std::env::args;
+use std::process::exit;
+
+fn main() {
+    let args = args().skip(1).collect::<Vec<_>>();
+
+    if let Err(err) = math::server::run(&args) {
+        eprintln!("{}", err);
+        exit(1);
+    }
+}
@@ -0,0 +1,15 @@
+use std::env::args;
+use std::io::stdin;
+use std::process::exit;
+
+fn main() {
+    let args = args().skip(1).collect::<Vec<_>>();
+
+    match math::cli::run(&args, &mut stdin().lock()) {
+        Ok(output) => println!("{}", output),
+        Err(err) => {
+            eprintln!("{}", err);
+            exit(1);
+        }
+    }
+}
@@ -0,0 +1,453 @@
+//! A command-line simulator of pool operations.
+//!
+//! The pool state is read from a JSON file tagged with the pool type, e.g. `{"xyk": {...}}`
+//! where `{...}` is the state accepted by the `XykPool` constructor.
+//! Routes read an array of such states, one per hop. When the operation is omitted, operations
+//! are read from stdin, one per line.
+
+use std::fmt::Display;
+use std::fs;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use cosmwasm_std::{Decimal, Uint128};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::astroport::pool::{route, Pool, PoolState};
+
+pub const USAGE: &str = "\
+Usage: astroport-math [--format json|table] <POOL_FILE> [OPERATION [ARGS...]]
+
+POOL_FILE is a JSON pool state tagged with the pool type, or - to read it from stdin.
+Without an OPERATION, operations are read from stdin, one per line.
+
+Operations:
+  swap <offer_amount> <ask_ind>
+  reverse-swap <ask_amount> <ask_ind>
+  provide <deposit> <deposit> [--slippage-tolerance <rate>] [--min-lp-to-receive <amount>]
+  withdraw <amount> [--min-assets-to-receive <amount>,<amount>]
+  spot-price <ask_ind>
+  route <offer_amount> <ask_ind>...    POOL_FILE holds an array of pool states, one per hop
+";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Table,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Format::Json),
+            "table" => Ok(Format::Table),
+            _ => Err(format!("Unknown format: {}", s)),
+        }
+    }
+}
+
+/// Runs the command with the arguments following the binary name and returns the output.
+pub fn run(args: &[String], stdin: &mut dyn BufRead) -> Result<String, String> {
+    let mut format = Format::Json;
+    let mut positional = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(USAGE.to_string()),
+            "--format" => format = parse(next_value(&mut args, "--format")?, "format")?,
+            _ => positional.push(arg.clone()),
+        }
+    }
+
+    let (pool_file, operation) = match positional.split_first() {
+        Some((pool_file, operation)) => (pool_file, operation),
+        None => return Err(USAGE.to_string()),
+    };
+
+    let pool_json = if pool_file == "-" {
+        if operation.is_empty() {
+            return Err("The pool state and the operations can't both be read from stdin".into());
+        }
+
+        let mut json = String::new();
+        stdin
+            .read_to_string(&mut json)
+            .map_err(|e| format!("Error while reading stdin: {}", e))?;
+        json
+    } else {
+        fs::read_to_string(pool_file)
+            .map_err(|e| format!("Error while reading {}: {}", pool_file, e))?
+    };
+    let pools = parse_pools(&pool_json)?;
+
+    if !operation.is_empty() {
+        return simulate(&pools, operation).map(|result| render(&result, format, false));
+    }
+
+    let mut outputs = vec![];
+    for (ind, line) in stdin.lines().enumerate() {
+        let line = line.map_err(|e| format!("Error while reading stdin: {}", e))?;
+        let operation = line
+            .split_whitespace()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        if operation.is_empty() {
+            continue;
+        }
+
+        let result =
+            simulate(&pools, &operation).map_err(|e| format!("Line {}: {}", ind + 1, e))?;
+        outputs.push(render(&result, format, true));
+    }
+
+    let separator = match format {
+        Format::Json => "\n",
+        Format::Table => "\n\n",
+    };
+    Ok(outputs.join(separator))
+}
+
+/// Parses a single pool state or an array of them.
+fn parse_pools(json: &str) -> Result<Vec<Pool>, String> {
+    let states = match serde_json::from_str::<Value>(json) {
+        Ok(Value::Array(_)) => serde_json::from_str::<Vec<PoolState>>(json),
+        _ => serde_json::from_str::<PoolState>(json).map(|state| vec![state]),
+    }
+    .map_err(|e| format!("Invalid pool state: {}", e))?;
+
+    states
+        .into_iter()
+        .map(|state| Pool::new(state).map_err(|e| format!("Error while creating pool: {}", e)))
+        .collect()
+}
+
+fn simulate(pools: &[Pool], operation: &[String]) -> Result<Value, String> {
+    let (name, args) = operation.split_first().ok_or_else(|| USAGE.to_string())?;
+
+    let mut positional = vec![];
+    let mut slippage_tolerance = None;
+    let mut min_lp_to_receive = None;
+    let mut min_assets_to_receive = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--slippage-tolerance" => {
+                let value = next_value(&mut args, arg)?;
+                slippage_tolerance = Some(parse::<Decimal>(value, "slippage_tolerance")?);
+            }
+            "--min-lp-to-receive" => {
+                let value = next_value(&mut args, arg)?;
+                min_lp_to_receive = Some(parse::<Uint128>(value, "min_lp_to_receive")?);
+            }
+            "--min-assets-to-receive" => {
+                let value = next_value(&mut args, arg)?;
+                min_assets_to_receive = Some(
+                    value
+                        .split(',')
+                        .map(|amount| parse::<Uint128>(amount, "min_assets_to_receive"))
+                        .collect::<Result<Vec<_>, _>>()?,
+                );
+            }
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    if name == "route" {
+        let (offer_amount, ask_inds) = positional
+            .split_first()
+            .ok_or("Usage: route <offer_amount> <ask_ind>...")?;
+        let ask_inds = ask_inds
+            .iter()
+            .map(|ask_ind| parse(ask_ind, "ask_ind"))
+            .collect::<Result<Vec<usize>, _>>()?;
+
+        return to_value(route(
+            pools,
+            parse(offer_amount, "offer_amount")?,
+            &ask_inds,
+        ));
+    }
+
+    let pool = match pools {
+        [pool] => pool,
+        _ => return Err(format!("{} expects a single pool state", name)),
+    };
+
+    match (name.as_str(), positional.as_slice()) {
+        ("swap", [offer_amount, ask_ind]) => to_value(pool.swap(
+            parse(offer_amount, "offer_amount")?,
+            parse(ask_ind, "ask_ind")?,
+        )),
+        ("reverse-swap", [ask_amount, ask_ind]) => to_value(
+            pool.reverse_swap(parse(ask_amount, "ask_amount")?, parse(ask_ind, "ask_ind")?),
+        ),
+        ("provide", deposits @ [_, _]) => {
+            let deposits = deposits
+                .iter()
+                .map(|deposit| parse(deposit, "deposits"))
+                .collect::<Result<Vec<Uint128>, _>>()?;
+            to_value(pool.provide(&deposits, slippage_tolerance, min_lp_to_receive))
+        }
+        ("withdraw", [amount]) => {
+            to_value(pool.withdraw(parse(amount, "amount")?, min_assets_to_receive, None))
+        }
+        ("spot-price", [ask_ind]) => to_value(pool.spot_price(parse(ask_ind, "ask_ind")?)),
+        ("swap" | "reverse-swap" | "provide" | "withdraw" | "spot-price", _) => {
+            Err(format!("Wrong arguments of {}\n\n{}", name, USAGE))
+        }
+        _ => Err(format!("Unknown operation: {}\n\n{}", name, USAGE)),
+    }
+}
+
+fn next_value<'a>(
+    args: &mut impl Iterator<Item = &'a String>,
+    name: &str,
+) -> Result<&'a str, String> {
+    args.next()
+        .map(String::as_str)
+        .ok_or_else(|| format!("Missing value of {}", name))
+}
+
+fn parse<T>(value: &str, name: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .parse()
+        .map_err(|e| format!("Invalid {}: {}", name, e))
+}
+
+fn to_value<T: Serialize, E: Display>(result: Result<T, E>) -> Result<Value, String> {
+    let result = result.map_err(|e| format!("Error while simulating: {}", e))?;
+
+    serde_json::to_value(result).map_err(|e| format!("Error while serializing result: {}", e))
+}
+
+/// Renders a result as JSON, on a single line if `compact`, or as a table of its fields with
+/// nested fields named by their paths, e.g. `swaps[0].return_amount`.
+fn render(result: &Value, format: Format, compact: bool) -> String {
+    match format {
+        Format::Json if compact => result.to_string(),
+        Format::Json => serde_json::to_string_pretty(result).unwrap_or_default(),
+        Format::Table if !result.is_object() => result
+            .as_str()
+            .map(String::from)
+            .unwrap_or_else(|| result.to_string()),
+        Format::Table => {
+            let mut rows = vec![];
+            flatten(result, String::new(), &mut rows);
+
+            let width = rows.iter().map(|(field, _)| field.len()).max().unwrap_or(0);
+            rows.iter()
+                .map(|(field, value)| format!("{:width$}  {}", field, value, width = width))
+                .map(|row| row.trim_end().to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    }
+}
+
+fn flatten(value: &Value, path: String, rows: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(fields) => {
+            for (field, value) in fields {
+                let path = if path.is_empty() {
+                    field.clone()
+                } else {
+                    format!("{}.{}", path, field)
+                };
+                flatten(value, path, rows);
+            }
+        }
+        Value::Array(items) => {
+            for (ind, item) in items.iter().enumerate() {
+                flatten(item, format!("{}[{}]", path, ind), rows);
+            }
+        }
+        Value::String(string) => rows.push((path, string.clone())),
+        _ => rows.push((path, value.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::path::PathBuf;
+
+    const XYK_STATE: &str = r#"{"xyk":{"asset_amounts":["1000000000","2000000000"],"total_share":"1414213562","total_fee_rate":"0.003"}}"#;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn run_with(args: &[String], stdin: &str) -> Result<String, String> {
+        run(args, &mut Cursor::new(stdin.as_bytes()))
+    }
+
+    /// Writes `json` into a pool file unique to `name`.
+    fn pool_file(name: &str, json: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("math-cli-{}-{}.json", name, std::process::id()));
+        fs::write(&path, json).unwrap();
+        path
+    }
+
+    fn xyk_pool() -> Pool {
+        Pool::new(serde_json::from_str(XYK_STATE).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn operations_are_simulated_on_the_pool_from_stdin() {
+        let output = run_with(&args(&["-", "swap", "1000000", "1"]), XYK_STATE).unwrap();
+
+        let swap = xyk_pool().swap(Uint128::new(1_000_000), 1).unwrap();
+        assert_eq!(
+            output,
+            serde_json::to_string_pretty(&serde_json::to_value(swap).unwrap()).unwrap()
+        );
+
+        let output = run_with(
+            &args(&["--format", "table", "-", "spot-price", "1"]),
+            XYK_STATE,
+        );
+        assert_eq!(output, Ok("2".to_string()));
+    }
+
+    #[test]
+    fn operations_are_read_from_stdin_one_per_line() {
+        let path = pool_file("lines", XYK_STATE);
+        let pool = xyk_pool();
+
+        let output = run_with(
+            &args(&[path.to_str().unwrap()]),
+            "spot-price 0\n\nwithdraw 1414213 --min-assets-to-receive 999999,1999999\n",
+        )
+        .unwrap();
+
+        let withdraw = pool.withdraw(
+            Uint128::new(1_414_213),
+            Some(vec![Uint128::new(999_999), Uint128::new(1_999_999)]),
+            None,
+        );
+        assert_eq!(
+            output,
+            format!(
+                "\"0.5\"\n{}",
+                serde_json::to_value(withdraw.unwrap()).unwrap()
+            )
+        );
+
+        let err = run_with(&args(&[path.to_str().unwrap()]), "spot-price 0\nswap 1\n");
+        fs::remove_file(path).unwrap();
+        assert_eq!(
+            err,
+            Err(format!("Line 2: Wrong arguments of swap\n\n{}", USAGE))
+        );
+    }
+
+    #[test]
+    fn routes_swap_through_every_pool() {
+        let stable = r#"{"stable":{"asset_amounts":["530256812","100446728"],"asset_precisions":[6,6],"total_share":"620000000","total_fee_rate":"0.0005","block_time":0,"init_amp_time":0,"init_amp":10000,"next_amp_time":0,"next_amp":10000}}"#;
+        let pools = format!("[{}, {}]", XYK_STATE, stable);
+
+        let output = run_with(&args(&["-", "route", "1000000", "1", "0"]), &pools).unwrap();
+
+        let pools = parse_pools(&pools).unwrap();
+        let expected = route(&pools, Uint128::new(1_000_000), &[1, 0]).unwrap();
+        assert_eq!(
+            output,
+            serde_json::to_string_pretty(&serde_json::to_value(expected).unwrap()).unwrap()
+        );
+        assert_eq!(
+            run_with(
+                &args(&["-", "swap", "1000000", "1"]),
+                &format!("[{}]", XYK_STATE)
+            ),
+            Ok(run_with(&args(&["-", "swap", "1000000", "1"]), XYK_STATE).unwrap())
+        );
+        assert_eq!(
+            run_with(
+                &args(&["-", "swap", "1000000", "1"]),
+                &format!("[{0}, {0}]", XYK_STATE)
+            ),
+            Err("swap expects a single pool state".to_string())
+        );
+    }
+
+    #[test]
+    fn tables_name_the_nested_fields_by_their_paths() {
+        let result = serde_json::json!({
+            "return_amount": "10",
+            "swaps": [{ "return_amount": "20" }, { "return_amount": "10" }],
+            "maker_fee_share": null,
+        });
+
+        assert_eq!(
+            render(&result, Format::Table, false),
+            "return_amount           10\n\
+             swaps[0].return_amount  20\n\
+             swaps[1].return_amount  10\n\
+             maker_fee_share         null"
+        );
+        assert_eq!(
+            render(&result, Format::Json, true),
+            r#"{"return_amount":"10","swaps":[{"return_amount":"20"},{"return_amount":"10"}],"maker_fee_share":null}"#
+        );
+    }
+
+    #[test]
+    fn wrong_commands_are_reported() {
+        let err = |command: &[&str], stdin: &str| run_with(&args(command), stdin).unwrap_err();
+
+        assert_eq!(run_with(&args(&["--help"]), ""), Ok(USAGE.to_string()));
+        assert_eq!(err(&[], ""), USAGE);
+        assert_eq!(err(&["--format"], ""), "Missing value of --format");
+        assert_eq!(
+            err(&["--format", "yaml", "-"], ""),
+            "Invalid format: Unknown format: yaml"
+        );
+        assert_eq!(
+            err(&["-"], XYK_STATE),
+            "The pool state and the operations can't both be read from stdin"
+        );
+        assert!(err(&["missing.json", "spot-price", "0"], "")
+            .starts_with("Error while reading missing.json: "));
+        assert!(err(&["-", "spot-price", "0"], r#"{"curve":{}}"#)
+            .starts_with("Invalid pool state: unknown variant `curve`"));
+        assert_eq!(
+            err(
+                &["-", "spot-price", "0"],
+                r#"{"xyk":{"asset_amounts":["1"],"total_share":"1","total_fee_rate":"0.003"}}"#
+            ),
+            "Error while creating pool: Generic error: Wrong asset length: expected 2, actual 1"
+        );
+        assert_eq!(
+            err(&["-", "mint", "1"], XYK_STATE),
+            format!("Unknown operation: mint\n\n{}", USAGE)
+        );
+        assert_eq!(
+            err(&["-", "swap", "1.5", "1"], XYK_STATE),
+            "Invalid offer_amount: Generic error: Parsing u128: invalid digit found in string"
+        );
+        assert_eq!(
+            err(&["-", "spot-price", "2"], XYK_STATE),
+            "Error while simulating: Generic error: Ask asset index 2 is out of range"
+        );
+        assert_eq!(
+            err(
+                &["-", "withdraw", "1", "--min-assets-to-receive"],
+                XYK_STATE
+            ),
+            "Missing value of --min-assets-to-receive"
+        );
+        assert_eq!(
+            err(&["-", "route"], XYK_STATE),
+            "Usage: route <offer_amount> <ask_ind>..."
+        );
+    }
+}
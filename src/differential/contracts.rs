@@ -0,0 +1,701 @@
+//! The reference of the harness: the Astroport pair contracts, created by the factory in
+//! cw-multi-test.
+//!
+//! The pair of a [`ContractPair`] is the one of the pair contract the simulations mirror: xyk
+//! pairs 1.5.1, stable pairs 3.4.1 and concentrated pairs 1.2.13, created by the factory 1.5.1.
+//! The precisions of the native tokens are served by a stand-in of the native coin registry.
+
+use astroport_pair_concentrated::state::CONFIG as CONCENTRATED_CONFIG;
+use astroport_pair_stable::state::CONFIG as STABLE_CONFIG;
+use cosmwasm_std::{
+    coin, to_json_binary, Addr, Binary, Coin, Decimal, Decimal256, Deps, DepsMut, Empty, Env,
+    MessageInfo, Response, StdError, StdResult, Uint128,
+};
+use cw_multi_test::{App, ContractWrapper, Executor};
+use cw_storage_plus::Map;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::{Operation, ReferencePair};
+use crate::astroport::asset::{Asset, AssetInfo};
+use crate::astroport::pair::{PairState, PoolResponse};
+use crate::astroport::pair_concentrated::state::{get_amp_gamma, RepegParams};
+use crate::astroport::pool::PoolState;
+use crate::astroport::{pair_concentrated, pair_stable, pair_xyk};
+
+/// The precisions of native tokens in the native coin registry.
+const COINS_INFO: Map<String, u8> = Map::new("coins_info");
+/// The precisions of the assets in the stable and concentrated pairs.
+const PRECISIONS: Map<String, u8> = Map::new("precisions");
+
+/// The tokens of the account trading with the pair, enough for the largest pool states.
+const USER_BALANCE: u128 = 10u128.pow(32);
+/// The seconds between the blocks the operations are executed in.
+const BLOCK_TIME: u64 = 5;
+
+const USER: &str = "user";
+const OWNER: &str = "owner";
+const MAKER: &str = "maker";
+
+/// The share of the fees sent to the maker of xyk and stable pairs, which the simulations don't
+/// depend on but the balances after swaps do.
+const MAKER_FEE_BPS: u16 = 3333;
+
+fn registry_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: Vec<(String, u8)>,
+) -> StdResult<Response> {
+    for (denom, precision) in msg {
+        COINS_INFO.save(deps.storage, denom, &precision)?;
+    }
+    Ok(Response::new())
+}
+
+fn registry_execute(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Err(StdError::generic_err("The registry is read only"))
+}
+
+fn registry_query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    Err(StdError::generic_err(
+        "The registry is queried by raw queries",
+    ))
+}
+
+#[derive(Deserialize)]
+struct PairInfo {
+    contract_addr: Addr,
+    liquidity_token: Addr,
+}
+
+#[derive(Deserialize)]
+struct FeeInfoResponse {
+    total_fee_bps: u16,
+    maker_fee_bps: u16,
+}
+
+#[derive(Deserialize)]
+struct BalanceResponse {
+    balance: Uint128,
+}
+
+/// A pair contract between two native tokens in cw-multi-test.
+pub struct ContractPair {
+    app: App,
+    factory: Addr,
+    pair: Addr,
+    lp_token: Addr,
+    asset_infos: Vec<AssetInfo>,
+    /// The pair type in the messages of the factory
+    pair_type: Value,
+    /// The pool state the pair was created with, the shape of the states read from the pair
+    pool: PoolState,
+}
+
+impl ContractPair {
+    /// Creates a pair for `state` through the factory and provides its balances.
+    ///
+    /// The pair type and the parameters of the pool come from `state`, the total amount of lps
+    /// and the prices are the ones of the contract after the first provide.
+    pub fn new(state: &PairState) -> StdResult<Self> {
+        let denoms = state
+            .asset_infos
+            .iter()
+            .map(|info| match info {
+                AssetInfo::NativeToken { denom } => Ok(denom.clone()),
+                AssetInfo::Token { .. } => Err(StdError::generic_err(
+                    "Only pairs of native tokens are supported",
+                )),
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let mut app = App::new(|router, _, storage| {
+            let balances = denoms
+                .iter()
+                .map(|denom| coin(USER_BALANCE, denom))
+                .collect();
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked(USER), balances)
+                .unwrap()
+        });
+
+        let token_code_id = app.store_code(Box::new(ContractWrapper::new_with_empty(
+            astroport_token::contract::execute,
+            astroport_token::contract::instantiate,
+            astroport_token::contract::query,
+        )));
+        let registry_code_id = app.store_code(Box::new(ContractWrapper::new_with_empty(
+            registry_execute,
+            registry_instantiate,
+            registry_query,
+        )));
+        let factory_code_id = app.store_code(Box::new(
+            ContractWrapper::new_with_empty(
+                astroport_factory::contract::execute,
+                astroport_factory::contract::instantiate,
+                astroport_factory::contract::query,
+            )
+            .with_reply_empty(astroport_factory::contract::reply),
+        ));
+
+        let (pair_code_id, pair_type, precisions, total_fee_bps, maker_fee_bps, init_params) =
+            match &state.pool {
+                PoolState::Xyk(pool) => {
+                    let code_id = app.store_code(Box::new(
+                        ContractWrapper::new_with_empty(
+                            astroport_pair::contract::execute,
+                            astroport_pair::contract::instantiate,
+                            astroport_pair::contract::query,
+                        )
+                        .with_reply_empty(astroport_pair::contract::reply),
+                    ));
+                    (
+                        code_id,
+                        json!({ "xyk": {} }),
+                        vec![6, 6],
+                        bps(pool.total_fee_rate)?,
+                        MAKER_FEE_BPS,
+                        None,
+                    )
+                }
+                PoolState::Stable(pool) => {
+                    let code_id = app.store_code(Box::new(
+                        ContractWrapper::new_with_empty(
+                            astroport_pair_stable::contract::execute,
+                            astroport_pair_stable::contract::instantiate,
+                            astroport_pair_stable::contract::query,
+                        )
+                        .with_reply_empty(astroport_pair_stable::contract::reply),
+                    ));
+                    let params = json!({ "amp": pool.init_amp / 100 });
+                    (
+                        code_id,
+                        json!({ "stable": {} }),
+                        pool.asset_precisions.clone(),
+                        bps(pool.total_fee_rate)?,
+                        MAKER_FEE_BPS,
+                        Some(to_json_binary(&params)?),
+                    )
+                }
+                PoolState::Concentrated(pool) => {
+                    let code_id = app.store_code(Box::new(
+                        ContractWrapper::new_with_empty(
+                            astroport_pair_concentrated::contract::execute,
+                            astroport_pair_concentrated::contract::instantiate,
+                            astroport_pair_concentrated::queries::query,
+                        )
+                        .with_reply_empty(astroport_pair_concentrated::contract::reply),
+                    ));
+                    // The pair starts from the parameters in effect, it can't be created ramping
+                    let amp_gamma = get_amp_gamma(
+                        pool.block_time,
+                        pool.initial_time,
+                        pool.initial_amp,
+                        pool.initial_gamma,
+                        pool.future_time,
+                        pool.future_amp,
+                        pool.future_gamma,
+                    );
+                    let params = json!({
+                        "amp": amp_gamma.amp,
+                        "gamma": amp_gamma.gamma,
+                        "mid_fee": decimal(pool.mid_fee)?,
+                        "out_fee": decimal(pool.out_fee)?,
+                        "fee_gamma": decimal(pool.fee_gamma)?,
+                        "repeg_profit_threshold": "0.000002",
+                        "min_price_scale_delta": "0.000146",
+                        "price_scale": decimal(pool.price_scale)?,
+                        "ma_half_time": 600,
+                    });
+                    let precisions = pool
+                        .asset_precisions
+                        .iter()
+                        .map(|&precision| u8::try_from(precision))
+                        .collect::<Result<_, _>>()
+                        .map_err(|err| StdError::generic_err(err.to_string()))?;
+                    (
+                        code_id,
+                        json!({ "custom": "concentrated" }),
+                        precisions,
+                        0,
+                        bps(decimal(pool.maker_fee_share)?)?,
+                        Some(to_json_binary(&params)?),
+                    )
+                }
+            };
+
+        let registry: Vec<(String, u8)> = denoms.iter().cloned().zip(precisions).collect();
+        let registry = app
+            .instantiate_contract(
+                registry_code_id,
+                Addr::unchecked(OWNER),
+                &registry,
+                &[],
+                "registry",
+                None,
+            )
+            .map_err(contract_error)?;
+
+        let factory = app
+            .instantiate_contract(
+                factory_code_id,
+                Addr::unchecked(OWNER),
+                &json!({
+                    "pair_configs": [{
+                        "code_id": pair_code_id,
+                        "pair_type": pair_type,
+                        "total_fee_bps": total_fee_bps,
+                        "maker_fee_bps": maker_fee_bps,
+                        "is_disabled": false,
+                        "is_generator_disabled": false,
+                    }],
+                    "token_code_id": token_code_id,
+                    "fee_address": MAKER,
+                    "generator_address": null,
+                    "owner": OWNER,
+                    "whitelist_code_id": 0,
+                    "coin_registry_address": registry,
+                }),
+                &[],
+                "factory",
+                None,
+            )
+            .map_err(contract_error)?;
+
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            factory.clone(),
+            &json!({
+                "create_pair": {
+                    "pair_type": pair_type,
+                    "asset_infos": state.asset_infos,
+                    "init_params": init_params,
+                }
+            }),
+            &[],
+        )
+        .map_err(contract_error)?;
+        let pair_info: PairInfo = app.wrap().query_wasm_smart(
+            &factory,
+            &json!({ "pair": { "asset_infos": state.asset_infos } }),
+        )?;
+
+        let mut pair = Self {
+            app,
+            factory,
+            pair: pair_info.contract_addr,
+            lp_token: pair_info.liquidity_token,
+            asset_infos: state.asset_infos.clone(),
+            pair_type,
+            pool: state.pool.clone(),
+        };
+
+        let balances = crate::astroport::pool::Pool::new(state.pool.clone())?.asset_amounts()?;
+        let assets = pair
+            .asset_infos
+            .iter()
+            .zip(balances)
+            .map(|(info, amount)| Asset {
+                info: info.clone(),
+                amount,
+            })
+            .collect();
+        pair.execute(&Operation::Provide {
+            assets,
+            slippage_tolerance: None,
+        })?;
+
+        Ok(pair)
+    }
+
+    /// The assets of the pair in the order of the pools.
+    pub fn asset_infos(&self) -> &[AssetInfo] {
+        &self.asset_infos
+    }
+
+    /// Queries the pair contract with `msg`.
+    pub fn query<T: DeserializeOwned>(&self, msg: &Value) -> StdResult<T> {
+        self.app.wrap().query_wasm_smart(&self.pair, msg)
+    }
+
+    /// Returns the share of the fees sent to the maker.
+    pub fn maker_fee_share(&self) -> StdResult<Decimal> {
+        Ok(Decimal::from_ratio(
+            self.fee_info()?.maker_fee_bps,
+            10_000u16,
+        ))
+    }
+
+    /// Returns the parameters of the repegs of a concentrated pair.
+    pub fn repeg_params(&self) -> StdResult<RepegParams> {
+        let config = CONCENTRATED_CONFIG.query(&self.app.wrap(), self.pair.clone())?;
+        let price_state = config.pool_state.price_state;
+
+        Ok(RepegParams {
+            last_price: price_state.last_price,
+            last_price_update: price_state.last_price_update,
+            xcp_profit: price_state.xcp_profit,
+            xcp_profit_real: price_state.xcp_profit_real,
+            ma_half_time: config.pool_params.ma_half_time,
+            repeg_profit_threshold: config.pool_params.repeg_profit_threshold,
+            min_price_scale_delta: config.pool_params.min_price_scale_delta,
+        })
+    }
+
+    fn balance(&self, info: &AssetInfo) -> StdResult<Uint128> {
+        match info {
+            AssetInfo::NativeToken { denom } => {
+                Ok(self.app.wrap().query_balance(USER, denom)?.amount)
+            }
+            AssetInfo::Token { contract_addr } => self.token_balance(contract_addr),
+        }
+    }
+
+    fn token_balance(&self, token: impl Into<String>) -> StdResult<Uint128> {
+        let response: BalanceResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(token, &json!({ "balance": { "address": USER } }))?;
+        Ok(response.balance)
+    }
+
+    fn fee_info(&self) -> StdResult<FeeInfoResponse> {
+        self.app.wrap().query_wasm_smart(
+            &self.factory,
+            &json!({ "fee_info": { "pair_type": self.pair_type } }),
+        )
+    }
+
+    fn precisions(&self) -> StdResult<Vec<u8>> {
+        self.asset_infos
+            .iter()
+            .map(|info| {
+                PRECISIONS
+                    .query(&self.app.wrap(), self.pair.clone(), info.to_string())?
+                    .ok_or_else(|| StdError::generic_err(format!("No precision of {info}")))
+            })
+            .collect()
+    }
+
+    /// Executes `msg` on `contract` with `funds`, then moves to the next block.
+    fn execute_contract(&mut self, contract: Addr, msg: &Value, funds: &[Coin]) -> StdResult<()> {
+        let result = self
+            .app
+            .execute_contract(Addr::unchecked(USER), contract, msg, funds)
+            .map(|_| ())
+            .map_err(contract_error);
+        self.app.update_block(|block| {
+            block.height += 1;
+            block.time = block.time.plus_seconds(BLOCK_TIME);
+        });
+        result
+    }
+}
+
+impl ReferencePair for ContractPair {
+    fn state(&mut self) -> StdResult<PairState> {
+        let response: PoolResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(&self.pair, &json!({ "pool": {} }))?;
+        let asset_amounts: Vec<Uint128> =
+            response.assets.iter().map(|asset| asset.amount).collect();
+        let block_time = self.app.block_info().time.seconds();
+
+        let pool = match &self.pool {
+            PoolState::Xyk(_) => PoolState::Xyk(pair_xyk::pool::PoolState {
+                asset_amounts,
+                total_share: response.total_share,
+                total_fee_rate: Decimal::from_ratio(self.fee_info()?.total_fee_bps, 10_000u16),
+            }),
+            PoolState::Stable(_) => {
+                let config = STABLE_CONFIG.query(&self.app.wrap(), self.pair.clone())?;
+                PoolState::Stable(pair_stable::pool::PoolState {
+                    asset_amounts: asset_amounts
+                        .iter()
+                        .map(|&amount| Decimal256::from_ratio(amount, 1u8))
+                        .collect(),
+                    asset_precisions: self.precisions()?,
+                    total_share: response.total_share,
+                    total_fee_rate: Decimal::from_ratio(self.fee_info()?.total_fee_bps, 10_000u16),
+                    block_time,
+                    init_amp_time: config.init_amp_time,
+                    init_amp: config.init_amp,
+                    next_amp_time: config.next_amp_time,
+                    next_amp: config.next_amp,
+                })
+            }
+            PoolState::Concentrated(_) => {
+                let config = CONCENTRATED_CONFIG.query(&self.app.wrap(), self.pair.clone())?;
+                let pool_state = config.pool_state;
+                PoolState::Concentrated(Box::new(pair_concentrated::pool::PoolState {
+                    asset_amounts: asset_amounts
+                        .iter()
+                        .map(|&amount| Decimal256::from_ratio(amount, 1u8))
+                        .collect(),
+                    asset_precisions: self.precisions()?.into_iter().map(u32::from).collect(),
+                    total_share: response.total_share,
+                    maker_fee_share: Decimal256::from_ratio(
+                        self.fee_info()?.maker_fee_bps,
+                        10_000u16,
+                    ),
+                    oracle_price: pool_state.price_state.oracle_price,
+                    price_scale: pool_state.price_state.price_scale,
+                    fee_gamma: config.pool_params.fee_gamma.into(),
+                    mid_fee: config.pool_params.mid_fee.into(),
+                    out_fee: config.pool_params.out_fee.into(),
+                    block_time,
+                    initial_time: pool_state.initial_time,
+                    initial_amp: pool_state.initial.amp,
+                    initial_gamma: pool_state.initial.gamma,
+                    future_time: pool_state.future_time,
+                    future_amp: pool_state.future.amp,
+                    future_gamma: pool_state.future.gamma,
+                    xcp_profit_real: Some(pool_state.price_state.xcp_profit_real),
+                }))
+            }
+        };
+
+        Ok(PairState {
+            asset_infos: self.asset_infos.clone(),
+            pool,
+        })
+    }
+
+    fn execute(&mut self, operation: &Operation) -> StdResult<Vec<Asset>> {
+        let balances = |pair: &Self| -> StdResult<Vec<Uint128>> {
+            pair.asset_infos
+                .iter()
+                .map(|info| pair.balance(info))
+                .collect()
+        };
+
+        match operation {
+            Operation::Swap { offer_asset } => {
+                let ask_info = self
+                    .asset_infos
+                    .iter()
+                    .find(|info| **info != offer_asset.info)
+                    .cloned()
+                    .ok_or_else(|| StdError::generic_err("No ask asset"))?;
+                let before = self.balance(&ask_info)?;
+                // A belief price of the maximum skips the spread assertion of the contracts
+                let msg = json!({
+                    "swap": {
+                        "offer_asset": offer_asset,
+                        "belief_price": Decimal::MAX,
+                        "max_spread": "0.5",
+                    }
+                });
+                self.execute_contract(
+                    self.pair.clone(),
+                    &msg,
+                    &funds(std::slice::from_ref(offer_asset)),
+                )?;
+
+                Ok(vec![Asset {
+                    amount: self.balance(&ask_info)? - before,
+                    info: ask_info,
+                }])
+            }
+            Operation::Provide {
+                assets,
+                slippage_tolerance,
+            } => {
+                let before = self.token_balance(&self.lp_token)?;
+                let msg = json!({
+                    "provide_liquidity": {
+                        "assets": assets,
+                        "slippage_tolerance": slippage_tolerance,
+                    }
+                });
+                self.execute_contract(self.pair.clone(), &msg, &funds(assets))?;
+
+                Ok(vec![Asset {
+                    info: AssetInfo::Token {
+                        contract_addr: self.lp_token.clone(),
+                    },
+                    amount: self.token_balance(&self.lp_token)? - before,
+                }])
+            }
+            Operation::Withdraw { lp_amount } => {
+                let before = balances(self)?;
+                let msg = json!({
+                    "send": {
+                        "contract": self.pair,
+                        "amount": lp_amount,
+                        "msg": to_json_binary(&json!({ "withdraw_liquidity": {} }))?,
+                    }
+                });
+                self.execute_contract(self.lp_token.clone(), &msg, &[])?;
+
+                Ok(balances(self)?
+                    .into_iter()
+                    .zip(before)
+                    .zip(&self.asset_infos)
+                    .map(|((after, before), info)| Asset {
+                        info: info.clone(),
+                        amount: after - before,
+                    })
+                    .collect())
+            }
+        }
+    }
+}
+
+/// The native tokens sent with a swap or a provide, sorted by denom as the bank expects.
+fn funds(assets: &[Asset]) -> Vec<Coin> {
+    let mut funds: Vec<Coin> = assets
+        .iter()
+        .filter(|asset| !asset.amount.is_zero())
+        .filter_map(|asset| match &asset.info {
+            AssetInfo::NativeToken { denom } => Some(coin(asset.amount.u128(), denom)),
+            AssetInfo::Token { .. } => None,
+        })
+        .collect();
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+    funds
+}
+
+/// Returns the basis points of a fee rate.
+fn bps(rate: Decimal) -> StdResult<u16> {
+    (rate * Uint128::new(10_000))
+        .u128()
+        .try_into()
+        .map_err(|_| StdError::generic_err(format!("The fee rate {rate} is over 100%")))
+}
+
+fn decimal(value: Decimal256) -> StdResult<Decimal> {
+    value
+        .try_into()
+        .map_err(|_| StdError::generic_err(format!("{value} is out of the range of a Decimal")))
+}
+
+/// Returns the error of a contract with its causes.
+fn contract_error(err: impl std::fmt::Display) -> StdError {
+    StdError::generic_err(format!("{err:#}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astroport::cosmwasm_ext::Decimal256Ext;
+    use crate::astroport::pair_concentrated::snapshot::PoolSnapshot;
+
+    /// Returns the pool state and the repeg parameters of a concentrated pair.
+    fn concentrated_state(
+        pair: &mut ContractPair,
+    ) -> StdResult<(pair_concentrated::pool::PoolState, RepegParams)> {
+        let PoolState::Concentrated(pool) = pair.state()?.pool else {
+            return Err(StdError::generic_err("Not a concentrated pair"));
+        };
+
+        Ok((*pool, pair.repeg_params()?))
+    }
+
+    /// Returns the state of a concentrated pool of 2:1 balances at the price scale of 2.
+    fn concentrated_pair_state() -> PairState {
+        let amp = Decimal::from_ratio(40u8, 1u8);
+        let gamma = Decimal::from_ratio(145u8, 1_000_000u32);
+        PairState {
+            asset_infos: ["uluna", "uusdc"]
+                .into_iter()
+                .map(|denom| AssetInfo::NativeToken {
+                    denom: denom.to_string(),
+                })
+                .collect(),
+            pool: PoolState::Concentrated(Box::new(pair_concentrated::pool::PoolState {
+                asset_amounts: vec![
+                    Decimal256::from_integer(2_000_000_000_000u128),
+                    Decimal256::from_integer(1_000_000_000_000u128),
+                ],
+                asset_precisions: vec![6, 6],
+                total_share: Uint128::zero(),
+                maker_fee_share: Decimal256::percent(50),
+                oracle_price: Decimal256::from_ratio(2u8, 1u8),
+                price_scale: Decimal256::from_ratio(2u8, 1u8),
+                fee_gamma: Decimal256::from_ratio(23u8, 100_000u32),
+                mid_fee: Decimal256::from_ratio(26u8, 10_000u32),
+                out_fee: Decimal256::from_ratio(45u8, 10_000u32),
+                block_time: 0,
+                initial_time: 0,
+                initial_amp: amp,
+                initial_gamma: gamma,
+                future_time: 0,
+                future_amp: amp,
+                future_gamma: gamma,
+                xcp_profit_real: None,
+            })),
+        }
+    }
+
+    #[test]
+    fn pairs_are_recreated_from_their_state() {
+        let mut pair = ContractPair::new(&concentrated_pair_state()).unwrap();
+        let state = pair.state().unwrap();
+
+        let mut recreated = ContractPair::new(&state).unwrap();
+
+        assert_eq!(recreated.state().unwrap(), state);
+    }
+
+    #[test]
+    fn swaps_repeg_the_price_scale_as_the_pair() {
+        let state = concentrated_pair_state();
+        let mut pair = ContractPair::new(&state).unwrap();
+
+        let mut repegs = 0;
+        for ask_ind in [1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1] {
+            let (pool, repeg) = concentrated_state(&mut pair).unwrap();
+            let offer_ind = 1 ^ ask_ind;
+            let offer_amount = Uint128::try_from(pool.asset_amounts[offer_ind].to_uint_floor())
+                .unwrap()
+                / Uint128::new(20);
+            let (_, pools, price_scale) = PoolSnapshot::new(
+                &pool.asset_amounts,
+                &pool.asset_precisions,
+                pool.maker_fee_share,
+                pool.oracle_price,
+                pool.price_scale,
+                pool.fee_gamma,
+                pool.mid_fee,
+                pool.out_fee,
+                pool.block_time,
+                pool.initial_time,
+                pool.initial_amp,
+                pool.initial_gamma,
+                pool.future_time,
+                pool.future_amp,
+                pool.future_gamma,
+            )
+            .unwrap()
+            .execute_swap(offer_amount, ask_ind, pool.total_share, Some(&repeg))
+            .unwrap();
+
+            pair.execute(&Operation::Swap {
+                offer_asset: Asset {
+                    info: state.asset_infos[offer_ind].clone(),
+                    amount: offer_amount,
+                },
+            })
+            .unwrap();
+
+            let (after, _) = concentrated_state(&mut pair).unwrap();
+            assert_eq!(pools, after.asset_amounts);
+            assert_eq!(price_scale, after.price_scale);
+            if price_scale != pool.price_scale {
+                repegs += 1;
+            }
+        }
+
+        assert!(repegs > 0, "The swaps didn't repeg the price scale");
+    }
+}
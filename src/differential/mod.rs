@@ -0,0 +1,290 @@
+//! A differential harness comparing the simulations with a reference implementation of the pair
+//! contracts, the Astroport pair contracts instantiated in cw-multi-test by [`ContractPair`].
+//!
+//! The reference is a [`ReferencePair`]. [`run`] sets up a reference pair for random pair states,
+//! executes random swaps, provides and withdraws on it, and compares the amounts received with
+//! the answers of [`Pair::query`] for the state of the pair before every operation. The first
+//! divergence is reported with the reproducing input, shrunk to the fewest and smallest
+//! operations:
+//!
+//! ```ignore
+//! run(ProptestConfig::with_cases(256), ContractPair::new)?;
+//! ```
+
+pub mod contracts;
+
+pub use contracts::ContractPair;
+
+use std::cell::RefCell;
+use std::fmt;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{from_json, Decimal, StdResult, Uint128};
+use proptest::prelude::*;
+use proptest::test_runner::{TestError, TestRunner};
+use serde::Serialize;
+
+use crate::astroport::asset::Asset;
+use crate::astroport::pair::{Pair, PairState, QueryMsg, SimulationResponse};
+use crate::astroport::pool::Pool;
+use crate::testing::pair_state;
+
+/// An operation executed on the reference pair.
+#[cw_serde]
+pub enum Operation {
+    Swap {
+        offer_asset: Asset,
+    },
+    Provide {
+        assets: Vec<Asset>,
+        slippage_tolerance: Option<Decimal>,
+    },
+    Withdraw {
+        lp_amount: Uint128,
+    },
+}
+
+/// A pair contract the simulations are compared with.
+pub trait ReferencePair {
+    /// Returns the current state of the pair.
+    fn state(&mut self) -> StdResult<PairState>;
+
+    /// Executes `operation` and returns the assets received: the ask asset of a swap, the lps of
+    /// a provide, which are compared by their amount only, or the assets of a withdraw.
+    fn execute(&mut self, operation: &Operation) -> StdResult<Vec<Asset>>;
+}
+
+/// The shape of an operation, made concrete against the state of the pair it is executed on.
+#[derive(Clone, Debug)]
+struct Step {
+    kind: u8,
+    ind: usize,
+    /// Basis points of the balances or of the total amount of lps
+    fractions: (u64, u64),
+}
+
+fn steps() -> impl Strategy<Value = Vec<Step>> {
+    prop::collection::vec(
+        (0u8..3, 0usize..2, (1u64..5_000, 1u64..5_000)).prop_map(|(kind, ind, fractions)| Step {
+            kind,
+            ind,
+            fractions,
+        }),
+        1..8,
+    )
+}
+
+/// Returns `fraction` basis points of `amount`, at least 1.
+fn part(amount: Uint128, fraction: u64) -> Uint128 {
+    amount
+        .multiply_ratio(fraction, 10_000u64)
+        .max(Uint128::one())
+}
+
+fn operation(state: &PairState, step: &Step) -> StdResult<Operation> {
+    let pool = Pool::new(state.pool.clone())?;
+    let balances = pool.asset_amounts()?;
+    let asset = |ind: usize, fraction: u64| Asset {
+        info: state.asset_infos[ind].clone(),
+        amount: part(balances[ind], fraction),
+    };
+
+    Ok(match step.kind {
+        0 => Operation::Swap {
+            offer_asset: asset(step.ind, step.fractions.0),
+        },
+        1 => Operation::Provide {
+            assets: vec![asset(0, step.fractions.0), asset(1, step.fractions.1)],
+            slippage_tolerance: None,
+        },
+        _ => Operation::Withdraw {
+            lp_amount: part(pool.total_share(), step.fractions.0),
+        },
+    })
+}
+
+/// Returns the amounts the simulation expects `operation` to return against `state`.
+fn simulate(state: &PairState, operation: &Operation) -> StdResult<Vec<Uint128>> {
+    let pair = Pair::new(state.clone())?;
+    match operation {
+        Operation::Swap { offer_asset } => {
+            let msg = QueryMsg::Simulation {
+                offer_asset: offer_asset.clone(),
+                ask_asset_info: None,
+            };
+            let response: SimulationResponse = from_json(&pair.query(msg)?)?;
+            Ok(vec![response.return_amount])
+        }
+        Operation::Provide {
+            assets,
+            slippage_tolerance,
+        } => {
+            let msg = QueryMsg::SimulateProvide {
+                assets: assets.clone(),
+                slippage_tolerance: *slippage_tolerance,
+            };
+            Ok(vec![from_json(&pair.query(msg)?)?])
+        }
+        Operation::Withdraw { lp_amount } => {
+            let msg = QueryMsg::SimulateWithdraw {
+                lp_amount: *lp_amount,
+            };
+            let assets: Vec<Asset> = from_json(&pair.query(msg)?)?;
+            Ok(assets.into_iter().map(|asset| asset.amount).collect())
+        }
+    }
+}
+
+/// A divergence between the simulation and the reference pair.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Divergence {
+    /// The pair state the reference was set up with
+    pub state: PairState,
+    /// The operations executed, the last one diverging
+    pub operations: Vec<Operation>,
+    /// The state of the reference before the last operation
+    pub state_before: PairState,
+    /// The amounts returned by the simulation, or its error
+    pub expected: Result<Vec<Uint128>, String>,
+    /// The amounts received from the reference, or its error
+    pub actual: Result<Vec<Uint128>, String>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Divergence after {} operations", self.operations.len())?;
+        writeln!(f, "  state: {}", json(&self.state))?;
+        writeln!(f, "  operations: {}", json(&self.operations))?;
+        writeln!(
+            f,
+            "  state before the last operation: {}",
+            json(&self.state_before)
+        )?;
+        writeln!(f, "  expected: {:?}", self.expected)?;
+        write!(f, "  actual: {:?}", self.actual)
+    }
+}
+
+fn json(value: &impl Serialize) -> String {
+    serde_json::to_string(value).unwrap_or_else(|err| err.to_string())
+}
+
+/// Executes the operations of `steps` on a reference pair set up for `state`, returning the
+/// first divergence if any.
+fn check<R, F>(setup: &mut F, state: &PairState, steps: &[Step]) -> StdResult<Option<Divergence>>
+where
+    R: ReferencePair,
+    F: FnMut(&PairState) -> StdResult<R>,
+{
+    let mut reference = setup(state)?;
+    let mut operations = vec![];
+    for step in steps {
+        let state_before = reference.state()?;
+        let operation = operation(&state_before, step)?;
+        operations.push(operation.clone());
+
+        let expected = simulate(&state_before, &operation).map_err(|err| err.to_string());
+        let actual = reference
+            .execute(&operation)
+            .map(|assets| assets.into_iter().map(|asset| asset.amount).collect())
+            .map_err(|err| err.to_string());
+
+        // The errors are worded differently, only their presence is compared
+        let diverges = match (&expected, &actual) {
+            (Ok(expected), Ok(actual)) => expected != actual,
+            (Err(_), Err(_)) => false,
+            _ => true,
+        };
+        if diverges {
+            return Ok(Some(Divergence {
+                state: state.clone(),
+                operations,
+                state_before,
+                expected,
+                actual,
+            }));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Compares the simulations with the reference pairs built by `setup` for random pair states.
+///
+/// Fails with the divergence of the smallest input found, or with the error of `setup` or of the
+/// reference when the pair state can't be queried.
+pub fn run<R, F>(config: ProptestConfig, setup: F) -> Result<(), String>
+where
+    R: ReferencePair,
+    F: FnMut(&PairState) -> StdResult<R>,
+{
+    let setup = RefCell::new(setup);
+    let mut runner = TestRunner::new(config);
+    let result = runner.run(&(pair_state(), steps()), |(state, steps)| {
+        match check(&mut *setup.borrow_mut(), &state, &steps) {
+            Ok(None) => Ok(()),
+            Ok(Some(divergence)) => Err(TestCaseError::fail(divergence.to_string())),
+            Err(err) => Err(TestCaseError::fail(format!("Reference error: {}", err))),
+        }
+    });
+
+    match result {
+        Ok(()) => Ok(()),
+        Err(TestError::Fail(reason, _)) => Err(reason.to_string()),
+        Err(TestError::Abort(reason)) => Err(reason.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A reference answering with the simulations of its initial state, optionally off by one.
+    struct Simulated {
+        state: PairState,
+        off_by_one: bool,
+    }
+
+    impl ReferencePair for Simulated {
+        fn state(&mut self) -> StdResult<PairState> {
+            Ok(self.state.clone())
+        }
+
+        fn execute(&mut self, operation: &Operation) -> StdResult<Vec<Asset>> {
+            let amounts = simulate(&self.state, operation)?;
+            Ok(amounts
+                .into_iter()
+                .map(|amount| Asset {
+                    info: self.state.asset_infos[0].clone(),
+                    amount: amount + Uint128::from(self.off_by_one as u8),
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn contract_pairs_match_the_simulations() {
+        let result = run(ProptestConfig::with_cases(64), ContractPair::new);
+        if let Err(report) = result {
+            panic!("{}", report);
+        }
+    }
+
+    #[test]
+    fn divergence_is_reported_with_its_input() {
+        let result = run(ProptestConfig::with_cases(32), |state| {
+            Ok(Simulated {
+                state: state.clone(),
+                off_by_one: true,
+            })
+        });
+
+        let report = result.unwrap_err();
+        assert!(
+            report.starts_with("Divergence after 1 operations"),
+            "{}",
+            report
+        );
+        assert!(report.contains("\"asset_infos\""), "{}", report);
+    }
+}
@@ -0,0 +1,287 @@
+//! Conversions of the values crossing the wasm boundary in the object API.
+//!
+//! Integers, e.g. amounts, are accepted as strings, bigints or safe integers and decimals as
+//! strings. Arrays and objects are accepted either as JS values or as their JSON representation,
+//! so the arguments of the string API can be passed as well.
+//!
+//! The TypeScript declarations of the results and the inputs are generated from the Rust types.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use js_sys::{Array, BigInt, Number, Object};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+use wasm_bindgen::prelude::*;
+
+/// The types the generated declarations refer to, see the module documentation.
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &str = r#"
+/** An amount as a decimal string */
+export type Uint128 = string;
+/** A decimal with 18 fractional digits as a string */
+export type Decimal = string;
+/** A 256-bit decimal with 18 fractional digits as a string */
+export type Decimal256 = string;
+/** An integer, e.g. an amount or an index, as a string, a bigint or a safe integer */
+export type IntegerInput = string | bigint | number;
+/** An input object whose amounts and decimals may also be passed as bigints */
+export type Input<T> = T extends string
+  ? string | bigint
+  : T extends (infer U)[]
+  ? Input<U>[]
+  : T extends object
+  ? { [K in keyof T]: Input<T[K]> }
+  : T;
+"#;
+
+fn invalid(name: &str, err: impl Display) -> String {
+    format!("Invalid {}: {}", name, err)
+}
+
+/// Parses the string representation of a scalar value.
+fn parse_str<T>(string: &str, name: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    string.parse().map_err(|e| invalid(name, e))
+}
+
+/// Deserializes the JSON representation of a value.
+fn parse_json<T: DeserializeOwned>(json: &str, name: &str) -> Result<T, String> {
+    serde_json::from_str(json).map_err(|e| invalid(name, e))
+}
+
+/// Returns the decimal representation of a string, a bigint or a safe integer.
+fn scalar_to_string(value: &JsValue) -> Option<String> {
+    if let Some(string) = value.as_string() {
+        Some(string)
+    } else if value.is_bigint() {
+        BigInt::from(value.clone())
+            .to_string(10)
+            .ok()
+            .map(String::from)
+    } else if Number::is_safe_integer(value) {
+        value.as_f64().map(|number| (number as i64).to_string())
+    } else {
+        None
+    }
+}
+
+/// Parses a scalar value, see the module documentation for the accepted representations.
+pub fn parse<T>(value: &JsValue, name: &str) -> Result<T, JsValue>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let string = scalar_to_string(value)
+        .ok_or_else(|| invalid(name, "expected a string, a bigint or a safe integer"))?;
+
+    Ok(parse_str(&string, name)?)
+}
+
+/// Parses an array of scalar values or its JSON representation.
+pub fn parse_vec<T>(value: &JsValue, name: &str) -> Result<Vec<T>, JsValue>
+where
+    T: FromStr + DeserializeOwned,
+    T::Err: Display,
+{
+    if let Some(json) = value.as_string() {
+        return Ok(parse_json(&json, name)?);
+    }
+
+    if !Array::is_array(value) {
+        return Err(invalid(name, "expected an array").into());
+    }
+
+    Array::from(value)
+        .iter()
+        .map(|item| parse(&item, name))
+        .collect()
+}
+
+/// Deserializes a JS value or its JSON representation. Bigints are treated as strings, thus
+/// they can be passed for amounts but not for fields such as timestamps.
+pub fn from_value<T: DeserializeOwned>(value: &JsValue, name: &str) -> Result<T, JsValue> {
+    if let Some(json) = value.as_string() {
+        return Ok(parse_json(&json, name)?);
+    }
+
+    let json = to_json(value).map_err(|e| invalid(name, e))?;
+
+    Ok(serde_json::from_value(json).map_err(|e| invalid(name, e))?)
+}
+
+fn to_json(value: &JsValue) -> Result<Value, String> {
+    if value.is_null() || value.is_undefined() {
+        Ok(Value::Null)
+    } else if let Some(boolean) = value.as_bool() {
+        Ok(Value::Bool(boolean))
+    } else if value.is_string() || value.is_bigint() {
+        Ok(Value::String(scalar_to_string(value).unwrap_or_default()))
+    } else if Number::is_safe_integer(value) {
+        Ok(Value::from(value.as_f64().unwrap_or_default() as i64))
+    } else if let Some(number) = value.as_f64() {
+        serde_json::Number::from_f64(number)
+            .map(Value::Number)
+            .ok_or_else(|| format!("unsupported number {}", number))
+    } else if Array::is_array(value) {
+        Array::from(value)
+            .iter()
+            .map(|item| to_json(&item))
+            .collect::<Result<Vec<Value>, String>>()
+            .map(Value::Array)
+    } else if value.is_object() {
+        Object::entries(&Object::from(value.clone()))
+            .iter()
+            .map(|entry| {
+                let entry = Array::from(&entry);
+                let key = entry.get(0).as_string().unwrap_or_default();
+                to_json(&entry.get(1)).map(|value| (key, value))
+            })
+            .collect::<Result<Map<String, Value>, String>>()
+            .map(Value::Object)
+    } else {
+        Err("unsupported value".to_string())
+    }
+}
+
+/// Converts a result into a plain JS object. Amounts and decimals stay strings and missing
+/// values become `null`, as in the JSON returned by the string API.
+pub fn to_value<T: Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{Decimal, Uint128};
+    use tsify::Tsify;
+
+    use crate::astroport::lib::batch::BatchItem;
+    use crate::astroport::lib::curve::Spacing;
+    use crate::astroport::pair_xyk::pool::PoolState;
+    use crate::astroport::{pair_concentrated, pair_stable};
+
+    /// Returns the fields of an interface declaration and whether they are optional.
+    fn declared_fields(decl: &str) -> Vec<(String, bool)> {
+        decl.lines()
+            .filter_map(|line| line.trim().strip_suffix(';')?.split_once(':'))
+            .map(|(name, _)| match name.strip_suffix('?') {
+                Some(name) => (name.to_string(), true),
+                None => (name.to_string(), false),
+            })
+            .collect()
+    }
+
+    /// Asserts that the JSON of `value` has the fields declared by `T`, except the optional
+    /// ones it skips. Optional fields can't be `null`.
+    fn assert_declared<T: Tsify + Serialize>(value: &T) {
+        let json = serde_json::to_value(value).unwrap();
+        let json = json.as_object().unwrap();
+        let keys = json.keys().cloned().collect::<Vec<_>>();
+
+        let declared = declared_fields(T::DECL);
+        for (name, _) in declared.iter().filter(|(_, optional)| *optional) {
+            assert_ne!(json.get(name), Some(&Value::Null), "{name} is null");
+        }
+        let declared = declared
+            .into_iter()
+            .filter(|(name, optional)| !optional || keys.contains(name))
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+        assert_eq!(keys, declared, "{}", T::DECL);
+    }
+
+    #[test]
+    fn scalars_are_parsed_from_their_strings() {
+        assert_eq!(
+            parse_str::<Uint128>("340282366920938463463374607431768211455", "amount"),
+            Ok(Uint128::MAX)
+        );
+        assert_eq!(parse_str::<usize>("1", "ask_ind"), Ok(1));
+        assert_eq!(
+            parse_str::<Decimal>("0.003", "total_fee_rate"),
+            Ok(Decimal::permille(3))
+        );
+    }
+
+    #[test]
+    fn invalid_scalars_are_reported_with_their_names() {
+        assert_eq!(
+            parse_str::<Uint128>("1.5", "amount"),
+            Err(
+                "Invalid amount: Generic error: Parsing u128: invalid digit found in string"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            parse_str::<usize>("-1", "ask_ind"),
+            Err("Invalid ask_ind: invalid digit found in string".to_string())
+        );
+    }
+
+    #[test]
+    fn values_are_parsed_from_their_json() {
+        assert_eq!(
+            parse_json::<Vec<Uint128>>(r#"["1000", "2000"]"#, "deposits"),
+            Ok(vec![Uint128::new(1_000), Uint128::new(2_000)])
+        );
+        assert_eq!(
+            parse_json::<PoolState>(
+                r#"{"asset_amounts":["1000","2000"],"total_share":"1414","total_fee_rate":"0.003"}"#,
+                "state"
+            ),
+            Ok(PoolState {
+                asset_amounts: vec![Uint128::new(1_000), Uint128::new(2_000)],
+                total_share: Uint128::new(1_414),
+                total_fee_rate: Decimal::permille(3),
+            })
+        );
+        assert_eq!(
+            parse_json::<Vec<Uint128>>("[1000, 2000]", "deposits"),
+            Err("Invalid deposits: invalid type: integer `1000`, expected string-encoded integer at line 1 column 5".to_string())
+        );
+        assert_eq!(
+            parse_json::<PoolState>(r#"{"asset_amounts":["1000","2000"]}"#, "state"),
+            Err("Invalid state: missing field `total_share` at line 1 column 33".to_string())
+        );
+    }
+
+    #[test]
+    fn declarations_match_the_json_of_the_values() {
+        let snapshot: Value =
+            serde_json::from_str(include_str!("../snapshots/concentrated/pool.json")).unwrap();
+        let mut state: pair_concentrated::pool::PoolState =
+            serde_json::from_value(snapshot["pool"]["concentrated"].clone()).unwrap();
+
+        assert_declared(&state);
+        state.xcp_profit_real = None;
+        assert_declared(&state);
+        assert_declared(&PoolState {
+            asset_amounts: vec![Uint128::new(1_000), Uint128::new(2_000)],
+            total_share: Uint128::new(1_414),
+            total_fee_rate: Decimal::permille(3),
+        });
+        assert_declared(&pair_stable::swap::empty_swap());
+    }
+
+    #[test]
+    fn declarations_of_enums_match_their_json() {
+        assert_eq!(Spacing::DECL, r#"export type Spacing = "linear" | "log";"#);
+        assert_eq!(serde_json::to_string(&Spacing::Log).unwrap(), r#""log""#);
+
+        assert_eq!(
+            BatchItem::<u8>::DECL,
+            "export type BatchItem<T> = { ok: T } | { error: string };"
+        );
+        assert_eq!(
+            serde_json::to_string(&[BatchItem::Ok(1u8), BatchItem::Error("Failed".to_string())])
+                .unwrap(),
+            r#"[{"ok":1},{"error":"Failed"}]"#
+        );
+    }
+}
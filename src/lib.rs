@@ -3,9 +3,24 @@ use std::str::FromStr;
 use wasm_bindgen::prelude::*;
 
 mod astroport;
+pub mod cli;
+#[cfg(any(test, feature = "differential"))]
+pub mod differential;
+mod js;
+#[cfg(feature = "mock")]
+pub mod mock;
+#[cfg(test)]
+mod properties;
+pub mod schema;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(test)]
+mod snapshots;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 mod utils;
 
-#[wasm_bindgen]
+#[wasm_bindgen(unchecked_return_type = "string")]
 pub fn concentrated_swap(
     offer_amount: &str,
     offer_asset_prec: &str,
@@ -116,25 +131,89 @@ pub fn concentrated_swap(
     Ok(JsValue::from_str(&json_result))
 }
 
-#[wasm_bindgen]
-pub fn concentrated_provide() -> Result<JsValue, JsValue> {
+#[wasm_bindgen(
+    js_name = concentratedSwap,
+    unchecked_return_type = "ConcentratedSwapSimulationResponse"
+)]
+#[allow(clippy::too_many_arguments)]
+pub fn concentrated_swap_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] offer_amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] offer_asset_prec: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_ind: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_asset_prec: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] maker_fee_share: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] oracle_price: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] price_scale: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] fee_gamma: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] mid_fee: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] out_fee: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] block_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] initial_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] inital_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] initial_gamma: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] future_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] future_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] future_gamma: JsValue,
+) -> Result<JsValue, JsValue> {
     utils::set_panic_hook();
 
-    let result = astroport::pair_concentrated::provide::simulate()
-        .map_err(|e| JsValue::from_str(&format!("Error while simulating provide: {}", e)))?;
+    let offer_amount = js::parse::<Decimal256>(&offer_amount, "offer_amount")?;
+    let offer_asset_prec = js::parse(&offer_asset_prec, "offer_asset_prec")?;
+    let ask_ind = js::parse(&ask_ind, "ask_ind")?;
+    let ask_asset_prec = js::parse(&ask_asset_prec, "ask_asset_prec")?;
+    let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+    let maker_fee_share = js::parse::<Decimal256>(&maker_fee_share, "maker_fee_share")?;
+    let oracle_price = js::parse::<Decimal256>(&oracle_price, "oracle_price")?;
+    let price_scale = js::parse::<Decimal256>(&price_scale, "price_scale")?;
+    let fee_gamma = js::parse::<Decimal256>(&fee_gamma, "fee_gamma")?;
+    let mid_fee = js::parse::<Decimal256>(&mid_fee, "mid_fee")?;
+    let out_fee = js::parse::<Decimal256>(&out_fee, "out_fee")?;
+    let block_time = js::parse(&block_time, "block_time")?;
+    let initial_time = js::parse(&initial_time, "initial_time")?;
+    let inital_amp = js::parse::<Decimal>(&inital_amp, "inital_amp")?;
+    let initial_gamma = js::parse::<Decimal>(&initial_gamma, "initial_gamma")?;
+    let future_time = js::parse(&future_time, "future_time")?;
+    let future_amp = js::parse::<Decimal>(&future_amp, "future_amp")?;
+    let future_gamma = js::parse::<Decimal>(&future_gamma, "future_gamma")?;
 
-    let json_result = serde_json::to_string(&result)
-        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+    let result = astroport::pair_concentrated::swap::simulate(
+        offer_amount,
+        offer_asset_prec,
+        ask_ind,
+        ask_asset_prec,
+        &asset_amounts,
+        maker_fee_share,
+        oracle_price,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
+        block_time,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating swap: {}", e)))?;
 
-    Ok(JsValue::from_str(&json_result))
+    js::to_value(&result)
 }
 
-#[wasm_bindgen]
-pub fn concentrated_withdraw() -> Result<JsValue, JsValue> {
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn concentrated_swap_batch(pools: &str, requests: &str) -> Result<JsValue, JsValue> {
     utils::set_panic_hook();
 
-    let result = astroport::pair_concentrated::withdraw::simulate()
-        .map_err(|e| JsValue::from_str(&format!("Error while simulating withdraw: {}", e)))?;
+    let pools = serde_json::from_str::<Vec<astroport::pair_concentrated::swap::BatchPool>>(pools)
+        .map_err(|e| JsValue::from_str(&format!("Invalid pools: {}", e)))?;
+
+    let requests =
+        serde_json::from_str::<Vec<astroport::pair_concentrated::swap::BatchSwapRequest>>(requests)
+            .map_err(|e| JsValue::from_str(&format!("Invalid requests: {}", e)))?;
+
+    let result = astroport::pair_concentrated::swap::simulate_batch(&pools, &requests);
 
     let json_result = serde_json::to_string(&result)
         .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
@@ -142,76 +221,120 @@ pub fn concentrated_withdraw() -> Result<JsValue, JsValue> {
     Ok(JsValue::from_str(&json_result))
 }
 
-#[wasm_bindgen]
-pub fn stable_swap(
-    offer_amount: &str,
-    offer_asset_prec: &str,
-    ask_ind: &str,
-    ask_asset_prec: &str,
-    asset_amounts: &str,
-    total_fee_rate: &str,
-    block_time: &str,
-    init_amp_time: &str,
-    init_amp: &str,
-    next_amp_time: &str,
-    next_amp: &str,
+#[wasm_bindgen(
+    js_name = concentratedSwapBatch,
+    unchecked_return_type = "BatchItem<ConcentratedSwapSimulationResponse>[]"
+)]
+pub fn concentrated_swap_batch_object(
+    #[wasm_bindgen(unchecked_param_type = "Input<ConcentratedBatchPool>[] | string")]
+    pools: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Input<ConcentratedBatchSwapRequest>[] | string")]
+    requests: JsValue,
 ) -> Result<JsValue, JsValue> {
     utils::set_panic_hook();
 
-    let offer_amount = Decimal256::from_str(offer_amount)
-        .map_err(|e| JsValue::from_str(&format!("Invalid offer_asset_prec: {}", e)))?;
+    let pools =
+        js::from_value::<Vec<astroport::pair_concentrated::swap::BatchPool>>(&pools, "pools")?;
 
-    let offer_asset_prec = offer_asset_prec
-        .parse()
-        .map_err(|e| JsValue::from_str(&format!("Invalid offer_asset_prec: {}", e)))?;
+    let requests = js::from_value::<Vec<astroport::pair_concentrated::swap::BatchSwapRequest>>(
+        &requests, "requests",
+    )?;
 
-    let ask_ind = ask_ind
-        .parse()
-        .map_err(|e| JsValue::from_str(&format!("Invalid ask_ind: {}", e)))?;
+    let result = astroport::pair_concentrated::swap::simulate_batch(&pools, &requests);
 
-    let ask_asset_prec = ask_asset_prec
-        .parse()
-        .map_err(|e| JsValue::from_str(&format!("Invalid ask_asset_prec: {}", e)))?;
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+#[allow(clippy::too_many_arguments)]
+pub fn concentrated_swap_to_price(
+    target_price: &str,
+    asset_amounts: &str,
+    asset_precisions: &str,
+    maker_fee_share: &str,
+    oracle_price: &str,
+    price_scale: &str,
+    fee_gamma: &str,
+    mid_fee: &str,
+    out_fee: &str,
+    block_time: &str,
+    initial_time: &str,
+    inital_amp: &str,
+    initial_gamma: &str,
+    future_time: &str,
+    future_amp: &str,
+    future_gamma: &str,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let target_price = Decimal::from_str(target_price)
+        .map_err(|e| JsValue::from_str(&format!("Invalid target_price: {}", e)))?;
 
     let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
         .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
 
-    let total_fee_rate = total_fee_rate
-        .parse()
-        .map_err(|e| JsValue::from_str(&format!("Invalid total_fee_rate: {}", e)))?;
+    let asset_precisions = serde_json::from_str::<Vec<u32>>(asset_precisions)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_precisions: {}", e)))?;
+
+    let maker_fee_share = Decimal256::from_str(maker_fee_share)
+        .map_err(|e| JsValue::from_str(&format!("Invalid maker_fee_share: {}", e)))?;
+
+    let oracle_price = Decimal256::from_str(oracle_price)
+        .map_err(|e| JsValue::from_str(&format!("Invalid oracle_price: {}", e)))?;
+
+    let price_scale = Decimal256::from_str(price_scale)
+        .map_err(|e| JsValue::from_str(&format!("Invalid price_scale: {}", e)))?;
+
+    let fee_gamma = Decimal256::from_str(fee_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid fee_gamma: {}", e)))?;
+
+    let mid_fee = Decimal256::from_str(mid_fee)
+        .map_err(|e| JsValue::from_str(&format!("Invalid mid_fee: {}", e)))?;
+
+    let out_fee = Decimal256::from_str(out_fee)
+        .map_err(|e| JsValue::from_str(&format!("Invalid out_fee: {}", e)))?;
 
     let block_time = block_time
         .parse()
         .map_err(|e| JsValue::from_str(&format!("Invalid block_time: {}", e)))?;
 
-    let init_amp_time = init_amp_time
+    let initial_time = initial_time
         .parse()
-        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp_time: {}", e)))?;
+        .map_err(|e| JsValue::from_str(&format!("Invalid initial_time: {}", e)))?;
 
-    let init_amp = init_amp
-        .parse()
-        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp: {}", e)))?;
+    let inital_amp = Decimal::from_str(inital_amp)
+        .map_err(|e| JsValue::from_str(&format!("Invalid inital_amp: {}", e)))?;
 
-    let next_amp_time = next_amp_time
-        .parse()
-        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp_time: {}", e)))?;
+    let initial_gamma = Decimal::from_str(initial_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid initial_gamma: {}", e)))?;
 
-    let next_amp = next_amp
+    let future_time = future_time
         .parse()
-        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp: {}", e)))?;
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_time: {}", e)))?;
 
-    let result = astroport::pair_stable::swap::simulate(
-        offer_amount,
-        offer_asset_prec,
-        ask_ind,
-        ask_asset_prec,
+    let future_amp = Decimal::from_str(future_amp)
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_amp: {}", e)))?;
+
+    let future_gamma = Decimal::from_str(future_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_gamma: {}", e)))?;
+
+    let result = astroport::pair_concentrated::swap::swap_to_price(
+        target_price,
         &asset_amounts,
-        total_fee_rate,
+        &asset_precisions,
+        maker_fee_share,
+        oracle_price,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
         block_time,
-        init_amp_time,
-        init_amp,
-        next_amp_time,
-        next_amp,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
     )
     .map_err(|e| JsValue::from_str(&format!("Error while simulating swap: {}", e)))?;
 
@@ -221,65 +344,163 @@ pub fn stable_swap(
     Ok(JsValue::from_str(&json_result))
 }
 
-#[wasm_bindgen]
-pub fn stable_provide(
-    deposits: &str,
+#[wasm_bindgen(
+    js_name = concentratedSwapToPrice,
+    unchecked_return_type = "ConcentratedSwapToPriceResponse"
+)]
+#[allow(clippy::too_many_arguments)]
+pub fn concentrated_swap_to_price_object(
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] target_price: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_precisions: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] maker_fee_share: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] oracle_price: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] price_scale: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] fee_gamma: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] mid_fee: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] out_fee: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] block_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] initial_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] inital_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] initial_gamma: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] future_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] future_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] future_gamma: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let target_price = js::parse::<Decimal>(&target_price, "target_price")?;
+    let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+    let asset_precisions = js::parse_vec::<u32>(&asset_precisions, "asset_precisions")?;
+    let maker_fee_share = js::parse::<Decimal256>(&maker_fee_share, "maker_fee_share")?;
+    let oracle_price = js::parse::<Decimal256>(&oracle_price, "oracle_price")?;
+    let price_scale = js::parse::<Decimal256>(&price_scale, "price_scale")?;
+    let fee_gamma = js::parse::<Decimal256>(&fee_gamma, "fee_gamma")?;
+    let mid_fee = js::parse::<Decimal256>(&mid_fee, "mid_fee")?;
+    let out_fee = js::parse::<Decimal256>(&out_fee, "out_fee")?;
+    let block_time = js::parse(&block_time, "block_time")?;
+    let initial_time = js::parse(&initial_time, "initial_time")?;
+    let inital_amp = js::parse::<Decimal>(&inital_amp, "inital_amp")?;
+    let initial_gamma = js::parse::<Decimal>(&initial_gamma, "initial_gamma")?;
+    let future_time = js::parse(&future_time, "future_time")?;
+    let future_amp = js::parse::<Decimal>(&future_amp, "future_amp")?;
+    let future_gamma = js::parse::<Decimal>(&future_gamma, "future_gamma")?;
+
+    let result = astroport::pair_concentrated::swap::swap_to_price(
+        target_price,
+        &asset_amounts,
+        &asset_precisions,
+        maker_fee_share,
+        oracle_price,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
+        block_time,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating swap: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+#[allow(clippy::too_many_arguments)]
+pub fn concentrated_depth(
+    price_impacts: &str,
     asset_amounts: &str,
     asset_precisions: &str,
-    total_share: &str,
+    maker_fee_share: &str,
+    oracle_price: &str,
+    price_scale: &str,
+    fee_gamma: &str,
+    mid_fee: &str,
+    out_fee: &str,
     block_time: &str,
-    init_amp_time: &str,
-    init_amp: &str,
-    next_amp_time: &str,
-    next_amp: &str,
+    initial_time: &str,
+    inital_amp: &str,
+    initial_gamma: &str,
+    future_time: &str,
+    future_amp: &str,
+    future_gamma: &str,
 ) -> Result<JsValue, JsValue> {
     utils::set_panic_hook();
 
-    let deposits = serde_json::from_str::<Vec<Decimal256>>(deposits)
-        .map_err(|e| JsValue::from_str(&format!("Invalid deposits: {}", e)))?;
+    let price_impacts = serde_json::from_str::<Vec<Decimal>>(price_impacts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid price_impacts: {}", e)))?;
 
     let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
         .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
 
-    let asset_precisions = serde_json::from_str::<Vec<u8>>(asset_precisions)
+    let asset_precisions = serde_json::from_str::<Vec<u32>>(asset_precisions)
         .map_err(|e| JsValue::from_str(&format!("Invalid asset_precisions: {}", e)))?;
 
-    let total_share = total_share
-        .parse()
-        .map_err(|e| JsValue::from_str(&format!("Invalid total_share: {}", e)))?;
+    let maker_fee_share = Decimal256::from_str(maker_fee_share)
+        .map_err(|e| JsValue::from_str(&format!("Invalid maker_fee_share: {}", e)))?;
+
+    let oracle_price = Decimal256::from_str(oracle_price)
+        .map_err(|e| JsValue::from_str(&format!("Invalid oracle_price: {}", e)))?;
+
+    let price_scale = Decimal256::from_str(price_scale)
+        .map_err(|e| JsValue::from_str(&format!("Invalid price_scale: {}", e)))?;
+
+    let fee_gamma = Decimal256::from_str(fee_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid fee_gamma: {}", e)))?;
+
+    let mid_fee = Decimal256::from_str(mid_fee)
+        .map_err(|e| JsValue::from_str(&format!("Invalid mid_fee: {}", e)))?;
+
+    let out_fee = Decimal256::from_str(out_fee)
+        .map_err(|e| JsValue::from_str(&format!("Invalid out_fee: {}", e)))?;
 
     let block_time = block_time
         .parse()
         .map_err(|e| JsValue::from_str(&format!("Invalid block_time: {}", e)))?;
 
-    let init_amp_time = init_amp_time
+    let initial_time = initial_time
         .parse()
-        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp_time: {}", e)))?;
+        .map_err(|e| JsValue::from_str(&format!("Invalid initial_time: {}", e)))?;
 
-    let init_amp = init_amp
-        .parse()
-        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp: {}", e)))?;
+    let inital_amp = Decimal::from_str(inital_amp)
+        .map_err(|e| JsValue::from_str(&format!("Invalid inital_amp: {}", e)))?;
 
-    let next_amp_time = next_amp_time
-        .parse()
-        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp_time: {}", e)))?;
+    let initial_gamma = Decimal::from_str(initial_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid initial_gamma: {}", e)))?;
 
-    let next_amp = next_amp
+    let future_time = future_time
         .parse()
-        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp: {}", e)))?;
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_time: {}", e)))?;
 
-    let result = astroport::pair_stable::provide::simulate(
-        &deposits,
+    let future_amp = Decimal::from_str(future_amp)
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_amp: {}", e)))?;
+
+    let future_gamma = Decimal::from_str(future_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_gamma: {}", e)))?;
+
+    let result = astroport::pair_concentrated::swap::simulate_depth(
+        &price_impacts,
         &asset_amounts,
         &asset_precisions,
-        total_share,
+        maker_fee_share,
+        oracle_price,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
         block_time,
-        init_amp_time,
-        init_amp,
-        next_amp_time,
-        next_amp,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
     )
-    .map_err(|e| JsValue::from_str(&format!("Error while simulating provide: {}", e)))?;
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating depth: {}", e)))?;
 
     let json_result = serde_json::to_string(&result)
         .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
@@ -287,17 +508,3819 @@ pub fn stable_provide(
     Ok(JsValue::from_str(&json_result))
 }
 
-#[wasm_bindgen]
-pub fn stable_withdraw(
+#[wasm_bindgen(js_name = concentratedDepth, unchecked_return_type = "DepthResponse[]")]
+#[allow(clippy::too_many_arguments)]
+pub fn concentrated_depth_object(
+    #[wasm_bindgen(unchecked_param_type = "Decimal[] | string")] price_impacts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_precisions: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] maker_fee_share: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] oracle_price: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] price_scale: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] fee_gamma: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] mid_fee: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] out_fee: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] block_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] initial_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] inital_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] initial_gamma: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] future_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] future_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] future_gamma: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let price_impacts = js::parse_vec::<Decimal>(&price_impacts, "price_impacts")?;
+    let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+    let asset_precisions = js::parse_vec::<u32>(&asset_precisions, "asset_precisions")?;
+    let maker_fee_share = js::parse::<Decimal256>(&maker_fee_share, "maker_fee_share")?;
+    let oracle_price = js::parse::<Decimal256>(&oracle_price, "oracle_price")?;
+    let price_scale = js::parse::<Decimal256>(&price_scale, "price_scale")?;
+    let fee_gamma = js::parse::<Decimal256>(&fee_gamma, "fee_gamma")?;
+    let mid_fee = js::parse::<Decimal256>(&mid_fee, "mid_fee")?;
+    let out_fee = js::parse::<Decimal256>(&out_fee, "out_fee")?;
+    let block_time = js::parse(&block_time, "block_time")?;
+    let initial_time = js::parse(&initial_time, "initial_time")?;
+    let inital_amp = js::parse::<Decimal>(&inital_amp, "inital_amp")?;
+    let initial_gamma = js::parse::<Decimal>(&initial_gamma, "initial_gamma")?;
+    let future_time = js::parse(&future_time, "future_time")?;
+    let future_amp = js::parse::<Decimal>(&future_amp, "future_amp")?;
+    let future_gamma = js::parse::<Decimal>(&future_gamma, "future_gamma")?;
+
+    let result = astroport::pair_concentrated::swap::simulate_depth(
+        &price_impacts,
+        &asset_amounts,
+        &asset_precisions,
+        maker_fee_share,
+        oracle_price,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
+        block_time,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating depth: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+#[allow(clippy::too_many_arguments)]
+pub fn concentrated_ladder(
+    price_step: &str,
+    levels: &str,
+    asset_amounts: &str,
+    asset_precisions: &str,
+    maker_fee_share: &str,
+    oracle_price: &str,
+    price_scale: &str,
+    fee_gamma: &str,
+    mid_fee: &str,
+    out_fee: &str,
+    block_time: &str,
+    initial_time: &str,
+    inital_amp: &str,
+    initial_gamma: &str,
+    future_time: &str,
+    future_amp: &str,
+    future_gamma: &str,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let price_step = Decimal::from_str(price_step)
+        .map_err(|e| JsValue::from_str(&format!("Invalid price_step: {}", e)))?;
+
+    let levels = levels
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid levels: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let asset_precisions = serde_json::from_str::<Vec<u32>>(asset_precisions)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_precisions: {}", e)))?;
+
+    let maker_fee_share = Decimal256::from_str(maker_fee_share)
+        .map_err(|e| JsValue::from_str(&format!("Invalid maker_fee_share: {}", e)))?;
+
+    let oracle_price = Decimal256::from_str(oracle_price)
+        .map_err(|e| JsValue::from_str(&format!("Invalid oracle_price: {}", e)))?;
+
+    let price_scale = Decimal256::from_str(price_scale)
+        .map_err(|e| JsValue::from_str(&format!("Invalid price_scale: {}", e)))?;
+
+    let fee_gamma = Decimal256::from_str(fee_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid fee_gamma: {}", e)))?;
+
+    let mid_fee = Decimal256::from_str(mid_fee)
+        .map_err(|e| JsValue::from_str(&format!("Invalid mid_fee: {}", e)))?;
+
+    let out_fee = Decimal256::from_str(out_fee)
+        .map_err(|e| JsValue::from_str(&format!("Invalid out_fee: {}", e)))?;
+
+    let block_time = block_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid block_time: {}", e)))?;
+
+    let initial_time = initial_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid initial_time: {}", e)))?;
+
+    let inital_amp = Decimal::from_str(inital_amp)
+        .map_err(|e| JsValue::from_str(&format!("Invalid inital_amp: {}", e)))?;
+
+    let initial_gamma = Decimal::from_str(initial_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid initial_gamma: {}", e)))?;
+
+    let future_time = future_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_time: {}", e)))?;
+
+    let future_amp = Decimal::from_str(future_amp)
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_amp: {}", e)))?;
+
+    let future_gamma = Decimal::from_str(future_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_gamma: {}", e)))?;
+
+    let result = astroport::pair_concentrated::swap::simulate_ladder(
+        price_step,
+        levels,
+        &asset_amounts,
+        &asset_precisions,
+        maker_fee_share,
+        oracle_price,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
+        block_time,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating ladder: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(js_name = concentratedLadder, unchecked_return_type = "LadderResponse")]
+#[allow(clippy::too_many_arguments)]
+pub fn concentrated_ladder_object(
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] price_step: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] levels: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_precisions: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] maker_fee_share: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] oracle_price: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] price_scale: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] fee_gamma: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] mid_fee: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] out_fee: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] block_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] initial_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] inital_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] initial_gamma: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] future_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] future_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] future_gamma: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let price_step = js::parse::<Decimal>(&price_step, "price_step")?;
+    let levels = js::parse(&levels, "levels")?;
+    let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+    let asset_precisions = js::parse_vec::<u32>(&asset_precisions, "asset_precisions")?;
+    let maker_fee_share = js::parse::<Decimal256>(&maker_fee_share, "maker_fee_share")?;
+    let oracle_price = js::parse::<Decimal256>(&oracle_price, "oracle_price")?;
+    let price_scale = js::parse::<Decimal256>(&price_scale, "price_scale")?;
+    let fee_gamma = js::parse::<Decimal256>(&fee_gamma, "fee_gamma")?;
+    let mid_fee = js::parse::<Decimal256>(&mid_fee, "mid_fee")?;
+    let out_fee = js::parse::<Decimal256>(&out_fee, "out_fee")?;
+    let block_time = js::parse(&block_time, "block_time")?;
+    let initial_time = js::parse(&initial_time, "initial_time")?;
+    let inital_amp = js::parse::<Decimal>(&inital_amp, "inital_amp")?;
+    let initial_gamma = js::parse::<Decimal>(&initial_gamma, "initial_gamma")?;
+    let future_time = js::parse(&future_time, "future_time")?;
+    let future_amp = js::parse::<Decimal>(&future_amp, "future_amp")?;
+    let future_gamma = js::parse::<Decimal>(&future_gamma, "future_gamma")?;
+
+    let result = astroport::pair_concentrated::swap::simulate_ladder(
+        price_step,
+        levels,
+        &asset_amounts,
+        &asset_precisions,
+        maker_fee_share,
+        oracle_price,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
+        block_time,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating ladder: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+#[allow(clippy::too_many_arguments)]
+pub fn concentrated_sample_curve(
+    min_offer_amount: &str,
+    max_offer_amount: &str,
+    points: &str,
+    spacing: &str,
+    ask_ind: &str,
+    asset_amounts: &str,
+    asset_precisions: &str,
+    maker_fee_share: &str,
+    oracle_price: &str,
+    price_scale: &str,
+    fee_gamma: &str,
+    mid_fee: &str,
+    out_fee: &str,
+    block_time: &str,
+    initial_time: &str,
+    inital_amp: &str,
+    initial_gamma: &str,
+    future_time: &str,
+    future_amp: &str,
+    future_gamma: &str,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let min_offer_amount = min_offer_amount
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid min_offer_amount: {}", e)))?;
+
+    let max_offer_amount = max_offer_amount
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid max_offer_amount: {}", e)))?;
+
+    let points = points
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid points: {}", e)))?;
+
+    let spacing = spacing
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid spacing: {}", e)))?;
+
+    let ask_ind = ask_ind
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid ask_ind: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let asset_precisions = serde_json::from_str::<Vec<u32>>(asset_precisions)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_precisions: {}", e)))?;
+
+    let maker_fee_share = Decimal256::from_str(maker_fee_share)
+        .map_err(|e| JsValue::from_str(&format!("Invalid maker_fee_share: {}", e)))?;
+
+    let oracle_price = Decimal256::from_str(oracle_price)
+        .map_err(|e| JsValue::from_str(&format!("Invalid oracle_price: {}", e)))?;
+
+    let price_scale = Decimal256::from_str(price_scale)
+        .map_err(|e| JsValue::from_str(&format!("Invalid price_scale: {}", e)))?;
+
+    let fee_gamma = Decimal256::from_str(fee_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid fee_gamma: {}", e)))?;
+
+    let mid_fee = Decimal256::from_str(mid_fee)
+        .map_err(|e| JsValue::from_str(&format!("Invalid mid_fee: {}", e)))?;
+
+    let out_fee = Decimal256::from_str(out_fee)
+        .map_err(|e| JsValue::from_str(&format!("Invalid out_fee: {}", e)))?;
+
+    let block_time = block_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid block_time: {}", e)))?;
+
+    let initial_time = initial_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid initial_time: {}", e)))?;
+
+    let inital_amp = Decimal::from_str(inital_amp)
+        .map_err(|e| JsValue::from_str(&format!("Invalid inital_amp: {}", e)))?;
+
+    let initial_gamma = Decimal::from_str(initial_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid initial_gamma: {}", e)))?;
+
+    let future_time = future_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_time: {}", e)))?;
+
+    let future_amp = Decimal::from_str(future_amp)
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_amp: {}", e)))?;
+
+    let future_gamma = Decimal::from_str(future_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_gamma: {}", e)))?;
+
+    let result = astroport::pair_concentrated::swap::sample_curve(
+        min_offer_amount,
+        max_offer_amount,
+        points,
+        spacing,
+        ask_ind,
+        &asset_amounts,
+        &asset_precisions,
+        maker_fee_share,
+        oracle_price,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
+        block_time,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while sampling curve: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(js_name = concentratedSampleCurve, unchecked_return_type = "CurvePoint[]")]
+#[allow(clippy::too_many_arguments)]
+pub fn concentrated_sample_curve_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] min_offer_amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] max_offer_amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] points: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Spacing")] spacing: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_ind: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_precisions: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] maker_fee_share: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] oracle_price: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] price_scale: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] fee_gamma: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] mid_fee: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] out_fee: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] block_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] initial_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] inital_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] initial_gamma: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] future_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] future_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] future_gamma: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let min_offer_amount = js::parse(&min_offer_amount, "min_offer_amount")?;
+    let max_offer_amount = js::parse(&max_offer_amount, "max_offer_amount")?;
+    let points = js::parse(&points, "points")?;
+    let spacing = js::parse(&spacing, "spacing")?;
+    let ask_ind = js::parse(&ask_ind, "ask_ind")?;
+    let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+    let asset_precisions = js::parse_vec::<u32>(&asset_precisions, "asset_precisions")?;
+    let maker_fee_share = js::parse::<Decimal256>(&maker_fee_share, "maker_fee_share")?;
+    let oracle_price = js::parse::<Decimal256>(&oracle_price, "oracle_price")?;
+    let price_scale = js::parse::<Decimal256>(&price_scale, "price_scale")?;
+    let fee_gamma = js::parse::<Decimal256>(&fee_gamma, "fee_gamma")?;
+    let mid_fee = js::parse::<Decimal256>(&mid_fee, "mid_fee")?;
+    let out_fee = js::parse::<Decimal256>(&out_fee, "out_fee")?;
+    let block_time = js::parse(&block_time, "block_time")?;
+    let initial_time = js::parse(&initial_time, "initial_time")?;
+    let inital_amp = js::parse::<Decimal>(&inital_amp, "inital_amp")?;
+    let initial_gamma = js::parse::<Decimal>(&initial_gamma, "initial_gamma")?;
+    let future_time = js::parse(&future_time, "future_time")?;
+    let future_amp = js::parse::<Decimal>(&future_amp, "future_amp")?;
+    let future_gamma = js::parse::<Decimal>(&future_gamma, "future_gamma")?;
+
+    let result = astroport::pair_concentrated::swap::sample_curve(
+        min_offer_amount,
+        max_offer_amount,
+        points,
+        spacing,
+        ask_ind,
+        &asset_amounts,
+        &asset_precisions,
+        maker_fee_share,
+        oracle_price,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
+        block_time,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while sampling curve: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+/// A pool state with the invariant computed once, for repeated quoting against the same
+/// balances.
+#[wasm_bindgen]
+pub struct ConcentratedPoolSnapshot {
+    snapshot: astroport::pair_concentrated::snapshot::PoolSnapshot,
+}
+
+#[wasm_bindgen]
+impl ConcentratedPoolSnapshot {
+    #[wasm_bindgen(constructor)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        asset_amounts: &str,
+        asset_precisions: &str,
+        maker_fee_share: &str,
+        oracle_price: &str,
+        price_scale: &str,
+        fee_gamma: &str,
+        mid_fee: &str,
+        out_fee: &str,
+        block_time: &str,
+        initial_time: &str,
+        inital_amp: &str,
+        initial_gamma: &str,
+        future_time: &str,
+        future_amp: &str,
+        future_gamma: &str,
+    ) -> Result<ConcentratedPoolSnapshot, JsValue> {
+        utils::set_panic_hook();
+
+        let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
+            .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+        let asset_precisions = serde_json::from_str::<Vec<u32>>(asset_precisions)
+            .map_err(|e| JsValue::from_str(&format!("Invalid asset_precisions: {}", e)))?;
+
+        let maker_fee_share = Decimal256::from_str(maker_fee_share)
+            .map_err(|e| JsValue::from_str(&format!("Invalid maker_fee_share: {}", e)))?;
+
+        let oracle_price = Decimal256::from_str(oracle_price)
+            .map_err(|e| JsValue::from_str(&format!("Invalid oracle_price: {}", e)))?;
+
+        let price_scale = Decimal256::from_str(price_scale)
+            .map_err(|e| JsValue::from_str(&format!("Invalid price_scale: {}", e)))?;
+
+        let fee_gamma = Decimal256::from_str(fee_gamma)
+            .map_err(|e| JsValue::from_str(&format!("Invalid fee_gamma: {}", e)))?;
+
+        let mid_fee = Decimal256::from_str(mid_fee)
+            .map_err(|e| JsValue::from_str(&format!("Invalid mid_fee: {}", e)))?;
+
+        let out_fee = Decimal256::from_str(out_fee)
+            .map_err(|e| JsValue::from_str(&format!("Invalid out_fee: {}", e)))?;
+
+        let block_time = block_time
+            .parse()
+            .map_err(|e| JsValue::from_str(&format!("Invalid block_time: {}", e)))?;
+
+        let initial_time = initial_time
+            .parse()
+            .map_err(|e| JsValue::from_str(&format!("Invalid initial_time: {}", e)))?;
+
+        let inital_amp = Decimal::from_str(inital_amp)
+            .map_err(|e| JsValue::from_str(&format!("Invalid inital_amp: {}", e)))?;
+
+        let initial_gamma = Decimal::from_str(initial_gamma)
+            .map_err(|e| JsValue::from_str(&format!("Invalid initial_gamma: {}", e)))?;
+
+        let future_time = future_time
+            .parse()
+            .map_err(|e| JsValue::from_str(&format!("Invalid future_time: {}", e)))?;
+
+        let future_amp = Decimal::from_str(future_amp)
+            .map_err(|e| JsValue::from_str(&format!("Invalid future_amp: {}", e)))?;
+
+        let future_gamma = Decimal::from_str(future_gamma)
+            .map_err(|e| JsValue::from_str(&format!("Invalid future_gamma: {}", e)))?;
+
+        let snapshot = astroport::pair_concentrated::snapshot::PoolSnapshot::new(
+            &asset_amounts,
+            &asset_precisions,
+            maker_fee_share,
+            oracle_price,
+            price_scale,
+            fee_gamma,
+            mid_fee,
+            out_fee,
+            block_time,
+            initial_time,
+            inital_amp,
+            initial_gamma,
+            future_time,
+            future_amp,
+            future_gamma,
+        )
+        .map_err(|e| JsValue::from_str(&format!("Error while creating pool snapshot: {}", e)))?;
+
+        Ok(ConcentratedPoolSnapshot { snapshot })
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "string")]
+    pub fn swap(&self, offer_amount: &str, ask_ind: &str) -> Result<JsValue, JsValue> {
+        let offer_amount = offer_amount
+            .parse()
+            .map_err(|e| JsValue::from_str(&format!("Invalid offer_amount: {}", e)))?;
+
+        let ask_ind = ask_ind
+            .parse()
+            .map_err(|e| JsValue::from_str(&format!("Invalid ask_ind: {}", e)))?;
+
+        let result = self
+            .snapshot
+            .swap(offer_amount, ask_ind)
+            .map_err(|e| JsValue::from_str(&format!("Error while simulating swap: {}", e)))?;
+
+        let json_result = serde_json::to_string(&result)
+            .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+        Ok(JsValue::from_str(&json_result))
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "string")]
+    pub fn quote(&self, offer_amount: &str, ask_ind: &str) -> Result<JsValue, JsValue> {
+        let offer_amount = offer_amount
+            .parse()
+            .map_err(|e| JsValue::from_str(&format!("Invalid offer_amount: {}", e)))?;
+
+        let ask_ind = ask_ind
+            .parse()
+            .map_err(|e| JsValue::from_str(&format!("Invalid ask_ind: {}", e)))?;
+
+        let result = self
+            .snapshot
+            .quote(offer_amount, ask_ind)
+            .map_err(|e| JsValue::from_str(&format!("Error while quoting swap: {}", e)))?;
+
+        let json_result = serde_json::to_string(&result)
+            .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+        Ok(JsValue::from_str(&json_result))
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "string")]
+    pub fn spot_price(&self, ask_ind: &str) -> Result<JsValue, JsValue> {
+        let ask_ind = ask_ind
+            .parse()
+            .map_err(|e| JsValue::from_str(&format!("Invalid ask_ind: {}", e)))?;
+
+        let result = self
+            .snapshot
+            .spot_price(ask_ind)
+            .map_err(|e| JsValue::from_str(&format!("Error while computing spot price: {}", e)))?;
+
+        let json_result = serde_json::to_string(&result)
+            .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+        Ok(JsValue::from_str(&json_result))
+    }
+}
+
+/// A concentrated liquidity pool kept on the JS side, see [`XykPool`]. Amp, Gamma and the
+/// invariant are computed once per update.
+#[wasm_bindgen]
+pub struct ConcentratedPool {
+    pool: astroport::pair_concentrated::pool::Pool,
+}
+
+#[wasm_bindgen]
+impl ConcentratedPool {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        #[wasm_bindgen(unchecked_param_type = "Input<ConcentratedPoolState> | string")]
+        state: JsValue,
+    ) -> Result<ConcentratedPool, JsValue> {
+        utils::set_panic_hook();
+
+        let state =
+            js::from_value::<astroport::pair_concentrated::pool::PoolState>(&state, "state")?;
+
+        let pool = astroport::pair_concentrated::pool::Pool::new(state)
+            .map_err(|e| JsValue::from_str(&format!("Error while creating pool: {}", e)))?;
+
+        Ok(ConcentratedPool { pool })
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "ConcentratedPoolState")]
+    pub fn state(&self) -> Result<JsValue, JsValue> {
+        js::to_value(self.pool.state())
+    }
+
+    pub fn update(
+        &mut self,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+        #[wasm_bindgen(unchecked_optional_param_type = "IntegerInput")] total_share: Option<
+            JsValue,
+        >,
+    ) -> Result<(), JsValue> {
+        let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+
+        let total_share = total_share
+            .map(|total_share| js::parse(&total_share, "total_share"))
+            .transpose()?;
+
+        self.pool
+            .update(asset_amounts, total_share)
+            .map_err(|e| JsValue::from_str(&format!("Error while updating pool: {}", e)))
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "ConcentratedSwapSimulationResponse")]
+    pub fn swap(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput")] offer_amount: JsValue,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_ind: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let offer_amount = js::parse(&offer_amount, "offer_amount")?;
+
+        let ask_ind = js::parse(&ask_ind, "ask_ind")?;
+
+        let result = self
+            .pool
+            .swap(offer_amount, ask_ind)
+            .map_err(|e| JsValue::from_str(&format!("Error while simulating swap: {}", e)))?;
+
+        js::to_value(&result)
+    }
+
+    #[wasm_bindgen(
+        js_name = reverseSwap,
+        unchecked_return_type = "ConcentratedReverseSwapSimulationResponse"
+    )]
+    pub fn reverse_swap(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_amount: JsValue,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_ind: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let ask_amount = js::parse(&ask_amount, "ask_amount")?;
+
+        let ask_ind = js::parse(&ask_ind, "ask_ind")?;
+
+        let result = self.pool.reverse_swap(ask_amount, ask_ind).map_err(|e| {
+            JsValue::from_str(&format!("Error while simulating reverse swap: {}", e))
+        })?;
+
+        js::to_value(&result)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "ConcentratedProvideSimulationResponse")]
+    pub fn provide(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] deposits: JsValue,
+        #[wasm_bindgen(unchecked_optional_param_type = "Decimal")] slippage_tolerance: Option<
+            JsValue,
+        >,
+        #[wasm_bindgen(unchecked_optional_param_type = "IntegerInput")] min_lp_to_receive: Option<
+            JsValue,
+        >,
+    ) -> Result<JsValue, JsValue> {
+        let deposits = js::parse_vec::<Decimal256>(&deposits, "deposits")?;
+
+        let slippage_tolerance = slippage_tolerance
+            .map(|slippage_tolerance| js::parse(&slippage_tolerance, "slippage_tolerance"))
+            .transpose()?;
+
+        let min_lp_to_receive = min_lp_to_receive
+            .map(|min_lp_to_receive| js::parse(&min_lp_to_receive, "min_lp_to_receive"))
+            .transpose()?;
+
+        let result = self
+            .pool
+            .provide(&deposits, slippage_tolerance, min_lp_to_receive)
+            .map_err(|e| JsValue::from_str(&format!("Error while simulating provide: {}", e)))?;
+
+        js::to_value(&result)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "ConcentratedWithdrawSimulationResponse")]
+    pub fn withdraw(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput")] amount: JsValue,
+        #[wasm_bindgen(unchecked_optional_param_type = "IntegerInput[] | string")]
+        min_assets_to_receive: Option<JsValue>,
+    ) -> Result<JsValue, JsValue> {
+        let amount = js::parse(&amount, "amount")?;
+
+        let min_assets_to_receive = min_assets_to_receive
+            .map(|min_assets_to_receive| {
+                js::parse_vec::<Uint128>(&min_assets_to_receive, "min_assets_to_receive")
+            })
+            .transpose()?;
+
+        let result = self
+            .pool
+            .withdraw(amount, min_assets_to_receive)
+            .map_err(|e| JsValue::from_str(&format!("Error while simulating withdraw: {}", e)))?;
+
+        js::to_value(&result)
+    }
+
+    #[wasm_bindgen(js_name = spotPrice, unchecked_return_type = "Decimal")]
+    pub fn spot_price(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_ind: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let ask_ind = js::parse(&ask_ind, "ask_ind")?;
+
+        let result = self
+            .pool
+            .spot_price(ask_ind)
+            .map_err(|e| JsValue::from_str(&format!("Error while computing spot price: {}", e)))?;
+
+        js::to_value(&result)
+    }
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+#[allow(clippy::too_many_arguments)]
+pub fn concentrated_provide(
+    deposits: &str,
+    asset_amounts: &str,
+    asset_precisions: &str,
+    total_share: &str,
+    price_scale: &str,
+    fee_gamma: &str,
+    mid_fee: &str,
+    out_fee: &str,
+    block_time: &str,
+    initial_time: &str,
+    inital_amp: &str,
+    initial_gamma: &str,
+    future_time: &str,
+    future_amp: &str,
+    future_gamma: &str,
+    min_lp_to_receive: Option<String>,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let deposits = serde_json::from_str::<Vec<Decimal256>>(deposits)
+        .map_err(|e| JsValue::from_str(&format!("Invalid deposits: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let asset_precisions = serde_json::from_str::<Vec<u32>>(asset_precisions)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_precisions: {}", e)))?;
+
+    let total_share = total_share
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_share: {}", e)))?;
+
+    let price_scale = Decimal256::from_str(price_scale)
+        .map_err(|e| JsValue::from_str(&format!("Invalid price_scale: {}", e)))?;
+
+    let fee_gamma = Decimal256::from_str(fee_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid fee_gamma: {}", e)))?;
+
+    let mid_fee = Decimal256::from_str(mid_fee)
+        .map_err(|e| JsValue::from_str(&format!("Invalid mid_fee: {}", e)))?;
+
+    let out_fee = Decimal256::from_str(out_fee)
+        .map_err(|e| JsValue::from_str(&format!("Invalid out_fee: {}", e)))?;
+
+    let block_time = block_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid block_time: {}", e)))?;
+
+    let initial_time = initial_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid initial_time: {}", e)))?;
+
+    let inital_amp = Decimal::from_str(inital_amp)
+        .map_err(|e| JsValue::from_str(&format!("Invalid inital_amp: {}", e)))?;
+
+    let initial_gamma = Decimal::from_str(initial_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid initial_gamma: {}", e)))?;
+
+    let future_time = future_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_time: {}", e)))?;
+
+    let future_amp = Decimal::from_str(future_amp)
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_amp: {}", e)))?;
+
+    let future_gamma = Decimal::from_str(future_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_gamma: {}", e)))?;
+
+    let min_lp_to_receive = min_lp_to_receive
+        .map(|min_lp_to_receive| min_lp_to_receive.parse())
+        .transpose()
+        .map_err(|e| JsValue::from_str(&format!("Invalid min_lp_to_receive: {}", e)))?;
+
+    let result = astroport::pair_concentrated::provide::simulate(
+        &deposits,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
+        block_time,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+        None,
+        None,
+        min_lp_to_receive,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating provide: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(
+    js_name = concentratedProvide,
+    unchecked_return_type = "ConcentratedProvideSimulationResponse"
+)]
+#[allow(clippy::too_many_arguments)]
+pub fn concentrated_provide_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] deposits: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_precisions: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] total_share: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] price_scale: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] fee_gamma: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] mid_fee: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] out_fee: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] block_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] initial_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] inital_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] initial_gamma: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] future_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] future_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] future_gamma: JsValue,
+    #[wasm_bindgen(unchecked_optional_param_type = "IntegerInput")] min_lp_to_receive: Option<
+        JsValue,
+    >,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let deposits = js::parse_vec::<Decimal256>(&deposits, "deposits")?;
+    let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+    let asset_precisions = js::parse_vec::<u32>(&asset_precisions, "asset_precisions")?;
+    let total_share = js::parse(&total_share, "total_share")?;
+    let price_scale = js::parse::<Decimal256>(&price_scale, "price_scale")?;
+    let fee_gamma = js::parse::<Decimal256>(&fee_gamma, "fee_gamma")?;
+    let mid_fee = js::parse::<Decimal256>(&mid_fee, "mid_fee")?;
+    let out_fee = js::parse::<Decimal256>(&out_fee, "out_fee")?;
+    let block_time = js::parse(&block_time, "block_time")?;
+    let initial_time = js::parse(&initial_time, "initial_time")?;
+    let inital_amp = js::parse::<Decimal>(&inital_amp, "inital_amp")?;
+    let initial_gamma = js::parse::<Decimal>(&initial_gamma, "initial_gamma")?;
+    let future_time = js::parse(&future_time, "future_time")?;
+    let future_amp = js::parse::<Decimal>(&future_amp, "future_amp")?;
+    let future_gamma = js::parse::<Decimal>(&future_gamma, "future_gamma")?;
+
+    let min_lp_to_receive = min_lp_to_receive
+        .map(|min_lp_to_receive| js::parse(&min_lp_to_receive, "min_lp_to_receive"))
+        .transpose()?;
+
+    let result = astroport::pair_concentrated::provide::simulate(
+        &deposits,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
+        block_time,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+        None,
+        None,
+        min_lp_to_receive,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating provide: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+#[allow(clippy::too_many_arguments)]
+pub fn concentrated_reverse_provide(
+    share_amount: &str,
+    asset_amounts: &str,
+    asset_precisions: &str,
+    total_share: &str,
+    price_scale: &str,
+    fee_gamma: &str,
+    mid_fee: &str,
+    out_fee: &str,
+    block_time: &str,
+    initial_time: &str,
+    inital_amp: &str,
+    initial_gamma: &str,
+    future_time: &str,
+    future_amp: &str,
+    future_gamma: &str,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let share_amount = share_amount
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid share_amount: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let asset_precisions = serde_json::from_str::<Vec<u32>>(asset_precisions)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_precisions: {}", e)))?;
+
+    let total_share = total_share
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_share: {}", e)))?;
+
+    let price_scale = Decimal256::from_str(price_scale)
+        .map_err(|e| JsValue::from_str(&format!("Invalid price_scale: {}", e)))?;
+
+    let fee_gamma = Decimal256::from_str(fee_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid fee_gamma: {}", e)))?;
+
+    let mid_fee = Decimal256::from_str(mid_fee)
+        .map_err(|e| JsValue::from_str(&format!("Invalid mid_fee: {}", e)))?;
+
+    let out_fee = Decimal256::from_str(out_fee)
+        .map_err(|e| JsValue::from_str(&format!("Invalid out_fee: {}", e)))?;
+
+    let block_time = block_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid block_time: {}", e)))?;
+
+    let initial_time = initial_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid initial_time: {}", e)))?;
+
+    let inital_amp = Decimal::from_str(inital_amp)
+        .map_err(|e| JsValue::from_str(&format!("Invalid inital_amp: {}", e)))?;
+
+    let initial_gamma = Decimal::from_str(initial_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid initial_gamma: {}", e)))?;
+
+    let future_time = future_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_time: {}", e)))?;
+
+    let future_amp = Decimal::from_str(future_amp)
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_amp: {}", e)))?;
+
+    let future_gamma = Decimal::from_str(future_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_gamma: {}", e)))?;
+
+    let result = astroport::pair_concentrated::provide::simulate_reverse(
+        share_amount,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
+        block_time,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating provide: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(
+    js_name = concentratedReverseProvide,
+    unchecked_return_type = "ConcentratedReverseProvideSimulationResponse"
+)]
+#[allow(clippy::too_many_arguments)]
+pub fn concentrated_reverse_provide_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] share_amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_precisions: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] total_share: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] price_scale: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] fee_gamma: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] mid_fee: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] out_fee: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] block_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] initial_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] inital_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] initial_gamma: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] future_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] future_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] future_gamma: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let share_amount = js::parse(&share_amount, "share_amount")?;
+    let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+    let asset_precisions = js::parse_vec::<u32>(&asset_precisions, "asset_precisions")?;
+    let total_share = js::parse(&total_share, "total_share")?;
+    let price_scale = js::parse::<Decimal256>(&price_scale, "price_scale")?;
+    let fee_gamma = js::parse::<Decimal256>(&fee_gamma, "fee_gamma")?;
+    let mid_fee = js::parse::<Decimal256>(&mid_fee, "mid_fee")?;
+    let out_fee = js::parse::<Decimal256>(&out_fee, "out_fee")?;
+    let block_time = js::parse(&block_time, "block_time")?;
+    let initial_time = js::parse(&initial_time, "initial_time")?;
+    let inital_amp = js::parse::<Decimal>(&inital_amp, "inital_amp")?;
+    let initial_gamma = js::parse::<Decimal>(&initial_gamma, "initial_gamma")?;
+    let future_time = js::parse(&future_time, "future_time")?;
+    let future_amp = js::parse::<Decimal>(&future_amp, "future_amp")?;
+    let future_gamma = js::parse::<Decimal>(&future_gamma, "future_gamma")?;
+
+    let result = astroport::pair_concentrated::provide::simulate_reverse(
+        share_amount,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
+        block_time,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating provide: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn concentrated_withdraw(
+    amount: &str,
+    asset_amounts: &str,
+    asset_precisions: &str,
+    total_share: &str,
+    min_assets_to_receive: Option<String>,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let amount = amount
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid amount: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let asset_precisions = serde_json::from_str::<Vec<u32>>(asset_precisions)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_precisions: {}", e)))?;
+
+    let total_share = total_share
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_share: {}", e)))?;
+
+    let min_assets_to_receive = min_assets_to_receive
+        .map(|min_assets_to_receive| serde_json::from_str::<Vec<Uint128>>(&min_assets_to_receive))
+        .transpose()
+        .map_err(|e| JsValue::from_str(&format!("Invalid min_assets_to_receive: {}", e)))?;
+
+    let result = astroport::pair_concentrated::withdraw::simulate(
+        amount,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        min_assets_to_receive,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating withdraw: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(
+    js_name = concentratedWithdraw,
+    unchecked_return_type = "ConcentratedWithdrawSimulationResponse"
+)]
+pub fn concentrated_withdraw_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_precisions: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] total_share: JsValue,
+    #[wasm_bindgen(unchecked_optional_param_type = "IntegerInput[] | string")]
+    min_assets_to_receive: Option<JsValue>,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let amount = js::parse(&amount, "amount")?;
+    let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+    let asset_precisions = js::parse_vec::<u32>(&asset_precisions, "asset_precisions")?;
+    let total_share = js::parse(&total_share, "total_share")?;
+
+    let min_assets_to_receive = min_assets_to_receive
+        .map(|min_assets_to_receive| {
+            js::parse_vec::<Uint128>(&min_assets_to_receive, "min_assets_to_receive")
+        })
+        .transpose()?;
+
+    let result = astroport::pair_concentrated::withdraw::simulate(
+        amount,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        min_assets_to_receive,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating withdraw: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+#[allow(clippy::too_many_arguments)]
+pub fn concentrated_zap_in(
+    offer_amount: &str,
+    offer_ind: &str,
+    asset_amounts: &str,
+    asset_precisions: &str,
+    total_share: &str,
+    maker_fee_share: &str,
+    oracle_price: &str,
+    price_scale: &str,
+    fee_gamma: &str,
+    mid_fee: &str,
+    out_fee: &str,
+    block_time: &str,
+    initial_time: &str,
+    inital_amp: &str,
+    initial_gamma: &str,
+    future_time: &str,
+    future_amp: &str,
+    future_gamma: &str,
+    repeg: Option<String>,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let offer_amount = Decimal256::from_str(offer_amount)
+        .map_err(|e| JsValue::from_str(&format!("Invalid offer_amount: {}", e)))?;
+
+    let offer_ind = offer_ind
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid offer_ind: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let asset_precisions = serde_json::from_str::<Vec<u32>>(asset_precisions)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_precisions: {}", e)))?;
+
+    let total_share = total_share
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_share: {}", e)))?;
+
+    let maker_fee_share = Decimal256::from_str(maker_fee_share)
+        .map_err(|e| JsValue::from_str(&format!("Invalid maker_fee_share: {}", e)))?;
+
+    let oracle_price = Decimal256::from_str(oracle_price)
+        .map_err(|e| JsValue::from_str(&format!("Invalid oracle_price: {}", e)))?;
+
+    let price_scale = Decimal256::from_str(price_scale)
+        .map_err(|e| JsValue::from_str(&format!("Invalid price_scale: {}", e)))?;
+
+    let fee_gamma = Decimal256::from_str(fee_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid fee_gamma: {}", e)))?;
+
+    let mid_fee = Decimal256::from_str(mid_fee)
+        .map_err(|e| JsValue::from_str(&format!("Invalid mid_fee: {}", e)))?;
+
+    let out_fee = Decimal256::from_str(out_fee)
+        .map_err(|e| JsValue::from_str(&format!("Invalid out_fee: {}", e)))?;
+
+    let block_time = block_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid block_time: {}", e)))?;
+
+    let initial_time = initial_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid initial_time: {}", e)))?;
+
+    let inital_amp = Decimal::from_str(inital_amp)
+        .map_err(|e| JsValue::from_str(&format!("Invalid inital_amp: {}", e)))?;
+
+    let initial_gamma = Decimal::from_str(initial_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid initial_gamma: {}", e)))?;
+
+    let future_time = future_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_time: {}", e)))?;
+
+    let future_amp = Decimal::from_str(future_amp)
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_amp: {}", e)))?;
+
+    let future_gamma = Decimal::from_str(future_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_gamma: {}", e)))?;
+
+    let repeg = repeg
+        .map(|repeg| {
+            serde_json::from_str::<astroport::pair_concentrated::state::RepegParams>(&repeg)
+        })
+        .transpose()
+        .map_err(|e| JsValue::from_str(&format!("Invalid repeg: {}", e)))?;
+
+    let result = astroport::pair_concentrated::zap::simulate_zap_in(
+        offer_amount,
+        offer_ind,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        maker_fee_share,
+        oracle_price,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
+        block_time,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+        repeg.as_ref(),
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating zap: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(
+    js_name = concentratedZapIn,
+    unchecked_return_type = "ConcentratedZapInSimulationResponse"
+)]
+#[allow(clippy::too_many_arguments)]
+pub fn concentrated_zap_in_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] offer_amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] offer_ind: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_precisions: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] total_share: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] maker_fee_share: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] oracle_price: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] price_scale: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] fee_gamma: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] mid_fee: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] out_fee: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] block_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] initial_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] inital_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] initial_gamma: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] future_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] future_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] future_gamma: JsValue,
+    #[wasm_bindgen(unchecked_optional_param_type = "ConcentratedRepegParams")] repeg: Option<
+        JsValue,
+    >,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let offer_amount = js::parse::<Decimal256>(&offer_amount, "offer_amount")?;
+    let offer_ind = js::parse(&offer_ind, "offer_ind")?;
+    let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+    let asset_precisions = js::parse_vec::<u32>(&asset_precisions, "asset_precisions")?;
+    let total_share = js::parse(&total_share, "total_share")?;
+    let maker_fee_share = js::parse::<Decimal256>(&maker_fee_share, "maker_fee_share")?;
+    let oracle_price = js::parse::<Decimal256>(&oracle_price, "oracle_price")?;
+    let price_scale = js::parse::<Decimal256>(&price_scale, "price_scale")?;
+    let fee_gamma = js::parse::<Decimal256>(&fee_gamma, "fee_gamma")?;
+    let mid_fee = js::parse::<Decimal256>(&mid_fee, "mid_fee")?;
+    let out_fee = js::parse::<Decimal256>(&out_fee, "out_fee")?;
+    let block_time = js::parse(&block_time, "block_time")?;
+    let initial_time = js::parse(&initial_time, "initial_time")?;
+    let inital_amp = js::parse::<Decimal>(&inital_amp, "inital_amp")?;
+    let initial_gamma = js::parse::<Decimal>(&initial_gamma, "initial_gamma")?;
+    let future_time = js::parse(&future_time, "future_time")?;
+    let future_amp = js::parse::<Decimal>(&future_amp, "future_amp")?;
+    let future_gamma = js::parse::<Decimal>(&future_gamma, "future_gamma")?;
+    let repeg = repeg
+        .map(|repeg| {
+            js::from_value::<astroport::pair_concentrated::state::RepegParams>(&repeg, "repeg")
+        })
+        .transpose()?;
+
+    let result = astroport::pair_concentrated::zap::simulate_zap_in(
+        offer_amount,
+        offer_ind,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        maker_fee_share,
+        oracle_price,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
+        block_time,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+        repeg.as_ref(),
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating zap: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+#[allow(clippy::too_many_arguments)]
+pub fn concentrated_zap_out(
+    amount: &str,
+    ask_ind: &str,
+    asset_amounts: &str,
+    asset_precisions: &str,
+    total_share: &str,
+    maker_fee_share: &str,
+    oracle_price: &str,
+    price_scale: &str,
+    fee_gamma: &str,
+    mid_fee: &str,
+    out_fee: &str,
+    block_time: &str,
+    initial_time: &str,
+    inital_amp: &str,
+    initial_gamma: &str,
+    future_time: &str,
+    future_amp: &str,
+    future_gamma: &str,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let amount = amount
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid amount: {}", e)))?;
+
+    let ask_ind = ask_ind
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid ask_ind: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let asset_precisions = serde_json::from_str::<Vec<u32>>(asset_precisions)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_precisions: {}", e)))?;
+
+    let total_share = total_share
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_share: {}", e)))?;
+
+    let maker_fee_share = Decimal256::from_str(maker_fee_share)
+        .map_err(|e| JsValue::from_str(&format!("Invalid maker_fee_share: {}", e)))?;
+
+    let oracle_price = Decimal256::from_str(oracle_price)
+        .map_err(|e| JsValue::from_str(&format!("Invalid oracle_price: {}", e)))?;
+
+    let price_scale = Decimal256::from_str(price_scale)
+        .map_err(|e| JsValue::from_str(&format!("Invalid price_scale: {}", e)))?;
+
+    let fee_gamma = Decimal256::from_str(fee_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid fee_gamma: {}", e)))?;
+
+    let mid_fee = Decimal256::from_str(mid_fee)
+        .map_err(|e| JsValue::from_str(&format!("Invalid mid_fee: {}", e)))?;
+
+    let out_fee = Decimal256::from_str(out_fee)
+        .map_err(|e| JsValue::from_str(&format!("Invalid out_fee: {}", e)))?;
+
+    let block_time = block_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid block_time: {}", e)))?;
+
+    let initial_time = initial_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid initial_time: {}", e)))?;
+
+    let inital_amp = Decimal::from_str(inital_amp)
+        .map_err(|e| JsValue::from_str(&format!("Invalid inital_amp: {}", e)))?;
+
+    let initial_gamma = Decimal::from_str(initial_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid initial_gamma: {}", e)))?;
+
+    let future_time = future_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_time: {}", e)))?;
+
+    let future_amp = Decimal::from_str(future_amp)
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_amp: {}", e)))?;
+
+    let future_gamma = Decimal::from_str(future_gamma)
+        .map_err(|e| JsValue::from_str(&format!("Invalid future_gamma: {}", e)))?;
+
+    let result = astroport::pair_concentrated::zap::simulate_zap_out(
+        amount,
+        ask_ind,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        maker_fee_share,
+        oracle_price,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
+        block_time,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating zap: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(
+    js_name = concentratedZapOut,
+    unchecked_return_type = "ConcentratedZapOutSimulationResponse"
+)]
+#[allow(clippy::too_many_arguments)]
+pub fn concentrated_zap_out_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_ind: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_precisions: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] total_share: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] maker_fee_share: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] oracle_price: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] price_scale: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] fee_gamma: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] mid_fee: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] out_fee: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] block_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] initial_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] inital_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] initial_gamma: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] future_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] future_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] future_gamma: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let amount = js::parse(&amount, "amount")?;
+    let ask_ind = js::parse(&ask_ind, "ask_ind")?;
+    let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+    let asset_precisions = js::parse_vec::<u32>(&asset_precisions, "asset_precisions")?;
+    let total_share = js::parse(&total_share, "total_share")?;
+    let maker_fee_share = js::parse::<Decimal256>(&maker_fee_share, "maker_fee_share")?;
+    let oracle_price = js::parse::<Decimal256>(&oracle_price, "oracle_price")?;
+    let price_scale = js::parse::<Decimal256>(&price_scale, "price_scale")?;
+    let fee_gamma = js::parse::<Decimal256>(&fee_gamma, "fee_gamma")?;
+    let mid_fee = js::parse::<Decimal256>(&mid_fee, "mid_fee")?;
+    let out_fee = js::parse::<Decimal256>(&out_fee, "out_fee")?;
+    let block_time = js::parse(&block_time, "block_time")?;
+    let initial_time = js::parse(&initial_time, "initial_time")?;
+    let inital_amp = js::parse::<Decimal>(&inital_amp, "inital_amp")?;
+    let initial_gamma = js::parse::<Decimal>(&initial_gamma, "initial_gamma")?;
+    let future_time = js::parse(&future_time, "future_time")?;
+    let future_amp = js::parse::<Decimal>(&future_amp, "future_amp")?;
+    let future_gamma = js::parse::<Decimal>(&future_gamma, "future_gamma")?;
+
+    let result = astroport::pair_concentrated::zap::simulate_zap_out(
+        amount,
+        ask_ind,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        maker_fee_share,
+        oracle_price,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
+        block_time,
+        initial_time,
+        inital_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating zap: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn stable_swap(
+    offer_amount: &str,
+    offer_asset_prec: &str,
+    ask_ind: &str,
+    ask_asset_prec: &str,
+    asset_amounts: &str,
+    total_fee_rate: &str,
+    block_time: &str,
+    init_amp_time: &str,
+    init_amp: &str,
+    next_amp_time: &str,
+    next_amp: &str,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let offer_amount = Decimal256::from_str(offer_amount)
+        .map_err(|e| JsValue::from_str(&format!("Invalid offer_asset_prec: {}", e)))?;
+
+    let offer_asset_prec = offer_asset_prec
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid offer_asset_prec: {}", e)))?;
+
+    let ask_ind = ask_ind
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid ask_ind: {}", e)))?;
+
+    let ask_asset_prec = ask_asset_prec
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid ask_asset_prec: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let total_fee_rate = total_fee_rate
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_fee_rate: {}", e)))?;
+
+    let block_time = block_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid block_time: {}", e)))?;
+
+    let init_amp_time = init_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp_time: {}", e)))?;
+
+    let init_amp = init_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp: {}", e)))?;
+
+    let next_amp_time = next_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp_time: {}", e)))?;
+
+    let next_amp = next_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp: {}", e)))?;
+
+    let result = astroport::pair_stable::swap::simulate(
+        offer_amount,
+        offer_asset_prec,
+        ask_ind,
+        ask_asset_prec,
+        &asset_amounts,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating swap: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(js_name = stableSwap, unchecked_return_type = "StableSwapSimulationResponse")]
+#[allow(clippy::too_many_arguments)]
+pub fn stable_swap_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] offer_amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] offer_asset_prec: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_ind: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_asset_prec: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] total_fee_rate: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] block_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let offer_amount = js::parse::<Decimal256>(&offer_amount, "offer_amount")?;
+    let offer_asset_prec = js::parse(&offer_asset_prec, "offer_asset_prec")?;
+    let ask_ind = js::parse(&ask_ind, "ask_ind")?;
+    let ask_asset_prec = js::parse(&ask_asset_prec, "ask_asset_prec")?;
+    let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+    let total_fee_rate = js::parse(&total_fee_rate, "total_fee_rate")?;
+    let block_time = js::parse(&block_time, "block_time")?;
+    let init_amp_time = js::parse(&init_amp_time, "init_amp_time")?;
+    let init_amp = js::parse(&init_amp, "init_amp")?;
+    let next_amp_time = js::parse(&next_amp_time, "next_amp_time")?;
+    let next_amp = js::parse(&next_amp, "next_amp")?;
+
+    let result = astroport::pair_stable::swap::simulate(
+        offer_amount,
+        offer_asset_prec,
+        ask_ind,
+        ask_asset_prec,
+        &asset_amounts,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating swap: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn stable_swap_batch(pools: &str, requests: &str) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let pools = serde_json::from_str::<Vec<astroport::pair_stable::swap::BatchPool>>(pools)
+        .map_err(|e| JsValue::from_str(&format!("Invalid pools: {}", e)))?;
+
+    let requests =
+        serde_json::from_str::<Vec<astroport::pair_stable::swap::BatchSwapRequest>>(requests)
+            .map_err(|e| JsValue::from_str(&format!("Invalid requests: {}", e)))?;
+
+    let result = astroport::pair_stable::swap::simulate_batch(&pools, &requests);
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(
+    js_name = stableSwapBatch,
+    unchecked_return_type = "BatchItem<StableSwapSimulationResponse>[]"
+)]
+pub fn stable_swap_batch_object(
+    #[wasm_bindgen(unchecked_param_type = "Input<StableBatchPool>[] | string")] pools: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Input<StableBatchSwapRequest>[] | string")]
+    requests: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let pools = js::from_value::<Vec<astroport::pair_stable::swap::BatchPool>>(&pools, "pools")?;
+
+    let requests = js::from_value::<Vec<astroport::pair_stable::swap::BatchSwapRequest>>(
+        &requests, "requests",
+    )?;
+
+    let result = astroport::pair_stable::swap::simulate_batch(&pools, &requests);
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+#[allow(clippy::too_many_arguments)]
+pub fn stable_swap_to_price(
+    target_price: &str,
+    asset_amounts: &str,
+    asset_precisions: &str,
+    total_fee_rate: &str,
+    block_time: &str,
+    init_amp_time: &str,
+    init_amp: &str,
+    next_amp_time: &str,
+    next_amp: &str,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let target_price = Decimal::from_str(target_price)
+        .map_err(|e| JsValue::from_str(&format!("Invalid target_price: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let asset_precisions = serde_json::from_str::<Vec<u8>>(asset_precisions)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_precisions: {}", e)))?;
+
+    let total_fee_rate = Decimal::from_str(total_fee_rate)
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_fee_rate: {}", e)))?;
+
+    let block_time = block_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid block_time: {}", e)))?;
+
+    let init_amp_time = init_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp_time: {}", e)))?;
+
+    let init_amp = init_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp: {}", e)))?;
+
+    let next_amp_time = next_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp_time: {}", e)))?;
+
+    let next_amp = next_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp: {}", e)))?;
+
+    let result = astroport::pair_stable::swap::swap_to_price(
+        target_price,
+        &asset_amounts,
+        &asset_precisions,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating swap: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(js_name = stableSwapToPrice, unchecked_return_type = "StableSwapToPriceResponse")]
+#[allow(clippy::too_many_arguments)]
+pub fn stable_swap_to_price_object(
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] target_price: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_precisions: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] total_fee_rate: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] block_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let target_price = js::parse::<Decimal>(&target_price, "target_price")?;
+    let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+    let asset_precisions = js::parse_vec::<u8>(&asset_precisions, "asset_precisions")?;
+    let total_fee_rate = js::parse::<Decimal>(&total_fee_rate, "total_fee_rate")?;
+    let block_time = js::parse(&block_time, "block_time")?;
+    let init_amp_time = js::parse(&init_amp_time, "init_amp_time")?;
+    let init_amp = js::parse(&init_amp, "init_amp")?;
+    let next_amp_time = js::parse(&next_amp_time, "next_amp_time")?;
+    let next_amp = js::parse(&next_amp, "next_amp")?;
+
+    let result = astroport::pair_stable::swap::swap_to_price(
+        target_price,
+        &asset_amounts,
+        &asset_precisions,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating swap: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+#[allow(clippy::too_many_arguments)]
+pub fn stable_depth(
+    price_impacts: &str,
+    asset_amounts: &str,
+    asset_precisions: &str,
+    total_fee_rate: &str,
+    block_time: &str,
+    init_amp_time: &str,
+    init_amp: &str,
+    next_amp_time: &str,
+    next_amp: &str,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let price_impacts = serde_json::from_str::<Vec<Decimal>>(price_impacts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid price_impacts: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let asset_precisions = serde_json::from_str::<Vec<u8>>(asset_precisions)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_precisions: {}", e)))?;
+
+    let total_fee_rate = Decimal::from_str(total_fee_rate)
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_fee_rate: {}", e)))?;
+
+    let block_time = block_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid block_time: {}", e)))?;
+
+    let init_amp_time = init_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp_time: {}", e)))?;
+
+    let init_amp = init_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp: {}", e)))?;
+
+    let next_amp_time = next_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp_time: {}", e)))?;
+
+    let next_amp = next_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp: {}", e)))?;
+
+    let result = astroport::pair_stable::swap::simulate_depth(
+        &price_impacts,
+        &asset_amounts,
+        &asset_precisions,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating depth: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(js_name = stableDepth, unchecked_return_type = "DepthResponse[]")]
+#[allow(clippy::too_many_arguments)]
+pub fn stable_depth_object(
+    #[wasm_bindgen(unchecked_param_type = "Decimal[] | string")] price_impacts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_precisions: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] total_fee_rate: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] block_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let price_impacts = js::parse_vec::<Decimal>(&price_impacts, "price_impacts")?;
+    let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+    let asset_precisions = js::parse_vec::<u8>(&asset_precisions, "asset_precisions")?;
+    let total_fee_rate = js::parse::<Decimal>(&total_fee_rate, "total_fee_rate")?;
+    let block_time = js::parse(&block_time, "block_time")?;
+    let init_amp_time = js::parse(&init_amp_time, "init_amp_time")?;
+    let init_amp = js::parse(&init_amp, "init_amp")?;
+    let next_amp_time = js::parse(&next_amp_time, "next_amp_time")?;
+    let next_amp = js::parse(&next_amp, "next_amp")?;
+
+    let result = astroport::pair_stable::swap::simulate_depth(
+        &price_impacts,
+        &asset_amounts,
+        &asset_precisions,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating depth: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+#[allow(clippy::too_many_arguments)]
+pub fn stable_ladder(
+    price_step: &str,
+    levels: &str,
+    asset_amounts: &str,
+    asset_precisions: &str,
+    total_fee_rate: &str,
+    block_time: &str,
+    init_amp_time: &str,
+    init_amp: &str,
+    next_amp_time: &str,
+    next_amp: &str,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let price_step = Decimal::from_str(price_step)
+        .map_err(|e| JsValue::from_str(&format!("Invalid price_step: {}", e)))?;
+
+    let levels = levels
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid levels: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let asset_precisions = serde_json::from_str::<Vec<u8>>(asset_precisions)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_precisions: {}", e)))?;
+
+    let total_fee_rate = Decimal::from_str(total_fee_rate)
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_fee_rate: {}", e)))?;
+
+    let block_time = block_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid block_time: {}", e)))?;
+
+    let init_amp_time = init_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp_time: {}", e)))?;
+
+    let init_amp = init_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp: {}", e)))?;
+
+    let next_amp_time = next_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp_time: {}", e)))?;
+
+    let next_amp = next_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp: {}", e)))?;
+
+    let result = astroport::pair_stable::swap::simulate_ladder(
+        price_step,
+        levels,
+        &asset_amounts,
+        &asset_precisions,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating ladder: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(js_name = stableLadder, unchecked_return_type = "LadderResponse")]
+#[allow(clippy::too_many_arguments)]
+pub fn stable_ladder_object(
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] price_step: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] levels: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_precisions: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] total_fee_rate: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] block_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let price_step = js::parse::<Decimal>(&price_step, "price_step")?;
+    let levels = js::parse(&levels, "levels")?;
+    let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+    let asset_precisions = js::parse_vec::<u8>(&asset_precisions, "asset_precisions")?;
+    let total_fee_rate = js::parse::<Decimal>(&total_fee_rate, "total_fee_rate")?;
+    let block_time = js::parse(&block_time, "block_time")?;
+    let init_amp_time = js::parse(&init_amp_time, "init_amp_time")?;
+    let init_amp = js::parse(&init_amp, "init_amp")?;
+    let next_amp_time = js::parse(&next_amp_time, "next_amp_time")?;
+    let next_amp = js::parse(&next_amp, "next_amp")?;
+
+    let result = astroport::pair_stable::swap::simulate_ladder(
+        price_step,
+        levels,
+        &asset_amounts,
+        &asset_precisions,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating ladder: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+#[allow(clippy::too_many_arguments)]
+pub fn stable_sample_curve(
+    min_offer_amount: &str,
+    max_offer_amount: &str,
+    points: &str,
+    spacing: &str,
+    ask_ind: &str,
+    asset_amounts: &str,
+    asset_precisions: &str,
+    total_fee_rate: &str,
+    block_time: &str,
+    init_amp_time: &str,
+    init_amp: &str,
+    next_amp_time: &str,
+    next_amp: &str,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let min_offer_amount = min_offer_amount
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid min_offer_amount: {}", e)))?;
+
+    let max_offer_amount = max_offer_amount
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid max_offer_amount: {}", e)))?;
+
+    let points = points
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid points: {}", e)))?;
+
+    let spacing = spacing
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid spacing: {}", e)))?;
+
+    let ask_ind = ask_ind
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid ask_ind: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let asset_precisions = serde_json::from_str::<Vec<u8>>(asset_precisions)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_precisions: {}", e)))?;
+
+    let total_fee_rate = Decimal::from_str(total_fee_rate)
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_fee_rate: {}", e)))?;
+
+    let block_time = block_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid block_time: {}", e)))?;
+
+    let init_amp_time = init_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp_time: {}", e)))?;
+
+    let init_amp = init_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp: {}", e)))?;
+
+    let next_amp_time = next_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp_time: {}", e)))?;
+
+    let next_amp = next_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp: {}", e)))?;
+
+    let result = astroport::pair_stable::swap::sample_curve(
+        min_offer_amount,
+        max_offer_amount,
+        points,
+        spacing,
+        ask_ind,
+        &asset_amounts,
+        &asset_precisions,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while sampling curve: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(js_name = stableSampleCurve, unchecked_return_type = "CurvePoint[]")]
+#[allow(clippy::too_many_arguments)]
+pub fn stable_sample_curve_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] min_offer_amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] max_offer_amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] points: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Spacing")] spacing: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_ind: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_precisions: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] total_fee_rate: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] block_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let min_offer_amount = js::parse(&min_offer_amount, "min_offer_amount")?;
+    let max_offer_amount = js::parse(&max_offer_amount, "max_offer_amount")?;
+    let points = js::parse(&points, "points")?;
+    let spacing = js::parse(&spacing, "spacing")?;
+    let ask_ind = js::parse(&ask_ind, "ask_ind")?;
+    let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+    let asset_precisions = js::parse_vec::<u8>(&asset_precisions, "asset_precisions")?;
+    let total_fee_rate = js::parse::<Decimal>(&total_fee_rate, "total_fee_rate")?;
+    let block_time = js::parse(&block_time, "block_time")?;
+    let init_amp_time = js::parse(&init_amp_time, "init_amp_time")?;
+    let init_amp = js::parse(&init_amp, "init_amp")?;
+    let next_amp_time = js::parse(&next_amp_time, "next_amp_time")?;
+    let next_amp = js::parse(&next_amp, "next_amp")?;
+
+    let result = astroport::pair_stable::swap::sample_curve(
+        min_offer_amount,
+        max_offer_amount,
+        points,
+        spacing,
+        ask_ind,
+        &asset_amounts,
+        &asset_precisions,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while sampling curve: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+/// A pool state with the invariant computed once, for repeated quoting against the same
+/// balances.
+#[wasm_bindgen]
+pub struct StablePoolSnapshot {
+    snapshot: astroport::pair_stable::snapshot::PoolSnapshot,
+}
+
+#[wasm_bindgen]
+impl StablePoolSnapshot {
+    #[wasm_bindgen(constructor)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        asset_amounts: &str,
+        asset_precisions: &str,
+        total_fee_rate: &str,
+        block_time: &str,
+        init_amp_time: &str,
+        init_amp: &str,
+        next_amp_time: &str,
+        next_amp: &str,
+    ) -> Result<StablePoolSnapshot, JsValue> {
+        utils::set_panic_hook();
+
+        let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
+            .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+        let asset_precisions = serde_json::from_str::<Vec<u8>>(asset_precisions)
+            .map_err(|e| JsValue::from_str(&format!("Invalid asset_precisions: {}", e)))?;
+
+        let total_fee_rate = Decimal::from_str(total_fee_rate)
+            .map_err(|e| JsValue::from_str(&format!("Invalid total_fee_rate: {}", e)))?;
+
+        let block_time = block_time
+            .parse()
+            .map_err(|e| JsValue::from_str(&format!("Invalid block_time: {}", e)))?;
+
+        let init_amp_time = init_amp_time
+            .parse()
+            .map_err(|e| JsValue::from_str(&format!("Invalid init_amp_time: {}", e)))?;
+
+        let init_amp = init_amp
+            .parse()
+            .map_err(|e| JsValue::from_str(&format!("Invalid init_amp: {}", e)))?;
+
+        let next_amp_time = next_amp_time
+            .parse()
+            .map_err(|e| JsValue::from_str(&format!("Invalid next_amp_time: {}", e)))?;
+
+        let next_amp = next_amp
+            .parse()
+            .map_err(|e| JsValue::from_str(&format!("Invalid next_amp: {}", e)))?;
+
+        let snapshot = astroport::pair_stable::snapshot::PoolSnapshot::new(
+            &asset_amounts,
+            &asset_precisions,
+            total_fee_rate,
+            block_time,
+            init_amp_time,
+            init_amp,
+            next_amp_time,
+            next_amp,
+        )
+        .map_err(|e| JsValue::from_str(&format!("Error while creating pool snapshot: {}", e)))?;
+
+        Ok(StablePoolSnapshot { snapshot })
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "string")]
+    pub fn swap(&self, offer_amount: &str, ask_ind: &str) -> Result<JsValue, JsValue> {
+        let offer_amount = offer_amount
+            .parse()
+            .map_err(|e| JsValue::from_str(&format!("Invalid offer_amount: {}", e)))?;
+
+        let ask_ind = ask_ind
+            .parse()
+            .map_err(|e| JsValue::from_str(&format!("Invalid ask_ind: {}", e)))?;
+
+        let result = self
+            .snapshot
+            .swap(offer_amount, ask_ind)
+            .map_err(|e| JsValue::from_str(&format!("Error while simulating swap: {}", e)))?;
+
+        let json_result = serde_json::to_string(&result)
+            .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+        Ok(JsValue::from_str(&json_result))
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "string")]
+    pub fn quote(&self, offer_amount: &str, ask_ind: &str) -> Result<JsValue, JsValue> {
+        let offer_amount = offer_amount
+            .parse()
+            .map_err(|e| JsValue::from_str(&format!("Invalid offer_amount: {}", e)))?;
+
+        let ask_ind = ask_ind
+            .parse()
+            .map_err(|e| JsValue::from_str(&format!("Invalid ask_ind: {}", e)))?;
+
+        let result = self
+            .snapshot
+            .quote(offer_amount, ask_ind)
+            .map_err(|e| JsValue::from_str(&format!("Error while quoting swap: {}", e)))?;
+
+        let json_result = serde_json::to_string(&result)
+            .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+        Ok(JsValue::from_str(&json_result))
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "string")]
+    pub fn spot_price(&self, ask_ind: &str) -> Result<JsValue, JsValue> {
+        let ask_ind = ask_ind
+            .parse()
+            .map_err(|e| JsValue::from_str(&format!("Invalid ask_ind: {}", e)))?;
+
+        let result = self
+            .snapshot
+            .spot_price(ask_ind)
+            .map_err(|e| JsValue::from_str(&format!("Error while computing spot price: {}", e)))?;
+
+        let json_result = serde_json::to_string(&result)
+            .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+        Ok(JsValue::from_str(&json_result))
+    }
+}
+
+/// A stableswap pool kept on the JS side, see [`XykPool`]. The invariant is computed once per
+/// update.
+#[wasm_bindgen]
+pub struct StablePool {
+    pool: astroport::pair_stable::pool::Pool,
+}
+
+#[wasm_bindgen]
+impl StablePool {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        #[wasm_bindgen(unchecked_param_type = "Input<StablePoolState> | string")] state: JsValue,
+    ) -> Result<StablePool, JsValue> {
+        utils::set_panic_hook();
+
+        let state = js::from_value::<astroport::pair_stable::pool::PoolState>(&state, "state")?;
+
+        let pool = astroport::pair_stable::pool::Pool::new(state)
+            .map_err(|e| JsValue::from_str(&format!("Error while creating pool: {}", e)))?;
+
+        Ok(StablePool { pool })
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "StablePoolState")]
+    pub fn state(&self) -> Result<JsValue, JsValue> {
+        js::to_value(self.pool.state())
+    }
+
+    pub fn update(
+        &mut self,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+        #[wasm_bindgen(unchecked_optional_param_type = "IntegerInput")] total_share: Option<
+            JsValue,
+        >,
+    ) -> Result<(), JsValue> {
+        let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+
+        let total_share = total_share
+            .map(|total_share| js::parse(&total_share, "total_share"))
+            .transpose()?;
+
+        self.pool
+            .update(asset_amounts, total_share)
+            .map_err(|e| JsValue::from_str(&format!("Error while updating pool: {}", e)))
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "StableSwapSimulationResponse")]
+    pub fn swap(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput")] offer_amount: JsValue,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_ind: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let offer_amount = js::parse(&offer_amount, "offer_amount")?;
+
+        let ask_ind = js::parse(&ask_ind, "ask_ind")?;
+
+        let result = self
+            .pool
+            .swap(offer_amount, ask_ind)
+            .map_err(|e| JsValue::from_str(&format!("Error while simulating swap: {}", e)))?;
+
+        js::to_value(&result)
+    }
+
+    #[wasm_bindgen(
+        js_name = reverseSwap,
+        unchecked_return_type = "StableReverseSwapSimulationResponse"
+    )]
+    pub fn reverse_swap(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_amount: JsValue,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_ind: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let ask_amount = js::parse(&ask_amount, "ask_amount")?;
+
+        let ask_ind = js::parse(&ask_ind, "ask_ind")?;
+
+        let result = self.pool.reverse_swap(ask_amount, ask_ind).map_err(|e| {
+            JsValue::from_str(&format!("Error while simulating reverse swap: {}", e))
+        })?;
+
+        js::to_value(&result)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "StableProvideSimulationResponse")]
+    pub fn provide(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] deposits: JsValue,
+        #[wasm_bindgen(unchecked_optional_param_type = "Decimal")] slippage_tolerance: Option<
+            JsValue,
+        >,
+        #[wasm_bindgen(unchecked_optional_param_type = "IntegerInput")] min_lp_to_receive: Option<
+            JsValue,
+        >,
+    ) -> Result<JsValue, JsValue> {
+        let deposits = js::parse_vec::<Decimal256>(&deposits, "deposits")?;
+
+        let slippage_tolerance = slippage_tolerance
+            .map(|slippage_tolerance| js::parse(&slippage_tolerance, "slippage_tolerance"))
+            .transpose()?;
+
+        let min_lp_to_receive = min_lp_to_receive
+            .map(|min_lp_to_receive| js::parse(&min_lp_to_receive, "min_lp_to_receive"))
+            .transpose()?;
+
+        let result = self
+            .pool
+            .provide(&deposits, slippage_tolerance, min_lp_to_receive)
+            .map_err(|e| JsValue::from_str(&format!("Error while simulating provide: {}", e)))?;
+
+        js::to_value(&result)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "StableWithdrawSimulationResponse")]
+    pub fn withdraw(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput")] amount: JsValue,
+        #[wasm_bindgen(unchecked_optional_param_type = "IntegerInput[] | string")]
+        min_assets_to_receive: Option<JsValue>,
+    ) -> Result<JsValue, JsValue> {
+        let amount = js::parse(&amount, "amount")?;
+
+        let min_assets_to_receive = min_assets_to_receive
+            .map(|min_assets_to_receive| {
+                js::parse_vec::<Uint128>(&min_assets_to_receive, "min_assets_to_receive")
+            })
+            .transpose()?;
+
+        let result = self
+            .pool
+            .withdraw(amount, min_assets_to_receive)
+            .map_err(|e| JsValue::from_str(&format!("Error while simulating withdraw: {}", e)))?;
+
+        js::to_value(&result)
+    }
+
+    #[wasm_bindgen(js_name = spotPrice, unchecked_return_type = "Decimal")]
+    pub fn spot_price(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_ind: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let ask_ind = js::parse(&ask_ind, "ask_ind")?;
+
+        let result = self
+            .pool
+            .spot_price(ask_ind)
+            .map_err(|e| JsValue::from_str(&format!("Error while computing spot price: {}", e)))?;
+
+        js::to_value(&result)
+    }
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn stable_provide(
+    deposits: &str,
+    asset_amounts: &str,
+    asset_precisions: &str,
+    total_share: &str,
+    block_time: &str,
+    init_amp_time: &str,
+    init_amp: &str,
+    next_amp_time: &str,
+    next_amp: &str,
+    slippage_tolerance: Option<String>,
+    min_lp_to_receive: Option<String>,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let deposits = serde_json::from_str::<Vec<Decimal256>>(deposits)
+        .map_err(|e| JsValue::from_str(&format!("Invalid deposits: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let asset_precisions = serde_json::from_str::<Vec<u8>>(asset_precisions)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_precisions: {}", e)))?;
+
+    let total_share = total_share
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_share: {}", e)))?;
+
+    let block_time = block_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid block_time: {}", e)))?;
+
+    let init_amp_time = init_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp_time: {}", e)))?;
+
+    let init_amp = init_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp: {}", e)))?;
+
+    let next_amp_time = next_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp_time: {}", e)))?;
+
+    let next_amp = next_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp: {}", e)))?;
+
+    let slippage_tolerance = slippage_tolerance
+        .map(|slippage_tolerance| slippage_tolerance.parse())
+        .transpose()
+        .map_err(|e| JsValue::from_str(&format!("Invalid slippage_tolerance: {}", e)))?;
+
+    let min_lp_to_receive = min_lp_to_receive
+        .map(|min_lp_to_receive| min_lp_to_receive.parse())
+        .transpose()
+        .map_err(|e| JsValue::from_str(&format!("Invalid min_lp_to_receive: {}", e)))?;
+
+    let result = astroport::pair_stable::provide::simulate(
+        &deposits,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+        slippage_tolerance,
+        min_lp_to_receive,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating provide: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(js_name = stableProvide, unchecked_return_type = "StableProvideSimulationResponse")]
+#[allow(clippy::too_many_arguments)]
+pub fn stable_provide_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] deposits: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_precisions: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] total_share: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] block_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp: JsValue,
+    #[wasm_bindgen(unchecked_optional_param_type = "Decimal")] slippage_tolerance: Option<JsValue>,
+    #[wasm_bindgen(unchecked_optional_param_type = "IntegerInput")] min_lp_to_receive: Option<
+        JsValue,
+    >,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let deposits = js::parse_vec::<Decimal256>(&deposits, "deposits")?;
+    let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+    let asset_precisions = js::parse_vec::<u8>(&asset_precisions, "asset_precisions")?;
+    let total_share = js::parse(&total_share, "total_share")?;
+    let block_time = js::parse(&block_time, "block_time")?;
+    let init_amp_time = js::parse(&init_amp_time, "init_amp_time")?;
+    let init_amp = js::parse(&init_amp, "init_amp")?;
+    let next_amp_time = js::parse(&next_amp_time, "next_amp_time")?;
+    let next_amp = js::parse(&next_amp, "next_amp")?;
+
+    let slippage_tolerance = slippage_tolerance
+        .map(|slippage_tolerance| js::parse(&slippage_tolerance, "slippage_tolerance"))
+        .transpose()?;
+
+    let min_lp_to_receive = min_lp_to_receive
+        .map(|min_lp_to_receive| js::parse(&min_lp_to_receive, "min_lp_to_receive"))
+        .transpose()?;
+
+    let result = astroport::pair_stable::provide::simulate(
+        &deposits,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+        slippage_tolerance,
+        min_lp_to_receive,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating provide: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+#[allow(clippy::too_many_arguments)]
+pub fn stable_reverse_provide(
+    share_amount: &str,
+    asset_amounts: &str,
+    asset_precisions: &str,
+    total_share: &str,
+    block_time: &str,
+    init_amp_time: &str,
+    init_amp: &str,
+    next_amp_time: &str,
+    next_amp: &str,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let share_amount = share_amount
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid share_amount: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let asset_precisions = serde_json::from_str::<Vec<u8>>(asset_precisions)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_precisions: {}", e)))?;
+
+    let total_share = total_share
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_share: {}", e)))?;
+
+    let block_time = block_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid block_time: {}", e)))?;
+
+    let init_amp_time = init_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp_time: {}", e)))?;
+
+    let init_amp = init_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp: {}", e)))?;
+
+    let next_amp_time = next_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp_time: {}", e)))?;
+
+    let next_amp = next_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp: {}", e)))?;
+
+    let result = astroport::pair_stable::provide::simulate_reverse(
+        share_amount,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating provide: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(
+    js_name = stableReverseProvide,
+    unchecked_return_type = "StableReverseProvideSimulationResponse"
+)]
+#[allow(clippy::too_many_arguments)]
+pub fn stable_reverse_provide_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] share_amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_precisions: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] total_share: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] block_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let share_amount = js::parse(&share_amount, "share_amount")?;
+    let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+    let asset_precisions = js::parse_vec::<u8>(&asset_precisions, "asset_precisions")?;
+    let total_share = js::parse(&total_share, "total_share")?;
+    let block_time = js::parse(&block_time, "block_time")?;
+    let init_amp_time = js::parse(&init_amp_time, "init_amp_time")?;
+    let init_amp = js::parse(&init_amp, "init_amp")?;
+    let next_amp_time = js::parse(&next_amp_time, "next_amp_time")?;
+    let next_amp = js::parse(&next_amp, "next_amp")?;
+
+    let result = astroport::pair_stable::provide::simulate_reverse(
+        share_amount,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating provide: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn stable_withdraw(
+    amount: &str,
+    asset_amounts: &str,
+    total_share: &str,
+    min_assets_to_receive: Option<String>,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let amount = amount
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid amount: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Uint128>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let total_share = total_share
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_share: {}", e)))?;
+
+    let min_assets_to_receive = min_assets_to_receive
+        .map(|min_assets_to_receive| serde_json::from_str::<Vec<Uint128>>(&min_assets_to_receive))
+        .transpose()
+        .map_err(|e| JsValue::from_str(&format!("Invalid min_assets_to_receive: {}", e)))?;
+
+    let result = astroport::pair_stable::withdraw::simulate(
+        amount,
+        &asset_amounts,
+        total_share,
+        min_assets_to_receive,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating withdraw: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(
+    js_name = stableWithdraw,
+    unchecked_return_type = "StableWithdrawSimulationResponse"
+)]
+pub fn stable_withdraw_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] total_share: JsValue,
+    #[wasm_bindgen(unchecked_optional_param_type = "IntegerInput[] | string")]
+    min_assets_to_receive: Option<JsValue>,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let amount = js::parse(&amount, "amount")?;
+    let asset_amounts = js::parse_vec::<Uint128>(&asset_amounts, "asset_amounts")?;
+    let total_share = js::parse(&total_share, "total_share")?;
+
+    let min_assets_to_receive = min_assets_to_receive
+        .map(|min_assets_to_receive| {
+            js::parse_vec::<Uint128>(&min_assets_to_receive, "min_assets_to_receive")
+        })
+        .transpose()?;
+
+    let result = astroport::pair_stable::withdraw::simulate(
+        amount,
+        &asset_amounts,
+        total_share,
+        min_assets_to_receive,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating withdraw: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+#[allow(clippy::too_many_arguments)]
+pub fn stable_withdraw_one(
+    amount: &str,
+    ask_ind: &str,
+    asset_amounts: &str,
+    asset_precisions: &str,
+    total_share: &str,
+    total_fee_rate: &str,
+    block_time: &str,
+    init_amp_time: &str,
+    init_amp: &str,
+    next_amp_time: &str,
+    next_amp: &str,
+    min_return_amount: Option<String>,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let amount = amount
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid amount: {}", e)))?;
+
+    let ask_ind = ask_ind
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid ask_ind: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let asset_precisions = serde_json::from_str::<Vec<u8>>(asset_precisions)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_precisions: {}", e)))?;
+
+    let total_share = total_share
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_share: {}", e)))?;
+
+    let total_fee_rate = total_fee_rate
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_fee_rate: {}", e)))?;
+
+    let block_time = block_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid block_time: {}", e)))?;
+
+    let init_amp_time = init_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp_time: {}", e)))?;
+
+    let init_amp = init_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp: {}", e)))?;
+
+    let next_amp_time = next_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp_time: {}", e)))?;
+
+    let next_amp = next_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp: {}", e)))?;
+
+    let min_return_amount = min_return_amount
+        .map(|min_return_amount| min_return_amount.parse())
+        .transpose()
+        .map_err(|e| JsValue::from_str(&format!("Invalid min_return_amount: {}", e)))?;
+
+    let result = astroport::pair_stable::withdraw::simulate_one(
+        amount,
+        ask_ind,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+        min_return_amount,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating withdraw: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(
+    js_name = stableWithdrawOne,
+    unchecked_return_type = "StableWithdrawOneSimulationResponse"
+)]
+#[allow(clippy::too_many_arguments)]
+pub fn stable_withdraw_one_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_ind: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_precisions: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] total_share: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] total_fee_rate: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] block_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp: JsValue,
+    #[wasm_bindgen(unchecked_optional_param_type = "IntegerInput")] min_return_amount: Option<
+        JsValue,
+    >,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let amount = js::parse(&amount, "amount")?;
+    let ask_ind = js::parse(&ask_ind, "ask_ind")?;
+    let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+    let asset_precisions = js::parse_vec::<u8>(&asset_precisions, "asset_precisions")?;
+    let total_share = js::parse(&total_share, "total_share")?;
+    let total_fee_rate = js::parse(&total_fee_rate, "total_fee_rate")?;
+    let block_time = js::parse(&block_time, "block_time")?;
+    let init_amp_time = js::parse(&init_amp_time, "init_amp_time")?;
+    let init_amp = js::parse(&init_amp, "init_amp")?;
+    let next_amp_time = js::parse(&next_amp_time, "next_amp_time")?;
+    let next_amp = js::parse(&next_amp, "next_amp")?;
+
+    let min_return_amount = min_return_amount
+        .map(|min_return_amount| js::parse(&min_return_amount, "min_return_amount"))
+        .transpose()?;
+
+    let result = astroport::pair_stable::withdraw::simulate_one(
+        amount,
+        ask_ind,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+        min_return_amount,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating withdraw: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+#[allow(clippy::too_many_arguments)]
+pub fn stable_withdraw_imbalanced(
+    withdrawals: &str,
+    asset_amounts: &str,
+    asset_precisions: &str,
+    total_share: &str,
+    total_fee_rate: &str,
+    block_time: &str,
+    init_amp_time: &str,
+    init_amp: &str,
+    next_amp_time: &str,
+    next_amp: &str,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let withdrawals = serde_json::from_str::<Vec<Decimal256>>(withdrawals)
+        .map_err(|e| JsValue::from_str(&format!("Invalid withdrawals: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let asset_precisions = serde_json::from_str::<Vec<u8>>(asset_precisions)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_precisions: {}", e)))?;
+
+    let total_share = total_share
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_share: {}", e)))?;
+
+    let total_fee_rate = total_fee_rate
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_fee_rate: {}", e)))?;
+
+    let block_time = block_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid block_time: {}", e)))?;
+
+    let init_amp_time = init_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp_time: {}", e)))?;
+
+    let init_amp = init_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp: {}", e)))?;
+
+    let next_amp_time = next_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp_time: {}", e)))?;
+
+    let next_amp = next_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp: {}", e)))?;
+
+    let result = astroport::pair_stable::withdraw::simulate_imbalanced(
+        &withdrawals,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating withdraw: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(
+    js_name = stableWithdrawImbalanced,
+    unchecked_return_type = "StableImbalancedWithdrawSimulationResponse"
+)]
+#[allow(clippy::too_many_arguments)]
+pub fn stable_withdraw_imbalanced_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] withdrawals: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_precisions: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] total_share: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] total_fee_rate: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] block_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let withdrawals = js::parse_vec::<Decimal256>(&withdrawals, "withdrawals")?;
+    let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+    let asset_precisions = js::parse_vec::<u8>(&asset_precisions, "asset_precisions")?;
+    let total_share = js::parse(&total_share, "total_share")?;
+    let total_fee_rate = js::parse(&total_fee_rate, "total_fee_rate")?;
+    let block_time = js::parse(&block_time, "block_time")?;
+    let init_amp_time = js::parse(&init_amp_time, "init_amp_time")?;
+    let init_amp = js::parse(&init_amp, "init_amp")?;
+    let next_amp_time = js::parse(&next_amp_time, "next_amp_time")?;
+    let next_amp = js::parse(&next_amp, "next_amp")?;
+
+    let result = astroport::pair_stable::withdraw::simulate_imbalanced(
+        &withdrawals,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating withdraw: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+#[allow(clippy::too_many_arguments)]
+pub fn stable_zap_in(
+    offer_amount: &str,
+    offer_ind: &str,
+    asset_amounts: &str,
+    asset_precisions: &str,
+    total_share: &str,
+    total_fee_rate: &str,
+    block_time: &str,
+    init_amp_time: &str,
+    init_amp: &str,
+    next_amp_time: &str,
+    next_amp: &str,
+    maker_fee_share: Option<String>,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let offer_amount = Decimal256::from_str(offer_amount)
+        .map_err(|e| JsValue::from_str(&format!("Invalid offer_amount: {}", e)))?;
+
+    let offer_ind = offer_ind
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid offer_ind: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let asset_precisions = serde_json::from_str::<Vec<u8>>(asset_precisions)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_precisions: {}", e)))?;
+
+    let total_share = total_share
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_share: {}", e)))?;
+
+    let total_fee_rate = total_fee_rate
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_fee_rate: {}", e)))?;
+
+    let block_time = block_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid block_time: {}", e)))?;
+
+    let init_amp_time = init_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp_time: {}", e)))?;
+
+    let init_amp = init_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp: {}", e)))?;
+
+    let next_amp_time = next_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp_time: {}", e)))?;
+
+    let next_amp = next_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp: {}", e)))?;
+
+    let maker_fee_share = maker_fee_share
+        .map(|maker_fee_share| maker_fee_share.parse())
+        .transpose()
+        .map_err(|e| JsValue::from_str(&format!("Invalid maker_fee_share: {}", e)))?;
+
+    let result = astroport::pair_stable::zap::simulate_zap_in(
+        offer_amount,
+        offer_ind,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+        maker_fee_share,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating zap: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(js_name = stableZapIn, unchecked_return_type = "StableZapInSimulationResponse")]
+#[allow(clippy::too_many_arguments)]
+pub fn stable_zap_in_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] offer_amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] offer_ind: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_precisions: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] total_share: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] total_fee_rate: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] block_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp: JsValue,
+    #[wasm_bindgen(unchecked_optional_param_type = "Decimal")] maker_fee_share: Option<JsValue>,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let offer_amount = js::parse::<Decimal256>(&offer_amount, "offer_amount")?;
+    let offer_ind = js::parse(&offer_ind, "offer_ind")?;
+    let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+    let asset_precisions = js::parse_vec::<u8>(&asset_precisions, "asset_precisions")?;
+    let total_share = js::parse(&total_share, "total_share")?;
+    let total_fee_rate = js::parse(&total_fee_rate, "total_fee_rate")?;
+    let block_time = js::parse(&block_time, "block_time")?;
+    let init_amp_time = js::parse(&init_amp_time, "init_amp_time")?;
+    let init_amp = js::parse(&init_amp, "init_amp")?;
+    let next_amp_time = js::parse(&next_amp_time, "next_amp_time")?;
+    let next_amp = js::parse(&next_amp, "next_amp")?;
+    let maker_fee_share = maker_fee_share
+        .map(|maker_fee_share| js::parse(&maker_fee_share, "maker_fee_share"))
+        .transpose()?;
+
+    let result = astroport::pair_stable::zap::simulate_zap_in(
+        offer_amount,
+        offer_ind,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+        maker_fee_share,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating zap: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+#[allow(clippy::too_many_arguments)]
+pub fn stable_zap_out(
     amount: &str,
+    ask_ind: &str,
+    asset_amounts: &str,
+    asset_precisions: &str,
+    total_share: &str,
+    total_fee_rate: &str,
+    block_time: &str,
+    init_amp_time: &str,
+    init_amp: &str,
+    next_amp_time: &str,
+    next_amp: &str,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let amount = amount
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid amount: {}", e)))?;
+
+    let ask_ind = ask_ind
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid ask_ind: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Decimal256>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let asset_precisions = serde_json::from_str::<Vec<u8>>(asset_precisions)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_precisions: {}", e)))?;
+
+    let total_share = total_share
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_share: {}", e)))?;
+
+    let total_fee_rate = total_fee_rate
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_fee_rate: {}", e)))?;
+
+    let block_time = block_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid block_time: {}", e)))?;
+
+    let init_amp_time = init_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp_time: {}", e)))?;
+
+    let init_amp = init_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid init_amp: {}", e)))?;
+
+    let next_amp_time = next_amp_time
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp_time: {}", e)))?;
+
+    let next_amp = next_amp
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid next_amp: {}", e)))?;
+
+    let result = astroport::pair_stable::zap::simulate_zap_out(
+        amount,
+        ask_ind,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating zap: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(js_name = stableZapOut, unchecked_return_type = "StableZapOutSimulationResponse")]
+#[allow(clippy::too_many_arguments)]
+pub fn stable_zap_out_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_ind: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_precisions: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] total_share: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] total_fee_rate: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] block_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] init_amp: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp_time: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] next_amp: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let amount = js::parse(&amount, "amount")?;
+    let ask_ind = js::parse(&ask_ind, "ask_ind")?;
+    let asset_amounts = js::parse_vec::<Decimal256>(&asset_amounts, "asset_amounts")?;
+    let asset_precisions = js::parse_vec::<u8>(&asset_precisions, "asset_precisions")?;
+    let total_share = js::parse(&total_share, "total_share")?;
+    let total_fee_rate = js::parse(&total_fee_rate, "total_fee_rate")?;
+    let block_time = js::parse(&block_time, "block_time")?;
+    let init_amp_time = js::parse(&init_amp_time, "init_amp_time")?;
+    let init_amp = js::parse(&init_amp, "init_amp")?;
+    let next_amp_time = js::parse(&next_amp_time, "next_amp_time")?;
+    let next_amp = js::parse(&next_amp, "next_amp")?;
+
+    let result = astroport::pair_stable::zap::simulate_zap_out(
+        amount,
+        ask_ind,
+        &asset_amounts,
+        &asset_precisions,
+        total_share,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating zap: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn xyk_swap(
+    offer_amount: &str,
+    ask_ind: &str,
+    asset_amounts: &str,
+    total_fee_rate: &str,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let offer_amount = offer_amount
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid offer_amount: {}", e)))?;
+
+    let ask_ind = ask_ind
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid ask_ind: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Uint128>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let total_fee_rate = total_fee_rate
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_fee_rate: {}", e)))?;
+
+    let result =
+        astroport::pair_xyk::swap::simulate(offer_amount, ask_ind, &asset_amounts, total_fee_rate)
+            .map_err(|e| JsValue::from_str(&format!("Error while simulating swap: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(js_name = xykSwap, unchecked_return_type = "XykSwapSimulationResponse")]
+pub fn xyk_swap_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] offer_amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_ind: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] total_fee_rate: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let offer_amount = js::parse(&offer_amount, "offer_amount")?;
+    let ask_ind = js::parse(&ask_ind, "ask_ind")?;
+    let asset_amounts = js::parse_vec::<Uint128>(&asset_amounts, "asset_amounts")?;
+    let total_fee_rate = js::parse(&total_fee_rate, "total_fee_rate")?;
+
+    let result =
+        astroport::pair_xyk::swap::simulate(offer_amount, ask_ind, &asset_amounts, total_fee_rate)
+            .map_err(|e| JsValue::from_str(&format!("Error while simulating swap: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn xyk_swap_batch(pools: &str, requests: &str) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let pools = serde_json::from_str::<Vec<astroport::pair_xyk::swap::BatchPool>>(pools)
+        .map_err(|e| JsValue::from_str(&format!("Invalid pools: {}", e)))?;
+
+    let requests =
+        serde_json::from_str::<Vec<astroport::pair_xyk::swap::BatchSwapRequest>>(requests)
+            .map_err(|e| JsValue::from_str(&format!("Invalid requests: {}", e)))?;
+
+    let result = astroport::pair_xyk::swap::simulate_batch(&pools, &requests);
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(
+    js_name = xykSwapBatch,
+    unchecked_return_type = "BatchItem<XykSwapSimulationResponse>[]"
+)]
+pub fn xyk_swap_batch_object(
+    #[wasm_bindgen(unchecked_param_type = "Input<XykBatchPool>[] | string")] pools: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Input<XykBatchSwapRequest>[] | string")]
+    requests: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let pools = js::from_value::<Vec<astroport::pair_xyk::swap::BatchPool>>(&pools, "pools")?;
+
+    let requests =
+        js::from_value::<Vec<astroport::pair_xyk::swap::BatchSwapRequest>>(&requests, "requests")?;
+
+    let result = astroport::pair_xyk::swap::simulate_batch(&pools, &requests);
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn xyk_swap_to_price(
+    target_price: &str,
+    asset_amounts: &str,
+    total_fee_rate: &str,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let target_price = Decimal::from_str(target_price)
+        .map_err(|e| JsValue::from_str(&format!("Invalid target_price: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Uint128>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let total_fee_rate = Decimal::from_str(total_fee_rate)
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_fee_rate: {}", e)))?;
+
+    let result =
+        astroport::pair_xyk::swap::swap_to_price(target_price, &asset_amounts, total_fee_rate)
+            .map_err(|e| JsValue::from_str(&format!("Error while simulating swap: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(js_name = xykSwapToPrice, unchecked_return_type = "XykSwapToPriceResponse")]
+pub fn xyk_swap_to_price_object(
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] target_price: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] total_fee_rate: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let target_price = js::parse::<Decimal>(&target_price, "target_price")?;
+    let asset_amounts = js::parse_vec::<Uint128>(&asset_amounts, "asset_amounts")?;
+    let total_fee_rate = js::parse::<Decimal>(&total_fee_rate, "total_fee_rate")?;
+
+    let result =
+        astroport::pair_xyk::swap::swap_to_price(target_price, &asset_amounts, total_fee_rate)
+            .map_err(|e| JsValue::from_str(&format!("Error while simulating swap: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn xyk_depth(
+    price_impacts: &str,
+    asset_amounts: &str,
+    total_fee_rate: &str,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let price_impacts = serde_json::from_str::<Vec<Decimal>>(price_impacts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid price_impacts: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Uint128>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let total_fee_rate = Decimal::from_str(total_fee_rate)
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_fee_rate: {}", e)))?;
+
+    let result =
+        astroport::pair_xyk::swap::simulate_depth(&price_impacts, &asset_amounts, total_fee_rate)
+            .map_err(|e| JsValue::from_str(&format!("Error while simulating depth: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(js_name = xykDepth, unchecked_return_type = "DepthResponse[]")]
+pub fn xyk_depth_object(
+    #[wasm_bindgen(unchecked_param_type = "Decimal[] | string")] price_impacts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] total_fee_rate: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let price_impacts = js::parse_vec::<Decimal>(&price_impacts, "price_impacts")?;
+    let asset_amounts = js::parse_vec::<Uint128>(&asset_amounts, "asset_amounts")?;
+    let total_fee_rate = js::parse::<Decimal>(&total_fee_rate, "total_fee_rate")?;
+
+    let result =
+        astroport::pair_xyk::swap::simulate_depth(&price_impacts, &asset_amounts, total_fee_rate)
+            .map_err(|e| JsValue::from_str(&format!("Error while simulating depth: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn xyk_ladder(
+    price_step: &str,
+    levels: &str,
+    asset_amounts: &str,
+    total_fee_rate: &str,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let price_step = Decimal::from_str(price_step)
+        .map_err(|e| JsValue::from_str(&format!("Invalid price_step: {}", e)))?;
+
+    let levels = levels
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid levels: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Uint128>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let total_fee_rate = Decimal::from_str(total_fee_rate)
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_fee_rate: {}", e)))?;
+
+    let result = astroport::pair_xyk::swap::simulate_ladder(
+        price_step,
+        levels,
+        &asset_amounts,
+        total_fee_rate,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating ladder: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(js_name = xykLadder, unchecked_return_type = "LadderResponse")]
+pub fn xyk_ladder_object(
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] price_step: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] levels: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] total_fee_rate: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let price_step = js::parse::<Decimal>(&price_step, "price_step")?;
+    let levels = js::parse(&levels, "levels")?;
+    let asset_amounts = js::parse_vec::<Uint128>(&asset_amounts, "asset_amounts")?;
+    let total_fee_rate = js::parse::<Decimal>(&total_fee_rate, "total_fee_rate")?;
+
+    let result = astroport::pair_xyk::swap::simulate_ladder(
+        price_step,
+        levels,
+        &asset_amounts,
+        total_fee_rate,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating ladder: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn xyk_sample_curve(
+    min_offer_amount: &str,
+    max_offer_amount: &str,
+    points: &str,
+    spacing: &str,
+    ask_ind: &str,
     asset_amounts: &str,
-    total_share: &str,
+    total_fee_rate: &str,
 ) -> Result<JsValue, JsValue> {
     utils::set_panic_hook();
 
-    let amount = amount
+    let min_offer_amount = min_offer_amount
         .parse()
-        .map_err(|e| JsValue::from_str(&format!("Invalid amount: {}", e)))?;
+        .map_err(|e| JsValue::from_str(&format!("Invalid min_offer_amount: {}", e)))?;
+
+    let max_offer_amount = max_offer_amount
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid max_offer_amount: {}", e)))?;
+
+    let points = points
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid points: {}", e)))?;
+
+    let spacing = spacing
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid spacing: {}", e)))?;
+
+    let ask_ind = ask_ind
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid ask_ind: {}", e)))?;
+
+    let asset_amounts = serde_json::from_str::<Vec<Uint128>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let total_fee_rate = Decimal::from_str(total_fee_rate)
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_fee_rate: {}", e)))?;
+
+    let result = astroport::pair_xyk::swap::sample_curve(
+        min_offer_amount,
+        max_offer_amount,
+        points,
+        spacing,
+        ask_ind,
+        &asset_amounts,
+        total_fee_rate,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while sampling curve: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(js_name = xykSampleCurve, unchecked_return_type = "CurvePoint[]")]
+pub fn xyk_sample_curve_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] min_offer_amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] max_offer_amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] points: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Spacing")] spacing: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_ind: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] total_fee_rate: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let min_offer_amount = js::parse(&min_offer_amount, "min_offer_amount")?;
+    let max_offer_amount = js::parse(&max_offer_amount, "max_offer_amount")?;
+    let points = js::parse(&points, "points")?;
+    let spacing = js::parse(&spacing, "spacing")?;
+    let ask_ind = js::parse(&ask_ind, "ask_ind")?;
+    let asset_amounts = js::parse_vec::<Uint128>(&asset_amounts, "asset_amounts")?;
+    let total_fee_rate = js::parse::<Decimal>(&total_fee_rate, "total_fee_rate")?;
+
+    let result = astroport::pair_xyk::swap::sample_curve(
+        min_offer_amount,
+        max_offer_amount,
+        points,
+        spacing,
+        ask_ind,
+        &asset_amounts,
+        total_fee_rate,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while sampling curve: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+/// An xyk pool kept on the JS side: built once from its state, queried any number of times and
+/// updated as the balances change. Results are returned as plain JS objects.
+#[wasm_bindgen]
+pub struct XykPool {
+    pool: astroport::pair_xyk::pool::Pool,
+}
+
+#[wasm_bindgen]
+impl XykPool {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        #[wasm_bindgen(unchecked_param_type = "Input<XykPoolState> | string")] state: JsValue,
+    ) -> Result<XykPool, JsValue> {
+        utils::set_panic_hook();
+
+        let state = js::from_value::<astroport::pair_xyk::pool::PoolState>(&state, "state")?;
+
+        let pool = astroport::pair_xyk::pool::Pool::new(state)
+            .map_err(|e| JsValue::from_str(&format!("Error while creating pool: {}", e)))?;
+
+        Ok(XykPool { pool })
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "XykPoolState")]
+    pub fn state(&self) -> Result<JsValue, JsValue> {
+        js::to_value(self.pool.state())
+    }
+
+    pub fn update(
+        &mut self,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+        #[wasm_bindgen(unchecked_optional_param_type = "IntegerInput")] total_share: Option<
+            JsValue,
+        >,
+    ) -> Result<(), JsValue> {
+        let asset_amounts = js::parse_vec::<Uint128>(&asset_amounts, "asset_amounts")?;
+
+        let total_share = total_share
+            .map(|total_share| js::parse(&total_share, "total_share"))
+            .transpose()?;
+
+        self.pool
+            .update(asset_amounts, total_share)
+            .map_err(|e| JsValue::from_str(&format!("Error while updating pool: {}", e)))
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "XykSwapSimulationResponse")]
+    pub fn swap(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput")] offer_amount: JsValue,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_ind: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let offer_amount = js::parse(&offer_amount, "offer_amount")?;
+
+        let ask_ind = js::parse(&ask_ind, "ask_ind")?;
+
+        let result = self
+            .pool
+            .swap(offer_amount, ask_ind)
+            .map_err(|e| JsValue::from_str(&format!("Error while simulating swap: {}", e)))?;
+
+        js::to_value(&result)
+    }
+
+    #[wasm_bindgen(
+        js_name = reverseSwap,
+        unchecked_return_type = "XykReverseSwapSimulationResponse"
+    )]
+    pub fn reverse_swap(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_amount: JsValue,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_ind: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let ask_amount = js::parse(&ask_amount, "ask_amount")?;
+
+        let ask_ind = js::parse(&ask_ind, "ask_ind")?;
+
+        let result = self.pool.reverse_swap(ask_amount, ask_ind).map_err(|e| {
+            JsValue::from_str(&format!("Error while simulating reverse swap: {}", e))
+        })?;
+
+        js::to_value(&result)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "XykProvideSimulationResponse")]
+    pub fn provide(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] deposits: JsValue,
+        #[wasm_bindgen(unchecked_optional_param_type = "Decimal")] slippage_tolerance: Option<
+            JsValue,
+        >,
+        #[wasm_bindgen(unchecked_optional_param_type = "IntegerInput")] min_lp_to_receive: Option<
+            JsValue,
+        >,
+    ) -> Result<JsValue, JsValue> {
+        let deposits = js::parse_vec::<Uint128>(&deposits, "deposits")?;
+
+        let slippage_tolerance = slippage_tolerance
+            .map(|slippage_tolerance| js::parse(&slippage_tolerance, "slippage_tolerance"))
+            .transpose()?;
+
+        let min_lp_to_receive = min_lp_to_receive
+            .map(|min_lp_to_receive| js::parse(&min_lp_to_receive, "min_lp_to_receive"))
+            .transpose()?;
+
+        let result = self
+            .pool
+            .provide(&deposits, slippage_tolerance, min_lp_to_receive)
+            .map_err(|e| JsValue::from_str(&format!("Error while simulating provide: {}", e)))?;
+
+        js::to_value(&result)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "XykWithdrawSimulationResponse")]
+    pub fn withdraw(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput")] amount: JsValue,
+        #[wasm_bindgen(unchecked_optional_param_type = "IntegerInput[] | string")]
+        min_assets_to_receive: Option<JsValue>,
+    ) -> Result<JsValue, JsValue> {
+        let amount = js::parse(&amount, "amount")?;
+
+        let min_assets_to_receive = min_assets_to_receive
+            .map(|min_assets_to_receive| {
+                js::parse_vec::<Uint128>(&min_assets_to_receive, "min_assets_to_receive")
+            })
+            .transpose()?;
+
+        let result = self
+            .pool
+            .withdraw(amount, min_assets_to_receive)
+            .map_err(|e| JsValue::from_str(&format!("Error while simulating withdraw: {}", e)))?;
+
+        js::to_value(&result)
+    }
+
+    #[wasm_bindgen(js_name = spotPrice, unchecked_return_type = "Decimal")]
+    pub fn spot_price(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_ind: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let ask_ind = js::parse(&ask_ind, "ask_ind")?;
+
+        let result = self
+            .pool
+            .spot_price(ask_ind)
+            .map_err(|e| JsValue::from_str(&format!("Error while computing spot price: {}", e)))?;
+
+        js::to_value(&result)
+    }
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn xyk_provide(
+    deposits: &str,
+    asset_amounts: &str,
+    total_share: &str,
+    slippage_tolerance: Option<String>,
+    min_lp_to_receive: Option<String>,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let deposits = serde_json::from_str::<Vec<Uint128>>(deposits)
+        .map_err(|e| JsValue::from_str(&format!("Invalid deposits: {}", e)))?;
 
     let asset_amounts = serde_json::from_str::<Vec<Uint128>>(asset_amounts)
         .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
@@ -306,8 +4329,24 @@ pub fn stable_withdraw(
         .parse()
         .map_err(|e| JsValue::from_str(&format!("Invalid total_share: {}", e)))?;
 
-    let result = astroport::pair_stable::withdraw::simulate(amount, &asset_amounts, total_share)
-        .map_err(|e| JsValue::from_str(&format!("Error while simulating withdraw: {}", e)))?;
+    let slippage_tolerance = slippage_tolerance
+        .map(|slippage_tolerance| slippage_tolerance.parse())
+        .transpose()
+        .map_err(|e| JsValue::from_str(&format!("Invalid slippage_tolerance: {}", e)))?;
+
+    let min_lp_to_receive = min_lp_to_receive
+        .map(|min_lp_to_receive| min_lp_to_receive.parse())
+        .transpose()
+        .map_err(|e| JsValue::from_str(&format!("Invalid min_lp_to_receive: {}", e)))?;
+
+    let result = astroport::pair_xyk::provide::simulate(
+        &deposits,
+        &asset_amounts,
+        total_share,
+        slippage_tolerance,
+        min_lp_to_receive,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating provide: {}", e)))?;
 
     let json_result = serde_json::to_string(&result)
         .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
@@ -315,33 +4354,125 @@ pub fn stable_withdraw(
     Ok(JsValue::from_str(&json_result))
 }
 
-#[wasm_bindgen]
-pub fn xyk_swap(
-    offer_amount: &str,
-    ask_ind: &str,
+#[wasm_bindgen(js_name = xykProvide, unchecked_return_type = "XykProvideSimulationResponse")]
+pub fn xyk_provide_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] deposits: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] total_share: JsValue,
+    #[wasm_bindgen(unchecked_optional_param_type = "Decimal")] slippage_tolerance: Option<JsValue>,
+    #[wasm_bindgen(unchecked_optional_param_type = "IntegerInput")] min_lp_to_receive: Option<
+        JsValue,
+    >,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let deposits = js::parse_vec::<Uint128>(&deposits, "deposits")?;
+    let asset_amounts = js::parse_vec::<Uint128>(&asset_amounts, "asset_amounts")?;
+    let total_share = js::parse(&total_share, "total_share")?;
+
+    let slippage_tolerance = slippage_tolerance
+        .map(|slippage_tolerance| js::parse(&slippage_tolerance, "slippage_tolerance"))
+        .transpose()?;
+
+    let min_lp_to_receive = min_lp_to_receive
+        .map(|min_lp_to_receive| js::parse(&min_lp_to_receive, "min_lp_to_receive"))
+        .transpose()?;
+
+    let result = astroport::pair_xyk::provide::simulate(
+        &deposits,
+        &asset_amounts,
+        total_share,
+        slippage_tolerance,
+        min_lp_to_receive,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating provide: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn xyk_reverse_provide(
+    share_amount: &str,
     asset_amounts: &str,
-    total_fee_rate: &str,
+    total_share: &str,
 ) -> Result<JsValue, JsValue> {
     utils::set_panic_hook();
 
-    let offer_amount = offer_amount
+    let share_amount = share_amount
         .parse()
-        .map_err(|e| JsValue::from_str(&format!("Invalid offer_amount: {}", e)))?;
+        .map_err(|e| JsValue::from_str(&format!("Invalid share_amount: {}", e)))?;
 
-    let ask_ind = ask_ind
+    let asset_amounts = serde_json::from_str::<Vec<Uint128>>(asset_amounts)
+        .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
+
+    let total_share = total_share
         .parse()
-        .map_err(|e| JsValue::from_str(&format!("Invalid ask_ind: {}", e)))?;
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_share: {}", e)))?;
+
+    let result =
+        astroport::pair_xyk::provide::simulate_reverse(share_amount, &asset_amounts, total_share)
+            .map_err(|e| JsValue::from_str(&format!("Error while simulating provide: {}", e)))?;
+
+    let json_result = serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+#[wasm_bindgen(
+    js_name = xykReverseProvide,
+    unchecked_return_type = "XykReverseProvideSimulationResponse"
+)]
+pub fn xyk_reverse_provide_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] share_amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] total_share: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let share_amount = js::parse(&share_amount, "share_amount")?;
+    let asset_amounts = js::parse_vec::<Uint128>(&asset_amounts, "asset_amounts")?;
+    let total_share = js::parse(&total_share, "total_share")?;
+
+    let result =
+        astroport::pair_xyk::provide::simulate_reverse(share_amount, &asset_amounts, total_share)
+            .map_err(|e| JsValue::from_str(&format!("Error while simulating provide: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn xyk_withdraw(
+    amount: &str,
+    asset_amounts: &str,
+    total_share: &str,
+    min_assets_to_receive: Option<String>,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let amount = amount
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid amount: {}", e)))?;
 
     let asset_amounts = serde_json::from_str::<Vec<Uint128>>(asset_amounts)
         .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
 
-    let total_fee_rate = total_fee_rate
+    let total_share = total_share
         .parse()
-        .map_err(|e| JsValue::from_str(&format!("Invalid total_fee_rate: {}", e)))?;
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_share: {}", e)))?;
 
-    let result =
-        astroport::pair_xyk::swap::simulate(offer_amount, ask_ind, &asset_amounts, total_fee_rate)
-            .map_err(|e| JsValue::from_str(&format!("Error while simulating swap: {}", e)))?;
+    let min_assets_to_receive = min_assets_to_receive
+        .map(|min_assets_to_receive| serde_json::from_str::<Vec<Uint128>>(&min_assets_to_receive))
+        .transpose()
+        .map_err(|e| JsValue::from_str(&format!("Invalid min_assets_to_receive: {}", e)))?;
+
+    let result = astroport::pair_xyk::withdraw::simulate(
+        amount,
+        &asset_amounts,
+        total_share,
+        min_assets_to_receive,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating withdraw: {}", e)))?;
 
     let json_result = serde_json::to_string(&result)
         .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
@@ -349,16 +4480,55 @@ pub fn xyk_swap(
     Ok(JsValue::from_str(&json_result))
 }
 
-#[wasm_bindgen]
-pub fn xyk_provide(
-    deposits: &str,
+#[wasm_bindgen(js_name = xykWithdraw, unchecked_return_type = "XykWithdrawSimulationResponse")]
+pub fn xyk_withdraw_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] total_share: JsValue,
+    #[wasm_bindgen(unchecked_optional_param_type = "IntegerInput[] | string")]
+    min_assets_to_receive: Option<JsValue>,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let amount = js::parse(&amount, "amount")?;
+    let asset_amounts = js::parse_vec::<Uint128>(&asset_amounts, "asset_amounts")?;
+    let total_share = js::parse(&total_share, "total_share")?;
+
+    let min_assets_to_receive = min_assets_to_receive
+        .map(|min_assets_to_receive| {
+            js::parse_vec::<Uint128>(&min_assets_to_receive, "min_assets_to_receive")
+        })
+        .transpose()?;
+
+    let result = astroport::pair_xyk::withdraw::simulate(
+        amount,
+        &asset_amounts,
+        total_share,
+        min_assets_to_receive,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating withdraw: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn xyk_zap_in(
+    offer_amount: &str,
+    offer_ind: &str,
     asset_amounts: &str,
     total_share: &str,
+    total_fee_rate: &str,
+    maker_fee_share: Option<String>,
 ) -> Result<JsValue, JsValue> {
     utils::set_panic_hook();
 
-    let deposits = serde_json::from_str::<Vec<Uint128>>(deposits)
-        .map_err(|e| JsValue::from_str(&format!("Invalid deposits: {}", e)))?;
+    let offer_amount = offer_amount
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid offer_amount: {}", e)))?;
+
+    let offer_ind = offer_ind
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid offer_ind: {}", e)))?;
 
     let asset_amounts = serde_json::from_str::<Vec<Uint128>>(asset_amounts)
         .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
@@ -367,8 +4537,24 @@ pub fn xyk_provide(
         .parse()
         .map_err(|e| JsValue::from_str(&format!("Invalid total_share: {}", e)))?;
 
-    let result = astroport::pair_xyk::provide::simulate(&deposits, &asset_amounts, total_share)
-        .map_err(|e| JsValue::from_str(&format!("Error while simulating provide: {}", e)))?;
+    let total_fee_rate = total_fee_rate
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_fee_rate: {}", e)))?;
+
+    let maker_fee_share = maker_fee_share
+        .map(|maker_fee_share| maker_fee_share.parse())
+        .transpose()
+        .map_err(|e| JsValue::from_str(&format!("Invalid maker_fee_share: {}", e)))?;
+
+    let result = astroport::pair_xyk::zap::simulate_zap_in(
+        offer_amount,
+        offer_ind,
+        &asset_amounts,
+        total_share,
+        total_fee_rate,
+        maker_fee_share,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating zap: {}", e)))?;
 
     let json_result = serde_json::to_string(&result)
         .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
@@ -376,11 +4562,46 @@ pub fn xyk_provide(
     Ok(JsValue::from_str(&json_result))
 }
 
-#[wasm_bindgen]
-pub fn xyk_withdraw(
+#[wasm_bindgen(js_name = xykZapIn, unchecked_return_type = "XykZapInSimulationResponse")]
+pub fn xyk_zap_in_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] offer_amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] offer_ind: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] total_share: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] total_fee_rate: JsValue,
+    #[wasm_bindgen(unchecked_optional_param_type = "Decimal")] maker_fee_share: Option<JsValue>,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let offer_amount = js::parse(&offer_amount, "offer_amount")?;
+    let offer_ind = js::parse(&offer_ind, "offer_ind")?;
+    let asset_amounts = js::parse_vec::<Uint128>(&asset_amounts, "asset_amounts")?;
+    let total_share = js::parse(&total_share, "total_share")?;
+    let total_fee_rate = js::parse(&total_fee_rate, "total_fee_rate")?;
+    let maker_fee_share = maker_fee_share
+        .map(|maker_fee_share| js::parse(&maker_fee_share, "maker_fee_share"))
+        .transpose()?;
+
+    let result = astroport::pair_xyk::zap::simulate_zap_in(
+        offer_amount,
+        offer_ind,
+        &asset_amounts,
+        total_share,
+        total_fee_rate,
+        maker_fee_share,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating zap: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn xyk_zap_out(
     amount: &str,
+    ask_ind: &str,
     asset_amounts: &str,
     total_share: &str,
+    total_fee_rate: &str,
 ) -> Result<JsValue, JsValue> {
     utils::set_panic_hook();
 
@@ -388,6 +4609,10 @@ pub fn xyk_withdraw(
         .parse()
         .map_err(|e| JsValue::from_str(&format!("Invalid amount: {}", e)))?;
 
+    let ask_ind = ask_ind
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid ask_ind: {}", e)))?;
+
     let asset_amounts = serde_json::from_str::<Vec<Uint128>>(asset_amounts)
         .map_err(|e| JsValue::from_str(&format!("Invalid asset_amounts: {}", e)))?;
 
@@ -395,11 +4620,88 @@ pub fn xyk_withdraw(
         .parse()
         .map_err(|e| JsValue::from_str(&format!("Invalid total_share: {}", e)))?;
 
-    let result = astroport::pair_xyk::withdraw::simulate(amount, &asset_amounts, total_share)
-        .map_err(|e| JsValue::from_str(&format!("Error while simulating withdraw: {}", e)))?;
+    let total_fee_rate = total_fee_rate
+        .parse()
+        .map_err(|e| JsValue::from_str(&format!("Invalid total_fee_rate: {}", e)))?;
+
+    let result = astroport::pair_xyk::zap::simulate_zap_out(
+        amount,
+        ask_ind,
+        &asset_amounts,
+        total_share,
+        total_fee_rate,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating zap: {}", e)))?;
 
     let json_result = serde_json::to_string(&result)
         .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
 
     Ok(JsValue::from_str(&json_result))
 }
+
+#[wasm_bindgen(js_name = xykZapOut, unchecked_return_type = "XykZapOutSimulationResponse")]
+pub fn xyk_zap_out_object(
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] amount: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] ask_ind: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput[] | string")] asset_amounts: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "IntegerInput")] total_share: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Decimal")] total_fee_rate: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let amount = js::parse(&amount, "amount")?;
+    let ask_ind = js::parse(&ask_ind, "ask_ind")?;
+    let asset_amounts = js::parse_vec::<Uint128>(&asset_amounts, "asset_amounts")?;
+    let total_share = js::parse(&total_share, "total_share")?;
+    let total_fee_rate = js::parse(&total_fee_rate, "total_fee_rate")?;
+
+    let result = astroport::pair_xyk::zap::simulate_zap_out(
+        amount,
+        ask_ind,
+        &asset_amounts,
+        total_share,
+        total_fee_rate,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Error while simulating zap: {}", e)))?;
+
+    js::to_value(&result)
+}
+
+#[wasm_bindgen(unchecked_return_type = "string")]
+pub fn pair_query(pair: &str, query: &str) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let pair = serde_json::from_str::<astroport::pair::PairState>(pair)
+        .map_err(|e| JsValue::from_str(&format!("Invalid pair: {}", e)))?;
+
+    let query = serde_json::from_str::<astroport::pair::QueryMsg>(query)
+        .map_err(|e| JsValue::from_str(&format!("Invalid query: {}", e)))?;
+
+    let result = astroport::pair::Pair::new(pair)
+        .and_then(|pair| pair.query(query))
+        .map_err(|e| JsValue::from_str(&format!("Error while querying pair: {}", e)))?;
+
+    let json_result = String::from_utf8(result.0)
+        .map_err(|e| JsValue::from_str(&format!("Error while serializing result: {}", e)))?;
+
+    Ok(JsValue::from_str(&json_result))
+}
+
+/// Returns the parsed response, typed `any` like the smart queries of CosmJS.
+#[wasm_bindgen(js_name = pairQuery, unchecked_return_type = "any")]
+pub fn pair_query_object(
+    #[wasm_bindgen(unchecked_param_type = "Input<PairState> | string")] pair: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Input<PairQueryMsg> | string")] query: JsValue,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let pair = js::from_value::<astroport::pair::PairState>(&pair, "pair")?;
+    let query = js::from_value::<astroport::pair::QueryMsg>(&query, "query")?;
+
+    let result = astroport::pair::Pair::new(pair)
+        .and_then(|pair| pair.query(query))
+        .and_then(|result| cosmwasm_std::from_json::<serde_json::Value>(&result))
+        .map_err(|e| JsValue::from_str(&format!("Error while querying pair: {}", e)))?;
+
+    js::to_value(&result)
+}
@@ -0,0 +1,212 @@
+//! A mock of the pair contracts for the unit tests of contracts querying them.
+//!
+//! [`PairQuerier`] answers the smart queries of the registered pairs with the responses computed
+//! by [`Pair::query`], and is installed as the wasm handler of a [`MockQuerier`]:
+//!
+//! ```ignore
+//! let mut deps = mock_dependencies();
+//! PairQuerier::new()
+//!     .with_pair("pair", serde_json::from_str::<PairState>(PAIR_JSON)?)?
+//!     .install(&mut deps.querier);
+//! ```
+
+use std::collections::HashMap;
+
+use cosmwasm_std::testing::MockQuerier;
+use cosmwasm_std::{
+    from_json, ContractResult, QuerierResult, StdResult, SystemError, SystemResult, WasmQuery,
+};
+
+use crate::astroport::pair::{Pair, QueryMsg};
+
+pub use crate::astroport::asset::{Asset, AssetInfo};
+pub use crate::astroport::pair::PairState;
+pub use crate::astroport::pair_concentrated::pool::PoolState as ConcentratedPoolState;
+pub use crate::astroport::pair_stable::pool::PoolState as StablePoolState;
+pub use crate::astroport::pair_xyk::pool::PoolState as XykPoolState;
+pub use crate::astroport::pool::PoolState;
+
+/// Answers the queries of pair contracts registered by their addresses.
+#[derive(Clone, Debug, Default)]
+pub struct PairQuerier {
+    pairs: HashMap<String, Pair>,
+}
+
+impl PairQuerier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_pair(mut self, address: impl Into<String>, state: PairState) -> StdResult<Self> {
+        self.update_pair(address, state)?;
+        Ok(self)
+    }
+
+    /// Registers a pair, replacing the state of the pair with the same address if any.
+    pub fn update_pair(&mut self, address: impl Into<String>, state: PairState) -> StdResult<()> {
+        self.pairs.insert(address.into(), Pair::new(state)?);
+        Ok(())
+    }
+
+    /// Answers a query of a registered pair, or returns `None` if `request` isn't one. Failed
+    /// queries are returned as contract errors, the same as by the pair contract.
+    pub fn query(&self, request: &WasmQuery) -> Option<QuerierResult> {
+        let (pair, msg) = match request {
+            WasmQuery::Smart { contract_addr, msg } => (self.pairs.get(contract_addr)?, msg),
+            _ => return None,
+        };
+
+        let result = from_json::<QueryMsg>(msg).and_then(|msg| pair.query(msg));
+        Some(SystemResult::Ok(ContractResult::from(result)))
+    }
+
+    /// Sets `self` as the wasm handler of `querier`. Queries of other contracts fail with
+    /// `NoSuchContract`, the same as by the default handler.
+    pub fn install<C: serde::de::DeserializeOwned>(self, querier: &mut MockQuerier<C>) {
+        querier.update_wasm(move |request| {
+            self.query(request).unwrap_or_else(|| {
+                let addr = match request {
+                    WasmQuery::Smart { contract_addr, .. }
+                    | WasmQuery::Raw { contract_addr, .. } => contract_addr.clone(),
+                    WasmQuery::ContractInfo { contract_addr } => contract_addr.clone(),
+                    _ => String::new(),
+                };
+                SystemResult::Err(SystemError::NoSuchContract { addr })
+            })
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::{to_json_binary, Decimal, QuerierWrapper, Uint128};
+
+    use crate::astroport::pair::PoolResponse;
+
+    fn info(denom: &str) -> AssetInfo {
+        AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        }
+    }
+
+    fn pair_state(asset_amounts: [u128; 2]) -> PairState {
+        PairState {
+            asset_infos: vec![info("uluna"), info("uusd")],
+            pool: PoolState::Xyk(XykPoolState {
+                asset_amounts: asset_amounts.map(Uint128::new).to_vec(),
+                total_share: Uint128::new(100_000_000),
+                total_fee_rate: Decimal::permille(3),
+            }),
+        }
+    }
+
+    fn pool_of(querier: &QuerierWrapper, address: &str) -> StdResult<PoolResponse> {
+        querier.query_wasm_smart(address, &QueryMsg::Pool {})
+    }
+
+    #[test]
+    fn installed_querier_answers_the_pairs() {
+        let mut deps = mock_dependencies();
+        PairQuerier::new()
+            .with_pair("pair", pair_state([1_000_000_000, 10_000_000]))
+            .unwrap()
+            .install(&mut deps.querier);
+
+        let pool = pool_of(&deps.as_ref().querier, "pair").unwrap();
+        assert_eq!(
+            pool.assets,
+            [
+                Asset {
+                    info: info("uluna"),
+                    amount: Uint128::new(1_000_000_000),
+                },
+                Asset {
+                    info: info("uusd"),
+                    amount: Uint128::new(10_000_000),
+                },
+            ]
+        );
+
+        assert_eq!(
+            pool_of(&deps.as_ref().querier, "other")
+                .unwrap_err()
+                .to_string(),
+            "Generic error: Querier system error: No such contract: other"
+        );
+        let simulation = deps.as_ref().querier.query_wasm_smart::<Uint128>(
+            "pair",
+            &QueryMsg::Simulation {
+                offer_asset: Asset {
+                    info: info("uatom"),
+                    amount: Uint128::new(1_000),
+                },
+                ask_asset_info: None,
+            },
+        );
+        assert_eq!(
+            simulation.unwrap_err().to_string(),
+            "Generic error: Querier contract error: Generic error: Asset mismatch between the requested and the stored asset in contract"
+        );
+    }
+
+    #[test]
+    fn updated_pairs_replace_their_states() {
+        let mut querier = PairQuerier::new()
+            .with_pair("pair", pair_state([1_000_000_000, 10_000_000]))
+            .unwrap();
+
+        querier
+            .update_pair("pair", pair_state([2_000_000_000, 20_000_000]))
+            .unwrap();
+
+        let response = querier
+            .query(&WasmQuery::Smart {
+                contract_addr: "pair".to_string(),
+                msg: to_json_binary(&QueryMsg::Pool {}).unwrap(),
+            })
+            .unwrap();
+        let pool: PoolResponse = from_json(response.unwrap().unwrap()).unwrap();
+        assert_eq!(pool.assets[0].amount, Uint128::new(2_000_000_000));
+        assert_eq!(pool.assets[1].amount, Uint128::new(20_000_000));
+    }
+
+    #[test]
+    fn other_requests_are_left_to_the_querier() {
+        let querier = PairQuerier::new()
+            .with_pair("pair", pair_state([1_000_000_000, 10_000_000]))
+            .unwrap();
+
+        assert_eq!(
+            querier.query(&WasmQuery::Raw {
+                contract_addr: "pair".to_string(),
+                key: b"config".into(),
+            }),
+            None
+        );
+        assert_eq!(
+            querier.query(&WasmQuery::Smart {
+                contract_addr: "other".to_string(),
+                msg: to_json_binary(&QueryMsg::Pool {}).unwrap(),
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn pairs_with_wrong_asset_infos_are_rejected() {
+        let state = PairState {
+            asset_infos: vec![info("uluna")],
+            ..pair_state([1_000_000_000, 10_000_000])
+        };
+
+        assert_eq!(
+            PairQuerier::new()
+                .with_pair("pair", state)
+                .unwrap_err()
+                .to_string(),
+            "Generic error: Wrong number of asset infos: expected 2, actual 1"
+        );
+    }
+}
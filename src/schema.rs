@@ -0,0 +1,249 @@
+//! JSON Schemas of the values passed to and returned by the exported functions.
+//!
+//! Schemas are named after the functions, e.g. `xyk_swap_response.json` describes the result of
+//! `xyk_swap` and `xykSwap`. Results shared by all pairs, such as depths, have a single schema.
+//! `pool_state.json` describes the pool files of the command-line simulator and
+//! `pair_state.json` and `pair_query.json` the arguments of `pair_query`.
+
+use std::path::Path;
+
+use cosmwasm_schema::{export_schema_with_title, schema_for};
+use cosmwasm_std::Decimal;
+
+use crate::astroport::lib::batch::BatchItem;
+use crate::astroport::lib::curve::{CurvePoint, Spacing};
+use crate::astroport::lib::depth::DepthResponse;
+use crate::astroport::lib::ladder::LadderResponse;
+use crate::astroport::pair::{PairState, QueryMsg};
+use crate::astroport::pool::{PoolState, RouteResponse};
+use crate::astroport::{pair_concentrated, pair_stable, pair_xyk};
+
+/// Writes the schemas into `out_dir`, overwriting existing files.
+pub fn export(out_dir: &Path) {
+    let export = |schema, title| export_schema_with_title(&schema, out_dir, title);
+
+    export(schema_for!(Spacing), "spacing");
+    export(schema_for!(DepthResponse), "depth_response");
+    export(schema_for!(LadderResponse), "ladder_response");
+    export(schema_for!(Vec<CurvePoint>), "sample_curve_response");
+    export(schema_for!(Decimal), "spot_price_response");
+    export(schema_for!(PoolState), "pool_state");
+    export(schema_for!(RouteResponse), "route_response");
+    export(schema_for!(PairState), "pair_state");
+    export(schema_for!(QueryMsg), "pair_query");
+
+    export(schema_for!(pair_xyk::pool::PoolState), "xyk_pool_state");
+    export(
+        schema_for!(Vec<pair_xyk::swap::BatchPool>),
+        "xyk_swap_batch_pools",
+    );
+    export(
+        schema_for!(Vec<pair_xyk::swap::BatchSwapRequest>),
+        "xyk_swap_batch_requests",
+    );
+    export(
+        schema_for!(pair_xyk::swap::SimulationResponse),
+        "xyk_swap_response",
+    );
+    export(
+        schema_for!(Vec<BatchItem<pair_xyk::swap::SimulationResponse>>),
+        "xyk_swap_batch_response",
+    );
+    export(
+        schema_for!(pair_xyk::swap::ReverseSimulationResponse),
+        "xyk_reverse_swap_response",
+    );
+    export(
+        schema_for!(pair_xyk::swap::SwapToPriceResponse),
+        "xyk_swap_to_price_response",
+    );
+    export(
+        schema_for!(pair_xyk::provide::SimulationResponse),
+        "xyk_provide_response",
+    );
+    export(
+        schema_for!(pair_xyk::provide::ReverseSimulationResponse),
+        "xyk_reverse_provide_response",
+    );
+    export(
+        schema_for!(pair_xyk::withdraw::SimulationResponse),
+        "xyk_withdraw_response",
+    );
+    export(
+        schema_for!(pair_xyk::zap::ZapInSimulationResponse),
+        "xyk_zap_in_response",
+    );
+    export(
+        schema_for!(pair_xyk::zap::ZapOutSimulationResponse),
+        "xyk_zap_out_response",
+    );
+
+    export(
+        schema_for!(pair_stable::pool::PoolState),
+        "stable_pool_state",
+    );
+    export(
+        schema_for!(Vec<pair_stable::swap::BatchPool>),
+        "stable_swap_batch_pools",
+    );
+    export(
+        schema_for!(Vec<pair_stable::swap::BatchSwapRequest>),
+        "stable_swap_batch_requests",
+    );
+    export(
+        schema_for!(pair_stable::swap::SimulationResponse),
+        "stable_swap_response",
+    );
+    export(
+        schema_for!(Vec<BatchItem<pair_stable::swap::SimulationResponse>>),
+        "stable_swap_batch_response",
+    );
+    export(
+        schema_for!(pair_stable::swap::ReverseSimulationResponse),
+        "stable_reverse_swap_response",
+    );
+    export(
+        schema_for!(pair_stable::swap::SwapToPriceResponse),
+        "stable_swap_to_price_response",
+    );
+    export(
+        schema_for!(pair_stable::provide::SimulationResponse),
+        "stable_provide_response",
+    );
+    export(
+        schema_for!(pair_stable::provide::ReverseSimulationResponse),
+        "stable_reverse_provide_response",
+    );
+    export(
+        schema_for!(pair_stable::withdraw::SimulationResponse),
+        "stable_withdraw_response",
+    );
+    export(
+        schema_for!(pair_stable::withdraw::WithdrawOneSimulationResponse),
+        "stable_withdraw_one_response",
+    );
+    export(
+        schema_for!(pair_stable::withdraw::ImbalancedWithdrawSimulationResponse),
+        "stable_withdraw_imbalanced_response",
+    );
+    export(
+        schema_for!(pair_stable::zap::ZapInSimulationResponse),
+        "stable_zap_in_response",
+    );
+    export(
+        schema_for!(pair_stable::zap::ZapOutSimulationResponse),
+        "stable_zap_out_response",
+    );
+
+    export(
+        schema_for!(pair_concentrated::pool::PoolState),
+        "concentrated_pool_state",
+    );
+    export(
+        schema_for!(pair_concentrated::state::RepegParams),
+        "concentrated_repeg_params",
+    );
+    export(
+        schema_for!(Vec<pair_concentrated::swap::BatchPool>),
+        "concentrated_swap_batch_pools",
+    );
+    export(
+        schema_for!(Vec<pair_concentrated::swap::BatchSwapRequest>),
+        "concentrated_swap_batch_requests",
+    );
+    export(
+        schema_for!(pair_concentrated::swap::SwapSimulationResponse),
+        "concentrated_swap_response",
+    );
+    export(
+        schema_for!(Vec<BatchItem<pair_concentrated::swap::SwapSimulationResponse>>),
+        "concentrated_swap_batch_response",
+    );
+    export(
+        schema_for!(pair_concentrated::swap::ReverseSwapSimulationResponse),
+        "concentrated_reverse_swap_response",
+    );
+    export(
+        schema_for!(pair_concentrated::swap::SwapToPriceResponse),
+        "concentrated_swap_to_price_response",
+    );
+    export(
+        schema_for!(pair_concentrated::provide::SimulationResponse),
+        "concentrated_provide_response",
+    );
+    export(
+        schema_for!(pair_concentrated::provide::ReverseSimulationResponse),
+        "concentrated_reverse_provide_response",
+    );
+    export(
+        schema_for!(pair_concentrated::withdraw::SimulationResponse),
+        "concentrated_withdraw_response",
+    );
+    export(
+        schema_for!(pair_concentrated::zap::ZapInSimulationResponse),
+        "concentrated_zap_in_response",
+    );
+    export(
+        schema_for!(pair_concentrated::zap::ZapOutSimulationResponse),
+        "concentrated_zap_out_response",
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{create_dir_all, read_dir, read_to_string, remove_dir_all};
+    use std::path::PathBuf;
+
+    fn file_names(dir: &Path) -> Vec<String> {
+        let mut names = read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn committed_schemas_are_up_to_date() {
+        let committed = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("schema");
+        let out_dir = std::env::temp_dir().join(format!("math-schema-{}", std::process::id()));
+        create_dir_all(&out_dir).unwrap();
+
+        export(&out_dir);
+
+        let names = file_names(&out_dir);
+        assert_eq!(names, file_names(&committed), "run `make schema`");
+        for name in names {
+            assert_eq!(
+                read_to_string(out_dir.join(&name)).unwrap(),
+                read_to_string(committed.join(&name)).unwrap(),
+                "{name} is out of date, run `make schema`"
+            );
+        }
+        remove_dir_all(&out_dir).unwrap();
+    }
+
+    #[test]
+    fn pool_state_schemas_require_the_fields_of_the_states() {
+        let required = |schema: cosmwasm_schema::schemars::schema::RootSchema| {
+            let mut required = schema
+                .schema
+                .object
+                .unwrap()
+                .required
+                .into_iter()
+                .collect::<Vec<_>>();
+            required.sort();
+            required
+        };
+
+        assert_eq!(
+            required(schema_for!(pair_xyk::pool::PoolState)),
+            ["asset_amounts", "total_fee_rate", "total_share"]
+        );
+        let concentrated = required(schema_for!(pair_concentrated::pool::PoolState));
+        assert!(concentrated.contains(&"price_scale".to_string()));
+        assert!(!concentrated.contains(&"xcp_profit_real".to_string()));
+    }
+}
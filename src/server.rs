@@ -0,0 +1,273 @@
+//! A local stand-in for the LCD of a chain, answering smart queries of pair contracts.
+//!
+//! The pairs are read from a fixture file mapping contract addresses to pair states, e.g.
+//! `{"terra1...": {"asset_infos": [...], "pool": {"xyk": {...}}}}`. Smart queries are served at
+//! `/cosmwasm/wasm/v1/contract/{addr}/smart/{base64_query}` as `{"data": ...}`, and failed
+//! queries as gRPC gateway errors, the same as by the LCD.
+
+use std::collections::HashMap;
+use std::fs;
+
+use cosmwasm_std::{from_json, Binary};
+use percent_encoding::percent_decode_str;
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Response, Server};
+
+use crate::astroport::pair::{Pair, PairState, QueryMsg};
+
+pub const USAGE: &str = "\
+Usage: astroport-math-server <FIXTURE_FILE> [--listen <ADDRESS>]
+
+FIXTURE_FILE maps pair contract addresses to pair states.
+The server listens on 127.0.0.1:1317 by default, the port of the LCD.
+";
+
+const DEFAULT_LISTEN: &str = "127.0.0.1:1317";
+
+const SMART_QUERY_PREFIX: &str = "/cosmwasm/wasm/v1/contract/";
+
+/// gRPC status codes of the errors, as returned by the gateway of the LCD
+const CODE_UNKNOWN: u16 = 2;
+const CODE_INVALID_ARGUMENT: u16 = 3;
+const CODE_NOT_FOUND: u16 = 5;
+const CODE_UNIMPLEMENTED: u16 = 12;
+
+/// Runs the server with the arguments following the binary name, serving until it fails.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut listen = DEFAULT_LISTEN.to_string();
+    let mut positional = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            "--listen" => listen = args.next().ok_or("Missing value of --listen")?.to_string(),
+            _ => positional.push(arg),
+        }
+    }
+
+    let fixture_file = match positional.as_slice() {
+        [fixture_file] => fixture_file,
+        _ => return Err(USAGE.to_string()),
+    };
+    let pairs = fs::read_to_string(fixture_file)
+        .map_err(|e| format!("Error while reading {}: {}", fixture_file, e))
+        .and_then(|json| load_pairs(&json))?;
+
+    let server =
+        Server::http(&listen).map_err(|e| format!("Error while listening on {}: {}", listen, e))?;
+    eprintln!("Serving {} pairs on http://{}", pairs.len(), listen);
+
+    let content_type = Header::from_bytes("Content-Type", "application/json")
+        .map_err(|_| "Invalid header".to_string())?;
+    for request in server.incoming_requests() {
+        let (status, body) = match request.method() {
+            Method::Get => respond(&pairs, request.url()),
+            _ => error(CODE_UNIMPLEMENTED, "Method Not Allowed".to_string()),
+        };
+
+        let response = Response::from_string(body.to_string())
+            .with_status_code(status)
+            .with_header(content_type.clone());
+        if let Err(e) = request.respond(response) {
+            eprintln!("Error while responding: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses a fixture file, see the module documentation.
+pub fn load_pairs(json: &str) -> Result<HashMap<String, Pair>, String> {
+    serde_json::from_str::<HashMap<String, PairState>>(json)
+        .map_err(|e| format!("Invalid fixture: {}", e))?
+        .into_iter()
+        .map(|(address, state)| {
+            Pair::new(state)
+                .map(|pair| (address.clone(), pair))
+                .map_err(|e| format!("Error while creating pair {}: {}", address, e))
+        })
+        .collect()
+}
+
+/// Returns the HTTP status and the body of the response to a GET of `url`.
+pub fn respond(pairs: &HashMap<String, Pair>, url: &str) -> (u16, Value) {
+    let path = url.split('?').next().unwrap_or_default();
+    let (address, query) = match path
+        .strip_prefix(SMART_QUERY_PREFIX)
+        .and_then(|path| path.split_once("/smart/"))
+    {
+        Some(route) => route,
+        None => return error(CODE_UNIMPLEMENTED, "Not Implemented".to_string()),
+    };
+
+    let pair = match pairs.get(address) {
+        Some(pair) => pair,
+        None => return error(CODE_NOT_FOUND, format!("no such contract: {}", address)),
+    };
+
+    let query = match percent_decode_str(query)
+        .decode_utf8()
+        .map_err(|e| e.to_string())
+        .and_then(|query| Binary::from_base64(&query).map_err(|e| e.to_string()))
+    {
+        Ok(query) => query,
+        Err(err) => return error(CODE_INVALID_ARGUMENT, format!("invalid query: {}", err)),
+    };
+
+    match from_json::<QueryMsg>(&query)
+        .and_then(|msg| pair.query(msg))
+        .and_then(|data| from_json::<Value>(&data))
+    {
+        Ok(data) => (200, json!({ "data": data })),
+        Err(err) => error(
+            CODE_UNKNOWN,
+            format!("{}: query wasm contract failed: unknown request", err),
+        ),
+    }
+}
+
+fn error(code: u16, message: String) -> (u16, Value) {
+    let status = match code {
+        CODE_INVALID_ARGUMENT => 400,
+        CODE_NOT_FOUND => 404,
+        CODE_UNIMPLEMENTED => 501,
+        _ => 500,
+    };
+
+    (
+        status,
+        json!({ "code": code, "message": message, "details": [] }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::to_json_binary;
+
+    const FIXTURE: &str = r#"{
+        "terra1pair": {
+            "asset_infos": [
+                { "native_token": { "denom": "uluna" } },
+                { "native_token": { "denom": "uusd" } }
+            ],
+            "pool": {
+                "xyk": {
+                    "asset_amounts": ["1000000000", "10000000"],
+                    "total_share": "100000000",
+                    "total_fee_rate": "0.003"
+                }
+            }
+        }
+    }"#;
+
+    fn smart_query(address: &str, msg: &Value) -> String {
+        format!(
+            "{}{}/smart/{}",
+            SMART_QUERY_PREFIX,
+            address,
+            to_json_binary(msg).unwrap().to_base64()
+        )
+    }
+
+    #[test]
+    fn smart_queries_are_answered_by_the_pairs() {
+        let pairs = load_pairs(FIXTURE).unwrap();
+        let expected = json!({
+            "data": {
+                "assets": [
+                    { "info": { "native_token": { "denom": "uluna" } }, "amount": "1000000000" },
+                    { "info": { "native_token": { "denom": "uusd" } }, "amount": "10000000" }
+                ],
+                "total_share": "100000000"
+            }
+        });
+
+        let url = smart_query("terra1pair", &json!({ "pool": {} }));
+        assert_eq!(respond(&pairs, &url), (200, expected.clone()));
+        assert_eq!(
+            respond(&pairs, &format!("{}?height=100", url)),
+            (200, expected.clone())
+        );
+        assert_eq!(respond(&pairs, &url.replace('=', "%3D")), (200, expected));
+    }
+
+    #[test]
+    fn failed_queries_are_gateway_errors() {
+        let pairs = load_pairs(FIXTURE).unwrap();
+        let error = |status, code, message: &str| {
+            (
+                status,
+                json!({ "code": code, "message": message, "details": [] }),
+            )
+        };
+
+        assert_eq!(
+            respond(&pairs, "/cosmos/bank/v1beta1/balances/terra1pair"),
+            error(501, 12, "Not Implemented")
+        );
+        assert_eq!(
+            respond(&pairs, &smart_query("terra1other", &json!({ "pool": {} }))),
+            error(404, 5, "no such contract: terra1other")
+        );
+        assert_eq!(
+            respond(
+                &pairs,
+                &format!("{}terra1pair/smart/not-base64", SMART_QUERY_PREFIX)
+            ),
+            error(
+                400,
+                3,
+                "invalid query: Invalid Base64 string: Invalid byte 45, offset 3."
+            )
+        );
+        assert_eq!(
+            respond(&pairs, &smart_query("terra1pair", &json!({ "config": {} }))),
+            error(
+                500,
+                2,
+                "Error parsing into type math::astroport::pair::QueryMsg: unknown variant `config`, expected one of `pool`, `share`, `simulation`, `reverse_simulation`, `simulate_provide`, `simulate_withdraw`: query wasm contract failed: unknown request"
+            )
+        );
+        let msg = json!({ "simulation": {
+            "offer_asset": { "info": { "native_token": { "denom": "uatom" } }, "amount": "1000" }
+        } });
+        assert_eq!(
+            respond(&pairs, &smart_query("terra1pair", &msg)),
+            error(
+                500,
+                2,
+                "Generic error: Asset mismatch between the requested and the stored asset in contract: query wasm contract failed: unknown request"
+            )
+        );
+    }
+
+    #[test]
+    fn fixtures_are_rejected_with_the_failing_pair() {
+        assert!(load_pairs("[]")
+            .unwrap_err()
+            .starts_with("Invalid fixture: invalid type: sequence"));
+        assert_eq!(
+            load_pairs(&FIXTURE.replace(r#""1000000000", "#, "")).unwrap_err(),
+            "Error while creating pair terra1pair: Generic error: Wrong asset length: expected 2, actual 1"
+        );
+    }
+
+    #[test]
+    fn wrong_arguments_are_reported() {
+        let run = |args: &[&str]| run(&args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>());
+
+        assert_eq!(run(&[]), Err(USAGE.to_string()));
+        assert_eq!(run(&["a.json", "b.json"]), Err(USAGE.to_string()));
+        assert_eq!(
+            run(&["a.json", "--listen"]),
+            Err("Missing value of --listen".to_string())
+        );
+        assert!(run(&["missing.json"])
+            .unwrap_err()
+            .starts_with("Error while reading missing.json: "));
+    }
+}
@@ -0,0 +1,276 @@
+//! The single asset and the imbalanced withdraws of the Curve stableswap pools, the reference of
+//! the stable withdraws the pinned stable pair doesn't execute.
+//!
+//! A port of `calc_withdraw_one_coin` and `remove_liquidity_imbalance` of the Curve pools of two
+//! coins in their integer arithmetic: the balances are scaled to 18 decimals, the amplification
+//! has the precision of [`A_PRECISION`] and the fees are in [`FEE_DENOMINATOR`] units.
+
+use cosmwasm_std::{Decimal, StdError, StdResult, Uint128, Uint256};
+
+use crate::astroport::pair_stable::pool::PoolState;
+
+const N_COINS: u8 = 2;
+const A_PRECISION: u64 = 100;
+const FEE_DENOMINATOR: u64 = 10_000_000_000;
+/// The iterations limit of the Newton's methods of Curve
+const ITERATIONS: u8 = 255;
+
+fn abs_diff(a: Uint256, b: Uint256) -> Uint256 {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+/// `_A` of Curve: the amplification at the block time, ramped linearly between the amp times.
+fn amp(pool: &PoolState) -> Uint256 {
+    let amp = if pool.block_time < pool.next_amp_time {
+        let elapsed = pool.block_time - pool.init_amp_time;
+        let duration = pool.next_amp_time - pool.init_amp_time;
+        if pool.next_amp > pool.init_amp {
+            pool.init_amp + (pool.next_amp - pool.init_amp) * elapsed / duration
+        } else {
+            pool.init_amp - (pool.init_amp - pool.next_amp) * elapsed / duration
+        }
+    } else {
+        pool.next_amp
+    };
+
+    Uint256::from(amp)
+}
+
+/// The rates scaling the balances of `pool` to 18 decimals.
+fn rates(pool: &PoolState) -> Vec<Uint256> {
+    pool.asset_precisions
+        .iter()
+        .map(|&precision| Uint256::from(10u8).pow(18 - u32::from(precision)))
+        .collect()
+}
+
+fn balances(pool: &PoolState) -> StdResult<Vec<Uint256>> {
+    pool.asset_amounts
+        .iter()
+        .map(|amount| {
+            if amount.is_zero() || amount.floor() != *amount {
+                return Err(StdError::generic_err("Balances must be positive integers"));
+            }
+            Ok(amount.to_uint_floor())
+        })
+        .collect()
+}
+
+/// `fee * N / (4 * (N - 1))` of Curve in [`FEE_DENOMINATOR`] units.
+fn base_fee(total_fee_rate: Decimal) -> Uint256 {
+    let fee = Uint256::from(total_fee_rate * Uint128::new(FEE_DENOMINATOR.into()));
+    fee * Uint256::from(N_COINS) / Uint256::from(4 * (N_COINS - 1))
+}
+
+fn get_d(xp: &[Uint256], amp: Uint256) -> StdResult<Uint256> {
+    let n = Uint256::from(N_COINS);
+    let a_precision = Uint256::from(A_PRECISION);
+    let s = xp.iter().fold(Uint256::zero(), |sum, x| sum + x);
+    if s.is_zero() {
+        return Ok(Uint256::zero());
+    }
+
+    let mut d = s;
+    let ann = amp * n;
+    for _ in 0..ITERATIONS {
+        let d_p = xp.iter().fold(d, |d_p, x| d_p * d / (x * n));
+        let d_prev = d;
+        d = (ann * s / a_precision + d_p * n) * d
+            / ((ann - a_precision) * d / a_precision + (n + Uint256::one()) * d_p);
+        if abs_diff(d, d_prev) <= Uint256::one() {
+            return Ok(d);
+        }
+    }
+
+    Err(StdError::generic_err("get_D did not converge"))
+}
+
+fn get_y_d(amp: Uint256, i: usize, xp: &[Uint256], d: Uint256) -> StdResult<Uint256> {
+    let n = Uint256::from(N_COINS);
+    let a_precision = Uint256::from(A_PRECISION);
+    let ann = amp * n;
+
+    let mut c = d;
+    let mut s = Uint256::zero();
+    for (_, x) in xp.iter().enumerate().filter(|(j, _)| *j != i) {
+        s += x;
+        c = c * d / (x * n);
+    }
+    c = c * d * a_precision / (ann * n);
+    let b = s + d * a_precision / ann;
+
+    let mut y = d;
+    for _ in 0..ITERATIONS {
+        let y_prev = y;
+        y = (y * y + c) / (Uint256::from(2u8) * y + b - d);
+        if abs_diff(y, y_prev) <= Uint256::one() {
+            return Ok(y);
+        }
+    }
+
+    Err(StdError::generic_err("get_y_D did not converge"))
+}
+
+/// `calc_withdraw_one_coin` of Curve: the amount of the asset with index `i` returned for burning
+/// `amount` lps, and the fee charged on it.
+pub fn withdraw_one(pool: &PoolState, amount: Uint128, i: usize) -> StdResult<(Uint128, Uint128)> {
+    let amp = amp(pool);
+    let rates = rates(pool);
+    let xp: Vec<_> = balances(pool)?
+        .iter()
+        .zip(&rates)
+        .map(|(balance, rate)| balance * rate)
+        .collect();
+    let total_supply = Uint256::from(pool.total_share);
+
+    let d0 = get_d(&xp, amp)?;
+    let d1 = d0 - Uint256::from(amount) * d0 / total_supply;
+    let new_y = get_y_d(amp, i, &xp, d1)?;
+
+    let base_fee = base_fee(pool.total_fee_rate);
+    let fee_denominator = Uint256::from(FEE_DENOMINATOR);
+    let xp_reduced: Vec<_> = xp
+        .iter()
+        .enumerate()
+        .map(|(j, x)| {
+            let dx_expected = if j == i {
+                x * d1 / d0 - new_y
+            } else {
+                x - x * d1 / d0
+            };
+            x - base_fee * dx_expected / fee_denominator
+        })
+        .collect();
+
+    let dy = xp_reduced[i] - get_y_d(amp, i, &xp_reduced, d1)?;
+    let dy_0 = (xp[i] - new_y) / rates[i];
+    let dy = (dy - Uint256::one()) / rates[i];
+
+    Ok((dy.try_into()?, (dy_0 - dy).try_into()?))
+}
+
+/// `remove_liquidity_imbalance` of Curve: the amount of lps burnt to withdraw `amounts`.
+pub fn withdraw_imbalanced(pool: &PoolState, amounts: &[Uint128]) -> StdResult<Uint128> {
+    let amp = amp(pool);
+    let rates = rates(pool);
+    let xp = |balances: &[Uint256]| -> Vec<Uint256> {
+        balances
+            .iter()
+            .zip(&rates)
+            .map(|(balance, rate)| balance * rate)
+            .collect()
+    };
+    let old_balances = balances(pool)?;
+
+    let d0 = get_d(&xp(&old_balances), amp)?;
+    let mut new_balances = old_balances
+        .iter()
+        .zip(amounts)
+        .map(|(balance, amount)| Ok(balance.checked_sub(Uint256::from(*amount))?))
+        .collect::<StdResult<Vec<_>>>()?;
+    let d1 = get_d(&xp(&new_balances), amp)?;
+
+    let base_fee = base_fee(pool.total_fee_rate);
+    for (new_balance, old_balance) in new_balances.iter_mut().zip(&old_balances) {
+        let ideal_balance = d1 * old_balance / d0;
+        let fee = base_fee * abs_diff(ideal_balance, *new_balance) / Uint256::from(FEE_DENOMINATOR);
+        *new_balance -= fee;
+    }
+    let d2 = get_d(&xp(&new_balances), amp)?;
+
+    let burn_amount = (d0 - d2) * Uint256::from(pool.total_share) / d0 + Uint256::one();
+    Ok(burn_amount.try_into()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Decimal256;
+
+    use super::*;
+    use crate::astroport::cosmwasm_ext::Decimal256Ext;
+
+    /// The reference reproduces the Curve amounts the single asset withdraw of the crate is
+    /// tested with in `pair_stable::withdraw`.
+    #[test]
+    fn withdraw_one_matches_the_curve_amounts() {
+        // (amounts, precisions, total share, amount, ask index, amp, fee bps), (return, fee)
+        let cases: [(
+            ([u128; 2], [u8; 2], u128, u128, usize, u64, u64),
+            (u128, u128),
+        ); 4] = [
+            (
+                (
+                    [530256812, 100446728],
+                    [6, 6],
+                    300000000,
+                    30000000,
+                    1,
+                    10000,
+                    5,
+                ),
+                (58198149, 22950),
+            ),
+            (
+                (
+                    [530256812, 100446728],
+                    [6, 6],
+                    300000000,
+                    30000000,
+                    0,
+                    10000,
+                    5,
+                ),
+                (63482793, 5220),
+            ),
+            (
+                (
+                    [1000000000000, 1000000000000],
+                    [6, 6],
+                    2000000000000,
+                    1000000000,
+                    0,
+                    5000,
+                    5,
+                ),
+                (999745099, 249996),
+            ),
+            (
+                (
+                    [10u128.pow(21), 2000000000],
+                    [18, 6],
+                    2000000000000,
+                    50000000000,
+                    1,
+                    20000,
+                    4,
+                ),
+                (75090936, 10040),
+            ),
+        ];
+
+        for ((amounts, precisions, total_share, amount, i, amp, fee_bps), expected) in cases {
+            let pool = PoolState {
+                asset_amounts: amounts.map(Decimal256::from_integer).to_vec(),
+                asset_precisions: precisions.to_vec(),
+                total_share: Uint128::new(total_share),
+                total_fee_rate: Decimal::from_ratio(fee_bps, 10_000u64),
+                block_time: 0,
+                init_amp_time: 0,
+                init_amp: amp,
+                next_amp_time: 0,
+                next_amp: amp,
+            };
+
+            let (dy, fee) = withdraw_one(&pool, Uint128::new(amount), i).unwrap();
+            assert_eq!(
+                (dy.u128(), fee.u128()),
+                expected,
+                "{amounts:?} {amount} {i}"
+            );
+        }
+    }
+}
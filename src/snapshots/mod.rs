@@ -0,0 +1,657 @@
+//! Checks the simulations against the golden vectors in `snapshots`, recorded from the pair
+//! contracts by [`record`], see `snapshots/README.md` for the format.
+
+mod curve;
+mod record;
+
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use cosmwasm_std::{Decimal, Decimal256, Uint128};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::astroport::cosmwasm_ext::Decimal256Ext;
+use crate::astroport::lib::curve::Spacing;
+use crate::astroport::pair_concentrated::state::RepegParams;
+use crate::astroport::pool::{Pool, PoolState};
+use crate::astroport::{pair_concentrated, pair_stable, pair_xyk};
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct Snapshot {
+    description: String,
+    pool: PoolState,
+    cases: Vec<Case>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct Case {
+    #[serde(flatten)]
+    operation: Operation,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expected: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expected_error: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Operation {
+    Swap {
+        offer_amount: Uint128,
+        ask_ind: usize,
+    },
+    ReverseSwap {
+        ask_amount: Uint128,
+        ask_ind: usize,
+    },
+    Provide {
+        deposits: Vec<Uint128>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        slippage_tolerance: Option<Decimal>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_lp_to_receive: Option<Uint128>,
+    },
+    ReverseProvide {
+        share_amount: Uint128,
+    },
+    Withdraw {
+        amount: Uint128,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_assets_to_receive: Option<Vec<Uint128>>,
+    },
+    /// Stable pools only
+    WithdrawOne {
+        amount: Uint128,
+        ask_ind: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_return_amount: Option<Uint128>,
+    },
+    /// Stable pools only
+    WithdrawImbalanced {
+        withdrawals: Vec<Uint128>,
+    },
+    ZapIn {
+        offer_amount: Uint128,
+        offer_ind: usize,
+        /// The maker fee share of xyk and stable pools, filled in by the recorder
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        maker_fee_share: Option<Decimal>,
+        /// The repeg parameters of concentrated pools, filled in by the recorder
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        repeg: Option<RepegParams>,
+    },
+    ZapOut {
+        amount: Uint128,
+        ask_ind: usize,
+    },
+    SwapToPrice {
+        target_price: Decimal,
+    },
+    Depth {
+        price_impacts: Vec<Decimal>,
+    },
+    Ladder {
+        price_step: Decimal,
+        levels: u32,
+    },
+    Curve {
+        min_offer_amount: Uint128,
+        max_offer_amount: Uint128,
+        points: u32,
+        spacing: Spacing,
+        ask_ind: usize,
+    },
+}
+
+/// Serializes the result of a simulation, or the message of its error.
+fn to_value<T: Serialize, E: Display>(result: Result<T, E>) -> Result<Value, String> {
+    let response = result.map_err(|err| err.to_string())?;
+    serde_json::to_value(response).map_err(|err| err.to_string())
+}
+
+fn unsupported(operation: &Operation, pool_type: &str) -> Result<Value, String> {
+    Err(format!(
+        "{operation:?} is not supported by {pool_type} pools"
+    ))
+}
+
+fn integers(amounts: &[Uint128]) -> Vec<Decimal256> {
+    amounts
+        .iter()
+        .map(|amount| Decimal256::from_integer(*amount))
+        .collect()
+}
+
+fn simulate(state: &PoolState, operation: &Operation) -> Result<Value, String> {
+    let pool = Pool::new(state.clone()).map_err(|err| err.to_string())?;
+
+    match operation {
+        Operation::Swap {
+            offer_amount,
+            ask_ind,
+        } => to_value(pool.swap(*offer_amount, *ask_ind)),
+        Operation::ReverseSwap {
+            ask_amount,
+            ask_ind,
+        } => to_value(pool.reverse_swap(*ask_amount, *ask_ind)),
+        Operation::Provide {
+            deposits,
+            slippage_tolerance,
+            min_lp_to_receive,
+        } => to_value(pool.provide(deposits, *slippage_tolerance, *min_lp_to_receive)),
+        Operation::Withdraw {
+            amount,
+            min_assets_to_receive,
+        } => to_value(pool.withdraw(*amount, min_assets_to_receive.clone(), None)),
+        _ => match state {
+            PoolState::Xyk(state) => simulate_xyk(state, operation),
+            PoolState::Stable(state) => simulate_stable(state, operation),
+            PoolState::Concentrated(state) => simulate_concentrated(state, operation),
+        },
+    }
+}
+
+fn simulate_xyk(state: &pair_xyk::pool::PoolState, operation: &Operation) -> Result<Value, String> {
+    let pair_xyk::pool::PoolState {
+        asset_amounts,
+        total_share,
+        total_fee_rate,
+    } = state;
+
+    match operation {
+        Operation::ReverseProvide { share_amount } => to_value(
+            pair_xyk::provide::simulate_reverse(*share_amount, asset_amounts, *total_share),
+        ),
+        Operation::ZapIn {
+            offer_amount,
+            offer_ind,
+            maker_fee_share,
+            ..
+        } => to_value(pair_xyk::zap::simulate_zap_in(
+            *offer_amount,
+            *offer_ind,
+            asset_amounts,
+            *total_share,
+            *total_fee_rate,
+            *maker_fee_share,
+        )),
+        Operation::ZapOut { amount, ask_ind } => to_value(pair_xyk::zap::simulate_zap_out(
+            *amount,
+            *ask_ind,
+            asset_amounts,
+            *total_share,
+            *total_fee_rate,
+        )),
+        Operation::SwapToPrice { target_price } => to_value(pair_xyk::swap::swap_to_price(
+            *target_price,
+            asset_amounts,
+            *total_fee_rate,
+        )),
+        Operation::Depth { price_impacts } => to_value(pair_xyk::swap::simulate_depth(
+            price_impacts,
+            asset_amounts,
+            *total_fee_rate,
+        )),
+        Operation::Ladder { price_step, levels } => to_value(pair_xyk::swap::simulate_ladder(
+            *price_step,
+            *levels,
+            asset_amounts,
+            *total_fee_rate,
+        )),
+        Operation::Curve {
+            min_offer_amount,
+            max_offer_amount,
+            points,
+            spacing,
+            ask_ind,
+        } => to_value(pair_xyk::swap::sample_curve(
+            *min_offer_amount,
+            *max_offer_amount,
+            *points,
+            spacing.clone(),
+            *ask_ind,
+            asset_amounts,
+            *total_fee_rate,
+        )),
+        _ => unsupported(operation, "xyk"),
+    }
+}
+
+fn simulate_stable(
+    state: &pair_stable::pool::PoolState,
+    operation: &Operation,
+) -> Result<Value, String> {
+    let pair_stable::pool::PoolState {
+        asset_amounts,
+        asset_precisions,
+        total_share,
+        total_fee_rate,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp,
+    } = state.clone();
+
+    match operation {
+        Operation::ReverseProvide { share_amount } => {
+            to_value(pair_stable::provide::simulate_reverse(
+                *share_amount,
+                &asset_amounts,
+                &asset_precisions,
+                total_share,
+                block_time,
+                init_amp_time,
+                init_amp,
+                next_amp_time,
+                next_amp,
+            ))
+        }
+        Operation::WithdrawOne {
+            amount,
+            ask_ind,
+            min_return_amount,
+        } => to_value(pair_stable::withdraw::simulate_one(
+            *amount,
+            *ask_ind,
+            &asset_amounts,
+            &asset_precisions,
+            total_share,
+            total_fee_rate,
+            block_time,
+            init_amp_time,
+            init_amp,
+            next_amp_time,
+            next_amp,
+            *min_return_amount,
+        )),
+        Operation::WithdrawImbalanced { withdrawals } => {
+            to_value(pair_stable::withdraw::simulate_imbalanced(
+                &integers(withdrawals),
+                &asset_amounts,
+                &asset_precisions,
+                total_share,
+                total_fee_rate,
+                block_time,
+                init_amp_time,
+                init_amp,
+                next_amp_time,
+                next_amp,
+            ))
+        }
+        Operation::ZapIn {
+            offer_amount,
+            offer_ind,
+            maker_fee_share,
+            ..
+        } => to_value(pair_stable::zap::simulate_zap_in(
+            Decimal256::from_integer(*offer_amount),
+            *offer_ind,
+            &asset_amounts,
+            &asset_precisions,
+            total_share,
+            total_fee_rate,
+            block_time,
+            init_amp_time,
+            init_amp,
+            next_amp_time,
+            next_amp,
+            *maker_fee_share,
+        )),
+        Operation::ZapOut { amount, ask_ind } => to_value(pair_stable::zap::simulate_zap_out(
+            *amount,
+            *ask_ind,
+            &asset_amounts,
+            &asset_precisions,
+            total_share,
+            total_fee_rate,
+            block_time,
+            init_amp_time,
+            init_amp,
+            next_amp_time,
+            next_amp,
+        )),
+        Operation::SwapToPrice { target_price } => to_value(pair_stable::swap::swap_to_price(
+            *target_price,
+            &asset_amounts,
+            &asset_precisions,
+            total_fee_rate,
+            block_time,
+            init_amp_time,
+            init_amp,
+            next_amp_time,
+            next_amp,
+        )),
+        Operation::Depth { price_impacts } => to_value(pair_stable::swap::simulate_depth(
+            price_impacts,
+            &asset_amounts,
+            &asset_precisions,
+            total_fee_rate,
+            block_time,
+            init_amp_time,
+            init_amp,
+            next_amp_time,
+            next_amp,
+        )),
+        Operation::Ladder { price_step, levels } => to_value(pair_stable::swap::simulate_ladder(
+            *price_step,
+            *levels,
+            &asset_amounts,
+            &asset_precisions,
+            total_fee_rate,
+            block_time,
+            init_amp_time,
+            init_amp,
+            next_amp_time,
+            next_amp,
+        )),
+        Operation::Curve {
+            min_offer_amount,
+            max_offer_amount,
+            points,
+            spacing,
+            ask_ind,
+        } => to_value(pair_stable::swap::sample_curve(
+            *min_offer_amount,
+            *max_offer_amount,
+            *points,
+            spacing.clone(),
+            *ask_ind,
+            &asset_amounts,
+            &asset_precisions,
+            total_fee_rate,
+            block_time,
+            init_amp_time,
+            init_amp,
+            next_amp_time,
+            next_amp,
+        )),
+        _ => unsupported(operation, "stable"),
+    }
+}
+
+fn simulate_concentrated(
+    state: &pair_concentrated::pool::PoolState,
+    operation: &Operation,
+) -> Result<Value, String> {
+    let pair_concentrated::pool::PoolState {
+        asset_amounts,
+        asset_precisions,
+        total_share,
+        maker_fee_share,
+        oracle_price,
+        price_scale,
+        fee_gamma,
+        mid_fee,
+        out_fee,
+        block_time,
+        initial_time,
+        initial_amp,
+        initial_gamma,
+        future_time,
+        future_amp,
+        future_gamma,
+        ..
+    } = state.clone();
+
+    match operation {
+        Operation::ReverseProvide { share_amount } => {
+            to_value(pair_concentrated::provide::simulate_reverse(
+                *share_amount,
+                &asset_amounts,
+                &asset_precisions,
+                total_share,
+                price_scale,
+                fee_gamma,
+                mid_fee,
+                out_fee,
+                block_time,
+                initial_time,
+                initial_amp,
+                initial_gamma,
+                future_time,
+                future_amp,
+                future_gamma,
+            ))
+        }
+        Operation::ZapIn {
+            offer_amount,
+            offer_ind,
+            repeg,
+            ..
+        } => to_value(pair_concentrated::zap::simulate_zap_in(
+            Decimal256::from_integer(*offer_amount),
+            *offer_ind,
+            &asset_amounts,
+            &asset_precisions,
+            total_share,
+            maker_fee_share,
+            oracle_price,
+            price_scale,
+            fee_gamma,
+            mid_fee,
+            out_fee,
+            block_time,
+            initial_time,
+            initial_amp,
+            initial_gamma,
+            future_time,
+            future_amp,
+            future_gamma,
+            repeg.as_ref(),
+        )),
+        Operation::ZapOut { amount, ask_ind } => {
+            to_value(pair_concentrated::zap::simulate_zap_out(
+                *amount,
+                *ask_ind,
+                &asset_amounts,
+                &asset_precisions,
+                total_share,
+                maker_fee_share,
+                oracle_price,
+                price_scale,
+                fee_gamma,
+                mid_fee,
+                out_fee,
+                block_time,
+                initial_time,
+                initial_amp,
+                initial_gamma,
+                future_time,
+                future_amp,
+                future_gamma,
+            ))
+        }
+        Operation::SwapToPrice { target_price } => {
+            to_value(pair_concentrated::swap::swap_to_price(
+                *target_price,
+                &asset_amounts,
+                &asset_precisions,
+                maker_fee_share,
+                oracle_price,
+                price_scale,
+                fee_gamma,
+                mid_fee,
+                out_fee,
+                block_time,
+                initial_time,
+                initial_amp,
+                initial_gamma,
+                future_time,
+                future_amp,
+                future_gamma,
+            ))
+        }
+        Operation::Depth { price_impacts } => to_value(pair_concentrated::swap::simulate_depth(
+            price_impacts,
+            &asset_amounts,
+            &asset_precisions,
+            maker_fee_share,
+            oracle_price,
+            price_scale,
+            fee_gamma,
+            mid_fee,
+            out_fee,
+            block_time,
+            initial_time,
+            initial_amp,
+            initial_gamma,
+            future_time,
+            future_amp,
+            future_gamma,
+        )),
+        Operation::Ladder { price_step, levels } => {
+            to_value(pair_concentrated::swap::simulate_ladder(
+                *price_step,
+                *levels,
+                &asset_amounts,
+                &asset_precisions,
+                maker_fee_share,
+                oracle_price,
+                price_scale,
+                fee_gamma,
+                mid_fee,
+                out_fee,
+                block_time,
+                initial_time,
+                initial_amp,
+                initial_gamma,
+                future_time,
+                future_amp,
+                future_gamma,
+            ))
+        }
+        Operation::Curve {
+            min_offer_amount,
+            max_offer_amount,
+            points,
+            spacing,
+            ask_ind,
+        } => to_value(pair_concentrated::swap::sample_curve(
+            *min_offer_amount,
+            *max_offer_amount,
+            *points,
+            spacing.clone(),
+            *ask_ind,
+            &asset_amounts,
+            &asset_precisions,
+            maker_fee_share,
+            oracle_price,
+            price_scale,
+            fee_gamma,
+            mid_fee,
+            out_fee,
+            block_time,
+            initial_time,
+            initial_amp,
+            initial_gamma,
+            future_time,
+            future_amp,
+            future_gamma,
+        )),
+        _ => unsupported(operation, "concentrated"),
+    }
+}
+
+/// Returns whether every field of `expected` equals the field of `actual`. Fields missing from
+/// `expected` aren't checked.
+fn matches(expected: &Value, actual: &Value) -> bool {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => expected.iter().all(|(key, value)| {
+            actual
+                .get(key)
+                .map_or(false, |actual| matches(value, actual))
+        }),
+        (Value::Array(expected), Value::Array(actual)) => {
+            expected.len() == actual.len()
+                && expected
+                    .iter()
+                    .zip(actual)
+                    .all(|(expected, actual)| matches(expected, actual))
+        }
+        _ => expected == actual,
+    }
+}
+
+fn snapshots_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("snapshots")
+}
+
+fn snapshot_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("Error while reading {}: {}", dir.display(), err))
+        .map(|entry| entry.unwrap().path())
+        .flat_map(|path| {
+            if path.is_dir() {
+                snapshot_files(&path)
+            } else if path.extension().map_or(false, |ext| ext == "json") {
+                vec![path]
+            } else {
+                vec![]
+            }
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+fn read_snapshot(file: &Path) -> Snapshot {
+    fs::read_to_string(file)
+        .map_err(|err| err.to_string())
+        .and_then(|json| serde_json::from_str::<Snapshot>(&json).map_err(|e| e.to_string()))
+        .unwrap_or_else(|err| panic!("Invalid snapshot {}: {}", file.display(), err))
+}
+
+#[test]
+fn regression_snapshots() {
+    let dir = snapshots_dir();
+    let files = snapshot_files(&dir);
+    assert!(!files.is_empty(), "No snapshots in {}", dir.display());
+
+    let mut failures = vec![];
+    let mut case_count = 0;
+    for file in files {
+        let name = file.strip_prefix(&dir).unwrap().display().to_string();
+        let snapshot = read_snapshot(&file);
+
+        for (ind, case) in snapshot.cases.iter().enumerate() {
+            case_count += 1;
+            let result = simulate(&snapshot.pool, &case.operation);
+            let passed = match (&case.expected, &case.expected_error, &result) {
+                (Some(expected), None, Ok(actual)) => matches(expected, actual),
+                (None, Some(expected), Err(actual)) => actual.contains(expected.as_str()),
+                _ => false,
+            };
+
+            if !passed {
+                failures.push(format!(
+                    "{} ({}) case #{} {:?}\n  expected: {}\n  actual: {}",
+                    name,
+                    snapshot.description,
+                    ind,
+                    case.operation,
+                    case.expected
+                        .as_ref()
+                        .map(Value::to_string)
+                        .or_else(|| case.expected_error.as_ref().map(|e| format!("error {e}")))
+                        .unwrap_or_else(|| "either expected or expected_error".to_string()),
+                    match result {
+                        Ok(actual) => actual.to_string(),
+                        Err(err) => format!("error {err}"),
+                    }
+                ));
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} of {} regression snapshots failed:\n{}",
+        failures.len(),
+        case_count,
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,594 @@
+//! Records the golden vectors of `snapshots` from the pair contracts in cw-multi-test:
+//!
+//! ```text
+//! cargo test record_snapshots -- --ignored
+//! ```
+//!
+//! Every snapshot file is rewritten with the pool state read from a pair created for it and the
+//! `expected` of every case recorded from a fresh copy of that pair, see `snapshots/README.md`
+//! for what is recorded for every operation.
+
+use std::fs;
+
+use cosmwasm_std::{Decimal, Decimal256, StdError, StdResult, Uint128};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::{curve, read_snapshot, simulate, snapshot_files, snapshots_dir, Case, Operation};
+use crate::astroport::asset::{Asset, AssetInfo};
+use crate::astroport::cosmwasm_ext::{Decimal256Ext, MultiplyRatioCeil};
+use crate::astroport::lib::depth::DepthResponse;
+use crate::astroport::lib::ladder::LadderResponse;
+use crate::astroport::lib::search::{maximize, refine_deposits};
+use crate::astroport::pair::PairState;
+use crate::astroport::pool::{Pool, PoolState};
+use crate::differential::{self, ContractPair, ReferencePair};
+
+/// The relative tolerance of the prices the contracts quote after the offer amounts solved for
+/// a price, as the contracts don't expose a spot price.
+const PRICE_TOLERANCE: Decimal256 = Decimal256::permille(1);
+/// The offer pool divided by the offer amount of the swap probing the price of a contract
+const PROBE_FRACTION: u128 = 10_000;
+/// The slippage tolerance of the provide legs of zaps, the maximum of the pairs
+const ZAP_SLIPPAGE: Decimal = Decimal::percent(50);
+/// The width of the snapshot files, as formatted by Prettier
+const LINE_WIDTH: usize = 80;
+
+#[derive(Deserialize)]
+struct SwapQuote {
+    return_amount: Uint128,
+    spread_amount: Uint128,
+    commission_amount: Uint128,
+}
+
+#[derive(Deserialize)]
+struct ReverseSwapQuote {
+    offer_amount: Uint128,
+    spread_amount: Uint128,
+    commission_amount: Uint128,
+}
+
+/// Creates fresh pairs for the pool state of a snapshot.
+struct Recorder {
+    state: PairState,
+}
+
+impl Recorder {
+    fn pair(&self) -> StdResult<ContractPair> {
+        ContractPair::new(&self.state)
+    }
+
+    /// The pool state of a fresh pair, as read from the contract.
+    fn pool(&self) -> StdResult<PoolState> {
+        Ok(self.pair()?.state()?.pool)
+    }
+
+    fn asset(&self, ind: usize, amount: Uint128) -> Asset {
+        Asset {
+            info: self.state.asset_infos[ind].clone(),
+            amount,
+        }
+    }
+
+    fn assets(&self, amounts: &[Uint128]) -> Vec<Asset> {
+        amounts
+            .iter()
+            .enumerate()
+            .map(|(ind, amount)| self.asset(ind, *amount))
+            .collect()
+    }
+
+    fn quote_swap(
+        &self,
+        pair: &ContractPair,
+        offer_amount: Uint128,
+        ask_ind: usize,
+    ) -> StdResult<SwapQuote> {
+        pair.query(&json!({
+            "simulation": {
+                "offer_asset": self.asset(1 ^ ask_ind, offer_amount),
+                "ask_asset_info": self.state.asset_infos[ask_ind],
+            }
+        }))
+    }
+
+    fn swap(
+        &self,
+        pair: &mut ContractPair,
+        offer_amount: Uint128,
+        ask_ind: usize,
+    ) -> StdResult<Uint128> {
+        let received = pair.execute(&differential::Operation::Swap {
+            offer_asset: self.asset(1 ^ ask_ind, offer_amount),
+        })?;
+        Ok(received[0].amount)
+    }
+
+    fn provide(
+        &self,
+        pair: &mut ContractPair,
+        deposits: &[Uint128],
+        slippage_tolerance: Option<Decimal>,
+    ) -> StdResult<Uint128> {
+        let received = pair.execute(&differential::Operation::Provide {
+            assets: self.assets(deposits),
+            slippage_tolerance,
+        })?;
+        Ok(received[0].amount)
+    }
+
+    fn withdraw(&self, pair: &mut ContractPair, amount: Uint128) -> StdResult<Vec<Uint128>> {
+        let received = pair.execute(&differential::Operation::Withdraw { lp_amount: amount })?;
+        Ok(received.iter().map(|asset| asset.amount).collect())
+    }
+
+    /// Returns the price of the first asset in the second asset units the contract quotes for
+    /// swaps offering the asset with index `offer_ind`, fees excluded. The rate of a swap of a
+    /// small fraction of the pool is extrapolated to a zero offer from the rate of twice the
+    /// offer, which leaves an error of the order of the squared fraction.
+    fn quoted_price(&self, pair: &mut ContractPair, offer_ind: usize) -> StdResult<Decimal256> {
+        let pool = pair.state()?.pool;
+        let offer_pool = Pool::new(pool.clone())?.asset_amounts()?[offer_ind];
+        let probe = (offer_pool / Uint128::new(PROBE_FRACTION)).max(Uint128::one());
+
+        // The prices of stable and concentrated pools are in the units of the precisions
+        let (offer_precision, ask_precision) = match &pool {
+            PoolState::Xyk(_) => (0, 0),
+            PoolState::Stable(pool) => (
+                pool.asset_precisions[offer_ind].into(),
+                pool.asset_precisions[1 ^ offer_ind].into(),
+            ),
+            PoolState::Concentrated(pool) => (
+                pool.asset_precisions[offer_ind],
+                pool.asset_precisions[1 ^ offer_ind],
+            ),
+        };
+        let rate = |offer_amount: Uint128| -> StdResult<Decimal256> {
+            let quote = self.quote_swap(pair, offer_amount, 1 ^ offer_ind)?;
+            Ok(
+                in_units(quote.return_amount + quote.commission_amount, ask_precision)
+                    / in_units(offer_amount, offer_precision),
+            )
+        };
+        let rate = (rate(probe)? + rate(probe)?).checked_sub(rate(probe + probe)?)?;
+
+        Ok(if offer_ind == 0 {
+            rate
+        } else {
+            Decimal256::one() / rate
+        })
+    }
+
+    /// Checks that the contract quotes `price` after swapping `offer_amount` of the asset with
+    /// index `offer_ind`.
+    fn check_price_after(
+        &self,
+        offer_amount: Uint128,
+        offer_ind: usize,
+        price: Decimal256,
+    ) -> StdResult<()> {
+        let mut pair = self.pair()?;
+        if !offer_amount.is_zero() {
+            self.swap(&mut pair, offer_amount, 1 ^ offer_ind)?;
+        }
+        check_price(self.quoted_price(&mut pair, offer_ind)?, price)
+    }
+
+    /// The amount of lps minted by a zap which swaps `swap_amount` of the offer, then provides
+    /// the rest of the offer with the return of the swap.
+    fn zap_in(
+        &self,
+        offer_amount: Uint128,
+        offer_ind: usize,
+        swap_amount: Uint128,
+    ) -> StdResult<(Uint128, Vec<Uint128>, Uint128)> {
+        let mut pair = self.pair()?;
+        let return_amount = if swap_amount.is_zero() {
+            Uint128::zero()
+        } else {
+            self.swap(&mut pair, swap_amount, 1 ^ offer_ind)?
+        };
+
+        let mut deposits = vec![Uint128::zero(); 2];
+        deposits[offer_ind] = offer_amount - swap_amount;
+        deposits[1 ^ offer_ind] = return_amount;
+        let share_amount = self.provide(&mut pair, &deposits, Some(ZAP_SLIPPAGE))?;
+
+        Ok((return_amount, deposits, share_amount))
+    }
+
+    /// Records the expected result of `operation` from the contracts. The inputs the contracts
+    /// define, the maker fee share and the repeg parameters of zaps, are filled in.
+    fn expected(&self, operation: &mut Operation) -> StdResult<Value> {
+        match operation {
+            Operation::Swap {
+                offer_amount,
+                ask_ind,
+            } => {
+                let quote = self.quote_swap(&self.pair()?, *offer_amount, *ask_ind)?;
+                Ok(json!({
+                    "return_amount": quote.return_amount,
+                    "spread_amount": quote.spread_amount,
+                    "commission_amount": quote.commission_amount,
+                }))
+            }
+            Operation::ReverseSwap {
+                ask_amount,
+                ask_ind,
+            } => {
+                let quote: ReverseSwapQuote = self.pair()?.query(&json!({
+                    "reverse_simulation": {
+                        "ask_asset": self.asset(*ask_ind, *ask_amount),
+                        "offer_asset_info": self.state.asset_infos[1 ^ *ask_ind],
+                    }
+                }))?;
+                Ok(json!({
+                    "offer_amount": quote.offer_amount,
+                    "spread_amount": quote.spread_amount,
+                    "commission_amount": quote.commission_amount,
+                }))
+            }
+            Operation::Provide {
+                deposits,
+                slippage_tolerance,
+                min_lp_to_receive,
+            } => {
+                let share_amount =
+                    self.provide(&mut self.pair()?, deposits, *slippage_tolerance)?;
+                if let Some(min_lp_to_receive) = min_lp_to_receive {
+                    if share_amount < *min_lp_to_receive {
+                        return Err(StdError::generic_err(format!(
+                            "Received {share_amount} lps below the minimum of {min_lp_to_receive}"
+                        )));
+                    }
+                }
+                Ok(json!({ "share_amount": share_amount }))
+            }
+            Operation::ReverseProvide { share_amount } => {
+                let pool = Pool::new(self.pool()?)?;
+                let deposits = pool
+                    .asset_amounts()?
+                    .iter()
+                    .map(|amount| amount.multiply_ratio_ceil(*share_amount, pool.total_share()))
+                    .collect::<StdResult<Vec<_>>>()?;
+                let (deposits, share_amount) =
+                    refine_deposits(deposits, *share_amount, |deposits| {
+                        self.provide(&mut self.pair()?, deposits, None)
+                    })?;
+                Ok(json!({ "deposits": deposits, "share_amount": share_amount }))
+            }
+            Operation::Withdraw {
+                amount,
+                min_assets_to_receive,
+            } => {
+                let returned_amounts = self.withdraw(&mut self.pair()?, *amount)?;
+                if let Some(min_assets_to_receive) = min_assets_to_receive {
+                    check_min_amounts(&returned_amounts, min_assets_to_receive)?;
+                }
+                Ok(json!({ "returned_amounts": returned_amounts }))
+            }
+            Operation::WithdrawOne {
+                amount,
+                ask_ind,
+                min_return_amount,
+            } => {
+                let PoolState::Stable(pool) = self.pool()? else {
+                    return Err(StdError::generic_err(
+                        "Only stable pools withdraw one asset",
+                    ));
+                };
+                let (return_amount, commission_amount) =
+                    curve::withdraw_one(&pool, *amount, *ask_ind)?;
+                if let Some(min_return_amount) = min_return_amount {
+                    check_min_amounts(&[return_amount], &[*min_return_amount])?;
+                }
+                Ok(json!({
+                    "return_amount": return_amount,
+                    "commission_amount": commission_amount,
+                }))
+            }
+            Operation::WithdrawImbalanced { withdrawals } => {
+                let PoolState::Stable(pool) = self.pool()? else {
+                    return Err(StdError::generic_err(
+                        "Only stable pools withdraw imbalanced amounts",
+                    ));
+                };
+                let burn_amount = curve::withdraw_imbalanced(&pool, withdrawals)?;
+                Ok(json!({ "burn_amount": burn_amount }))
+            }
+            Operation::ZapIn {
+                offer_amount,
+                offer_ind,
+                maker_fee_share,
+                repeg,
+            } => {
+                let mut pair = self.pair()?;
+                match pair.state()?.pool {
+                    PoolState::Concentrated(_) => *repeg = Some(pair.repeg_params()?),
+                    _ => *maker_fee_share = Some(pair.maker_fee_share()?),
+                }
+
+                let swap_amount = maximize(Uint128::zero(), *offer_amount, |swap_amount| {
+                    Ok(self.zap_in(*offer_amount, *offer_ind, swap_amount)?.2)
+                });
+                let (return_amount, deposits, share_amount) =
+                    self.zap_in(*offer_amount, *offer_ind, swap_amount)?;
+                Ok(json!({
+                    "swap_amount": swap_amount,
+                    "swap": { "return_amount": return_amount },
+                    "deposits": deposits,
+                    "provide": { "share_amount": share_amount },
+                }))
+            }
+            Operation::ZapOut { amount, ask_ind } => {
+                let mut pair = self.pair()?;
+                let returned_amounts = self.withdraw(&mut pair, *amount)?;
+                let other_amount = returned_amounts[1 ^ *ask_ind];
+                let swap_return = if other_amount.is_zero() {
+                    Uint128::zero()
+                } else {
+                    self.swap(&mut pair, other_amount, *ask_ind)?
+                };
+                Ok(json!({
+                    "withdraw": { "returned_amounts": returned_amounts },
+                    "swap": { "return_amount": swap_return },
+                    "return_amount": returned_amounts[*ask_ind] + swap_return,
+                }))
+            }
+            Operation::SwapToPrice { target_price } => {
+                let target_price = Decimal256::from(*target_price);
+                let response = self.simulate(operation)?;
+                let offer_ind: usize = from_value(&response["offer_ind"])?;
+                let offer_amount: Uint128 = from_value(&response["offer_amount"])?;
+                self.check_price_after(offer_amount, offer_ind, target_price)?;
+
+                let quote = self.quote_swap(&self.pair()?, offer_amount, 1 ^ offer_ind)?;
+                Ok(json!({
+                    "offer_ind": offer_ind,
+                    "offer_amount": offer_amount,
+                    "swap": {
+                        "return_amount": quote.return_amount,
+                        "spread_amount": quote.spread_amount,
+                        "commission_amount": quote.commission_amount,
+                    },
+                }))
+            }
+            Operation::Depth { .. } => {
+                let response = self.simulate(operation)?;
+                let depth: Vec<DepthResponse> = from_value(&response)?;
+                for level in &depth {
+                    for (offer_ind, offer_amount) in level.offer_amounts.iter().enumerate() {
+                        if offer_amount.is_zero() {
+                            continue;
+                        }
+                        let mut pair = self.pair()?;
+                        let spot_price = self.quoted_price(&mut pair, offer_ind)?;
+                        let quote = self.quote_swap(&pair, *offer_amount, 1 ^ offer_ind)?;
+                        let gross = quote.return_amount + quote.commission_amount;
+                        // The ratio of the execution price to the spot price
+                        let ratio = if offer_ind == 0 {
+                            Decimal256::from_ratio(gross, *offer_amount) / spot_price
+                        } else {
+                            spot_price / Decimal256::from_ratio(*offer_amount, gross)
+                        };
+                        check_price(
+                            ratio,
+                            Decimal256::one() - Decimal256::from(level.price_impact),
+                        )?;
+                    }
+                }
+                Ok(response)
+            }
+            Operation::Ladder { .. } => {
+                let response = self.simulate(operation)?;
+                let mut ladder: LadderResponse = from_value(&response)?;
+                check_price(
+                    self.quoted_price(&mut self.pair()?, 0)?,
+                    ladder.mid_price.into(),
+                )?;
+
+                // The received side of every level is recorded, the offered side is solved for
+                // the price of the level
+                for level in &mut ladder.bids {
+                    self.check_price_after(level.base_amount, 0, level.price.into())?;
+                    level.quote_amount = self
+                        .quote_swap(&self.pair()?, level.base_amount, 1)?
+                        .return_amount;
+                }
+                for level in &mut ladder.asks {
+                    self.check_price_after(level.quote_amount, 1, level.price.into())?;
+                    level.base_amount = self
+                        .quote_swap(&self.pair()?, level.quote_amount, 0)?
+                        .return_amount;
+                }
+                Ok(serde_json::to_value(ladder)
+                    .map_err(|err| StdError::generic_err(err.to_string()))?)
+            }
+            Operation::Curve { ask_ind, .. } => {
+                let ask_ind = *ask_ind;
+                let response = self.simulate(operation)?;
+                let offer_amounts: Vec<Uint128> = response
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|point| from_value(&point["offer_amount"]))
+                    .collect::<StdResult<_>>()?;
+                let pair = self.pair()?;
+                let points = offer_amounts
+                    .into_iter()
+                    .map(|offer_amount| {
+                        let quote = self.quote_swap(&pair, offer_amount, ask_ind)?;
+                        Ok(json!({
+                            "offer_amount": offer_amount,
+                            "return_amount": quote.return_amount,
+                            "commission_amount": quote.commission_amount,
+                        }))
+                    })
+                    .collect::<StdResult<Vec<_>>>()?;
+                Ok(Value::Array(points))
+            }
+        }
+    }
+
+    /// Simulates `operation` on the pool state of a fresh pair with this crate, for the offer
+    /// amounts the contracts can't solve for.
+    fn simulate(&self, operation: &Operation) -> StdResult<Value> {
+        simulate(&self.pool()?, operation).map_err(StdError::generic_err)
+    }
+
+    /// Records `case`. A case expected to fail must fail on the contracts too, its message is
+    /// the one of this crate.
+    fn record(&self, case: &mut Case) -> Result<(), String> {
+        match (self.expected(&mut case.operation), &case.expected_error) {
+            (Ok(expected), None) => {
+                case.expected = Some(expected);
+                Ok(())
+            }
+            (Err(_), Some(_)) => Ok(()),
+            (Ok(expected), Some(err)) => Err(format!(
+                "expected the error {err}, the contracts return {expected}"
+            )),
+            (Err(err), None) => Err(err.to_string()),
+        }
+    }
+}
+
+/// Returns `amount` in the units of an asset with `precision` decimals.
+fn in_units(amount: Uint128, precision: u32) -> Decimal256 {
+    Decimal256::from_ratio(amount, 10u128.pow(precision))
+}
+
+fn from_value<T: serde::de::DeserializeOwned>(value: &Value) -> StdResult<T> {
+    T::deserialize(value).map_err(|err| StdError::generic_err(err.to_string()))
+}
+
+fn check_price(actual: Decimal256, expected: Decimal256) -> StdResult<()> {
+    if actual.abs_diff(expected) > expected * PRICE_TOLERANCE {
+        return Err(StdError::generic_err(format!(
+            "The contract quotes {actual} for the price of {expected}"
+        )));
+    }
+    Ok(())
+}
+
+fn check_min_amounts(amounts: &[Uint128], min_amounts: &[Uint128]) -> StdResult<()> {
+    if amounts
+        .iter()
+        .zip(min_amounts)
+        .any(|(amount, min)| amount < min)
+    {
+        return Err(StdError::generic_err(format!(
+            "Received {amounts:?} below the minimum of {min_amounts:?}"
+        )));
+    }
+    Ok(())
+}
+
+/// Formats `value` on one line.
+fn format_inline(value: &Value) -> String {
+    match value {
+        Value::Object(object) if !object.is_empty() => format!(
+            "{{ {} }}",
+            object
+                .iter()
+                .map(|(key, value)| format!(
+                    "{}: {}",
+                    Value::from(key.as_str()),
+                    format_inline(value)
+                ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Value::Array(array) => format!(
+            "[{}]",
+            array
+                .iter()
+                .map(format_inline)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        _ => value.to_string(),
+    }
+}
+
+/// Formats `value` as Prettier does: on one line when it fits into [`LINE_WIDTH`] after
+/// `column` columns, every field or item on its own line indented from `indent` otherwise.
+fn format(value: &Value, indent: usize, column: usize) -> String {
+    let inline = format_inline(value);
+    if column + inline.len() <= LINE_WIDTH {
+        return inline;
+    }
+
+    let padding = " ".repeat(indent + 2);
+    let (open, close, lines) = match value {
+        Value::Object(object) => (
+            '{',
+            '}',
+            object
+                .iter()
+                .map(|(key, value)| {
+                    let key = Value::from(key.as_str()).to_string();
+                    let value = format(value, indent + 2, indent + 2 + key.len() + 2);
+                    format!("{padding}{key}: {value}")
+                })
+                .collect::<Vec<_>>(),
+        ),
+        Value::Array(array) => (
+            '[',
+            ']',
+            array
+                .iter()
+                .map(|value| format!("{padding}{}", format(value, indent + 2, indent + 2)))
+                .collect(),
+        ),
+        _ => return inline,
+    };
+
+    format!(
+        "{open}\n{}\n{}{close}",
+        lines.join(",\n"),
+        " ".repeat(indent)
+    )
+}
+
+#[test]
+#[ignore = "rewrites the snapshots, run to record them from the contracts"]
+fn record_snapshots() {
+    let dir = snapshots_dir();
+    let mut failures = vec![];
+
+    for file in snapshot_files(&dir) {
+        let name = file.strip_prefix(&dir).unwrap().display().to_string();
+        let mut snapshot = read_snapshot(&file);
+        let recorder = Recorder {
+            state: PairState {
+                asset_infos: ["uaaa", "ubbb"]
+                    .into_iter()
+                    .map(|denom| AssetInfo::NativeToken {
+                        denom: denom.to_string(),
+                    })
+                    .collect(),
+                pool: snapshot.pool.clone(),
+            },
+        };
+
+        snapshot.pool = recorder
+            .pool()
+            .unwrap_or_else(|err| panic!("Error while creating the pair of {name}: {err}"));
+        for (ind, case) in snapshot.cases.iter_mut().enumerate() {
+            if let Err(err) = recorder.record(case) {
+                failures.push(format!("{name} case #{ind} {:?}: {err}", case.operation));
+            }
+        }
+
+        let value = serde_json::to_value(&snapshot).unwrap();
+        fs::write(&file, format(&value, 0, 0) + "\n").unwrap();
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} cases failed to record:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
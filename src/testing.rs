@@ -0,0 +1,155 @@
+//! Proptest strategies of pool states for fuzzing the simulations, e.g. in the tests of
+//! integrations of this crate.
+//!
+//! The generated states are realistic: a thousand to a billion tokens of the first asset and
+//! the second one within a factor of 10 of it at the price (price scale of concentrated pools),
+//! the total amount of lps close to the one of a pool created with such balances, and fees and
+//! amplifications within the bounds of the pair contracts.
+
+use cosmwasm_std::{Decimal, Decimal256, StdError, StdResult, Uint128};
+use proptest::prelude::*;
+
+pub use crate::astroport::pair::PairState;
+pub use crate::astroport::pair_concentrated::pool::PoolState as ConcentratedPoolState;
+pub use crate::astroport::pair_stable::pool::PoolState as StablePoolState;
+pub use crate::astroport::pair_xyk::pool::PoolState as XykPoolState;
+pub use crate::astroport::pool::PoolState;
+
+use crate::astroport::asset::AssetInfo;
+use crate::astroport::{pair_concentrated, pair_stable};
+
+/// A decimal in `[min, max]` with the precision of `1 / denominator`.
+fn decimal(min: u64, max: u64, denominator: u64) -> impl Strategy<Value = Decimal> {
+    (min..=max).prop_map(move |numerator| Decimal::from_ratio(numerator, denominator))
+}
+
+/// An amount of `tokens` whole tokens with the precision of `precision`.
+fn amount(tokens: f64, precision: u32) -> Uint128 {
+    Uint128::new((tokens * 10f64.powi(precision as i32)) as u128)
+}
+
+/// Whole tokens of the first asset and the ratio of the second one to it.
+fn balances() -> impl Strategy<Value = (f64, f64)> {
+    (1_000f64..1_000_000_000f64, 0.1f64..10f64)
+}
+
+pub fn xyk_pool_state() -> impl Strategy<Value = XykPoolState> {
+    (balances(), decimal(0, 100, 10_000)).prop_map(|((tokens, ratio), total_fee_rate)| {
+        let asset_amounts = vec![amount(tokens, 6), amount(tokens * ratio, 6)];
+        let total_share = amount((tokens * tokens * ratio).sqrt(), 6);
+
+        XykPoolState {
+            asset_amounts,
+            total_share,
+            total_fee_rate,
+        }
+    })
+}
+
+pub fn stable_pool_state() -> impl Strategy<Value = StablePoolState> {
+    (
+        balances(),
+        prop::sample::select(vec![6u8, 8, 18]),
+        decimal(1, 50, 10_000),
+        1u64..=1000,
+    )
+        .prop_map(|((tokens, ratio), precision, total_fee_rate, amp)| {
+            let asset_amounts = vec![
+                Decimal256::from_ratio(amount(tokens, 6), 1u8),
+                Decimal256::from_ratio(amount(tokens * ratio, precision.into()), 1u8),
+            ];
+
+            StablePoolState {
+                asset_amounts,
+                asset_precisions: vec![6, precision],
+                total_share: amount(tokens * (1.0 + ratio), 6),
+                total_fee_rate,
+                block_time: 0,
+                init_amp_time: 0,
+                init_amp: amp * 100,
+                next_amp_time: 0,
+                next_amp: amp * 100,
+            }
+        })
+}
+
+pub fn concentrated_pool_state() -> impl Strategy<Value = ConcentratedPoolState> {
+    (
+        balances(),
+        decimal(1, 10_000, 100),
+        decimal(0, 5, 10),
+        decimal(10, 200, 1),
+        decimal(50, 500, 1_000_000),
+    )
+        .prop_map(
+            |((tokens, ratio), price_scale, maker_fee_share, amp, gamma)| {
+                let price = price_scale.to_string().parse::<f64>().unwrap_or(1.0);
+                // The ratio is applied around the price, so that the pool is near balanced
+                let tokens_1 = tokens * ratio.sqrt() / price;
+                let price_scale = Decimal256::from(price_scale);
+
+                ConcentratedPoolState {
+                    asset_amounts: vec![
+                        Decimal256::from_ratio(amount(tokens, 6), 1u8),
+                        Decimal256::from_ratio(amount(tokens_1, 6), 1u8),
+                    ],
+                    asset_precisions: vec![6, 6],
+                    total_share: amount((tokens * tokens_1).sqrt(), 6),
+                    maker_fee_share: maker_fee_share.into(),
+                    oracle_price: price_scale,
+                    price_scale,
+                    fee_gamma: Decimal256::from_ratio(23u8, 100_000u32),
+                    mid_fee: Decimal256::from_ratio(26u8, 10_000u32),
+                    out_fee: Decimal256::from_ratio(45u8, 10_000u32),
+                    block_time: 0,
+                    initial_time: 0,
+                    initial_amp: amp,
+                    initial_gamma: gamma,
+                    future_time: 0,
+                    future_amp: amp,
+                    future_gamma: gamma,
+                    xcp_profit_real: None,
+                }
+            },
+        )
+}
+
+/// A pool state of any type.
+pub fn pool_state() -> impl Strategy<Value = PoolState> {
+    prop_oneof![
+        xyk_pool_state().prop_map(PoolState::Xyk),
+        stable_pool_state().prop_map(PoolState::Stable),
+        concentrated_pool_state().prop_map(|state| PoolState::Concentrated(Box::new(state))),
+    ]
+}
+
+/// A pair state of any type of pool between two native tokens.
+pub fn pair_state() -> impl Strategy<Value = PairState> {
+    pool_state().prop_map(|pool| PairState {
+        asset_infos: ["uluna", "uusdc"]
+            .into_iter()
+            .map(|denom| AssetInfo::NativeToken {
+                denom: denom.to_string(),
+            })
+            .collect(),
+        pool,
+    })
+}
+
+/// Returns the invariant of the pool balances: `sqrt(x * y)` for xyk pools and D for the
+/// others, which must not decrease after swaps.
+pub fn invariant(state: &PoolState) -> StdResult<Decimal256> {
+    match state {
+        PoolState::Xyk(state) => {
+            let product = Decimal256::from_ratio(state.asset_amounts[0], 1u8)
+                .checked_mul(Decimal256::from_ratio(state.asset_amounts[1], 1u8))?;
+            Ok(product.sqrt())
+        }
+        PoolState::Stable(state) => pair_stable::pool::Pool::new(state.clone())
+            .map(|pool| pool.d())
+            .map_err(|err| StdError::generic_err(err.to_string())),
+        PoolState::Concentrated(state) => pair_concentrated::pool::Pool::new(*state.clone())
+            .map(|pool| pool.d())
+            .map_err(|err| StdError::generic_err(err.to_string())),
+    }
+}
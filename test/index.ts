@@ -3,14 +3,90 @@ import { CosmWasmClient } from "@cosmjs/cosmwasm-stargate";
 import BigNumber from "bignumber.js";
 import {
   xyk_swap,
+  xykSwap,
+  xyk_swap_batch,
+  xyk_swap_to_price,
+  xyk_depth,
+  xyk_ladder,
+  xyk_sample_curve,
+  XykPool,
   xyk_provide,
+  xyk_reverse_provide,
   xyk_withdraw,
+  xykWithdraw,
+  xyk_zap_in,
+  xyk_zap_out,
   stable_swap,
+  stableSwap,
+  stable_swap_batch,
+  StablePoolSnapshot,
+  StablePool,
+  stable_swap_to_price,
+  stable_depth,
+  stable_ladder,
+  stable_sample_curve,
   stable_provide,
+  stableProvide,
+  stable_reverse_provide,
   stable_withdraw,
+  stable_withdraw_one,
+  stable_withdraw_imbalanced,
+  stable_zap_in,
+  stable_zap_out,
   concentrated_swap,
+  concentrated_swap_batch,
+  concentratedSwapBatch,
+  ConcentratedPoolSnapshot,
+  ConcentratedPool,
+  concentrated_swap_to_price,
+  concentrated_depth,
+  concentrated_ladder,
+  concentrated_sample_curve,
   concentrated_provide,
+  concentrated_reverse_provide,
   concentrated_withdraw,
+  concentrated_zap_in,
+  concentrated_zap_out,
+  pair_query,
+  pairQuery,
+} from "@astroport/math";
+import type {
+  Asset,
+  AssetInfo,
+  BatchItem,
+  CurvePoint,
+  DepthResponse,
+  LadderLevel,
+  LadderResponse,
+  PairPoolResponse,
+  PairReverseSimulationResponse,
+  PairSimulationResponse,
+  XykSwapSimulationResponse,
+  XykReverseSwapSimulationResponse,
+  XykSwapToPriceResponse,
+  XykProvideSimulationResponse,
+  XykReverseProvideSimulationResponse,
+  XykWithdrawSimulationResponse,
+  XykZapInSimulationResponse,
+  XykZapOutSimulationResponse,
+  StableSwapSimulationResponse,
+  StableReverseSwapSimulationResponse,
+  StableSwapToPriceResponse,
+  StableProvideSimulationResponse,
+  StableReverseProvideSimulationResponse,
+  StableWithdrawSimulationResponse,
+  StableWithdrawOneSimulationResponse,
+  StableImbalancedWithdrawSimulationResponse,
+  StableZapInSimulationResponse,
+  StableZapOutSimulationResponse,
+  ConcentratedSwapSimulationResponse,
+  ConcentratedReverseSwapSimulationResponse,
+  ConcentratedSwapToPriceResponse,
+  ConcentratedProvideSimulationResponse,
+  ConcentratedReverseProvideSimulationResponse,
+  ConcentratedWithdrawSimulationResponse,
+  ConcentratedZapInSimulationResponse,
+  ConcentratedZapOutSimulationResponse,
 } from "@astroport/math";
 
 function assert(condition: boolean, message?: string) {
@@ -19,36 +95,70 @@ function assert(condition: boolean, message?: string) {
   }
 }
 
-type AssetInfo = CW20AssetInfo | NativeAssetInfo;
-type CW20AssetInfo = { token: { contract_addr: string } };
-type NativeAssetInfo = { native_token: { denom: string } };
+type SwapResponse =
+  | XykSwapSimulationResponse
+  | StableSwapSimulationResponse
+  | ConcentratedSwapSimulationResponse;
 
-type Asset = {
-  info: AssetInfo;
-  amount: string;
-};
+function assert_prices(actual: SwapResponse, expected: SwapResponse) {
+  assert(
+    actual.return_amount === expected.return_amount &&
+      actual.spot_price_before === expected.spot_price_before &&
+      actual.execution_price === expected.execution_price &&
+      actual.spot_price_after === expected.spot_price_after &&
+      actual.price_impact === expected.price_impact
+  );
+}
 
-type PoolInfo = {
-  assets: Asset[];
-  total_share: string;
-};
+function assert_batch_ok(
+  actual: BatchItem<SwapResponse>,
+  expected: SwapResponse
+) {
+  assert("ok" in actual);
+  assert_prices((actual as { ok: SwapResponse }).ok, expected);
+}
 
-type SwapResult = {
-  return_amount: string;
-  spread_amount: string;
-  commission_amount: string;
-};
+function assert_depth(actual: DepthResponse[], expected: DepthResponse[]) {
+  assert(actual.length === expected.length);
+  actual.forEach((depth, i) =>
+    assert(
+      depth.price_impact === expected[i].price_impact &&
+        depth.offer_amounts[0] === expected[i].offer_amounts[0] &&
+        depth.offer_amounts[1] === expected[i].offer_amounts[1]
+    )
+  );
+}
 
-type ProvideResult = {
-  share_amount: string;
-  slippage?: string;
-  positive_slippage?: boolean;
-};
+function assert_ladder(actual: LadderResponse, expected: LadderResponse) {
+  const assert_levels = (levels: LadderLevel[], expected: LadderLevel[]) => {
+    assert(levels.length === expected.length);
+    levels.forEach((level, i) =>
+      assert(
+        level.price === expected[i].price &&
+          level.base_amount === expected[i].base_amount &&
+          level.quote_amount === expected[i].quote_amount
+      )
+    );
+  };
 
-type WithdrawResult = {
-  returned_amounts: [string, string];
-};
+  assert(actual.mid_price === expected.mid_price);
+  assert_levels(actual.bids, expected.bids);
+  assert_levels(actual.asks, expected.asks);
+}
 
+function assert_curve(actual: CurvePoint[], expected: CurvePoint[]) {
+  assert(actual.length === expected.length);
+  actual.forEach((point, i) =>
+    assert(
+      point.offer_amount === expected[i].offer_amount &&
+        point.return_amount === expected[i].return_amount &&
+        point.commission_amount === expected[i].commission_amount &&
+        point.price_impact === expected[i].price_impact
+    )
+  );
+}
+
+// The raw storage of the pair contracts, which isn't part of the bindings
 type XykPoolRawConfig = {
   block_time_last: number;
   price0_cumulative_last: string;
@@ -98,15 +208,18 @@ const XYK_POOL =
   "terra1p0t2kt26mredrp0va2uwzeyj7c7ny5g27ae6dxkcwas6hlrf39tsrehqzp";
 
 async function xyk_swap_test(client: CosmWasmClient) {
-  const pool_info: PoolInfo = await client.queryContractSmart(XYK_POOL, {
-    pool: {},
-  });
+  const pool_info: PairPoolResponse = await client.queryContractSmart(
+    XYK_POOL,
+    {
+      pool: {},
+    }
+  );
   const asset_amounts = pool_info.assets.map((asset) => asset.amount);
 
   let ask_ind = 0;
   let offer_amount: BigNumber | null = null;
-  let simulation: SwapResult | null = null;
-  let xyk_result: SwapResult | null = null;
+  let simulation: PairSimulationResponse | null = null;
+  let xyk_result: XykSwapSimulationResponse | null = null;
   try {
     for (let i = 3; i < 6; i++) {
       for (let j = 0; j < 2; j++) {
@@ -151,12 +264,419 @@ async function xyk_swap_test(client: CosmWasmClient) {
   }
 }
 
+async function xyk_price_impact_test() {
+  const expected_result: XykSwapSimulationResponse = {
+    return_amount: "996003997",
+    spread_amount: "999001",
+    commission_amount: "2997002",
+    spot_price_before: "1000",
+    execution_price: "996.003997",
+    spot_price_after: "998.005990012987012987",
+    price_impact: "0.000999001",
+  };
+  let xyk_result: XykSwapSimulationResponse | null = null;
+  try {
+    xyk_result = JSON.parse(
+      xyk_swap(
+        "1000000",
+        "1",
+        JSON.stringify(["1000000000", "1000000000000"]),
+        XYK_FEE
+      )
+    );
+    assert_prices(xyk_result, expected_result);
+
+    console.info(chalk.green("xyk price impact assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(xyk_result)));
+    console.error(chalk.red("xyk price impact assertions: fail"));
+    return false;
+  }
+}
+
+async function xyk_swap_batch_test() {
+  const expected_result: XykSwapSimulationResponse = {
+    return_amount: "996003997",
+    spread_amount: "999001",
+    commission_amount: "2997002",
+    spot_price_before: "1000",
+    execution_price: "996.003997",
+    spot_price_after: "998.005990012987012987",
+    price_impact: "0.000999001",
+  };
+  const request = { pool: 0, offer_amount: "1000000", ask_ind: 1 };
+  let xyk_result: BatchItem<XykSwapSimulationResponse>[] | null = null;
+  try {
+    xyk_result = JSON.parse(
+      xyk_swap_batch(
+        JSON.stringify([
+          {
+            asset_amounts: ["1000000000", "1000000000000"],
+            total_fee_rate: XYK_FEE,
+          },
+        ]),
+        JSON.stringify([request, { ...request, pool: 1 }])
+      )
+    );
+    assert(xyk_result.length === 2);
+    assert_batch_ok(xyk_result[0], expected_result);
+    assert("error" in xyk_result[1]);
+
+    console.info(chalk.green("xyk swap batch assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("actual: ", JSON.stringify(xyk_result)));
+    console.error(chalk.red("xyk swap batch assertions: fail"));
+    return false;
+  }
+}
+
+async function xyk_swap_to_price_test() {
+  const expected_result = {
+    offer_ind: 0,
+    offer_amount: "5045422",
+    spot_price_after: "989.999998996065274351",
+  };
+  let xyk_result: XykSwapToPriceResponse | null = null;
+  try {
+    xyk_result = JSON.parse(
+      xyk_swap_to_price(
+        "990",
+        JSON.stringify(["1000000000", "1000000000000"]),
+        XYK_FEE
+      )
+    );
+    assert(
+      xyk_result.offer_ind === expected_result.offer_ind &&
+        xyk_result.offer_amount === expected_result.offer_amount &&
+        xyk_result.swap.spot_price_after === expected_result.spot_price_after
+    );
+
+    console.info(chalk.green("xyk swap to price assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(xyk_result)));
+    console.error(chalk.red("xyk swap to price assertions: fail"));
+    return false;
+  }
+}
+
+async function xyk_depth_test() {
+  const expected_result: DepthResponse[] = [
+    { price_impact: "0.005", offer_amounts: ["5025125", "5024996984"] },
+    { price_impact: "0.02", offer_amounts: ["20408163", "20408133673"] },
+    { price_impact: "0.1", offer_amounts: ["111111111", "111111107777"] },
+  ];
+  let xyk_result: DepthResponse[] | null = null;
+  try {
+    xyk_result = JSON.parse(
+      xyk_depth(
+        JSON.stringify(["0.005", "0.02", "0.1"]),
+        JSON.stringify(["1000000000", "1000000000000"]),
+        XYK_FEE
+      )
+    );
+    assert_depth(xyk_result, expected_result);
+
+    console.info(chalk.green("xyk depth assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(xyk_result)));
+    console.error(chalk.red("xyk depth assertions: fail"));
+    return false;
+  }
+}
+
+async function xyk_ladder_test() {
+  const expected_result: LadderResponse = {
+    mid_price: "1000",
+    bids: [
+      { price: "995", base_amount: "2513194", quote_amount: "2499373008" },
+      { price: "990", base_amount: "5045422", quote_amount: "5005033229" },
+    ],
+    asks: [
+      { price: "1005", base_amount: "2486922", quote_amount: "2500643112" },
+      { price: "1010", base_amount: "4955355", quote_amount: "4995092093" },
+    ],
+  };
+  let xyk_result: LadderResponse | null = null;
+  try {
+    xyk_result = JSON.parse(
+      xyk_ladder(
+        "5",
+        "2",
+        JSON.stringify(["1000000000", "1000000000000"]),
+        XYK_FEE
+      )
+    );
+    assert_ladder(xyk_result, expected_result);
+
+    console.info(chalk.green("xyk ladder assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(xyk_result)));
+    console.error(chalk.red("xyk ladder assertions: fail"));
+    return false;
+  }
+}
+
+async function xyk_sample_curve_test() {
+  const expected_result: CurvePoint[] = [
+    {
+      offer_amount: "1000000",
+      return_amount: "996003997",
+      commission_amount: "2997002",
+      price_impact: "0.000999001",
+    },
+    {
+      offer_amount: "10000000",
+      return_amount: "9871287129",
+      commission_amount: "29702970",
+      price_impact: "0.0099009901",
+    },
+    {
+      offer_amount: "100000000",
+      return_amount: "90636363637",
+      commission_amount: "272727272",
+      price_impact: "0.09090909091",
+    },
+    {
+      offer_amount: "1000000000",
+      return_amount: "498500000000",
+      commission_amount: "1500000000",
+      price_impact: "0.5",
+    },
+  ];
+  let xyk_result: CurvePoint[] | null = null;
+  try {
+    xyk_result = JSON.parse(
+      xyk_sample_curve(
+        "1000000",
+        "1000000000",
+        "4",
+        "log",
+        "1",
+        JSON.stringify(["1000000000", "1000000000000"]),
+        XYK_FEE
+      )
+    );
+    assert_curve(xyk_result, expected_result);
+
+    console.info(chalk.green("xyk sample curve assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(xyk_result)));
+    console.error(chalk.red("xyk sample curve assertions: fail"));
+    return false;
+  }
+}
+
+async function xyk_pool_test() {
+  const expected_swap: XykSwapSimulationResponse = {
+    return_amount: "9976626",
+    spread_amount: "20038",
+    commission_amount: "30019",
+    spot_price_before: "0.010026683475848485",
+    execution_price: "0.009976626",
+    spot_price_after: "0.009986708450256508",
+    price_impact: "0.001998514852568365",
+  };
+  const expected_reverse_swap: XykReverseSwapSimulationResponse = {
+    offer_amount: "999999905",
+    spread_amount: "20037",
+    commission_amount: "30019",
+  };
+  let swap_result: XykSwapSimulationResponse | null = null;
+  let reverse_swap_result: XykReverseSwapSimulationResponse | null = null;
+  try {
+    const pool = new XykPool(
+      JSON.stringify({
+        asset_amounts: ["499395163721", "5007277236"],
+        total_share: "50000024999",
+        total_fee_rate: "0.003",
+      })
+    );
+
+    swap_result = pool.swap("1000000000", "1");
+    reverse_swap_result = pool.reverseSwap(swap_result.return_amount, "1");
+    assert_prices(swap_result, expected_swap);
+    assert(
+      JSON.stringify(reverse_swap_result) ===
+        JSON.stringify(expected_reverse_swap)
+    );
+
+    const provide_result = pool.provide(
+      JSON.stringify(["497668967", "4989969"])
+    );
+    assert(provide_result.share_amount === "49827194");
+
+    const withdraw_result = pool.withdraw("1000000000");
+    assert(
+      withdraw_result.returned_amounts[0] === "9987898280" &&
+        withdraw_result.returned_amounts[1] === "100145494"
+    );
+
+    assert(pool.spotPrice("1") === "0.010026683475848485");
+    pool.update(JSON.stringify(["500000000000", "5000000000"]), "50000000000");
+    assert(pool.spotPrice("1") === "0.01");
+    assert(pool.state().total_share === "50000000000");
+
+    console.info(chalk.green("xyk pool assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("swap: ", JSON.stringify(swap_result)));
+    console.error(
+      chalk.yellow("reverse swap: ", JSON.stringify(reverse_swap_result))
+    );
+    console.error(chalk.red("xyk pool assertions: fail"));
+    return false;
+  }
+}
+
+async function xyk_pair_query_test() {
+  const luna: AssetInfo = { native_token: { denom: "uluna" } };
+  const astro: AssetInfo = { token: { contract_addr: "terra1astro" } };
+  const pair = JSON.stringify({
+    asset_infos: [luna, astro],
+    pool: {
+      xyk: {
+        asset_amounts: ["499395163721", "5007277236"],
+        total_share: "50000024999",
+        total_fee_rate: "0.003",
+      },
+    },
+  });
+  let swap_result: PairSimulationResponse | null = null;
+  try {
+    swap_result = JSON.parse(
+      pair_query(
+        pair,
+        JSON.stringify({
+          simulation: { offer_asset: { info: luna, amount: "1000000000" } },
+        })
+      )
+    );
+    assert(
+      JSON.stringify(swap_result) ===
+        JSON.stringify({
+          return_amount: "9976626",
+          spread_amount: "20038",
+          commission_amount: "30019",
+        })
+    );
+
+    const reverse_swap_result: PairReverseSimulationResponse = pairQuery(pair, {
+      reverse_simulation: { ask_asset: { info: astro, amount: 9976626n } },
+    });
+    assert(reverse_swap_result.offer_amount === "999999905");
+
+    const share_amount: string = pairQuery(pair, {
+      simulate_provide: {
+        assets: [
+          { info: luna, amount: "497668967" },
+          { info: astro, amount: "4989969" },
+        ],
+      },
+    });
+    assert(share_amount === "49827194");
+
+    const assets: Asset[] = pairQuery(pair, {
+      simulate_withdraw: { lp_amount: "1000000000" },
+    });
+    assert(
+      assets[0].amount === "9987898280" && assets[1].amount === "100145494"
+    );
+
+    const pool: PairPoolResponse = pairQuery(pair, { pool: {} });
+    assert(
+      pool.total_share === "50000024999" &&
+        JSON.stringify(pool.assets[1].info) === JSON.stringify(astro)
+    );
+
+    console.info(chalk.green("xyk pair query assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("swap: ", JSON.stringify(swap_result)));
+    console.error(chalk.red("xyk pair query assertions: fail"));
+    return false;
+  }
+}
+
+async function xyk_object_api_test() {
+  const asset_amounts = ["1000000000", "1000000000000"];
+  let expected_result: XykSwapSimulationResponse | null = null;
+  let xyk_result: XykSwapSimulationResponse | null = null;
+  try {
+    expected_result = JSON.parse(
+      xyk_swap("1000000", "1", JSON.stringify(asset_amounts), XYK_FEE)
+    );
+    xyk_result = xykSwap(
+      BigInt(1000000),
+      1,
+      asset_amounts.map(BigInt),
+      XYK_FEE
+    );
+    assert(JSON.stringify(xyk_result) === JSON.stringify(expected_result));
+
+    const withdraw_result = xykWithdraw(
+      "1000000",
+      asset_amounts,
+      BigInt("31622776601")
+    );
+    assert(
+      withdraw_result.returned_amounts[0] === "31622" &&
+        withdraw_result.returned_amounts[1] === "31622776"
+    );
+
+    const pool = new XykPool({
+      asset_amounts: asset_amounts.map(BigInt),
+      total_share: BigInt("31622776601"),
+      total_fee_rate: XYK_FEE,
+    });
+    assert(
+      JSON.stringify(pool.swap(BigInt(1000000), 1)) ===
+        JSON.stringify(expected_result)
+    );
+
+    let error: string | null = null;
+    try {
+      xykSwap(1.5, 1, asset_amounts, XYK_FEE);
+    } catch (e) {
+      error = String(e);
+    }
+    assert(error !== null && error.startsWith("Invalid offer_amount"));
+
+    console.info(chalk.green("xyk object api assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(xyk_result)));
+    console.error(chalk.red("xyk object api assertions: fail"));
+    return false;
+  }
+}
+
 async function xyk_provide_test() {
   const asset_amounts: [string, string] = ["499395163721", "5007277236"];
   const total_share = "50000024999";
 
   const expected_share_amount = "49827194";
-  let xyk_result: ProvideResult | null = null;
+  let xyk_result: XykProvideSimulationResponse | null = null;
   try {
     const deposits: [string, string] = ["497668967", "4989969"];
 
@@ -167,7 +687,11 @@ async function xyk_provide_test() {
         total_share
       )
     );
-    assert(xyk_result.share_amount === expected_share_amount);
+    assert(
+      xyk_result.share_amount === expected_share_amount &&
+        xyk_result.lost_amounts[0] === "22" &&
+        xyk_result.lost_amounts[1] === "1"
+    );
 
     console.info(chalk.green("xyk provide assertions: pass"));
     return true;
@@ -182,12 +706,87 @@ async function xyk_provide_test() {
   }
 }
 
+async function xyk_provide_slippage_test() {
+  const asset_amounts: [string, string] = ["499395163721", "5007277236"];
+  const total_share = "50000024999";
+  const deposits: [string, string] = ["497668967", "5989969"];
+
+  try {
+    let failed = false;
+    try {
+      xyk_provide(
+        JSON.stringify(deposits),
+        JSON.stringify(asset_amounts),
+        total_share
+      );
+    } catch (e) {
+      failed = String(e).includes("max splippage tolerance");
+    }
+    assert(failed, "expected the default slippage tolerance to be exceeded");
+
+    const xyk_result: XykProvideSimulationResponse = JSON.parse(
+      xyk_provide(
+        JSON.stringify(deposits),
+        JSON.stringify(asset_amounts),
+        total_share,
+        "0.2"
+      )
+    );
+    assert(
+      xyk_result.share_amount === "49827196" &&
+        xyk_result.lost_amounts[0] === "2" &&
+        xyk_result.lost_amounts[1] === "999005"
+    );
+
+    console.info(chalk.green("xyk provide slippage assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.red("xyk provide slippage assertions: fail"));
+    return false;
+  }
+}
+
+async function xyk_reverse_provide_test() {
+  const asset_amounts: [string, string] = ["499395163721", "5007277236"];
+  const total_share = "50000024999";
+
+  const expected_result: XykReverseProvideSimulationResponse = {
+    deposits: ["497668946", "4989969"],
+    share_amount: "49827194",
+  };
+  let xyk_result: XykReverseProvideSimulationResponse | null = null;
+  try {
+    xyk_result = JSON.parse(
+      xyk_reverse_provide(
+        "49827194",
+        JSON.stringify(asset_amounts),
+        total_share
+      )
+    );
+    assert(
+      xyk_result.deposits[0] === expected_result.deposits[0] &&
+        xyk_result.deposits[1] === expected_result.deposits[1] &&
+        xyk_result.share_amount === expected_result.share_amount
+    );
+
+    console.info(chalk.green("xyk reverse provide assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(xyk_result)));
+    console.error(chalk.red("xyk reverse provide assertions: fail"));
+    return false;
+  }
+}
+
 async function xyk_withdraw_test() {
   const asset_amounts: [string, string] = ["978346165766", "124116104943"];
   const total_share = "348392451511";
 
   const expected_returned_amounts: [string, string] = ["26120383", "3313714"];
-  let xyk_result: WithdrawResult | null = null;
+  let xyk_result: XykWithdrawSimulationResponse | null = null;
   try {
     xyk_result = JSON.parse(
       xyk_withdraw("9301559", JSON.stringify(asset_amounts), total_share)
@@ -212,39 +811,213 @@ async function xyk_withdraw_test() {
   }
 }
 
-const STABLE_FEE = "0.0005";
-const STABLE_POOL =
-  "terra1v2ycfsv427m28tn32gjllza4p6hpe65excyxgtuszkycp73fjams85598j";
-
-async function stable_swap_test(client: CosmWasmClient) {
-  const pool_info: PoolInfo = await client.queryContractSmart(STABLE_POOL, {
-    pool: {},
-  });
-  const asset_amounts = pool_info.assets.map((asset) => asset.amount);
-  const rawConfig = await client.queryContractRaw(
-    STABLE_POOL,
-    Buffer.from("config", "utf8")
-  );
-  const pool_config: StablePoolRawConfig = JSON.parse(
-    Buffer.from(rawConfig).toString()
-  );
+async function xyk_min_receive_test() {
+  const asset_amounts: [string, string] = ["499395163721", "5007277236"];
+  const total_share = "50000024999";
+  const deposits: [string, string] = ["497668967", "4989969"];
 
-  const block_time = String(Math.floor(new Date().getTime() / 1000));
-  const init_amp_time = String(pool_config.init_amp_time);
-  const init_amp = String(pool_config.init_amp);
-  const next_amp_time = String(pool_config.next_amp_time);
-  const next_amp = String(pool_config.next_amp);
+  const rejects = (simulate: () => string, message: string) => {
+    try {
+      simulate();
+    } catch (e) {
+      return String(e).includes(message);
+    }
+    return false;
+  };
 
-  let ask_ind = 0;
-  let offer_amount: BigNumber | null = null;
-  let simulation: SwapResult | null = null;
-  let stable_result: SwapResult | null = null;
   try {
-    for (let i = 3; i < 6; i++) {
-      for (let j = 0; j < 2; j++) {
-        ask_ind = j;
-        offer_amount = BigNumber(asset_amounts[1 - ask_ind])
-          .dividedBy(i)
+    assert(
+      rejects(
+        () =>
+          xyk_provide(
+            JSON.stringify(deposits),
+            JSON.stringify(asset_amounts),
+            total_share,
+            undefined,
+            "49827195"
+          ),
+        "received 49827194, expected 49827195 LP tokens"
+      )
+    );
+    assert(
+      rejects(
+        () =>
+          xyk_withdraw(
+            "1000000",
+            JSON.stringify(asset_amounts),
+            total_share,
+            JSON.stringify(["9987898", "100146"])
+          ),
+        "Received 100145 asset #1 but expected 100146"
+      )
+    );
+
+    const xyk_result: XykWithdrawSimulationResponse = JSON.parse(
+      xyk_withdraw(
+        "1000000",
+        JSON.stringify(asset_amounts),
+        total_share,
+        JSON.stringify(["9987898", "100145"])
+      )
+    );
+    assert(
+      xyk_result.returned_amounts[0] === "9987898" &&
+        xyk_result.returned_amounts[1] === "100145"
+    );
+
+    console.info(chalk.green("xyk min receive assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.red("xyk min receive assertions: fail"));
+    return false;
+  }
+}
+
+async function xyk_zap_in_test() {
+  const asset_amounts: [string, string] = ["1000000000", "1000000000000"];
+  const total_share = "31622776601";
+
+  const expected_result = {
+    swap_amount: "500626",
+    share_amount: "15783666",
+    dust_amounts: ["1", "29"],
+  };
+  let xyk_result: XykZapInSimulationResponse | null = null;
+  try {
+    xyk_result = JSON.parse(
+      xyk_zap_in(
+        "1000000",
+        "0",
+        JSON.stringify(asset_amounts),
+        total_share,
+        XYK_FEE
+      )
+    );
+    assert(
+      xyk_result.swap_amount === expected_result.swap_amount &&
+        xyk_result.provide.share_amount === expected_result.share_amount &&
+        xyk_result.dust_amounts[0] === expected_result.dust_amounts[0] &&
+        xyk_result.dust_amounts[1] === expected_result.dust_amounts[1]
+    );
+
+    console.info(chalk.green("xyk zap in assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(xyk_result)));
+    console.error(chalk.red("xyk zap in assertions: fail"));
+    return false;
+  }
+}
+
+async function xyk_zap_in_large_offer_test() {
+  const asset_amounts: [string, string] = ["1000000000", "1000000000"];
+  const total_share = "1000000000";
+
+  // Offers of 10% and 50% of the pool
+  const expected_results = [
+    { swap_amount: "48885753", share_amount: "48731948" },
+    { swap_amount: "225158444", share_amount: "224331438" },
+  ];
+  let xyk_result: XykZapInSimulationResponse | null = null;
+  try {
+    for (const [ind, offer_amount] of ["100000000", "500000000"].entries()) {
+      xyk_result = JSON.parse(
+        xyk_zap_in(
+          offer_amount,
+          "0",
+          JSON.stringify(asset_amounts),
+          total_share,
+          XYK_FEE
+        )
+      );
+      assert(
+        xyk_result.swap_amount === expected_results[ind].swap_amount &&
+          xyk_result.provide.share_amount ===
+            expected_results[ind].share_amount
+      );
+    }
+
+    console.info(chalk.green("xyk zap in large offer assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_results)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(xyk_result)));
+    console.error(chalk.red("xyk zap in large offer assertions: fail"));
+    return false;
+  }
+}
+
+async function xyk_zap_out_test() {
+  const asset_amounts: [string, string] = ["1000000000", "1000000000000"];
+  const total_share = "31622776601";
+
+  const expected_return_amount = "996501";
+  let xyk_result: XykZapOutSimulationResponse | null = null;
+  try {
+    xyk_result = JSON.parse(
+      xyk_zap_out(
+        "15783666",
+        "0",
+        JSON.stringify(asset_amounts),
+        total_share,
+        XYK_FEE
+      )
+    );
+    assert(xyk_result.return_amount === expected_return_amount);
+
+    console.info(chalk.green("xyk zap out assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(
+      chalk.yellow("expected: ", JSON.stringify(expected_return_amount))
+    );
+    console.error(chalk.yellow("actual: ", JSON.stringify(xyk_result)));
+    console.error(chalk.red("xyk zap out assertions: fail"));
+    return false;
+  }
+}
+
+const STABLE_FEE = "0.0005";
+const STABLE_POOL =
+  "terra1v2ycfsv427m28tn32gjllza4p6hpe65excyxgtuszkycp73fjams85598j";
+
+async function stable_swap_test(client: CosmWasmClient) {
+  const pool_info: PairPoolResponse = await client.queryContractSmart(
+    STABLE_POOL,
+    {
+      pool: {},
+    }
+  );
+  const asset_amounts = pool_info.assets.map((asset) => asset.amount);
+  const rawConfig = await client.queryContractRaw(
+    STABLE_POOL,
+    Buffer.from("config", "utf8")
+  );
+  const pool_config: StablePoolRawConfig = JSON.parse(
+    Buffer.from(rawConfig).toString()
+  );
+
+  const block_time = String(Math.floor(new Date().getTime() / 1000));
+  const init_amp_time = String(pool_config.init_amp_time);
+  const init_amp = String(pool_config.init_amp);
+  const next_amp_time = String(pool_config.next_amp_time);
+  const next_amp = String(pool_config.next_amp);
+
+  let ask_ind = 0;
+  let offer_amount: BigNumber | null = null;
+  let simulation: PairSimulationResponse | null = null;
+  let stable_result: StableSwapSimulationResponse | null = null;
+  try {
+    for (let i = 3; i < 6; i++) {
+      for (let j = 0; j < 2; j++) {
+        ask_ind = j;
+        offer_amount = BigNumber(asset_amounts[1 - ask_ind])
+          .dividedBy(i)
           .decimalPlaces(0);
 
         simulation = await client.queryContractSmart(STABLE_POOL, {
@@ -285,17 +1058,750 @@ async function stable_swap_test(client: CosmWasmClient) {
       }
     }
 
-    console.info(chalk.green("stable assertions: pass"));
+    console.info(chalk.green("stable assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(chalk.yellow("expected: ", JSON.stringify(simulation)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(stable_result)));
+    console.error(chalk.red("stable assertions: fail"));
+    return false;
+  }
+}
+
+async function stable_price_impact_test() {
+  const expected_result: StableSwapSimulationResponse = {
+    return_amount: "80762858",
+    spread_amount: "19196741",
+    commission_amount: "40401",
+    spot_price_before: "0.955654476786583956",
+    execution_price: "0.80762858",
+    spot_price_after: "0.458287488861488839",
+    price_impact: "0.154472029768506767",
+  };
+  let stable_result: StableSwapSimulationResponse | null = null;
+  try {
+    stable_result = JSON.parse(
+      stable_swap(
+        "100000000",
+        "6", // offer_asset_prec
+        "1",
+        "6", // ask_asset_prec
+        JSON.stringify(["530256812", "100446728"]),
+        "0.0005",
+        "1692147376",
+        "1692039296",
+        "10000",
+        "1692039296",
+        "10000"
+      )
+    );
+    assert_prices(stable_result, expected_result);
+
+    console.info(chalk.green("stable price impact assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(stable_result)));
+    console.error(chalk.red("stable price impact assertions: fail"));
+    return false;
+  }
+}
+
+async function stable_swap_batch_test() {
+  const expected_result: StableSwapSimulationResponse = {
+    return_amount: "80762858",
+    spread_amount: "19196741",
+    commission_amount: "40401",
+    spot_price_before: "0.955654476786583956",
+    execution_price: "0.80762858",
+    spot_price_after: "0.458287488861488839",
+    price_impact: "0.154472029768506767",
+  };
+  let stable_result: BatchItem<StableSwapSimulationResponse>[] | null = null;
+  try {
+    stable_result = JSON.parse(
+      stable_swap_batch(
+        JSON.stringify([
+          {
+            asset_amounts: ["530256812", "100446728"],
+            asset_precisions: [6, 6],
+            total_fee_rate: "0.0005",
+            block_time: 1692147376,
+            init_amp_time: 1692039296,
+            init_amp: 10000,
+            next_amp_time: 1692039296,
+            next_amp: 10000,
+          },
+        ]),
+        JSON.stringify([{ pool: 0, offer_amount: "100000000", ask_ind: 1 }])
+      )
+    );
+    assert(stable_result.length === 1);
+    assert_batch_ok(stable_result[0], expected_result);
+
+    console.info(chalk.green("stable swap batch assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("actual: ", JSON.stringify(stable_result)));
+    console.error(chalk.red("stable swap batch assertions: fail"));
+    return false;
+  }
+}
+
+async function stable_swap_to_price_test() {
+  const expected_result = {
+    offer_ind: 0,
+    offer_amount: "37567814",
+    spot_price_after: "0.899999999143657776",
+  };
+  let stable_result: StableSwapToPriceResponse | null = null;
+  try {
+    stable_result = JSON.parse(
+      stable_swap_to_price(
+        "0.9",
+        JSON.stringify(["530256812", "100446728"]),
+        JSON.stringify([6, 6]),
+        "0.0005",
+        "1692147376",
+        "1692039296",
+        "10000",
+        "1692039296",
+        "10000"
+      )
+    );
+    assert(
+      stable_result.offer_ind === expected_result.offer_ind &&
+        stable_result.offer_amount === expected_result.offer_amount &&
+        stable_result.swap.spot_price_after === expected_result.spot_price_after
+    );
+
+    console.info(chalk.green("stable swap to price assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(stable_result)));
+    console.error(chalk.red("stable swap to price assertions: fail"));
+    return false;
+  }
+}
+
+async function stable_depth_test() {
+  const expected_result: DepthResponse[] = [
+    { price_impact: "0.005", offer_amounts: ["10415799", "12220167"] },
+    { price_impact: "0.02", offer_amounts: ["33084833", "75807070"] },
+    { price_impact: "0.1", offer_amounts: ["83146636", "549676854"] },
+  ];
+  let stable_result: DepthResponse[] | null = null;
+  try {
+    stable_result = JSON.parse(
+      stable_depth(
+        JSON.stringify(["0.005", "0.02", "0.1"]),
+        JSON.stringify(["530256812", "100446728"]),
+        JSON.stringify([6, 6]),
+        "0.0005",
+        "1692147376",
+        "1692039296",
+        "10000",
+        "1692039296",
+        "10000"
+      )
+    );
+    assert_depth(stable_result, expected_result);
+
+    console.info(chalk.green("stable depth assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(stable_result)));
+    console.error(chalk.red("stable depth assertions: fail"));
+    return false;
+  }
+}
+
+async function stable_ladder_test() {
+  const expected_result: LadderResponse = {
+    mid_price: "0.955654476786583956",
+    bids: [
+      {
+        price: "0.945654476786583956",
+        base_amount: "10405978",
+        quote_amount: "9889901",
+      },
+      {
+        price: "0.935654476786583956",
+        base_amount: "18501800",
+        quote_amount: "17502939",
+      },
+    ],
+    asks: [
+      {
+        price: "0.965654476786583956",
+        base_amount: "14153117",
+        quote_amount: "13607107",
+      },
+      {
+        price: "0.975654476786583956",
+        base_amount: "35187478",
+        quote_amount: "34042342",
+      },
+    ],
+  };
+  let stable_result: LadderResponse | null = null;
+  try {
+    stable_result = JSON.parse(
+      stable_ladder(
+        "0.01",
+        "2",
+        JSON.stringify(["530256812", "100446728"]),
+        JSON.stringify([6, 6]),
+        "0.0005",
+        "1692147376",
+        "1692039296",
+        "10000",
+        "1692039296",
+        "10000"
+      )
+    );
+    assert_ladder(stable_result, expected_result);
+
+    console.info(chalk.green("stable ladder assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(stable_result)));
+    console.error(chalk.red("stable ladder assertions: fail"));
+    return false;
+  }
+}
+
+async function stable_sample_curve_test() {
+  const expected_result: CurvePoint[] = [
+    {
+      offer_amount: "1000000",
+      return_amount: "954756",
+      commission_amount: "477",
+      price_impact: "0.000441034701162269",
+    },
+    {
+      offer_amount: "10000000",
+      return_amount: "9506090",
+      commission_amount: "4755",
+      price_impact: "0.004782038799159541",
+    },
+    {
+      offer_amount: "100000000",
+      return_amount: "80762858",
+      commission_amount: "40401",
+      price_impact: "0.154472029768506767",
+    },
+  ];
+  let stable_result: CurvePoint[] | null = null;
+  try {
+    stable_result = JSON.parse(
+      stable_sample_curve(
+        "1000000",
+        "100000000",
+        "3",
+        "log",
+        "1",
+        JSON.stringify(["530256812", "100446728"]),
+        JSON.stringify([6, 6]),
+        "0.0005",
+        "1692147376",
+        "1692039296",
+        "10000",
+        "1692039296",
+        "10000"
+      )
+    );
+    assert_curve(stable_result, expected_result);
+
+    console.info(chalk.green("stable sample curve assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(stable_result)));
+    console.error(chalk.red("stable sample curve assertions: fail"));
+    return false;
+  }
+}
+
+async function stable_snapshot_test() {
+  const expected_result: StableSwapSimulationResponse = {
+    return_amount: "80762858",
+    spread_amount: "19196741",
+    commission_amount: "40401",
+    spot_price_before: "0.955654476786583956",
+    execution_price: "0.80762858",
+    spot_price_after: "0.458287488861488839",
+    price_impact: "0.154472029768506767",
+  };
+  const expected_quote: CurvePoint = {
+    offer_amount: "10000000",
+    return_amount: "9506090",
+    commission_amount: "4755",
+    price_impact: "0.004782038799159541",
+  };
+  let stable_result: StableSwapSimulationResponse | null = null;
+  let stable_quote: CurvePoint | null = null;
+  try {
+    const snapshot = new StablePoolSnapshot(
+      JSON.stringify(["530256812", "100446728"]),
+      JSON.stringify([6, 6]),
+      "0.0005",
+      "1692147376",
+      "1692039296",
+      "10000",
+      "1692039296",
+      "10000"
+    );
+    stable_result = JSON.parse(snapshot.swap("100000000", "1"));
+    stable_quote = JSON.parse(snapshot.quote("10000000", "1"));
+    assert_prices(stable_result, expected_result);
+    assert_curve([stable_quote], [expected_quote]);
+
+    console.info(chalk.green("stable snapshot assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("actual: ", JSON.stringify(stable_result)));
+    console.error(chalk.yellow("quote: ", JSON.stringify(stable_quote)));
+    console.error(chalk.red("stable snapshot assertions: fail"));
+    return false;
+  }
+}
+
+async function stable_pool_test() {
+  const expected_swap: StableSwapSimulationResponse = {
+    return_amount: "80762858",
+    spread_amount: "19196741",
+    commission_amount: "40401",
+    spot_price_before: "0.955654476786583956",
+    execution_price: "0.80762858",
+    spot_price_after: "0.458287488861488839",
+    price_impact: "0.154472029768506767",
+  };
+  const expected_reverse_swap: StableReverseSwapSimulationResponse = {
+    offer_amount: "100000003",
+    spread_amount: "19196743",
+    commission_amount: "40401",
+  };
+  let swap_result: StableSwapSimulationResponse | null = null;
+  let reverse_swap_result: StableReverseSwapSimulationResponse | null = null;
+  try {
+    const pool = new StablePool(
+      JSON.stringify({
+        asset_amounts: ["530256812", "100446728"],
+        asset_precisions: [6, 6],
+        total_share: "300000000",
+        total_fee_rate: "0.0005",
+        block_time: 1692147376,
+        init_amp_time: 1692039296,
+        init_amp: 10000,
+        next_amp_time: 1692039296,
+        next_amp: 10000,
+      })
+    );
+
+    swap_result = pool.swap("100000000", "1");
+    reverse_swap_result = pool.reverseSwap(swap_result.return_amount, "1");
+    assert_prices(swap_result, expected_swap);
+    assert(
+      JSON.stringify(reverse_swap_result) ===
+        JSON.stringify(expected_reverse_swap)
+    );
+
+    const provide_result = pool.provide(
+      JSON.stringify(["791847812", "150000000"])
+    );
+    assert(provide_result.share_amount === "447998664");
+
+    const withdraw_result = pool.withdraw("30000000");
+    assert(
+      withdraw_result.returned_amounts[0] === "53025681" &&
+        withdraw_result.returned_amounts[1] === "10044672"
+    );
+
+    assert(pool.spotPrice("1") === "0.955654476786583956");
+    pool.update(JSON.stringify(["100446728", "530256812"]));
+    assert(pool.spotPrice("1") === "1.046403301915697772");
+
+    console.info(chalk.green("stable pool assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("swap: ", JSON.stringify(swap_result)));
+    console.error(
+      chalk.yellow("reverse swap: ", JSON.stringify(reverse_swap_result))
+    );
+    console.error(chalk.red("stable pool assertions: fail"));
+    return false;
+  }
+}
+
+async function stable_object_api_test() {
+  const asset_amounts = ["530256812", "100446728"];
+  let expected_result: StableSwapSimulationResponse | null = null;
+  let stable_result: StableSwapSimulationResponse | null = null;
+  try {
+    expected_result = JSON.parse(
+      stable_swap(
+        "100000000",
+        "6", // offer_asset_prec
+        "1",
+        "6", // ask_asset_prec
+        JSON.stringify(asset_amounts),
+        STABLE_FEE,
+        "1692147376",
+        "1692039296",
+        "10000",
+        "1692039296",
+        "10000"
+      )
+    );
+    stable_result = stableSwap(
+      BigInt(100000000),
+      6, // offer_asset_prec
+      1,
+      6, // ask_asset_prec
+      asset_amounts.map(BigInt),
+      STABLE_FEE,
+      BigInt(1692147376),
+      1692039296,
+      "10000",
+      1692039296,
+      10000
+    );
+    assert(JSON.stringify(stable_result) === JSON.stringify(expected_result));
+
+    const deposits = ["791847812", "150000000"];
+    const expected_provide: StableProvideSimulationResponse = JSON.parse(
+      stable_provide(
+        JSON.stringify(deposits),
+        JSON.stringify(asset_amounts),
+        JSON.stringify([6, 6]),
+        "300000000",
+        "1692147376",
+        "1692039296",
+        "10000",
+        "1692039296",
+        "10000"
+      )
+    );
+    const provide_result = stableProvide(
+      deposits.map(BigInt),
+      asset_amounts,
+      [6, 6],
+      BigInt(300000000),
+      1692147376,
+      1692039296,
+      10000,
+      1692039296,
+      10000
+    );
+    assert(JSON.stringify(provide_result) === JSON.stringify(expected_provide));
+
+    console.info(chalk.green("stable object api assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(stable_result)));
+    console.error(chalk.red("stable object api assertions: fail"));
+    return false;
+  }
+}
+
+async function stable_provide_test() {
+  const asset_amounts: [string, string] = ["530256812", "100446728"];
+  const asset_precisions: [number, number] = [6, 6];
+  const total_share = "300000000";
+
+  const block_time = "1692147376";
+  const init_amp_time = "1692039296";
+  const init_amp = "10000";
+  const next_amp_time = "1692039296";
+  const next_amp = "10000";
+
+  const expected_share_amount = "447998664";
+  let stable_result: StableProvideSimulationResponse | null = null;
+  try {
+    const deposits: [string, string] = ["791847812", "150000000"];
+
+    stable_result = JSON.parse(
+      stable_provide(
+        JSON.stringify(deposits),
+        JSON.stringify(asset_amounts),
+        JSON.stringify(asset_precisions),
+        total_share,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp
+      )
+    );
+    assert(
+      stable_result.share_amount === expected_share_amount &&
+        stable_result.lost_amounts[0] === "1" &&
+        stable_result.lost_amounts[1] === "1"
+    );
+
+    console.info(chalk.green("stable provide assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(
+      chalk.yellow("expected: ", JSON.stringify(expected_share_amount))
+    );
+    console.error(chalk.yellow("actual: ", JSON.stringify(stable_result)));
+    console.error(chalk.red("stable provide assertions: fail"));
+    return false;
+  }
+}
+
+async function stable_provide_slippage_test() {
+  const asset_amounts: [string, string] = ["530256812", "100446728"];
+  const asset_precisions: [number, number] = [6, 6];
+  const total_share = "300000000";
+  const deposits: [string, string] = ["1000000", "0"];
+
+  const simulate = (
+    slippage_tolerance?: string
+  ): StableProvideSimulationResponse =>
+    JSON.parse(
+      stable_provide(
+        JSON.stringify(deposits),
+        JSON.stringify(asset_amounts),
+        JSON.stringify(asset_precisions),
+        total_share,
+        "1692147376",
+        "1692039296",
+        "10000",
+        "1692039296",
+        "10000",
+        slippage_tolerance
+      )
+    );
+
+  try {
+    const stable_result = simulate("0.01");
+    assert(
+      stable_result.share_amount === "472164" &&
+        stable_result.lost_amounts[0] === "165180" &&
+        stable_result.lost_amounts[1] === "0"
+    );
+
+    // The pair contract doesn't check the tolerance unless it's specified
+    assert(simulate().share_amount === stable_result.share_amount);
+
+    let failed = false;
+    try {
+      simulate("0");
+    } catch (e) {
+      failed = String(e).includes("max splippage tolerance");
+    }
+    assert(failed, "expected the zero slippage tolerance to be exceeded");
+
+    console.info(chalk.green("stable provide slippage assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.red("stable provide slippage assertions: fail"));
+    return false;
+  }
+}
+
+async function stable_reverse_provide_test() {
+  const asset_amounts: [string, string] = ["530256812", "100446728"];
+  const asset_precisions: [number, number] = [6, 6];
+  const total_share = "300000000";
+
+  const block_time = "1692147376";
+  const init_amp_time = "1692039296";
+  const init_amp = "10000";
+  const next_amp_time = "1692039296";
+  const next_amp = "10000";
+
+  const expected_result: StableReverseProvideSimulationResponse = {
+    deposits: ["1767523", "334823"],
+    share_amount: "1000000",
+  };
+  let stable_result: StableReverseProvideSimulationResponse | null = null;
+  try {
+    stable_result = JSON.parse(
+      stable_reverse_provide(
+        "1000000",
+        JSON.stringify(asset_amounts),
+        JSON.stringify(asset_precisions),
+        total_share,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp
+      )
+    );
+    assert(
+      stable_result.deposits[0] === expected_result.deposits[0] &&
+        stable_result.deposits[1] === expected_result.deposits[1] &&
+        stable_result.share_amount === expected_result.share_amount
+    );
+
+    console.info(chalk.green("stable reverse provide assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(stable_result)));
+    console.error(chalk.red("stable reverse provide assertions: fail"));
+    return false;
+  }
+}
+
+async function stable_withdraw_test() {
+  const asset_amounts: [string, string] = ["530256812", "100446728"];
+  const total_share = "300000000";
+
+  const expected_returned_amounts: [string, string] = [
+    "530255044",
+    "100446393",
+  ];
+  let stable_result: StableWithdrawSimulationResponse | null = null;
+  try {
+    stable_result = JSON.parse(
+      stable_withdraw("299999000", JSON.stringify(asset_amounts), total_share)
+    );
+    assert(
+      stable_result.returned_amounts[0] === expected_returned_amounts[0] &&
+        stable_result.returned_amounts[1] === expected_returned_amounts[1]
+    );
+
+    console.info(chalk.green("stable withdraw assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(
+      chalk.yellow("expected: ", JSON.stringify(expected_returned_amounts))
+    );
+    console.error(
+      chalk.yellow("actual: ", JSON.stringify(stable_result.returned_amounts))
+    );
+    console.error(chalk.red("stable withdraw assertions: fail"));
+    return false;
+  }
+}
+
+async function stable_withdraw_one_test() {
+  const asset_amounts: [string, string] = ["530256812", "100446728"];
+  const asset_precisions: [number, number] = [6, 6];
+  const total_share = "300000000";
+
+  const block_time = "1692147376";
+  const init_amp_time = "1692039296";
+  const init_amp = "10000";
+  const next_amp_time = "1692039296";
+  const next_amp = "10000";
+
+  const expected_results: StableWithdrawOneSimulationResponse[] = [
+    { return_amount: "2117653", commission_amount: "175" },
+    { return_amount: "2021788", commission_amount: "843" },
+  ];
+  let stable_result: StableWithdrawOneSimulationResponse | null = null;
+  try {
+    for (let ask_ind = 0; ask_ind < 2; ask_ind++) {
+      stable_result = JSON.parse(
+        stable_withdraw_one(
+          "1000000",
+          String(ask_ind),
+          JSON.stringify(asset_amounts),
+          JSON.stringify(asset_precisions),
+          total_share,
+          STABLE_FEE,
+          block_time,
+          init_amp_time,
+          init_amp,
+          next_amp_time,
+          next_amp
+        )
+      );
+      assert(
+        stable_result.return_amount ===
+          expected_results[ask_ind].return_amount &&
+          stable_result.commission_amount ===
+            expected_results[ask_ind].commission_amount
+      );
+    }
+
+    console.info(chalk.green("stable withdraw one assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_results)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(stable_result)));
+    console.error(chalk.red("stable withdraw one assertions: fail"));
+    return false;
+  }
+}
+
+async function stable_withdraw_imbalanced_test() {
+  const asset_amounts: [string, string] = ["530256812", "100446728"];
+  const asset_precisions: [number, number] = [6, 6];
+  const total_share = "300000000";
+
+  const block_time = "1692147376";
+  const init_amp_time = "1692039296";
+  const init_amp = "10000";
+  const next_amp_time = "1692039296";
+  const next_amp = "10000";
+
+  const expected_result: StableImbalancedWithdrawSimulationResponse = {
+    burn_amount: "1000000",
+    slippage: "0.011569029642205104",
+    positive_slippage: true,
+  };
+  let stable_result: StableImbalancedWithdrawSimulationResponse | null = null;
+  try {
+    const withdrawals: [string, string] = ["2117653", "0"];
+
+    stable_result = JSON.parse(
+      stable_withdraw_imbalanced(
+        JSON.stringify(withdrawals),
+        JSON.stringify(asset_amounts),
+        JSON.stringify(asset_precisions),
+        total_share,
+        STABLE_FEE,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp
+      )
+    );
+    assert(
+      stable_result.burn_amount === expected_result.burn_amount &&
+        stable_result.slippage === expected_result.slippage &&
+        stable_result.positive_slippage === expected_result.positive_slippage
+    );
+
+    console.info(chalk.green("stable withdraw imbalanced assertions: pass"));
     return true;
   } catch (e) {
-    console.error(chalk.yellow("expected: ", JSON.stringify(simulation)));
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
     console.error(chalk.yellow("actual: ", JSON.stringify(stable_result)));
-    console.error(chalk.red("stable assertions: fail"));
+    console.error(chalk.red("stable withdraw imbalanced assertions: fail"));
     return false;
   }
 }
 
-async function stable_provide_test() {
+async function stable_zap_in_test() {
   const asset_amounts: [string, string] = ["530256812", "100446728"];
   const asset_precisions: [number, number] = [6, 6];
   const total_share = "300000000";
@@ -306,17 +1812,20 @@ async function stable_provide_test() {
   const next_amp_time = "1692039296";
   const next_amp = "10000";
 
-  const expected_share_amount = "447998664";
-  let stable_result: ProvideResult | null = null;
+  const expected_result = {
+    swap_amount: "2567",
+    share_amount: "472164",
+  };
+  let stable_result: StableZapInSimulationResponse | null = null;
   try {
-    const deposits: [string, string] = ["791847812", "150000000"];
-
     stable_result = JSON.parse(
-      stable_provide(
-        JSON.stringify(deposits),
+      stable_zap_in(
+        "1000000",
+        "0",
         JSON.stringify(asset_amounts),
         JSON.stringify(asset_precisions),
         total_share,
+        STABLE_FEE,
         block_time,
         init_amp_time,
         init_amp,
@@ -324,50 +1833,62 @@ async function stable_provide_test() {
         next_amp
       )
     );
-    assert(stable_result.share_amount === expected_share_amount);
+    assert(
+      stable_result.swap_amount === expected_result.swap_amount &&
+        stable_result.provide.share_amount === expected_result.share_amount
+    );
 
-    console.info(chalk.green("stable provide assertions: pass"));
+    console.info(chalk.green("stable zap in assertions: pass"));
     return true;
   } catch (e) {
     console.error(e);
-    console.error(
-      chalk.yellow("expected: ", JSON.stringify(expected_share_amount))
-    );
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
     console.error(chalk.yellow("actual: ", JSON.stringify(stable_result)));
-    console.error(chalk.red("stable provide assertions: fail"));
+    console.error(chalk.red("stable zap in assertions: fail"));
     return false;
   }
 }
 
-async function stable_withdraw_test() {
+async function stable_zap_out_test() {
   const asset_amounts: [string, string] = ["530256812", "100446728"];
+  const asset_precisions: [number, number] = [6, 6];
   const total_share = "300000000";
 
-  const expected_returned_amounts: [string, string] = [
-    "530255044",
-    "100446393",
-  ];
-  let stable_result: WithdrawResult | null = null;
+  const block_time = "1692147376";
+  const init_amp_time = "1692039296";
+  const init_amp = "10000";
+  const next_amp_time = "1692039296";
+  const next_amp = "10000";
+
+  const expected_return_amount = "2117653";
+  let stable_result: StableZapOutSimulationResponse | null = null;
   try {
     stable_result = JSON.parse(
-      stable_withdraw("299999000", JSON.stringify(asset_amounts), total_share)
-    );
-    assert(
-      stable_result.returned_amounts[0] === expected_returned_amounts[0] &&
-        stable_result.returned_amounts[1] === expected_returned_amounts[1]
+      stable_zap_out(
+        "1000000",
+        "0",
+        JSON.stringify(asset_amounts),
+        JSON.stringify(asset_precisions),
+        total_share,
+        STABLE_FEE,
+        block_time,
+        init_amp_time,
+        init_amp,
+        next_amp_time,
+        next_amp
+      )
     );
+    assert(stable_result.return_amount === expected_return_amount);
 
-    console.info(chalk.green("stable withdraw assertions: pass"));
+    console.info(chalk.green("stable zap out assertions: pass"));
     return true;
   } catch (e) {
     console.error(e);
     console.error(
-      chalk.yellow("expected: ", JSON.stringify(expected_returned_amounts))
-    );
-    console.error(
-      chalk.yellow("actual: ", JSON.stringify(stable_result.returned_amounts))
+      chalk.yellow("expected: ", JSON.stringify(expected_return_amount))
     );
-    console.error(chalk.red("stable withdraw assertions: fail"));
+    console.error(chalk.yellow("actual: ", JSON.stringify(stable_result)));
+    console.error(chalk.red("stable zap out assertions: fail"));
     return false;
   }
 }
@@ -377,9 +1898,12 @@ const PCL_POOL =
   "terra10d3gqg5w9wa8d6lrqvfhhw2f9h8q0839rg0g66v0hmk4ndsdk5vsvhzh7l";
 
 async function concentrated_swap_test(client: CosmWasmClient) {
-  const pool_info: PoolInfo = await client.queryContractSmart(PCL_POOL, {
-    pool: {},
-  });
+  const pool_info: PairPoolResponse = await client.queryContractSmart(
+    PCL_POOL,
+    {
+      pool: {},
+    }
+  );
   const asset_amounts = pool_info.assets.map((asset) => asset.amount);
   const rawConfig = await client.queryContractRaw(
     PCL_POOL,
@@ -402,8 +1926,8 @@ async function concentrated_swap_test(client: CosmWasmClient) {
   const future_amp = pool_config.pool_state.future.amp;
   const future_gamma = pool_config.pool_state.future.gamma;
 
-  let simulation: SwapResult | null = null;
-  let pcl_result: SwapResult | null = null;
+  let simulation: PairSimulationResponse | null = null;
+  let pcl_result: ConcentratedSwapSimulationResponse | null = null;
   try {
     for (let i = 3; i < 6; i++) {
       for (let j = 0; j < 2; j++) {
@@ -463,81 +1987,865 @@ async function concentrated_swap_test(client: CosmWasmClient) {
   }
 }
 
-async function concentrated_provide_test(client: CosmWasmClient) {
-  let simulation: unknown | null = null;
-  let pcl_result: unknown | null = null;
+const PCL_TEST_POOL = {
+  asset_amounts: ["1000000000000", "666666000000"],
+  asset_precisions: [6, 6],
+  total_share: "816496000000",
+  maker_fee_share: "0.5",
+  oracle_price: "1.5",
+  price_scale: "1.5",
+  fee_gamma: "0.00023",
+  mid_fee: "0.0026",
+  out_fee: "0.0045",
+  block_time: "100",
+  initial_time: "0",
+  amp: "40",
+  gamma: "0.000145",
+  future_time: "0",
+};
+
+async function concentrated_price_impact_test() {
+  const pool = PCL_TEST_POOL;
+  const expected_result: ConcentratedSwapSimulationResponse = {
+    return_amount: "61656583291",
+    spread_amount: "4734246345",
+    commission_amount: "275837028",
+    spot_price_before: "0.666666658436209872",
+    execution_price: "0.61656583291",
+    spot_price_after: "0.560262510880590016",
+    price_impact: "0.071013683746027391",
+  };
+  let pcl_result: ConcentratedSwapSimulationResponse | null = null;
+  try {
+    pcl_result = JSON.parse(
+      concentrated_swap(
+        "100000000000",
+        "6", // offer_asset_prec
+        "1",
+        "6", // ask_asset_prec
+        JSON.stringify(pool.asset_amounts),
+        pool.maker_fee_share,
+        pool.oracle_price,
+        pool.price_scale,
+        pool.fee_gamma,
+        pool.mid_fee,
+        pool.out_fee,
+        pool.block_time,
+        pool.initial_time,
+        pool.amp,
+        pool.gamma,
+        pool.future_time,
+        pool.amp,
+        pool.gamma
+      )
+    );
+    assert_prices(pcl_result, expected_result);
+
+    console.info(chalk.green("pcl price impact assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(pcl_result)));
+    console.error(chalk.red("pcl price impact assertions: fail"));
+    return false;
+  }
+}
+
+async function concentrated_swap_batch_test() {
+  const expected_result: ConcentratedSwapSimulationResponse = {
+    return_amount: "61656583291",
+    spread_amount: "4734246345",
+    commission_amount: "275837028",
+    spot_price_before: "0.666666658436209872",
+    execution_price: "0.61656583291",
+    spot_price_after: "0.560262510880590016",
+    price_impact: "0.071013683746027391",
+  };
+  const pool = PCL_TEST_POOL;
+  let pcl_result: BatchItem<ConcentratedSwapSimulationResponse>[] | null = null;
   try {
-    for (let i = 3; i < 6; i++) {
-      for (let j = 0; j < 2; j++) {
-        pcl_result = JSON.parse(concentrated_provide());
-        assert(pcl_result === simulation);
-      }
+    pcl_result = JSON.parse(
+      concentrated_swap_batch(
+        JSON.stringify([
+          {
+            asset_amounts: pool.asset_amounts,
+            asset_precisions: pool.asset_precisions,
+            maker_fee_share: pool.maker_fee_share,
+            oracle_price: pool.oracle_price,
+            price_scale: pool.price_scale,
+            fee_gamma: pool.fee_gamma,
+            mid_fee: pool.mid_fee,
+            out_fee: pool.out_fee,
+            block_time: Number(pool.block_time),
+            initial_time: Number(pool.initial_time),
+            initial_amp: pool.amp,
+            initial_gamma: pool.gamma,
+            future_time: Number(pool.future_time),
+            future_amp: pool.amp,
+            future_gamma: pool.gamma,
+          },
+        ]),
+        JSON.stringify([{ pool: 0, offer_amount: "100000000000", ask_ind: 1 }])
+      )
+    );
+    assert(pcl_result.length === 1);
+    assert_batch_ok(pcl_result[0], expected_result);
+
+    console.info(chalk.green("pcl swap batch assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("actual: ", JSON.stringify(pcl_result)));
+    console.error(chalk.red("pcl swap batch assertions: fail"));
+    return false;
+  }
+}
+
+async function concentrated_swap_to_price_test() {
+  const expected_result = {
+    offer_ind: 0,
+    offer_amount: "65211172845",
+    spot_price_after: "0.599999999999193668",
+  };
+  let pcl_result: ConcentratedSwapToPriceResponse | null = null;
+  try {
+    const pool = PCL_TEST_POOL;
+    pcl_result = JSON.parse(
+      concentrated_swap_to_price(
+        "0.6",
+        JSON.stringify(pool.asset_amounts),
+        JSON.stringify(pool.asset_precisions),
+        pool.maker_fee_share,
+        pool.oracle_price,
+        pool.price_scale,
+        pool.fee_gamma,
+        pool.mid_fee,
+        pool.out_fee,
+        pool.block_time,
+        pool.initial_time,
+        pool.amp,
+        pool.gamma,
+        pool.future_time,
+        pool.amp,
+        pool.gamma
+      )
+    );
+    assert(
+      pcl_result.offer_ind === expected_result.offer_ind &&
+        pcl_result.offer_amount === expected_result.offer_amount &&
+        pcl_result.swap.spot_price_after === expected_result.spot_price_after
+    );
+
+    console.info(chalk.green("pcl swap to price assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(pcl_result)));
+    console.error(chalk.red("pcl swap to price assertions: fail"));
+    return false;
+  }
+}
+
+async function concentrated_depth_test() {
+  const expected_result: DepthResponse[] = [
+    { price_impact: "0.005", offer_amounts: ["25137787701", "16759395545"] },
+    { price_impact: "0.02", offer_amounts: ["44470831689", "29648204614"] },
+    { price_impact: "0.1", offer_amounts: ["133744665447", "89164359356"] },
+  ];
+  let pcl_result: DepthResponse[] | null = null;
+  try {
+    const pool = PCL_TEST_POOL;
+    pcl_result = JSON.parse(
+      concentrated_depth(
+        JSON.stringify(["0.005", "0.02", "0.1"]),
+        JSON.stringify(pool.asset_amounts),
+        JSON.stringify(pool.asset_precisions),
+        pool.maker_fee_share,
+        pool.oracle_price,
+        pool.price_scale,
+        pool.fee_gamma,
+        pool.mid_fee,
+        pool.out_fee,
+        pool.block_time,
+        pool.initial_time,
+        pool.amp,
+        pool.gamma,
+        pool.future_time,
+        pool.amp,
+        pool.gamma
+      )
+    );
+    assert_depth(pcl_result, expected_result);
+
+    console.info(chalk.green("pcl depth assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(pcl_result)));
+    console.error(chalk.red("pcl depth assertions: fail"));
+    return false;
+  }
+}
+
+async function concentrated_ladder_test() {
+  const expected_result: LadderResponse = {
+    mid_price: "0.666666658436209872",
+    bids: [
+      {
+        price: "0.656666658436209872",
+        base_amount: "22845784734",
+        quote_amount: "15113517766",
+      },
+      {
+        price: "0.646666658436209872",
+        base_amount: "30376208162",
+        quote_amount: "19998286908",
+      },
+    ],
+    asks: [
+      {
+        price: "0.676666658436209872",
+        base_amount: "22549621916",
+        quote_amount: "15148468883",
+      },
+      {
+        price: "0.686666658436209872",
+        base_amount: "29597962049",
+        quote_amount: "19975034581",
+      },
+    ],
+  };
+  let pcl_result: LadderResponse | null = null;
+  try {
+    const pool = PCL_TEST_POOL;
+    pcl_result = JSON.parse(
+      concentrated_ladder(
+        "0.01",
+        "2",
+        JSON.stringify(pool.asset_amounts),
+        JSON.stringify(pool.asset_precisions),
+        pool.maker_fee_share,
+        pool.oracle_price,
+        pool.price_scale,
+        pool.fee_gamma,
+        pool.mid_fee,
+        pool.out_fee,
+        pool.block_time,
+        pool.initial_time,
+        pool.amp,
+        pool.gamma,
+        pool.future_time,
+        pool.amp,
+        pool.gamma
+      )
+    );
+    assert_ladder(pcl_result, expected_result);
+
+    console.info(chalk.green("pcl ladder assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(pcl_result)));
+    console.error(chalk.red("pcl ladder assertions: fail"));
+    return false;
+  }
+}
+
+async function concentrated_sample_curve_test() {
+  const expected_result: CurvePoint[] = [
+    {
+      offer_amount: "0",
+      return_amount: "0",
+      commission_amount: "0",
+      price_impact: "0",
+    },
+    {
+      offer_amount: "25000000000",
+      return_amount: "16518515003",
+      commission_amount: "66123201",
+      price_impact: "0.00492169547507651",
+    },
+    {
+      offer_amount: "50000000000",
+      return_amount: "32358236240",
+      commission_amount: "140916723",
+      price_impact: "0.025025399073270506",
+    },
+    {
+      offer_amount: "75000000000",
+      return_amount: "47374153131",
+      commission_amount: "210395341",
+      price_impact: "0.048309018810722746",
+    },
+    {
+      offer_amount: "100000000000",
+      return_amount: "61656583291",
+      commission_amount: "275837028",
+      price_impact: "0.071013683746027391",
+    },
+  ];
+  const pool = PCL_TEST_POOL;
+  let pcl_result: CurvePoint[] | null = null;
+  try {
+    pcl_result = JSON.parse(
+      concentrated_sample_curve(
+        "0",
+        "100000000000",
+        "5",
+        "linear",
+        "1",
+        JSON.stringify(pool.asset_amounts),
+        JSON.stringify(pool.asset_precisions),
+        pool.maker_fee_share,
+        pool.oracle_price,
+        pool.price_scale,
+        pool.fee_gamma,
+        pool.mid_fee,
+        pool.out_fee,
+        pool.block_time,
+        pool.initial_time,
+        pool.amp,
+        pool.gamma,
+        pool.future_time,
+        pool.amp,
+        pool.gamma
+      )
+    );
+    assert_curve(pcl_result, expected_result);
+
+    console.info(chalk.green("pcl sample curve assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(pcl_result)));
+    console.error(chalk.red("pcl sample curve assertions: fail"));
+    return false;
+  }
+}
+
+async function concentrated_snapshot_test() {
+  const expected_result: ConcentratedSwapSimulationResponse = {
+    return_amount: "61656583291",
+    spread_amount: "4734246345",
+    commission_amount: "275837028",
+    spot_price_before: "0.666666658436209872",
+    execution_price: "0.61656583291",
+    spot_price_after: "0.560262510880590016",
+    price_impact: "0.071013683746027391",
+  };
+  const expected_quote: CurvePoint = {
+    offer_amount: "25000000000",
+    return_amount: "16518515003",
+    commission_amount: "66123201",
+    price_impact: "0.00492169547507651",
+  };
+  const pool = PCL_TEST_POOL;
+  let pcl_result: ConcentratedSwapSimulationResponse | null = null;
+  let pcl_quote: CurvePoint | null = null;
+  try {
+    const snapshot = new ConcentratedPoolSnapshot(
+      JSON.stringify(pool.asset_amounts),
+      JSON.stringify(pool.asset_precisions),
+      pool.maker_fee_share,
+      pool.oracle_price,
+      pool.price_scale,
+      pool.fee_gamma,
+      pool.mid_fee,
+      pool.out_fee,
+      pool.block_time,
+      pool.initial_time,
+      pool.amp,
+      pool.gamma,
+      pool.future_time,
+      pool.amp,
+      pool.gamma
+    );
+    pcl_result = JSON.parse(snapshot.swap("100000000000", "1"));
+    pcl_quote = JSON.parse(snapshot.quote("25000000000", "1"));
+    assert_prices(pcl_result, expected_result);
+    assert_curve([pcl_quote], [expected_quote]);
+
+    console.info(chalk.green("pcl snapshot assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("actual: ", JSON.stringify(pcl_result)));
+    console.error(chalk.yellow("quote: ", JSON.stringify(pcl_quote)));
+    console.error(chalk.red("pcl snapshot assertions: fail"));
+    return false;
+  }
+}
+
+async function concentrated_pool_test() {
+  const expected_swap: ConcentratedSwapSimulationResponse = {
+    return_amount: "61656583291",
+    spread_amount: "4734246345",
+    commission_amount: "275837028",
+    spot_price_before: "0.666666658436209872",
+    execution_price: "0.61656583291",
+    spot_price_after: "0.560262510880590016",
+    price_impact: "0.071013683746027391",
+  };
+  // The reverse swap assumes the maximum fee rate
+  const expected_reverse_swap: ConcentratedReverseSwapSimulationResponse = {
+    offer_amount: "100005127026",
+    spread_amount: "4734792578",
+    commission_amount: "278708814",
+  };
+  let swap_result: ConcentratedSwapSimulationResponse | null = null;
+  let reverse_swap_result: ConcentratedReverseSwapSimulationResponse | null =
+    null;
+  try {
+    const pool = new ConcentratedPool(
+      JSON.stringify({
+        asset_amounts: PCL_TEST_POOL.asset_amounts,
+        asset_precisions: PCL_TEST_POOL.asset_precisions,
+        total_share: PCL_TEST_POOL.total_share,
+        maker_fee_share: PCL_TEST_POOL.maker_fee_share,
+        oracle_price: PCL_TEST_POOL.oracle_price,
+        price_scale: PCL_TEST_POOL.price_scale,
+        fee_gamma: PCL_TEST_POOL.fee_gamma,
+        mid_fee: PCL_TEST_POOL.mid_fee,
+        out_fee: PCL_TEST_POOL.out_fee,
+        block_time: Number(PCL_TEST_POOL.block_time),
+        initial_time: Number(PCL_TEST_POOL.initial_time),
+        initial_amp: PCL_TEST_POOL.amp,
+        initial_gamma: PCL_TEST_POOL.gamma,
+        future_time: Number(PCL_TEST_POOL.future_time),
+        future_amp: PCL_TEST_POOL.amp,
+        future_gamma: PCL_TEST_POOL.gamma,
+      })
+    );
+
+    swap_result = pool.swap("100000000000", "1");
+    reverse_swap_result = pool.reverseSwap(swap_result.return_amount, "1");
+    assert_prices(swap_result, expected_swap);
+    assert(
+      JSON.stringify(reverse_swap_result) ===
+        JSON.stringify(expected_reverse_swap)
+    );
+
+    const provide_result = pool.provide(JSON.stringify(["1000000", "666666"]));
+    assert(provide_result.share_amount === "816495");
+
+    const withdraw_result = pool.withdraw("816496");
+    assert(
+      withdraw_result.returned_amounts[0] === "999998" &&
+        withdraw_result.returned_amounts[1] === "666665"
+    );
+
+    assert(pool.spotPrice("1") === "0.666666658436209872");
+    pool.update(JSON.stringify(["1100000000000", "604343416709"]));
+    assert(pool.spotPrice("1") === "0.559753492071685852");
+
+    console.info(chalk.green("pcl pool assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("swap: ", JSON.stringify(swap_result)));
+    console.error(
+      chalk.yellow("reverse swap: ", JSON.stringify(reverse_swap_result))
+    );
+    console.error(chalk.red("pcl pool assertions: fail"));
+    return false;
+  }
+}
+
+async function concentrated_object_api_test() {
+  const pool = PCL_TEST_POOL;
+  const batch_pool = {
+    asset_amounts: pool.asset_amounts,
+    asset_precisions: pool.asset_precisions,
+    maker_fee_share: pool.maker_fee_share,
+    oracle_price: pool.oracle_price,
+    price_scale: pool.price_scale,
+    fee_gamma: pool.fee_gamma,
+    mid_fee: pool.mid_fee,
+    out_fee: pool.out_fee,
+    block_time: Number(pool.block_time),
+    initial_time: Number(pool.initial_time),
+    initial_amp: pool.amp,
+    initial_gamma: pool.gamma,
+    future_time: Number(pool.future_time),
+    future_amp: pool.amp,
+    future_gamma: pool.gamma,
+  };
+  const requests = [
+    { pool: 0, offer_amount: "100000000000", ask_ind: 1 },
+    { pool: 1, offer_amount: "100000000000", ask_ind: 1 },
+  ];
+  let expected_result:
+    | BatchItem<ConcentratedSwapSimulationResponse>[]
+    | null = null;
+  let pcl_result: BatchItem<ConcentratedSwapSimulationResponse>[] | null =
+    null;
+  try {
+    expected_result = JSON.parse(
+      concentrated_swap_batch(
+        JSON.stringify([batch_pool]),
+        JSON.stringify(requests)
+      )
+    );
+    pcl_result = concentratedSwapBatch(
+      [{ ...batch_pool, asset_amounts: pool.asset_amounts.map(BigInt) }],
+      requests.map((request) => ({
+        ...request,
+        offer_amount: BigInt(request.offer_amount),
+      }))
+    );
+    assert(JSON.stringify(pcl_result) === JSON.stringify(expected_result));
+
+    console.info(chalk.green("pcl object api assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(pcl_result)));
+    console.error(chalk.red("pcl object api assertions: fail"));
+    return false;
+  }
+}
+
+async function concentrated_provide_test() {
+  const expected_share_amounts: [string, string] = ["816495", "407717"];
+  let pcl_result: ConcentratedProvideSimulationResponse | null = null;
+  try {
+    const deposits: [string, string][] = [
+      ["1000000", "666666"],
+      ["1000000", "0"],
+    ];
+
+    for (let i = 0; i < deposits.length; i++) {
+      pcl_result = JSON.parse(
+        concentrated_provide(
+          JSON.stringify(deposits[i]),
+          JSON.stringify(PCL_TEST_POOL.asset_amounts),
+          JSON.stringify(PCL_TEST_POOL.asset_precisions),
+          PCL_TEST_POOL.total_share,
+          PCL_TEST_POOL.price_scale,
+          PCL_TEST_POOL.fee_gamma,
+          PCL_TEST_POOL.mid_fee,
+          PCL_TEST_POOL.out_fee,
+          PCL_TEST_POOL.block_time,
+          PCL_TEST_POOL.initial_time,
+          PCL_TEST_POOL.amp,
+          PCL_TEST_POOL.gamma,
+          PCL_TEST_POOL.future_time,
+          PCL_TEST_POOL.amp,
+          PCL_TEST_POOL.gamma
+        )
+      );
+      assert(pcl_result.share_amount === expected_share_amounts[i]);
     }
 
     console.info(chalk.green("pcl provide assertions: pass"));
     return true;
   } catch (e) {
     console.error(e);
-    console.error(chalk.yellow("expected: ", JSON.stringify(simulation)));
+    console.error(
+      chalk.yellow("expected: ", JSON.stringify(expected_share_amounts))
+    );
     console.error(chalk.yellow("actual: ", JSON.stringify(pcl_result)));
     console.error(chalk.red("pcl provide assertions: fail"));
     return false;
   }
 }
 
-async function concentrated_withdraw_test(client: CosmWasmClient) {
-  let simulation: unknown | null = null;
-  let pcl_result: unknown | null = null;
+async function concentrated_reverse_provide_test() {
+  const expected_result: ConcentratedReverseProvideSimulationResponse = {
+    deposits: ["1224746", "816497"],
+    share_amount: "1000000",
+  };
+  let pcl_result: ConcentratedReverseProvideSimulationResponse | null = null;
   try {
-    for (let i = 3; i < 6; i++) {
-      for (let j = 0; j < 2; j++) {
-        pcl_result = JSON.parse(concentrated_withdraw());
-        assert(pcl_result === simulation);
-      }
+    pcl_result = JSON.parse(
+      concentrated_reverse_provide(
+        "1000000",
+        JSON.stringify(PCL_TEST_POOL.asset_amounts),
+        JSON.stringify(PCL_TEST_POOL.asset_precisions),
+        PCL_TEST_POOL.total_share,
+        PCL_TEST_POOL.price_scale,
+        PCL_TEST_POOL.fee_gamma,
+        PCL_TEST_POOL.mid_fee,
+        PCL_TEST_POOL.out_fee,
+        PCL_TEST_POOL.block_time,
+        PCL_TEST_POOL.initial_time,
+        PCL_TEST_POOL.amp,
+        PCL_TEST_POOL.gamma,
+        PCL_TEST_POOL.future_time,
+        PCL_TEST_POOL.amp,
+        PCL_TEST_POOL.gamma
+      )
+    );
+    assert(
+      pcl_result.deposits[0] === expected_result.deposits[0] &&
+        pcl_result.deposits[1] === expected_result.deposits[1] &&
+        pcl_result.share_amount === expected_result.share_amount
+    );
+
+    console.info(chalk.green("pcl reverse provide assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_result)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(pcl_result)));
+    console.error(chalk.red("pcl reverse provide assertions: fail"));
+    return false;
+  }
+}
+
+async function concentrated_zap_in_test() {
+  const expected_results = [
+    { swap_amount: "10783855358", share_amount: "40258525793" },
+    { swap_amount: "6902805382", share_amount: "59697799046" },
+  ];
+  let pcl_result: ConcentratedZapInSimulationResponse | null = null;
+  try {
+    for (let offer_ind = 0; offer_ind < 2; offer_ind++) {
+      pcl_result = JSON.parse(
+        concentrated_zap_in(
+          "100000000000",
+          String(offer_ind),
+          JSON.stringify(PCL_TEST_POOL.asset_amounts),
+          JSON.stringify(PCL_TEST_POOL.asset_precisions),
+          PCL_TEST_POOL.total_share,
+          PCL_TEST_POOL.maker_fee_share,
+          PCL_TEST_POOL.oracle_price,
+          PCL_TEST_POOL.price_scale,
+          PCL_TEST_POOL.fee_gamma,
+          PCL_TEST_POOL.mid_fee,
+          PCL_TEST_POOL.out_fee,
+          PCL_TEST_POOL.block_time,
+          PCL_TEST_POOL.initial_time,
+          PCL_TEST_POOL.amp,
+          PCL_TEST_POOL.gamma,
+          PCL_TEST_POOL.future_time,
+          PCL_TEST_POOL.amp,
+          PCL_TEST_POOL.gamma
+        )
+      );
+      const expected = expected_results[offer_ind];
+      assert(
+        pcl_result.swap_amount === expected.swap_amount &&
+          pcl_result.provide.share_amount === expected.share_amount
+      );
     }
 
+    console.info(chalk.green("pcl zap in assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(chalk.yellow("expected: ", JSON.stringify(expected_results)));
+    console.error(chalk.yellow("actual: ", JSON.stringify(pcl_result)));
+    console.error(chalk.red("pcl zap in assertions: fail"));
+    return false;
+  }
+}
+
+async function concentrated_withdraw_test() {
+  // One lp is kept from the share, as in the pair contract
+  const expected_returned_amounts: [string, string] = ["999998", "666665"];
+  let pcl_result: ConcentratedWithdrawSimulationResponse | null = null;
+  try {
+    pcl_result = JSON.parse(
+      concentrated_withdraw(
+        "816496",
+        JSON.stringify(PCL_TEST_POOL.asset_amounts),
+        JSON.stringify(PCL_TEST_POOL.asset_precisions),
+        PCL_TEST_POOL.total_share
+      )
+    );
+    assert(
+      pcl_result.returned_amounts[0] === expected_returned_amounts[0] &&
+        pcl_result.returned_amounts[1] === expected_returned_amounts[1]
+    );
+
     console.info(chalk.green("pcl withdraw assertions: pass"));
     return true;
   } catch (e) {
     console.error(e);
-    console.error(chalk.yellow("expected: ", JSON.stringify(simulation)));
+    console.error(
+      chalk.yellow("expected: ", JSON.stringify(expected_returned_amounts))
+    );
     console.error(chalk.yellow("actual: ", JSON.stringify(pcl_result)));
     console.error(chalk.red("pcl withdraw assertions: fail"));
     return false;
   }
 }
 
+async function concentrated_zap_out_test() {
+  const expected_return_amounts: [string, string] = [
+    "97085695311",
+    "64723746964",
+  ];
+  let pcl_result: ConcentratedZapOutSimulationResponse | null = null;
+  try {
+    for (let ask_ind = 0; ask_ind < 2; ask_ind++) {
+      pcl_result = JSON.parse(
+        concentrated_zap_out(
+          "40258525793",
+          String(ask_ind),
+          JSON.stringify(PCL_TEST_POOL.asset_amounts),
+          JSON.stringify(PCL_TEST_POOL.asset_precisions),
+          PCL_TEST_POOL.total_share,
+          PCL_TEST_POOL.maker_fee_share,
+          PCL_TEST_POOL.oracle_price,
+          PCL_TEST_POOL.price_scale,
+          PCL_TEST_POOL.fee_gamma,
+          PCL_TEST_POOL.mid_fee,
+          PCL_TEST_POOL.out_fee,
+          PCL_TEST_POOL.block_time,
+          PCL_TEST_POOL.initial_time,
+          PCL_TEST_POOL.amp,
+          PCL_TEST_POOL.gamma,
+          PCL_TEST_POOL.future_time,
+          PCL_TEST_POOL.amp,
+          PCL_TEST_POOL.gamma
+        )
+      );
+      assert(pcl_result.return_amount === expected_return_amounts[ask_ind]);
+    }
+
+    console.info(chalk.green("pcl zap out assertions: pass"));
+    return true;
+  } catch (e) {
+    console.error(e);
+    console.error(
+      chalk.yellow("expected: ", JSON.stringify(expected_return_amounts))
+    );
+    console.error(chalk.yellow("actual: ", JSON.stringify(pcl_result)));
+    console.error(chalk.red("pcl zap out assertions: fail"));
+    return false;
+  }
+}
+
 (async function () {
   const client = await CosmWasmClient.connect(
     "https://terra-testnet-rpc.polkachu.com/"
   );
 
   const xyk_swap_test_result = await xyk_swap_test(client);
+  const xyk_price_impact_test_result = await xyk_price_impact_test();
+  const xyk_swap_to_price_test_result = await xyk_swap_to_price_test();
+  const xyk_depth_test_result = await xyk_depth_test();
+  const xyk_ladder_test_result = await xyk_ladder_test();
+  const xyk_swap_batch_test_result = await xyk_swap_batch_test();
+  const xyk_sample_curve_test_result = await xyk_sample_curve_test();
+  const xyk_pool_test_result = await xyk_pool_test();
+  const xyk_object_api_test_result = await xyk_object_api_test();
+  const xyk_pair_query_test_result = await xyk_pair_query_test();
   const xyk_provide_test_result = await xyk_provide_test();
+  const xyk_provide_slippage_test_result = await xyk_provide_slippage_test();
+  const xyk_reverse_provide_test_result = await xyk_reverse_provide_test();
   const xyk_withdraw_test_result = await xyk_withdraw_test();
+  const xyk_min_receive_test_result = await xyk_min_receive_test();
+  const xyk_zap_in_test_result = await xyk_zap_in_test();
+  const xyk_zap_in_large_offer_test_result =
+    await xyk_zap_in_large_offer_test();
+  const xyk_zap_out_test_result = await xyk_zap_out_test();
 
   const stable_test = await stable_swap_test(client);
+  const stable_price_impact_test_result = await stable_price_impact_test();
+  const stable_swap_to_price_test_result =
+    await stable_swap_to_price_test();
+  const stable_depth_test_result = await stable_depth_test();
+  const stable_ladder_test_result = await stable_ladder_test();
+  const stable_swap_batch_test_result = await stable_swap_batch_test();
+  const stable_sample_curve_test_result = await stable_sample_curve_test();
+  const stable_snapshot_test_result = await stable_snapshot_test();
+  const stable_pool_test_result = await stable_pool_test();
+  const stable_object_api_test_result = await stable_object_api_test();
   const stable_provide_test_result = await stable_provide_test();
+  const stable_provide_slippage_test_result =
+    await stable_provide_slippage_test();
+  const stable_reverse_provide_test_result =
+    await stable_reverse_provide_test();
   const stable_withdraw_test_result = await stable_withdraw_test();
+  const stable_withdraw_one_test_result = await stable_withdraw_one_test();
+  const stable_withdraw_imbalanced_test_result =
+    await stable_withdraw_imbalanced_test();
+  const stable_zap_in_test_result = await stable_zap_in_test();
+  const stable_zap_out_test_result = await stable_zap_out_test();
 
   const concentrated_test = await concentrated_swap_test(client);
-  const concentrated_provide_test_result = await concentrated_provide_test(
-    client
-  );
-  const concentrated_withdraw_test_result = await concentrated_withdraw_test(
-    client
-  );
+  const concentrated_price_impact_test_result =
+    await concentrated_price_impact_test();
+  const concentrated_swap_to_price_test_result =
+    await concentrated_swap_to_price_test();
+  const concentrated_depth_test_result = await concentrated_depth_test();
+  const concentrated_ladder_test_result = await concentrated_ladder_test();
+  const concentrated_swap_batch_test_result =
+    await concentrated_swap_batch_test();
+  const concentrated_sample_curve_test_result =
+    await concentrated_sample_curve_test();
+  const concentrated_snapshot_test_result = await concentrated_snapshot_test();
+  const concentrated_pool_test_result = await concentrated_pool_test();
+  const concentrated_object_api_test_result =
+    await concentrated_object_api_test();
+  const concentrated_provide_test_result = await concentrated_provide_test();
+  const concentrated_reverse_provide_test_result =
+    await concentrated_reverse_provide_test();
+  const concentrated_zap_in_test_result = await concentrated_zap_in_test();
+  const concentrated_withdraw_test_result = await concentrated_withdraw_test();
+  const concentrated_zap_out_test_result = await concentrated_zap_out_test();
 
   if (
     !xyk_swap_test_result ||
+    !xyk_swap_to_price_test_result ||
+    !xyk_depth_test_result ||
+    !xyk_pair_query_test_result ||
+    !xyk_object_api_test_result ||
+    !xyk_pool_test_result ||
+    !xyk_sample_curve_test_result ||
+    !xyk_swap_batch_test_result ||
+    !xyk_ladder_test_result ||
+    !xyk_price_impact_test_result ||
     !xyk_provide_test_result ||
+    !xyk_provide_slippage_test_result ||
+    !xyk_reverse_provide_test_result ||
     !xyk_withdraw_test_result ||
+    !xyk_min_receive_test_result ||
+    !xyk_zap_in_test_result ||
+    !xyk_zap_in_large_offer_test_result ||
+    !xyk_zap_out_test_result ||
     !stable_test ||
+    !stable_swap_to_price_test_result ||
+    !stable_depth_test_result ||
+    !stable_object_api_test_result ||
+    !stable_pool_test_result ||
+    !stable_snapshot_test_result ||
+    !stable_sample_curve_test_result ||
+    !stable_swap_batch_test_result ||
+    !stable_ladder_test_result ||
+    !stable_price_impact_test_result ||
     !stable_provide_test_result ||
+    !stable_provide_slippage_test_result ||
+    !stable_reverse_provide_test_result ||
     !stable_withdraw_test_result ||
+    !stable_withdraw_one_test_result ||
+    !stable_withdraw_imbalanced_test_result ||
+    !stable_zap_in_test_result ||
+    !stable_zap_out_test_result ||
     !concentrated_test ||
+    !concentrated_swap_to_price_test_result ||
+    !concentrated_depth_test_result ||
+    !concentrated_object_api_test_result ||
+    !concentrated_pool_test_result ||
+    !concentrated_snapshot_test_result ||
+    !concentrated_sample_curve_test_result ||
+    !concentrated_swap_batch_test_result ||
+    !concentrated_ladder_test_result ||
+    !concentrated_price_impact_test_result ||
     !concentrated_provide_test_result ||
-    !concentrated_withdraw_test_result
+    !concentrated_reverse_provide_test_result ||
+    !concentrated_zap_in_test_result ||
+    !concentrated_withdraw_test_result ||
+    !concentrated_zap_out_test_result
   ) {
     throw new Error("Tests failed!");
   }